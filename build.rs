@@ -0,0 +1,50 @@
+/// Build script
+///
+/// Sets `BICEP_DOCS_BUILD_ID` to the commit the tool is built from, or to the
+/// package version when it is not built from a git checkout. The batch cache keys
+/// on it along with the package version, so a tool built from another commit
+/// regenerates cached documentation even when the version has not changed, as with
+/// development builds.
+use std::{env, fs, path::PathBuf, process::Command};
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    let git_dir = manifest_dir.join(".git");
+
+    // Only the repository of this package is asked, not one that encloses it
+    let commit = git_dir
+        .exists()
+        .then(|| {
+            Command::new("git")
+                .arg("--git-dir")
+                .arg(&git_dir)
+                .args(["rev-parse", "--short=12", "HEAD"])
+                .output()
+                .ok()
+        })
+        .flatten()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|commit| !commit.is_empty());
+
+    // Rebuild when the checked out commit changes
+    println!("cargo:rerun-if-changed=build.rs");
+    let head = git_dir.join("HEAD");
+    if head.is_file() {
+        println!("cargo:rerun-if-changed={}", head.display());
+        let reference = fs::read_to_string(&head).unwrap_or_default();
+        if let Some(reference) = reference.trim().strip_prefix("ref: ") {
+            let reference = git_dir.join(reference);
+            if reference.is_file() {
+                println!("cargo:rerun-if-changed={}", reference.display());
+            }
+            let packed = git_dir.join("packed-refs");
+            if packed.is_file() {
+                println!("cargo:rerun-if-changed={}", packed.display());
+            }
+        }
+    }
+
+    let build_id = commit.unwrap_or_else(|| env::var("CARGO_PKG_VERSION").unwrap_or_default());
+    println!("cargo:rustc-env=BICEP_DOCS_BUILD_ID={build_id}");
+}
@@ -0,0 +1,607 @@
+//! Azure Verified Modules conformance checks.
+//!
+//! The [Azure Verified Modules](https://azure.github.io/Azure-Verified-Modules/)
+//! specification asks every resource module to share one interface: the same
+//! parameters for location, tags, locks, role assignments, diagnostic settings
+//! and telemetry, the same outputs, and a telemetry deployment. This module checks
+//! a parsed [`BicepDocument`] against those rules.
+//!
+//! Rules are collected in versioned [`RuleSet`]s. A rule keeps its ID once
+//! published, and new rules only join a new version, so pinning a version keeps
+//! the result of a check stable when rules are added.
+
+use std::fmt;
+
+use super::{
+    diagnostics::{Diagnostic, Severity},
+    lint::SourceMap,
+    references::{SymbolId, SymbolKind},
+};
+use crate::parsing::{BicepDocument, BicepParameter, BicepType, BicepValue};
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// The versions of the rule set, oldest first
+pub const RULE_SET_VERSIONS: &[&str] = &["1.0"];
+
+/// A single conformance rule
+#[derive(Debug, Clone, Copy)]
+pub struct AvmRule {
+    /// The stable ID of the rule, such as `AVM1001`
+    pub id: &'static str,
+    /// What the rule requires
+    pub summary: &'static str,
+    /// The rule set version the rule was added in
+    pub since: &'static str,
+    /// Find the declarations that break the rule
+    check: fn(&BicepDocument) -> Vec<Finding>,
+}
+
+/// A declaration, or missing declaration, that breaks a rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvmViolation {
+    /// The ID of the rule that is broken
+    pub rule: &'static str,
+    /// The declaration that breaks the rule, or None when one is missing
+    pub symbol: Option<SymbolId>,
+    /// What is wrong
+    pub message: String,
+}
+
+/// The declaration a rule finds at fault, if any, and what is wrong with it
+type Finding = (Option<SymbolId>, String);
+
+/// The rules of one version of the rule set
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+    /// The version of the rule set
+    pub version: &'static str,
+    /// The rules in this version
+    pub rules: Vec<&'static AvmRule>,
+}
+
+// ---------------------------------------------------------------
+// Rules
+// ---------------------------------------------------------------
+
+/// Every rule, in ID order
+static RULES: [AvmRule; 10] = [
+    AvmRule {
+        id: "AVM1001",
+        summary: "A `location` parameter of type string with a default value",
+        since: "1.0",
+        check: check_location,
+    },
+    AvmRule {
+        id: "AVM1002",
+        summary: "An optional `tags` parameter of an object type",
+        since: "1.0",
+        check: check_tags,
+    },
+    AvmRule {
+        id: "AVM1003",
+        summary: "A `lock` parameter, where declared, of the optional `lockType`",
+        since: "1.0",
+        check: check_lock,
+    },
+    AvmRule {
+        id: "AVM1004",
+        summary: "A `roleAssignments` parameter, where declared, of the optional `roleAssignmentType[]`",
+        since: "1.0",
+        check: check_role_assignments,
+    },
+    AvmRule {
+        id: "AVM1005",
+        summary: "A `diagnosticSettings` parameter, where declared, of an optional array of a diagnostic setting type",
+        since: "1.0",
+        check: check_diagnostic_settings,
+    },
+    AvmRule {
+        id: "AVM1006",
+        summary: "An `enableTelemetry` parameter of type bool that defaults to true",
+        since: "1.0",
+        check: check_enable_telemetry,
+    },
+    AvmRule {
+        id: "AVM1101",
+        summary: "A `resourceId` output of type string",
+        since: "1.0",
+        check: check_resource_id_output,
+    },
+    AvmRule {
+        id: "AVM1102",
+        summary: "A `name` output of type string",
+        since: "1.0",
+        check: check_name_output,
+    },
+    AvmRule {
+        id: "AVM1103",
+        summary: "A `resourceGroupName` output of type string for resource group deployments",
+        since: "1.0",
+        check: check_resource_group_name_output,
+    },
+    AvmRule {
+        id: "AVM1201",
+        summary: "A `Microsoft.Resources/deployments` telemetry resource named `46d3xbcp.*` that deploys only when `enableTelemetry` is true",
+        since: "1.0",
+        check: check_telemetry_deployment,
+    },
+];
+
+/// The prefix of the names of AVM telemetry deployments
+const TELEMETRY_PREFIX: &str = "46d3xbcp.";
+
+fn check_location(document: &BicepDocument) -> Vec<Finding> {
+    let Some(parameter) = document.parameters.get("location") else {
+        return vec![missing(SymbolKind::Parameter, "location")];
+    };
+    let mut findings = Vec::new();
+    if parameter.parameter_type != BicepType::String {
+        findings.push(wrong_type("location", parameter, "string"));
+    }
+    if parameter.default_value.is_none() {
+        findings.push((
+            Some(parameter_id("location")),
+            "Parameter `location` has no default value; use `resourceGroup().location`".to_string(),
+        ));
+    }
+    findings
+}
+
+fn check_tags(document: &BicepDocument) -> Vec<Finding> {
+    let Some(parameter) = document.parameters.get("tags") else {
+        return vec![missing(SymbolKind::Parameter, "tags")];
+    };
+    let mut findings = Vec::new();
+    if !matches!(
+        parameter.parameter_type,
+        BicepType::Object(_) | BicepType::CustomType(_)
+    ) {
+        findings.push(wrong_type("tags", parameter, "object?"));
+    }
+    if !is_optional(parameter) {
+        findings.push(required("tags"));
+    }
+    findings
+}
+
+fn check_lock(document: &BicepDocument) -> Vec<Finding> {
+    check_interface_type(
+        document,
+        "lock",
+        |bicep_type| is_named_type(bicep_type, "lockType"),
+        "lockType?",
+    )
+}
+
+fn check_role_assignments(document: &BicepDocument) -> Vec<Finding> {
+    check_interface_type(
+        document,
+        "roleAssignments",
+        |bicep_type| matches!(bicep_type, BicepType::Array(item) if is_named_type(item, "roleAssignmentType")),
+        "roleAssignmentType[]?",
+    )
+}
+
+fn check_diagnostic_settings(document: &BicepDocument) -> Vec<Finding> {
+    check_interface_type(
+        document,
+        "diagnosticSettings",
+        |bicep_type| {
+            matches!(bicep_type, BicepType::Array(item) if matches!(
+                item.as_ref(),
+                BicepType::CustomType(name) if type_name(name).starts_with("diagnosticSetting")
+            ))
+        },
+        "diagnosticSettingFullType[]?",
+    )
+}
+
+fn check_enable_telemetry(document: &BicepDocument) -> Vec<Finding> {
+    let Some(parameter) = document.parameters.get("enableTelemetry") else {
+        return vec![missing(SymbolKind::Parameter, "enableTelemetry")];
+    };
+    let mut findings = Vec::new();
+    if parameter.parameter_type != BicepType::Bool {
+        findings.push(wrong_type("enableTelemetry", parameter, "bool"));
+    }
+    if parameter.default_value != Some(BicepValue::Bool(true)) {
+        findings.push((
+            Some(parameter_id("enableTelemetry")),
+            "Parameter `enableTelemetry` does not default to true".to_string(),
+        ));
+    }
+    findings
+}
+
+fn check_resource_id_output(document: &BicepDocument) -> Vec<Finding> {
+    check_string_output(document, "resourceId")
+}
+
+fn check_name_output(document: &BicepDocument) -> Vec<Finding> {
+    check_string_output(document, "name")
+}
+
+fn check_resource_group_name_output(document: &BicepDocument) -> Vec<Finding> {
+    match document.target_scope.as_deref() {
+        None | Some("resourceGroup") => check_string_output(document, "resourceGroupName"),
+        Some(_) => Vec::new(),
+    }
+}
+
+fn check_telemetry_deployment(document: &BicepDocument) -> Vec<Finding> {
+    let telemetry = document.resources.iter().find(|(_, resource)| {
+        resource
+            .resource_type
+            .eq_ignore_ascii_case("Microsoft.Resources/deployments")
+            && resource.name.contains(TELEMETRY_PREFIX)
+    });
+    let Some((name, resource)) = telemetry else {
+        return vec![(
+            None,
+            format!("No `Microsoft.Resources/deployments` resource named `{TELEMETRY_PREFIX}*` deploys the telemetry"),
+        )];
+    };
+    if resource
+        .condition
+        .as_deref()
+        .is_some_and(|condition| condition.contains("enableTelemetry"))
+    {
+        return Vec::new();
+    }
+    vec![(
+        Some(SymbolId {
+            kind: SymbolKind::Resource,
+            name: name.clone(),
+        }),
+        format!("Telemetry resource `{name}` does not deploy only when `enableTelemetry` is true"),
+    )]
+}
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+impl RuleSet {
+    /// The newest version of the rule set
+    pub fn latest() -> Self {
+        Self::version(RULE_SET_VERSIONS[RULE_SET_VERSIONS.len() - 1])
+            .expect("the newest rule set version is known")
+    }
+
+    /// A version of the rule set
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - One of [`RULE_SET_VERSIONS`]
+    ///
+    /// # Returns
+    ///
+    /// The rules added in that version or before it, or None for an unknown version
+    pub fn version(version: &str) -> Option<Self> {
+        let index = RULE_SET_VERSIONS
+            .iter()
+            .position(|known| *known == version)?;
+        Some(Self {
+            version: RULE_SET_VERSIONS[index],
+            rules: RULES
+                .iter()
+                .filter(|rule| RULE_SET_VERSIONS[..=index].contains(&rule.since))
+                .collect(),
+        })
+    }
+
+    /// Check a document against the rules
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The module to check
+    ///
+    /// # Returns
+    ///
+    /// The violations, in rule order
+    pub fn check(&self, document: &BicepDocument) -> Vec<AvmViolation> {
+        self.rules
+            .iter()
+            .flat_map(|rule| {
+                (rule.check)(document)
+                    .into_iter()
+                    .map(|(symbol, message)| AvmViolation {
+                        rule: rule.id,
+                        symbol,
+                        message,
+                    })
+            })
+            .collect()
+    }
+
+    /// Check a document against the rules and report the violations as diagnostics
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The module to check
+    /// * `source` - The Bicep source of the module, to place the diagnostics
+    ///
+    /// # Returns
+    ///
+    /// An error diagnostic for each violation, in rule order
+    pub fn diagnostics(&self, document: &BicepDocument, source: Option<&str>) -> Vec<Diagnostic> {
+        let source_map = source.map(SourceMap::scan).unwrap_or_default();
+        self.check(document)
+            .into_iter()
+            .map(|violation| Diagnostic {
+                rule: violation.rule.to_string(),
+                severity: Severity::Error,
+                message: violation.message,
+                span: violation
+                    .symbol
+                    .as_ref()
+                    .and_then(|symbol| source_map.span(symbol)),
+                symbol: violation.symbol,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for AvmViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rule, self.message)
+    }
+}
+
+/// A finding for a declaration the rule requires and the document lacks
+fn missing(kind: SymbolKind, name: &str) -> Finding {
+    let kind_name = match kind {
+        SymbolKind::Output => "output",
+        _ => "parameter",
+    };
+    (None, format!("No `{name}` {kind_name} is declared"))
+}
+
+/// A finding for a parameter declared with the wrong type
+fn wrong_type(name: &str, parameter: &BicepParameter, expected: &str) -> Finding {
+    (
+        Some(parameter_id(name)),
+        format!(
+            "Parameter `{name}` is of type `{}` rather than `{expected}`",
+            parameter.parameter_type
+        ),
+    )
+}
+
+/// A finding for a parameter that must be optional and is required
+fn required(name: &str) -> Finding {
+    (
+        Some(parameter_id(name)),
+        format!("Parameter `{name}` is required; make it nullable or give it a default value"),
+    )
+}
+
+fn parameter_id(name: &str) -> SymbolId {
+    SymbolId {
+        kind: SymbolKind::Parameter,
+        name: name.to_string(),
+    }
+}
+
+/// Whether a parameter can be left out
+fn is_optional(parameter: &BicepParameter) -> bool {
+    parameter.is_nullable || parameter.default_value.is_some()
+}
+
+/// The name of a type without the namespace it is imported through, such as `avm.lockType`
+fn type_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Whether a type refers to a custom type by name
+fn is_named_type(bicep_type: &BicepType, name: &str) -> bool {
+    matches!(bicep_type, BicepType::CustomType(custom) if type_name(custom) == name)
+}
+
+/// Check an optional interface parameter, which need only be declared where the resource supports it
+fn check_interface_type(
+    document: &BicepDocument,
+    name: &str,
+    has_type: impl Fn(&BicepType) -> bool,
+    expected: &str,
+) -> Vec<Finding> {
+    let Some(parameter) = document.parameters.get(name) else {
+        return Vec::new();
+    };
+    let mut findings = Vec::new();
+    if !has_type(&parameter.parameter_type) {
+        findings.push(wrong_type(name, parameter, expected));
+    }
+    if !is_optional(parameter) {
+        findings.push(required(name));
+    }
+    findings
+}
+
+/// Check that an output is declared with type string
+fn check_string_output(document: &BicepDocument, name: &str) -> Vec<Finding> {
+    match document.outputs.get(name) {
+        None => vec![missing(SymbolKind::Output, name)],
+        Some(output) if output.output_type != BicepType::String => vec![(
+            Some(SymbolId {
+                kind: SymbolKind::Output,
+                name: name.to_string(),
+            }),
+            format!(
+                "Output `{name}` is of type `{}` rather than `string`",
+                output.output_type
+            ),
+        )],
+        Some(_) => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{BicepOutput, BicepResource};
+
+    fn parameter(parameter_type: BicepType, default_value: Option<BicepValue>) -> BicepParameter {
+        BicepParameter {
+            parameter_type,
+            default_value,
+            ..Default::default()
+        }
+    }
+
+    fn output(name: &str) -> BicepOutput {
+        BicepOutput {
+            description: None,
+            descriptions: Default::default(),
+            output_type: BicepType::String,
+            value: BicepValue::Identifier(format!("account.{name}")),
+            discriminator: None,
+            min_length: None,
+            max_length: None,
+            min_value: None,
+            max_value: None,
+            metadata: None,
+            sealed: false,
+            secure: false,
+        }
+    }
+
+    /// A module that follows every rule
+    fn conformant() -> BicepDocument {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "location".to_string(),
+            parameter(
+                BicepType::String,
+                Some(BicepValue::Identifier(
+                    "resourceGroup().location".to_string(),
+                )),
+            ),
+        );
+        document.parameters.insert(
+            "tags".to_string(),
+            BicepParameter {
+                is_nullable: true,
+                ..parameter(BicepType::Object(None), None)
+            },
+        );
+        document.parameters.insert(
+            "lock".to_string(),
+            BicepParameter {
+                is_nullable: true,
+                ..parameter(BicepType::CustomType("avm.lockType".to_string()), None)
+            },
+        );
+        document.parameters.insert(
+            "roleAssignments".to_string(),
+            BicepParameter {
+                is_nullable: true,
+                ..parameter(
+                    BicepType::Array(Box::new(BicepType::CustomType(
+                        "roleAssignmentType".to_string(),
+                    ))),
+                    None,
+                )
+            },
+        );
+        document.parameters.insert(
+            "enableTelemetry".to_string(),
+            parameter(BicepType::Bool, Some(BicepValue::Bool(true))),
+        );
+        for name in ["resourceId", "name", "resourceGroupName"] {
+            document.outputs.insert(name.to_string(), output(name));
+        }
+        document.resources.insert(
+            "avmTelemetry".to_string(),
+            BicepResource {
+                resource_type: "Microsoft.Resources/deployments".to_string(),
+                api_version: "2024-03-01".to_string(),
+                name: "46d3xbcp.res.storage-account.${uniqueString(deployment().name)}".to_string(),
+                condition: Some("enableTelemetry".to_string()),
+                ..Default::default()
+            },
+        );
+        document
+    }
+
+    #[test]
+    fn test_conformant_module() {
+        assert_eq!(RuleSet::latest().check(&conformant()), Vec::new());
+    }
+
+    #[test]
+    fn test_violations() {
+        let mut document = conformant();
+        document.parameters.shift_remove("location");
+        document
+            .parameters
+            .insert("lock".to_string(), parameter(BicepType::Object(None), None));
+        document.parameters.insert(
+            "enableTelemetry".to_string(),
+            parameter(BicepType::Bool, Some(BicepValue::Bool(false))),
+        );
+        document.outputs.shift_remove("resourceGroupName");
+        document.resources["avmTelemetry"].condition = None;
+
+        let violations = RuleSet::latest().check(&document);
+        let rules: Vec<_> = violations.iter().map(|violation| violation.rule).collect();
+        assert_eq!(
+            rules,
+            vec!["AVM1001", "AVM1003", "AVM1003", "AVM1006", "AVM1103", "AVM1201"]
+        );
+        assert_eq!(violations[0].symbol, None);
+        assert_eq!(
+            violations[1].to_string(),
+            "AVM1003: Parameter `lock` is of type `object` rather than `lockType?`"
+        );
+        assert_eq!(
+            violations[5].symbol,
+            Some(SymbolId {
+                kind: SymbolKind::Resource,
+                name: "avmTelemetry".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let mut document = conformant();
+        document.parameters.insert(
+            "enableTelemetry".to_string(),
+            parameter(BicepType::Bool, Some(BicepValue::Bool(false))),
+        );
+        let source = "param location string = resourceGroup().location\nparam enableTelemetry bool = false\n";
+
+        let diagnostics = RuleSet::latest().diagnostics(&document, Some(source));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "AVM1006");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].span.map(|span| span.start_line), Some(2));
+    }
+
+    #[test]
+    fn test_resource_group_name_only_for_resource_group_scope() {
+        let mut document = conformant();
+        document.outputs.shift_remove("resourceGroupName");
+        document.target_scope = Some("subscription".to_string());
+        assert_eq!(RuleSet::latest().check(&document), Vec::new());
+    }
+
+    #[test]
+    fn test_rule_set_versions() {
+        let latest = RuleSet::latest();
+        assert_eq!(latest.version, *RULE_SET_VERSIONS.last().unwrap());
+        assert_eq!(latest.rules.len(), RULES.len());
+        assert!(RuleSet::version("0.1").is_none());
+
+        let mut ids: Vec<_> = RULES.iter().map(|rule| rule.id).collect();
+        ids.dedup();
+        assert_eq!(ids.len(), RULES.len(), "rule IDs are unique");
+        assert!(RULES
+            .iter()
+            .all(|rule| RULE_SET_VERSIONS.contains(&rule.since)));
+    }
+}
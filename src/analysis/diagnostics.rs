@@ -0,0 +1,116 @@
+//! Diagnostics shared by the analysis passes.
+//!
+//! Lint rules, conformance checks, documentation coverage and parse errors all
+//! report a [`Diagnostic`]: the rule that was broken, how serious it is, and the
+//! declaration and source span it concerns. Reporters such as the SARIF log
+//! only deal with this one shape.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::references::SymbolId;
+use crate::error::{BicepError, Span};
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth knowing, but not a problem
+    Info,
+    /// A problem that should be fixed
+    Warning,
+    /// A problem that must be fixed
+    Error,
+}
+
+/// A problem found in a template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The ID of the rule that is broken
+    pub rule: String,
+    /// How serious the problem is
+    pub severity: Severity,
+    /// What is wrong
+    pub message: String,
+    /// The declaration the problem is in, if it is in one
+    pub symbol: Option<SymbolId>,
+    /// Where the problem is in the source, when the source is known
+    pub span: Option<Span>,
+}
+
+/// The codes of the errors that reading a template can report, and what each means
+pub const ERROR_RULES: &[(&str, &str)] = &[
+    ("BD0001", "The template can be read"),
+    ("BD1001", "The template is free of syntax errors"),
+    ("BD1002", "The template uses only supported constructs"),
+    ("BD1003", "The template's values are valid"),
+];
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+impl Diagnostic {
+    /// The diagnostic for an error reading or parsing a template
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The error
+    ///
+    /// # Returns
+    ///
+    /// An error diagnostic whose rule is the [code](BicepError::code) of the error
+    pub fn from_error(error: &BicepError) -> Self {
+        let code = error.code();
+        let text = error.to_string();
+        let message = text
+            .strip_prefix(&format!("[{code}] "))
+            .unwrap_or(&text)
+            .to_string();
+        Diagnostic {
+            rule: code.to_string(),
+            severity: Severity::Error,
+            message,
+            symbol: None,
+            span: error.span(),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rule, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_error() {
+        let error = crate::parse_arm_document("{\n  \"resources\": [\n}").unwrap_err();
+        let diagnostic = Diagnostic::from_error(&error);
+        assert_eq!(diagnostic.rule, "BD1001");
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert!(!diagnostic.message.starts_with('['));
+        assert_eq!(diagnostic.span.map(|span| span.start_line), Some(3));
+        assert!(ERROR_RULES.iter().any(|(code, _)| *code == diagnostic.rule));
+    }
+}
@@ -0,0 +1,1515 @@
+//! Constant folding of Bicep expressions.
+//!
+//! This module evaluates the values of a parsed [`BicepDocument`] wherever they are
+//! knowable at compile time: parameter default values, variables and any expression
+//! built from them with pure functions such as `concat`, `format` or `toLower`.
+//! Anything that depends on the deployment (e.g. `resourceGroup()`, resource
+//! properties or `utcNow()`) is not statically knowable and evaluates to `None`, so
+//! callers can fall back to the expression text. So do the hashes computed by `guid`
+//! and `uniqueString`, which only Azure Resource Manager can compute faithfully.
+
+use indexmap::IndexMap;
+
+use super::expressions::{tokenize, tokenize_template, StringSegment, Token};
+use crate::parsing::{BicepDocument, BicepValue};
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// Maximum nesting of user-defined function calls and loops during evaluation
+const MAX_DEPTH: usize = 32;
+
+/// Largest padded length and range count that Azure Resource Manager accepts
+const MAX_LENGTH: i64 = 10000;
+
+/// Parsed expression tree
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Value(BicepValue),
+    Null,
+    Template(Vec<TemplatePart>),
+    Symbol(String),
+    Array(Vec<Expr>),
+    Object(Vec<(String, Expr)>),
+    Member(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Call(Option<Box<Expr>>, String, Vec<Expr>),
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Ternary(Box<Expr>, Box<Expr>, Box<Expr>),
+    Lambda(Vec<String>, Box<Expr>),
+    For {
+        item: String,
+        index: Option<String>,
+        source: Box<Expr>,
+        condition: Option<Box<Expr>>,
+        body: Box<Expr>,
+    },
+}
+
+/// A segment of an interpolated string
+#[derive(Debug, Clone, PartialEq)]
+enum TemplatePart {
+    Literal(String),
+    Expr(Expr),
+}
+
+/// Recursive descent parser over expression tokens
+struct ExprParser<'t> {
+    tokens: &'t [Token],
+    pos: usize,
+}
+
+/// Binary operators by precedence level, lowest first
+const BINARY_OPERATORS: &[&[&str]] = &[
+    &["??"],
+    &["||"],
+    &["&&"],
+    &["==", "!=", "=~", "!~"],
+    &["<", "<=", ">", ">="],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+/// Evaluates compile-time-constant values of a Bicep document
+///
+/// Parameter values come from the supplied overrides or, failing that, from the
+/// parameters' default values. Variables are evaluated from those parameters.
+#[derive(Debug, Clone)]
+pub struct Evaluator<'a> {
+    document: &'a BicepDocument,
+    values: IndexMap<String, BicepValue>,
+}
+
+/// Local bindings of loop and lambda variables
+type Locals = Vec<(String, BicepValue)>;
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+impl<'t> ExprParser<'t> {
+    fn parse(tokens: &'t [Token]) -> Option<Expr> {
+        let mut parser = ExprParser { tokens, pos: 0 };
+        let expr = parser.expression()?;
+        (parser.pos == tokens.len()).then_some(expr)
+    }
+
+    fn peek(&self) -> Option<&'t Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&'t Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn next(&mut self) -> Option<&'t Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Identifier(name)) if name == keyword)
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let found = self.is_symbol(symbol);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Option<()> {
+        self.eat_symbol(symbol).then_some(())
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        match self.next()? {
+            Token::Identifier(name) => Some(name.clone()),
+            _ => None,
+        }
+    }
+
+    fn expression(&mut self) -> Option<Expr> {
+        let condition = self.binary(0)?;
+        if self.eat_symbol("?") {
+            let when_true = self.expression()?;
+            self.expect_symbol(":")?;
+            let when_false = self.expression()?;
+            return Some(Expr::Ternary(
+                Box::new(condition),
+                Box::new(when_true),
+                Box::new(when_false),
+            ));
+        }
+        Some(condition)
+    }
+
+    fn binary(&mut self, level: usize) -> Option<Expr> {
+        let Some(operators) = BINARY_OPERATORS.get(level) else {
+            return self.unary();
+        };
+
+        let mut left = self.binary(level + 1)?;
+        while let Some(Token::Symbol(symbol)) = self.peek() {
+            let Some(operator) = operators.iter().find(|op| *op == symbol) else {
+                break;
+            };
+            self.pos += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(operator, Box::new(left), Box::new(right));
+        }
+        Some(left)
+    }
+
+    fn unary(&mut self) -> Option<Expr> {
+        for operator in ["!", "-"] {
+            if self.eat_symbol(operator) {
+                return Some(Expr::Unary(operator, Box::new(self.unary()?)));
+            }
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> Option<Expr> {
+        let mut expr = self.primary()?;
+
+        loop {
+            if self.eat_symbol(".") || self.eat_symbol("?.") {
+                let name = self.identifier()?;
+                if self.eat_symbol("(") {
+                    let args = self.arguments()?;
+                    expr = Expr::Call(Some(Box::new(expr)), name, args);
+                } else {
+                    expr = Expr::Member(Box::new(expr), name);
+                }
+            } else if self.eat_symbol("[") {
+                let index = self.expression()?;
+                self.expect_symbol("]")?;
+                expr = Expr::Index(Box::new(expr), Box::new(index));
+            } else if self.is_symbol("?") && matches!(self.peek_at(1), Some(Token::Symbol("["))) {
+                self.pos += 2;
+                let index = self.expression()?;
+                self.expect_symbol("]")?;
+                expr = Expr::Index(Box::new(expr), Box::new(index));
+            } else if self.is_symbol("!")
+                && !matches!(
+                    self.peek_at(1),
+                    Some(Token::Identifier(_) | Token::Integer(_) | Token::String(_))
+                )
+            {
+                // Non-null assertion
+                self.pos += 1;
+            } else {
+                return Some(expr);
+            }
+        }
+    }
+
+    fn arguments(&mut self) -> Option<Vec<Expr>> {
+        let mut args = Vec::new();
+        while !self.eat_symbol(")") {
+            args.push(self.expression()?);
+            if !self.is_symbol(")") {
+                self.expect_symbol(",")?;
+            }
+        }
+        Some(args)
+    }
+
+    fn primary(&mut self) -> Option<Expr> {
+        match self.next()? {
+            Token::Integer(value) => Some(Expr::Value(BicepValue::Int(*value))),
+            Token::String(segments) => Self::template(segments),
+            Token::Identifier(name) => match name.as_str() {
+                "true" => Some(Expr::Value(BicepValue::Bool(true))),
+                "false" => Some(Expr::Value(BicepValue::Bool(false))),
+                "null" => Some(Expr::Null),
+                _ if self.eat_symbol("=>") => Some(Expr::Lambda(
+                    vec![name.clone()],
+                    Box::new(self.expression()?),
+                )),
+                _ if self.eat_symbol("(") => {
+                    Some(Expr::Call(None, name.clone(), self.arguments()?))
+                },
+                _ => Some(Expr::Symbol(name.clone())),
+            },
+            Token::Symbol("(") => {
+                if let Some(params) = self.lambda_parameters() {
+                    return Some(Expr::Lambda(params, Box::new(self.expression()?)));
+                }
+                let expr = self.expression()?;
+                self.expect_symbol(")")?;
+                Some(expr)
+            },
+            Token::Symbol("[") => self.array(),
+            Token::Symbol("{") => self.object(),
+            _ => None,
+        }
+    }
+
+    /// Parse `a, b) =>` after an opening parenthesis, leaving the position untouched
+    /// if the parenthesis does not start a lambda
+    fn lambda_parameters(&mut self) -> Option<Vec<String>> {
+        let start = self.pos;
+        let mut params = Vec::new();
+
+        while let Some(Token::Identifier(name)) = self.peek() {
+            params.push(name.clone());
+            self.pos += 1;
+            if !self.eat_symbol(",") {
+                break;
+            }
+        }
+
+        if self.eat_symbol(")") && self.eat_symbol("=>") {
+            Some(params)
+        } else {
+            self.pos = start;
+            None
+        }
+    }
+
+    fn array(&mut self) -> Option<Expr> {
+        if self.is_keyword("for") {
+            self.pos += 1;
+            let (item, index) = if self.eat_symbol("(") {
+                let item = self.identifier()?;
+                self.expect_symbol(",")?;
+                let index = self.identifier()?;
+                self.expect_symbol(")")?;
+                (item, Some(index))
+            } else {
+                (self.identifier()?, None)
+            };
+            if self.identifier()? != "in" {
+                return None;
+            }
+            let source = self.expression()?;
+            self.expect_symbol(":")?;
+            let condition = if self.is_keyword("if") {
+                self.pos += 1;
+                self.expect_symbol("(")?;
+                let condition = self.expression()?;
+                self.expect_symbol(")")?;
+                Some(Box::new(condition))
+            } else {
+                None
+            };
+            let body = self.expression()?;
+            self.expect_symbol("]")?;
+            return Some(Expr::For {
+                item,
+                index,
+                source: Box::new(source),
+                condition,
+                body: Box::new(body),
+            });
+        }
+
+        let mut items = Vec::new();
+        while !self.eat_symbol("]") {
+            items.push(self.expression()?);
+            self.eat_symbol(",");
+        }
+        Some(Expr::Array(items))
+    }
+
+    fn object(&mut self) -> Option<Expr> {
+        let mut properties = Vec::new();
+        while !self.eat_symbol("}") {
+            let key = match self.next()? {
+                Token::Identifier(name) => name.clone(),
+                Token::String(segments) => match segments.as_slice() {
+                    [StringSegment::Literal(text)] => text.clone(),
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            self.expect_symbol(":")?;
+            properties.push((key, self.expression()?));
+            self.eat_symbol(",");
+        }
+        Some(Expr::Object(properties))
+    }
+
+    fn template(segments: &[StringSegment]) -> Option<Expr> {
+        let mut parts = Vec::with_capacity(segments.len());
+        for segment in segments {
+            match segment {
+                StringSegment::Literal(text) => parts.push(TemplatePart::Literal(text.clone())),
+                StringSegment::Interpolation(tokens) => {
+                    parts.push(TemplatePart::Expr(ExprParser::parse(tokens)?))
+                },
+            }
+        }
+
+        match parts.as_slice() {
+            [TemplatePart::Literal(text)] => Some(Expr::Value(BicepValue::String(text.clone()))),
+            _ => Some(Expr::Template(parts)),
+        }
+    }
+}
+
+/// Whether a namespace qualifies built-in functions (e.g. `sys.concat`)
+fn is_builtin_namespace(namespace: &str) -> bool {
+    matches!(namespace, "sys" | "az")
+}
+
+impl<'a> Evaluator<'a> {
+    /// Create an evaluator using the parameters' default values
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The parsed Bicep document
+    pub fn new(document: &'a BicepDocument) -> Self {
+        Self::with_parameters(document, IndexMap::new())
+    }
+
+    /// Create an evaluator with explicit parameter values
+    ///
+    /// Parameters without a supplied value fall back to their default value.
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The parsed Bicep document
+    /// * `parameters` - Parameter values, e.g. read from a parameters file
+    pub fn with_parameters(
+        document: &'a BicepDocument,
+        parameters: IndexMap<String, BicepValue>,
+    ) -> Self {
+        let values = parameters
+            .into_iter()
+            .filter(|(name, _)| document.parameters.contains_key(name))
+            .collect();
+
+        let mut evaluator = Evaluator { document, values };
+        evaluator.resolve_symbols();
+        evaluator
+    }
+
+    /// Evaluate parameters and variables until no more values can be resolved
+    ///
+    /// Declarations may refer to each other in any order, so each pass evaluates
+    /// whatever has become knowable from the previous pass. Cycles never resolve.
+    fn resolve_symbols(&mut self) {
+        let document = self.document;
+        let pending = document
+            .parameters
+            .iter()
+            .filter_map(|(name, param)| param.default_value.as_ref().map(|value| (name, value)))
+            .chain(
+                document
+                    .variables
+                    .iter()
+                    .map(|(name, variable)| (name, &variable.value)),
+            )
+            .collect::<Vec<_>>();
+
+        loop {
+            let mut resolved = Vec::new();
+            for (name, value) in &pending {
+                if self.values.contains_key(*name) {
+                    continue;
+                }
+                if let Some(result) = self.evaluate_value(value) {
+                    resolved.push(((*name).clone(), result));
+                }
+            }
+
+            if resolved.is_empty() {
+                break;
+            }
+            self.values.extend(resolved);
+        }
+    }
+
+    /// Get the constant value of a parameter or variable
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The parameter or variable name
+    ///
+    /// # Returns
+    ///
+    /// The value, or None if it is not statically knowable
+    pub fn symbol_value(&self, name: &str) -> Option<&BicepValue> {
+        self.values.get(name)
+    }
+
+    /// Evaluate a stored value
+    ///
+    /// Expressions are evaluated as source text, and strings as literals whose
+    /// interpolations, if any, are evaluated. Arrays and objects are evaluated element-wise
+    /// and are only knowable if all of their elements are.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value as stored in the document
+    ///
+    /// # Returns
+    ///
+    /// The evaluated value, or None if it is not statically knowable
+    pub fn evaluate_value(&self, value: &BicepValue) -> Option<BicepValue> {
+        match value {
+            BicepValue::String(text) => self.evaluate_template(text, &[]),
+            BicepValue::Identifier(text) => self.evaluate_source(text, &[]),
+            BicepValue::Int(_) | BicepValue::Bool(_) => Some(value.clone()),
+            BicepValue::Array(items) => items
+                .iter()
+                .map(|item| self.evaluate_value(item))
+                .collect::<Option<Vec<_>>>()
+                .map(BicepValue::Array),
+            BicepValue::Object(props) => props
+                .iter()
+                .map(|(key, prop)| Some((key.clone(), self.evaluate_value(prop)?)))
+                .collect::<Option<IndexMap<_, _>>>()
+                .map(BicepValue::Object),
+        }
+    }
+
+    /// Evaluate the content of a string literal, such as a resource name
+    ///
+    /// The text is never read as an expression: a string without interpolations is
+    /// its own value, and one with interpolations is knowable if they all are.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The content of the string, without its quotes
+    /// * `locals` - Names bound in addition to the document's symbols, such as loop
+    ///   variables
+    ///
+    /// # Returns
+    ///
+    /// The evaluated string, or None if an interpolation is not statically knowable
+    pub fn evaluate_template(
+        &self,
+        text: &str,
+        locals: &[(String, BicepValue)],
+    ) -> Option<BicepValue> {
+        // Text that does not read as a template, e.g. with an unclosed `${`, is literal
+        let Ok(segments) = tokenize_template(text) else {
+            return Some(BicepValue::String(text.to_string()));
+        };
+        let expr = ExprParser::template(&segments)?;
+        self.eval(&expr, &mut locals.to_vec(), 0)
+    }
+
+    /// Evaluate expression source text
+    ///
+    /// Expressions stored by the parser, conditions, loop sources and source text such
+    /// as the value of a `param` statement in a `.bicepparam` file are evaluated this way.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The expression source text
+    /// * `locals` - Names bound in addition to the document's symbols
+    ///
+    /// # Returns
+    ///
+    /// The evaluated value, or None if the text is invalid or not statically knowable
+    pub fn evaluate_source(
+        &self,
+        text: &str,
+        locals: &[(String, BicepValue)],
+    ) -> Option<BicepValue> {
+        let tokens = tokenize(text).ok()?;
+        let expr = ExprParser::parse(&tokens)?;
+        self.eval(&expr, &mut locals.to_vec(), 0)
+    }
+
+    /// Evaluate a value for display alongside the original
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value as stored in the document
+    ///
+    /// # Returns
+    ///
+    /// The evaluated value if it is statically knowable and differs from the original
+    pub fn effective_value(&self, value: &BicepValue) -> Option<BicepValue> {
+        self.evaluate_value(value)
+            .filter(|evaluated| evaluated != value)
+    }
+
+    fn eval(&self, expr: &Expr, locals: &mut Locals, depth: usize) -> Option<BicepValue> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        match expr {
+            Expr::Value(value) => Some(value.clone()),
+            Expr::Null | Expr::Lambda(..) => None,
+            Expr::Template(parts) => {
+                let mut text = String::new();
+                for part in parts {
+                    match part {
+                        TemplatePart::Literal(literal) => text.push_str(literal),
+                        TemplatePart::Expr(expr) => {
+                            text.push_str(&to_text(&self.eval(expr, locals, depth)?))
+                        },
+                    }
+                }
+                Some(BicepValue::String(text))
+            },
+            Expr::Symbol(name) => locals
+                .iter()
+                .rev()
+                .find(|(local, _)| local == name)
+                .map(|(_, value)| value.clone())
+                .or_else(|| self.values.get(name).cloned()),
+            Expr::Array(items) => items
+                .iter()
+                .map(|item| self.eval(item, locals, depth))
+                .collect::<Option<Vec<_>>>()
+                .map(BicepValue::Array),
+            Expr::Object(props) => props
+                .iter()
+                .map(|(key, prop)| Some((key.clone(), self.eval(prop, locals, depth)?)))
+                .collect::<Option<IndexMap<_, _>>>()
+                .map(BicepValue::Object),
+            Expr::Member(base, name) => match self.eval(base, locals, depth)? {
+                BicepValue::Object(props) => get_property(&props, name).cloned(),
+                _ => None,
+            },
+            Expr::Index(base, index) => {
+                match (
+                    self.eval(base, locals, depth)?,
+                    self.eval(index, locals, depth)?,
+                ) {
+                    (BicepValue::Array(items), BicepValue::Int(i)) => {
+                        items.get(usize::try_from(i).ok()?).cloned()
+                    },
+                    (BicepValue::Object(props), BicepValue::String(key)) => {
+                        get_property(&props, &key).cloned()
+                    },
+                    _ => None,
+                }
+            },
+            Expr::Unary(operator, operand) => match (*operator, self.eval(operand, locals, depth)?)
+            {
+                ("!", BicepValue::Bool(value)) => Some(BicepValue::Bool(!value)),
+                ("-", BicepValue::Int(value)) => value.checked_neg().map(BicepValue::Int),
+                _ => None,
+            },
+            Expr::Binary(operator, left, right) => {
+                self.eval_binary(operator, left, right, locals, depth)
+            },
+            Expr::Ternary(condition, when_true, when_false) => {
+                match self.eval(condition, locals, depth)? {
+                    BicepValue::Bool(true) => self.eval(when_true, locals, depth),
+                    BicepValue::Bool(false) => self.eval(when_false, locals, depth),
+                    _ => None,
+                }
+            },
+            Expr::For {
+                item,
+                index,
+                source,
+                condition,
+                body,
+            } => {
+                let BicepValue::Array(items) = self.eval(source, locals, depth)? else {
+                    return None;
+                };
+
+                let mut results = Vec::with_capacity(items.len());
+                for (i, value) in items.into_iter().enumerate() {
+                    let scope = locals.len();
+                    locals.push((item.clone(), value));
+                    if let Some(index) = index {
+                        locals.push((index.clone(), BicepValue::Int(i as i64)));
+                    }
+
+                    let include = match condition {
+                        Some(condition) => self.eval(condition, locals, depth + 1),
+                        None => Some(BicepValue::Bool(true)),
+                    };
+                    let result = match include {
+                        Some(BicepValue::Bool(true)) => {
+                            self.eval(body, locals, depth + 1).map(Some)
+                        },
+                        Some(BicepValue::Bool(false)) => Some(None),
+                        _ => None,
+                    };
+                    locals.truncate(scope);
+
+                    if let Some(value) = result? {
+                        results.push(value);
+                    }
+                }
+                Some(BicepValue::Array(results))
+            },
+            Expr::Call(target, name, args) => {
+                match target.as_deref() {
+                    None => {},
+                    Some(Expr::Symbol(namespace)) if is_builtin_namespace(namespace) => {},
+                    // Resource methods (e.g. listKeys) and imported namespaces are not constant
+                    Some(_) => return None,
+                }
+
+                if target.is_none() {
+                    if let Some(function) = self.document.functions.get(name) {
+                        let args = args
+                            .iter()
+                            .map(|arg| self.eval(arg, locals, depth))
+                            .collect::<Option<Vec<_>>>()?;
+                        return self.call_user_function(function, args, depth);
+                    }
+                }
+
+                self.call_builtin(name, args, locals, depth)
+            },
+        }
+    }
+
+    fn eval_binary(
+        &self,
+        operator: &str,
+        left: &Expr,
+        right: &Expr,
+        locals: &mut Locals,
+        depth: usize,
+    ) -> Option<BicepValue> {
+        // Short-circuiting operators can be known even when one side is not
+        match operator {
+            "??" if *left == Expr::Null => return self.eval(right, locals, depth),
+            "??" => return self.eval(left, locals, depth),
+            "&&" | "||" => {
+                let short_circuit = operator == "||";
+                return match self.eval(left, locals, depth)? {
+                    BicepValue::Bool(value) if value == short_circuit => {
+                        Some(BicepValue::Bool(value))
+                    },
+                    BicepValue::Bool(_) => match self.eval(right, locals, depth)? {
+                        BicepValue::Bool(value) => Some(BicepValue::Bool(value)),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+            },
+            _ => {},
+        }
+
+        let left = self.eval(left, locals, depth)?;
+        let right = self.eval(right, locals, depth)?;
+
+        match (operator, &left, &right) {
+            ("==", _, _) => Some(BicepValue::Bool(left == right)),
+            ("!=", _, _) => Some(BicepValue::Bool(left != right)),
+            ("=~", BicepValue::String(a), BicepValue::String(b)) => {
+                Some(BicepValue::Bool(a.to_lowercase() == b.to_lowercase()))
+            },
+            ("!~", BicepValue::String(a), BicepValue::String(b)) => {
+                Some(BicepValue::Bool(a.to_lowercase() != b.to_lowercase()))
+            },
+            (_, BicepValue::Int(a), BicepValue::Int(b)) => match operator {
+                "<" => Some(BicepValue::Bool(a < b)),
+                "<=" => Some(BicepValue::Bool(a <= b)),
+                ">" => Some(BicepValue::Bool(a > b)),
+                ">=" => Some(BicepValue::Bool(a >= b)),
+                "+" => a.checked_add(*b).map(BicepValue::Int),
+                "-" => a.checked_sub(*b).map(BicepValue::Int),
+                "*" => a.checked_mul(*b).map(BicepValue::Int),
+                "/" => a.checked_div(*b).map(BicepValue::Int),
+                "%" => a.checked_rem(*b).map(BicepValue::Int),
+                _ => None,
+            },
+            (_, BicepValue::String(a), BicepValue::String(b)) => match operator {
+                "<" => Some(BicepValue::Bool(a < b)),
+                "<=" => Some(BicepValue::Bool(a <= b)),
+                ">" => Some(BicepValue::Bool(a > b)),
+                ">=" => Some(BicepValue::Bool(a >= b)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn call_user_function(
+        &self,
+        function: &crate::parsing::BicepFunction,
+        args: Vec<BicepValue>,
+        depth: usize,
+    ) -> Option<BicepValue> {
+        if args.len() != function.arguments.len() {
+            return None;
+        }
+
+        let tokens = tokenize(&function.expression).ok()?;
+        let body = ExprParser::parse(&tokens)?;
+
+        // Function bodies only see their own arguments
+        let mut locals = function
+            .arguments
+            .iter()
+            .map(|arg| arg.name.clone())
+            .zip(args)
+            .collect::<Locals>();
+        self.eval(&body, &mut locals, depth + 1)
+    }
+
+    /// Apply a lambda to arguments
+    fn apply(
+        &self,
+        lambda: &Expr,
+        args: Vec<BicepValue>,
+        locals: &mut Locals,
+        depth: usize,
+    ) -> Option<BicepValue> {
+        let Expr::Lambda(params, body) = lambda else {
+            return None;
+        };
+        if params.len() > args.len() {
+            return None;
+        }
+
+        let scope = locals.len();
+        locals.extend(params.iter().cloned().zip(args));
+        let result = self.eval(body, locals, depth + 1);
+        locals.truncate(scope);
+        result
+    }
+
+    fn call_builtin(
+        &self,
+        name: &str,
+        args: &[Expr],
+        locals: &mut Locals,
+        depth: usize,
+    ) -> Option<BicepValue> {
+        let name = name.to_lowercase();
+
+        // Functions taking lambdas evaluate their arguments themselves
+        match (name.as_str(), args) {
+            ("map", [array, lambda]) | ("filter", [array, lambda]) => {
+                let BicepValue::Array(items) = self.eval(array, locals, depth)? else {
+                    return None;
+                };
+                let mut results = Vec::with_capacity(items.len());
+                for (i, item) in items.into_iter().enumerate() {
+                    let result = self.apply(
+                        lambda,
+                        vec![item.clone(), BicepValue::Int(i as i64)],
+                        locals,
+                        depth,
+                    )?;
+                    match (name.as_str(), result) {
+                        ("map", value) => results.push(value),
+                        (_, BicepValue::Bool(true)) => results.push(item),
+                        (_, BicepValue::Bool(false)) => {},
+                        _ => return None,
+                    }
+                }
+                return Some(BicepValue::Array(results));
+            },
+            ("reduce", [array, initial, lambda]) => {
+                let BicepValue::Array(items) = self.eval(array, locals, depth)? else {
+                    return None;
+                };
+                let mut accumulator = self.eval(initial, locals, depth)?;
+                for (i, item) in items.into_iter().enumerate() {
+                    accumulator = self.apply(
+                        lambda,
+                        vec![accumulator, item, BicepValue::Int(i as i64)],
+                        locals,
+                        depth,
+                    )?;
+                }
+                return Some(accumulator);
+            },
+            ("coalesce", _) => {
+                // Known values are never null, so the first non-null literal wins
+                let first = args.iter().find(|arg| **arg != Expr::Null)?;
+                return self.eval(first, locals, depth);
+            },
+            _ => {},
+        }
+
+        let args = args
+            .iter()
+            .map(|arg| self.eval(arg, locals, depth))
+            .collect::<Option<Vec<_>>>()?;
+        call_pure_function(&name, &args)
+    }
+}
+
+/// Look up an object property, ignoring case as ARM does
+fn get_property<'v>(props: &'v IndexMap<String, BicepValue>, name: &str) -> Option<&'v BicepValue> {
+    props.get(name).or_else(|| {
+        props
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    })
+}
+
+/// Convert a value to text the way string interpolation does
+fn to_text(value: &BicepValue) -> String {
+    match value {
+        BicepValue::String(text) | BicepValue::Identifier(text) => text.clone(),
+        BicepValue::Int(n) => n.to_string(),
+        BicepValue::Bool(b) => b.to_string(),
+        BicepValue::Array(_) | BicepValue::Object(_) => {
+            serde_json::to_string(value).unwrap_or_default()
+        },
+    }
+}
+
+/// Convert a JSON value into a Bicep value
+pub(super) fn from_json(value: serde_json::Value) -> Option<BicepValue> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::Bool(b) => Some(BicepValue::Bool(b)),
+        serde_json::Value::Number(n) => n.as_i64().map(BicepValue::Int),
+        serde_json::Value::String(s) => Some(BicepValue::String(s)),
+        serde_json::Value::Array(items) => items
+            .into_iter()
+            .map(from_json)
+            .collect::<Option<Vec<_>>>()
+            .map(BicepValue::Array),
+        serde_json::Value::Object(props) => props
+            .into_iter()
+            .map(|(key, value)| Some((key, from_json(value)?)))
+            .collect::<Option<IndexMap<_, _>>>()
+            .map(BicepValue::Object),
+    }
+}
+
+/// Clamp a count argument to the bounds of a collection
+fn clamp_count(count: i64, len: usize) -> usize {
+    usize::try_from(count.max(0)).unwrap_or(0).min(len)
+}
+
+/// Evaluate a pure ARM template function with known arguments
+///
+/// # Arguments
+///
+/// * `name` - The lowercase function name
+/// * `args` - The evaluated arguments
+///
+/// # Returns
+///
+/// The result, or None if the function is unknown, not constant or the arguments are invalid
+fn call_pure_function(name: &str, args: &[BicepValue]) -> Option<BicepValue> {
+    use BicepValue::{Array, Bool, Int, Object, String as Str};
+
+    let result = match (name, args) {
+        // String functions
+        ("concat", [Array(_), ..]) => {
+            let mut items = Vec::new();
+            for arg in args {
+                let Array(values) = arg else { return None };
+                items.extend(values.iter().cloned());
+            }
+            Array(items)
+        },
+        ("concat", _) => Str(args.iter().map(to_text).collect()),
+        ("format", [Str(template), values @ ..]) => Str(format_string(template, values)?),
+        ("tolower", [Str(s)]) => Str(s.to_lowercase()),
+        ("toupper", [Str(s)]) => Str(s.to_uppercase()),
+        ("trim", [Str(s)]) => Str(s.trim().to_string()),
+        ("replace", [Str(s), Str(old), Str(new)]) => Str(s.replace(old.as_str(), new)),
+        ("substring", [Str(s), Int(start), rest @ ..]) => {
+            let chars: Vec<char> = s.chars().collect();
+            let start = usize::try_from(*start).ok()?;
+            let length = match rest {
+                [] => chars.len().checked_sub(start)?,
+                [Int(length)] => usize::try_from(*length).ok()?,
+                _ => return None,
+            };
+            Str(chars
+                .get(start..start.checked_add(length)?)?
+                .iter()
+                .collect())
+        },
+        ("startswith", [Str(s), Str(prefix)]) => {
+            Bool(s.to_lowercase().starts_with(&prefix.to_lowercase()))
+        },
+        ("endswith", [Str(s), Str(suffix)]) => {
+            Bool(s.to_lowercase().ends_with(&suffix.to_lowercase()))
+        },
+        ("indexof", [Str(s), Str(needle)]) => Int(char_index(
+            s.to_lowercase().find(&needle.to_lowercase()),
+            &s.to_lowercase(),
+        )),
+        ("lastindexof", [Str(s), Str(needle)]) => Int(char_index(
+            s.to_lowercase().rfind(&needle.to_lowercase()),
+            &s.to_lowercase(),
+        )),
+        ("indexof", [Array(items), item]) => Int(items
+            .iter()
+            .position(|i| i == item)
+            .map_or(-1, |i| i as i64)),
+        ("lastindexof", [Array(items), item]) => Int(items
+            .iter()
+            .rposition(|i| i == item)
+            .map_or(-1, |i| i as i64)),
+        ("split", [Str(s), Str(delimiter)]) => Array(
+            s.split(delimiter.as_str())
+                .map(|part| Str(part.to_string()))
+                .collect(),
+        ),
+        ("split", [Str(s), Array(delimiters)]) => {
+            let delimiters = delimiters
+                .iter()
+                .map(|d| match d {
+                    Str(d) => Some(d.as_str()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let mut parts = vec![s.clone()];
+            for delimiter in delimiters {
+                parts = parts
+                    .iter()
+                    .flat_map(|part| part.split(delimiter).map(str::to_string))
+                    .collect();
+            }
+            Array(parts.into_iter().map(Str).collect())
+        },
+        ("join", [Array(items), Str(delimiter)]) => {
+            if items.iter().any(|i| matches!(i, Array(_) | Object(_))) {
+                return None;
+            }
+            Str(items
+                .iter()
+                .map(to_text)
+                .collect::<Vec<_>>()
+                .join(delimiter))
+        },
+        ("padleft", [value, Int(width), rest @ ..]) => {
+            if *width > MAX_LENGTH {
+                return None;
+            }
+            let padding = match rest {
+                [] => ' ',
+                [Str(c)] if c.chars().count() == 1 => c.chars().next()?,
+                _ => return None,
+            };
+            let text = to_text(value);
+            let missing = clamp_count(*width, usize::MAX).saturating_sub(text.chars().count());
+            Str(std::iter::repeat_n(padding, missing)
+                .chain(text.chars())
+                .collect())
+        },
+        ("base64", [Str(s)]) => Str(base64_encode(s.as_bytes())),
+        ("base64tostring", [Str(s)]) => Str(String::from_utf8(base64_decode(s)?).ok()?),
+        ("string", [value]) => Str(to_text(value)),
+        ("int", [Int(n)]) => Int(*n),
+        ("int", [Str(s)]) => Int(s.trim().parse().ok()?),
+        ("bool", [Bool(b)]) => Bool(*b),
+        ("bool", [Int(n)]) => Bool(*n != 0),
+        ("bool", [Str(s)]) => Bool(s.trim().eq_ignore_ascii_case("true")),
+        ("json", [Str(s)]) => from_json(serde_json::from_str(s).ok()?)?,
+
+        // Collection functions
+        ("length", [Str(s)]) => Int(s.chars().count() as i64),
+        ("length", [Array(items)]) => Int(items.len() as i64),
+        ("length", [Object(props)]) => Int(props.len() as i64),
+        ("empty", [Str(s)]) => Bool(s.is_empty()),
+        ("empty", [Array(items)]) => Bool(items.is_empty()),
+        ("empty", [Object(props)]) => Bool(props.is_empty()),
+        ("contains", [Str(s), needle]) => Bool(s.contains(&to_text(needle))),
+        ("contains", [Array(items), item]) => Bool(items.contains(item)),
+        ("contains", [Object(props), Str(key)]) => Bool(get_property(props, key).is_some()),
+        ("first", [Str(s)]) => Str(s.chars().next()?.to_string()),
+        ("first", [Array(items)]) => items.first()?.clone(),
+        ("last", [Str(s)]) => Str(s.chars().last()?.to_string()),
+        ("last", [Array(items)]) => items.last()?.clone(),
+        ("take", [Str(s), Int(n)]) => Str(s.chars().take(clamp_count(*n, usize::MAX)).collect()),
+        ("take", [Array(items), Int(n)]) => Array(items[..clamp_count(*n, items.len())].to_vec()),
+        ("skip", [Str(s), Int(n)]) => Str(s.chars().skip(clamp_count(*n, usize::MAX)).collect()),
+        ("skip", [Array(items), Int(n)]) => Array(items[clamp_count(*n, items.len())..].to_vec()),
+        ("union", [Object(_), ..]) => {
+            let mut merged = IndexMap::new();
+            for arg in args {
+                let Object(props) = arg else { return None };
+                merge_objects(&mut merged, props);
+            }
+            Object(merged)
+        },
+        ("union", [Array(_), ..]) => {
+            let mut merged: Vec<BicepValue> = Vec::new();
+            for arg in args {
+                let Array(items) = arg else { return None };
+                for item in items {
+                    if !merged.contains(item) {
+                        merged.push(item.clone());
+                    }
+                }
+            }
+            Array(merged)
+        },
+        ("intersection", [Object(first), rest @ ..]) => {
+            let mut common = first.clone();
+            for arg in rest {
+                let Object(props) = arg else { return None };
+                common.retain(|key, value| props.get(key) == Some(value));
+            }
+            Object(common)
+        },
+        ("intersection", [Array(first), rest @ ..]) => {
+            let mut common: Vec<BicepValue> = Vec::new();
+            for item in first {
+                let in_all = rest
+                    .iter()
+                    .all(|arg| matches!(arg, Array(items) if items.contains(item)));
+                if in_all && !common.contains(item) {
+                    common.push(item.clone());
+                }
+            }
+            Array(common)
+        },
+        ("range", [Int(start), Int(count)]) => {
+            if !(0..=MAX_LENGTH).contains(count) {
+                return None;
+            }
+            Array(
+                (0..*count)
+                    .map(|i| start.checked_add(i).map(Int))
+                    .collect::<Option<_>>()?,
+            )
+        },
+        ("array", [Array(items)]) => Array(items.clone()),
+        ("array", [value]) => Array(vec![value.clone()]),
+        ("createarray", _) => Array(args.to_vec()),
+        ("createobject", _) => {
+            if !args.len().is_multiple_of(2) {
+                return None;
+            }
+            let mut props = IndexMap::new();
+            for pair in args.chunks(2) {
+                let Str(key) = &pair[0] else { return None };
+                props.insert(key.clone(), pair[1].clone());
+            }
+            Object(props)
+        },
+        ("items", [Object(props)]) => Array(
+            props
+                .iter()
+                .map(|(key, value)| {
+                    let mut item = IndexMap::new();
+                    item.insert("key".to_string(), Str(key.clone()));
+                    item.insert("value".to_string(), value.clone());
+                    Object(item)
+                })
+                .collect(),
+        ),
+        ("min" | "max", [Array(items)]) => return call_pure_function(name, items),
+        ("min" | "max", [_, ..]) => {
+            let numbers = args
+                .iter()
+                .map(|arg| match arg {
+                    Int(n) => Some(*n),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Int(if name == "min" {
+                numbers.into_iter().min()?
+            } else {
+                numbers.into_iter().max()?
+            })
+        },
+        _ => return None,
+    };
+
+    Some(result)
+}
+
+/// Convert a byte offset into a character offset, or -1 when not found
+fn char_index(byte_index: Option<usize>, text: &str) -> i64 {
+    byte_index.map_or(-1, |i| text[..i].chars().count() as i64)
+}
+
+/// Deep-merge object properties the way `union` does for objects
+fn merge_objects(target: &mut IndexMap<String, BicepValue>, source: &IndexMap<String, BicepValue>) {
+    for (key, value) in source {
+        match (target.get_mut(key), value) {
+            (Some(BicepValue::Object(existing)), BicepValue::Object(nested)) => {
+                merge_objects(existing, nested)
+            },
+            _ => {
+                target.insert(key.clone(), value.clone());
+            },
+        }
+    }
+}
+
+/// Apply .NET-style composite formatting (`{0}`, `{1}`, `{{`, `}}`) as used by `format`
+///
+/// Format specifiers after a colon (e.g. `{0:N2}`) are not supported.
+fn format_string(template: &str, values: &[BicepValue]) -> Option<String> {
+    let mut result = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                result.push('{');
+            },
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                result.push('}');
+            },
+            '{' => {
+                let mut index = String::new();
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                    index.push(c);
+                }
+                let index: usize = index.trim().parse().ok()?;
+                result.push_str(&to_text(values.get(index)?));
+            },
+            _ => result.push(c),
+        }
+    }
+
+    Some(result)
+}
+
+/// Standard base64 alphabet
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as padded standard base64
+fn base64_encode(data: &[u8]) -> String {
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let triple = (u32::from(bytes[0]) << 16) | (u32::from(bytes[1]) << 8) | u32::from(bytes[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (triple >> (18 - 6 * i)) & 0x3f;
+                result.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+/// Decode padded standard base64
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+    let mut result = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in text.bytes() {
+        let value = BASE64_ALPHABET.iter().position(|b| *b == c)? as u32;
+        buffer = (buffer << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{
+        BicepFunction, BicepFunctionArgument, BicepParameter, BicepType, BicepVariable,
+    };
+
+    fn sample_document() -> BicepDocument {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "prefix".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::String,
+                default_value: Some(BicepValue::String("Contoso".to_string())),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "environment".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::String,
+                default_value: Some(BicepValue::String("dev".to_string())),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "location".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::String,
+                default_value: Some(BicepValue::Identifier(
+                    "resourceGroup().location".to_string(),
+                )),
+                ..Default::default()
+            },
+        );
+        let expression = |text: &str| BicepValue::Identifier(text.to_string());
+        let string = |text: &str| BicepValue::String(text.to_string());
+        for (name, value) in [
+            (
+                "vaultName",
+                expression("toLower('${prefix}-${environment}-kv')"),
+            ),
+            ("baseName", string("${prefix}${suffix}")),
+            ("suffix", string("-app")),
+            ("sku", string("Standard_LRS")),
+            ("regional", string("${location}-kv")),
+        ] {
+            document.variables.insert(
+                name.to_string(),
+                BicepVariable {
+                    description: None,
+                    value,
+                    is_exported: false,
+                },
+            );
+        }
+        document
+    }
+
+    fn evaluate(evaluator: &Evaluator, text: &str) -> Option<BicepValue> {
+        evaluator.evaluate_source(text, &[])
+    }
+
+    fn string(text: &str) -> Option<BicepValue> {
+        Some(BicepValue::String(text.to_string()))
+    }
+
+    #[test]
+    fn test_symbols_resolve_in_any_order() {
+        let document = sample_document();
+        let evaluator = Evaluator::new(&document);
+
+        assert_eq!(
+            evaluator.symbol_value("vaultName"),
+            Some(&BicepValue::String("contoso-dev-kv".to_string()))
+        );
+        assert_eq!(
+            evaluator.symbol_value("baseName"),
+            Some(&BicepValue::String("Contoso-app".to_string()))
+        );
+        assert_eq!(
+            evaluator.symbol_value("sku"),
+            Some(&BicepValue::String("Standard_LRS".to_string()))
+        );
+        assert_eq!(evaluator.symbol_value("location"), None);
+        assert_eq!(evaluator.symbol_value("regional"), None);
+    }
+
+    #[test]
+    fn test_parameter_overrides() {
+        let document = sample_document();
+        let mut parameters = IndexMap::new();
+        parameters.insert(
+            "environment".to_string(),
+            BicepValue::String("prod".to_string()),
+        );
+        parameters.insert(
+            "location".to_string(),
+            BicepValue::String("westeurope".to_string()),
+        );
+        let evaluator = Evaluator::with_parameters(&document, parameters);
+
+        assert_eq!(
+            evaluator.symbol_value("vaultName"),
+            Some(&BicepValue::String("contoso-prod-kv".to_string()))
+        );
+        assert_eq!(
+            evaluator.symbol_value("regional"),
+            Some(&BicepValue::String("westeurope-kv".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_string_functions() {
+        let document = sample_document();
+        let evaluator = Evaluator::new(&document);
+
+        assert_eq!(
+            evaluate(&evaluator, "concat(prefix, '-', 1)"),
+            string("Contoso-1")
+        );
+        assert_eq!(
+            evaluate(&evaluator, "format('{0}-{1}-{{x}}', prefix, environment)"),
+            string("Contoso-dev-{x}")
+        );
+        assert_eq!(evaluate(&evaluator, "toUpper(environment)"), string("DEV"));
+        assert_eq!(
+            evaluate(&evaluator, "replace(prefix, 'C', 'K')"),
+            string("Kontoso")
+        );
+        assert_eq!(
+            evaluate(&evaluator, "substring(prefix, 1, 3)"),
+            string("ont")
+        );
+        assert_eq!(evaluate(&evaluator, "take(prefix, 3)"), string("Con"));
+        assert_eq!(
+            evaluate(&evaluator, "padLeft(environment, 5, '0')"),
+            string("00dev")
+        );
+        assert_eq!(evaluate(&evaluator, "base64('hello')"), string("aGVsbG8="));
+        assert_eq!(
+            evaluate(&evaluator, "base64ToString('aGVsbG8=')"),
+            string("hello")
+        );
+    }
+
+    #[test]
+    fn test_collection_functions_and_operators() {
+        let document = sample_document();
+        let evaluator = Evaluator::new(&document);
+
+        assert_eq!(
+            evaluate(&evaluator, "length([1, 2, 3]) * 2 + 1"),
+            Some(BicepValue::Int(7))
+        );
+        assert_eq!(
+            evaluate(&evaluator, "contains(['dev', 'test'], environment)"),
+            Some(BicepValue::Bool(true))
+        );
+        assert_eq!(
+            evaluate(
+                &evaluator,
+                "union({ a: 1, b: { c: 2 } }, { b: { d: 3 } }).b.d"
+            ),
+            Some(BicepValue::Int(3))
+        );
+        assert_eq!(
+            evaluate(&evaluator, "environment == 'prod' ? 'P' : 'N'"),
+            string("N")
+        );
+        assert_eq!(
+            evaluate(&evaluator, "[for i in range(1, 3): '${prefix}${i}']"),
+            Some(BicepValue::Array(vec![
+                BicepValue::String("Contoso1".to_string()),
+                BicepValue::String("Contoso2".to_string()),
+                BicepValue::String("Contoso3".to_string()),
+            ]))
+        );
+        assert_eq!(
+            evaluate(
+                &evaluator,
+                "filter(map(range(0, 4), x => x * 2), x => x > 2)"
+            ),
+            Some(BicepValue::Array(vec![
+                BicepValue::Int(4),
+                BicepValue::Int(6)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_user_defined_functions() {
+        let mut document = sample_document();
+        document.functions.insert(
+            "buildName".to_string(),
+            BicepFunction {
+                description: None,
+                metadata: IndexMap::new(),
+                arguments: vec![BicepFunctionArgument {
+                    name: "kind".to_string(),
+                    argument_type: BicepType::String,
+                    is_nullable: false,
+                }],
+                return_type: BicepType::String,
+                expression: "'${kind}-name'".to_string(),
+                is_exported: false,
+            },
+        );
+        let evaluator = Evaluator::new(&document);
+
+        assert_eq!(
+            evaluate(&evaluator, "buildName(environment)"),
+            string("dev-name")
+        );
+    }
+
+    #[test]
+    fn test_non_constant_expressions() {
+        let document = sample_document();
+        let evaluator = Evaluator::new(&document);
+
+        assert_eq!(evaluate(&evaluator, "resourceGroup().id"), None);
+        // Hashes are left to Azure Resource Manager, even of constant arguments
+        assert_eq!(
+            evaluate(&evaluator, "uniqueString(prefix, environment)"),
+            None
+        );
+        assert_eq!(evaluate(&evaluator, "guid(prefix)"), None);
+        assert_eq!(evaluator.evaluate_template("${location}-suffix", &[]), None);
+    }
+
+    #[test]
+    fn test_length_limits() {
+        let document = sample_document();
+        let evaluator = Evaluator::new(&document);
+
+        // Azure Resource Manager rejects a padded length over 10000
+        let padded = evaluate(&evaluator, "padLeft('a', 10000, '0')");
+        assert!(matches!(padded, Some(BicepValue::String(ref s)) if s.len() == 10000));
+        assert_eq!(evaluate(&evaluator, "padLeft('a', 10001, '0')"), None);
+
+        // and a range of more than 10000 items
+        let range = evaluate(&evaluator, "range(0, 10000)");
+        assert!(matches!(range, Some(BicepValue::Array(ref items)) if items.len() == 10000));
+        assert_eq!(evaluate(&evaluator, "range(0, 10001)"), None);
+        assert_eq!(evaluate(&evaluator, "range(0, 9223372036854775807)"), None);
+    }
+
+    #[test]
+    fn test_string_literals() {
+        let document = sample_document();
+        let evaluator = Evaluator::new(&document);
+
+        // Strings are never read as expressions, even when they name a symbol
+        for text in [
+            "prefix",
+            "toLower(prefix)",
+            "The name of the vault",
+            "10.0.0.0/16",
+        ] {
+            assert_eq!(
+                evaluator.evaluate_value(&BicepValue::String(text.to_string())),
+                string(text)
+            );
+        }
+        assert_eq!(
+            evaluator.evaluate_value(&BicepValue::Identifier("prefix".to_string())),
+            string("Contoso")
+        );
+        assert_eq!(
+            evaluator.evaluate_template("${prefix}-${i}", &[("i".to_string(), BicepValue::Int(2))]),
+            string("Contoso-2")
+        );
+    }
+
+    #[test]
+    fn test_effective_value() {
+        let document = sample_document();
+        let evaluator = Evaluator::new(&document);
+
+        assert_eq!(
+            evaluator.effective_value(&BicepValue::String("${prefix}-kv".to_string())),
+            string("Contoso-kv")
+        );
+        assert_eq!(
+            evaluator.effective_value(&BicepValue::String("westeurope".to_string())),
+            None
+        );
+    }
+}
@@ -0,0 +1,911 @@
+//! Expansion of a Bicep document for a given set of parameter values.
+//!
+//! This module reads parameter values from `.bicepparam` or ARM `parameters.json`
+//! files, substitutes them into a parsed [`BicepDocument`] and works out which
+//! resources and modules would deploy: conditions are evaluated, loops are unrolled
+//! and names are resolved wherever they are statically knowable.
+
+use std::{fmt, fs, path::Path};
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use tracing::warn;
+
+use super::evaluator::{from_json, Evaluator};
+use super::expressions::loop_variables;
+use crate::{
+    error::BicepError,
+    parsing::{BicepDocument, BicepValue},
+};
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// The kind of declaration that deploys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentKind {
+    Resource,
+    Module,
+}
+
+/// Whether a declaration deploys for the given parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentStatus {
+    /// The declaration deploys
+    Deployed,
+    /// The condition is false, or the loop has no iterations
+    Skipped,
+    /// The condition or loop depends on values that are not statically knowable
+    Unknown,
+}
+
+/// A single deployed instance of a resource or module
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeploymentInstance {
+    /// The loop index, for declarations with a loop
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    /// The resolved name, or the name expression if it could not be resolved
+    pub name: String,
+    /// Whether the name was resolved
+    pub resolved: bool,
+}
+
+/// The expansion of a single resource or module declaration
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExpandedDeployment {
+    /// The symbolic name of the declaration
+    pub symbol: String,
+    /// Whether the declaration is a resource or a module
+    pub kind: DeploymentKind,
+    /// The resource type, or the module source
+    #[serde(rename = "type")]
+    pub deployment_type: String,
+    /// Whether the declaration deploys
+    pub status: DeploymentStatus,
+    /// The instances that deploy
+    pub instances: Vec<DeploymentInstance>,
+}
+
+/// The resources and modules a document deploys for a given set of parameters
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Expansion {
+    /// Required parameters that have neither a supplied value nor a default value
+    pub missing_parameters: Vec<String>,
+    /// The expanded resource and module declarations, in document order
+    pub deployments: Vec<ExpandedDeployment>,
+}
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+/// Read parameter values from a parameters file
+///
+/// Files with a `.json` extension are read as ARM parameters files, anything else as
+/// a `.bicepparam` file.
+///
+/// # Arguments
+///
+/// * `path` - Path to the parameters file
+///
+/// # Returns
+///
+/// A Result containing the statically knowable parameter values
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not a valid parameters file
+pub fn read_parameters_file<P: AsRef<Path>>(
+    path: P,
+) -> Result<IndexMap<String, BicepValue>, BicepError> {
+    let path = path.as_ref();
+    let source = fs::read_to_string(path).map_err(|e| BicepError::io(path, e))?;
+
+    if path.extension().is_some_and(|ext| ext == "json") {
+        parse_parameters_json(&source)
+    } else {
+        parse_bicepparam(&source)
+    }
+}
+
+/// Parse the parameter values of a `.bicepparam` file
+///
+/// `param` values may refer to the file's `var` declarations. Values that are not
+/// statically knowable (e.g. `readEnvironmentVariable()` or `az.getSecret()`) are
+/// skipped with a warning.
+///
+/// # Arguments
+///
+/// * `source` - The content of the `.bicepparam` file
+///
+/// # Returns
+///
+/// A Result containing the parameter values
+///
+/// # Errors
+///
+/// Returns an error if a `param` or `var` statement has no value
+pub fn parse_bicepparam(source: &str) -> Result<IndexMap<String, BicepValue>, BicepError> {
+    let document = BicepDocument::default();
+    let evaluator = Evaluator::new(&document);
+
+    let mut variables: Vec<(String, BicepValue)> = Vec::new();
+    let mut parameters = IndexMap::new();
+
+    for statement in split_statements(source) {
+        let Some((keyword, rest)) = statement.split_once(char::is_whitespace) else {
+            continue;
+        };
+        if !matches!(keyword, "param" | "var") {
+            // using, import, extends and type statements carry no values
+            continue;
+        }
+
+        let Some((name, value)) = rest.split_once('=') else {
+            return Err(BicepError::Syntax {
+                message: format!("Missing value in statement: {statement}"),
+                span: None,
+            });
+        };
+        let name = name.trim().to_string();
+
+        match evaluator.evaluate_source(value.trim(), &variables) {
+            Some(value) if keyword == "var" => variables.push((name, value)),
+            Some(value) => {
+                parameters.insert(name, value);
+            },
+            None => warn!("Value of {keyword} '{name}' is not statically knowable, skipping"),
+        }
+    }
+
+    Ok(parameters)
+}
+
+/// Parse the parameter values of an ARM `parameters.json` file
+///
+/// Key Vault references are skipped with a warning.
+///
+/// # Arguments
+///
+/// * `source` - The content of the parameters file
+///
+/// # Returns
+///
+/// A Result containing the parameter values
+///
+/// # Errors
+///
+/// Returns an error if the content is not JSON or has no `parameters` object
+pub fn parse_parameters_json(source: &str) -> Result<IndexMap<String, BicepValue>, BicepError> {
+    let json: serde_json::Value = serde_json::from_str(source)?;
+    let entries = json
+        .get("parameters")
+        .and_then(|parameters| parameters.as_object())
+        .ok_or_else(|| BicepError::Syntax {
+            message: "Parameters file has no 'parameters' object".to_string(),
+            span: None,
+        })?;
+
+    let mut parameters = IndexMap::new();
+    for (name, entry) in entries {
+        match entry.get("value").cloned().and_then(from_json) {
+            Some(value) => {
+                parameters.insert(name.clone(), value);
+            },
+            None => warn!("Parameter '{name}' has no literal value, skipping"),
+        }
+    }
+
+    Ok(parameters)
+}
+
+/// Split `.bicepparam` source into statements
+///
+/// Statements end at a line break outside of brackets and strings. Comments are removed.
+fn split_statements(source: &str) -> Vec<String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    let mut flush = |current: &mut String| {
+        let statement = current.trim();
+        if !statement.is_empty() {
+            statements.push(statement.to_string());
+        }
+        current.clear();
+    };
+
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1)) {
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            },
+            ('/', Some('*')) => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            },
+            ('\'', _) => {
+                i = copy_string(&chars, i, &mut current);
+                continue;
+            },
+            ('(' | '[' | '{', _) => depth += 1,
+            (')' | ']' | '}', _) => depth = depth.saturating_sub(1),
+            ('\n', _) if depth == 0 => {
+                flush(&mut current);
+                i += 1;
+                continue;
+            },
+            _ => {},
+        }
+        current.push(chars[i]);
+        i += 1;
+    }
+    flush(&mut current);
+
+    statements
+}
+
+/// Copy a string literal starting at `start`, including any interpolations
+///
+/// Returns the position after the closing quote.
+fn copy_string(chars: &[char], start: usize, output: &mut String) -> usize {
+    let multiline = chars.get(start + 1) == Some(&'\'') && chars.get(start + 2) == Some(&'\'');
+    let mut i = start;
+
+    if multiline {
+        output.push_str("'''");
+        i += 3;
+        while i < chars.len() {
+            if chars[i..].starts_with(&['\'', '\'', '\'']) {
+                output.push_str("'''");
+                return i + 3;
+            }
+            output.push(chars[i]);
+            i += 1;
+        }
+        return i;
+    }
+
+    output.push('\'');
+    i += 1;
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1)) {
+            ('\\', Some(escaped)) => {
+                output.push('\\');
+                output.push(*escaped);
+                i += 2;
+            },
+            ('\'', _) => {
+                output.push('\'');
+                return i + 1;
+            },
+            ('$', Some('{')) => {
+                output.push_str("${");
+                i += 2;
+                let mut depth = 1;
+                while i < chars.len() && depth > 0 {
+                    match chars[i] {
+                        '\'' => {
+                            i = copy_string(chars, i, output);
+                            continue;
+                        },
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {},
+                    }
+                    output.push(chars[i]);
+                    i += 1;
+                }
+            },
+            (c, _) => {
+                output.push(c);
+                i += 1;
+            },
+        }
+    }
+    i
+}
+
+/// Expand a document for a set of parameter values
+///
+/// # Arguments
+///
+/// * `document` - The parsed Bicep document
+/// * `parameters` - Parameter values, e.g. read with [`read_parameters_file`]
+///
+/// # Returns
+///
+/// The resources and modules the document deploys. Existing resources are not included.
+pub fn expand_document(
+    document: &BicepDocument,
+    parameters: IndexMap<String, BicepValue>,
+) -> Expansion {
+    let missing_parameters = document
+        .parameters
+        .iter()
+        .filter(|(name, parameter)| {
+            parameter.default_value.is_none()
+                && !parameter.is_nullable
+                && !parameters.contains_key(*name)
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    let evaluator = Evaluator::with_parameters(document, parameters);
+    let mut deployments: Vec<ExpandedDeployment> = Vec::new();
+    let mut parents = Vec::new();
+
+    for (symbol, resource) in &document.resources {
+        if resource.existing {
+            continue;
+        }
+
+        deployments.push(expand_declaration(
+            &evaluator,
+            symbol,
+            DeploymentKind::Resource,
+            &resource.resource_type,
+            Some(&resource.name),
+            resource.condition.as_deref(),
+            resource.loop_statement.as_deref(),
+        ));
+        // Child resources are nested in their parent or name it with a `parent:` property
+        parents.push(match &resource.parent {
+            Some(parent) => Some(parent_symbol(parent)),
+            None => symbol
+                .rsplit_once("::")
+                .map(|(parent, _)| parent.to_string()),
+        });
+    }
+    propagate_parent_status(&mut deployments, &parents);
+
+    for (symbol, module) in &document.modules {
+        deployments.push(expand_declaration(
+            &evaluator,
+            symbol,
+            DeploymentKind::Module,
+            &module.source.to_string(),
+            module.deployment_name.as_deref(),
+            module.condition.as_deref(),
+            module.loop_statement.as_deref(),
+        ));
+    }
+
+    Expansion {
+        missing_parameters,
+        deployments,
+    }
+}
+
+/// Get the symbol of the resource a `parent:` property refers to
+///
+/// The property is an expression such as `account::blobService` or `vaults[i]`.
+fn parent_symbol(parent: &str) -> String {
+    parent
+        .trim()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | ':'))
+        .collect::<String>()
+        .trim_end_matches(':')
+        .to_string()
+}
+
+/// Give child resources the status of their parent, as they only deploy with it
+///
+/// A parent can be declared after its children, and be a child itself, so statuses
+/// are passed down until none changes.
+///
+/// # Arguments
+///
+/// * `deployments` - The expanded resources
+/// * `parents` - The symbol of the parent of each resource, if it has one
+fn propagate_parent_status(deployments: &mut [ExpandedDeployment], parents: &[Option<String>]) {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (index, parent) in parents.iter().enumerate() {
+            let Some(parent_status) = parent.as_ref().and_then(|parent| {
+                deployments
+                    .iter()
+                    .find(|d| d.kind == DeploymentKind::Resource && &d.symbol == parent)
+                    .map(|d| d.status)
+            }) else {
+                continue;
+            };
+            let deployment = &mut deployments[index];
+            match (parent_status, deployment.status) {
+                (
+                    DeploymentStatus::Skipped,
+                    DeploymentStatus::Deployed | DeploymentStatus::Unknown,
+                ) => {
+                    deployment.status = DeploymentStatus::Skipped;
+                    deployment.instances.clear();
+                    changed = true;
+                },
+                (DeploymentStatus::Unknown, DeploymentStatus::Deployed) => {
+                    deployment.status = DeploymentStatus::Unknown;
+                    changed = true;
+                },
+                _ => {},
+            }
+        }
+    }
+}
+
+/// Expand a single resource or module declaration
+fn expand_declaration(
+    evaluator: &Evaluator,
+    symbol: &str,
+    kind: DeploymentKind,
+    deployment_type: &str,
+    name: Option<&str>,
+    condition: Option<&str>,
+    loop_statement: Option<&str>,
+) -> ExpandedDeployment {
+    let mut status = DeploymentStatus::Deployed;
+    let mut instances = Vec::new();
+
+    match loop_statement {
+        None => {
+            status = condition_status(evaluator, condition, &[]);
+            if status != DeploymentStatus::Skipped {
+                instances.push(resolve_name(evaluator, name, &[], None));
+            }
+        },
+        Some(loop_statement) => {
+            let source = loop_statement
+                .strip_prefix("for ")
+                .and_then(|rest| rest.split_once(" in "))
+                .map(|(_, source)| source.trim());
+
+            match source.and_then(|source| evaluator.evaluate_source(source, &[])) {
+                Some(BicepValue::Array(items)) => {
+                    let variables = loop_variables(loop_statement);
+                    for (i, item) in items.into_iter().enumerate() {
+                        let mut locals = Vec::new();
+                        if let Some(item_name) = variables.first() {
+                            locals.push((item_name.clone(), item));
+                        }
+                        if let Some(index_name) = variables.get(1) {
+                            locals.push((index_name.clone(), BicepValue::Int(i as i64)));
+                        }
+
+                        match condition_status(evaluator, condition, &locals) {
+                            DeploymentStatus::Skipped => continue,
+                            DeploymentStatus::Unknown => status = DeploymentStatus::Unknown,
+                            DeploymentStatus::Deployed => {},
+                        }
+                        instances.push(resolve_name(evaluator, name, &locals, Some(i)));
+                    }
+
+                    if instances.is_empty() {
+                        status = DeploymentStatus::Skipped;
+                    }
+                },
+                _ => status = DeploymentStatus::Unknown,
+            }
+        },
+    }
+
+    ExpandedDeployment {
+        symbol: symbol.to_string(),
+        kind,
+        deployment_type: deployment_type.to_string(),
+        status,
+        instances,
+    }
+}
+
+/// Evaluate a condition to a deployment status
+fn condition_status(
+    evaluator: &Evaluator,
+    condition: Option<&str>,
+    locals: &[(String, BicepValue)],
+) -> DeploymentStatus {
+    let Some(condition) = condition else {
+        return DeploymentStatus::Deployed;
+    };
+
+    match evaluator.evaluate_source(condition, locals) {
+        Some(BicepValue::Bool(true)) => DeploymentStatus::Deployed,
+        Some(BicepValue::Bool(false)) => DeploymentStatus::Skipped,
+        _ => DeploymentStatus::Unknown,
+    }
+}
+
+/// Resolve the name of a deployed instance
+///
+/// A module without a `name` property, whose deployment name Bicep generates, has an
+/// empty, unresolved name.
+fn resolve_name(
+    evaluator: &Evaluator,
+    name: Option<&str>,
+    locals: &[(String, BicepValue)],
+    index: Option<usize>,
+) -> DeploymentInstance {
+    let Some(name) = name else {
+        return DeploymentInstance {
+            index,
+            name: String::new(),
+            resolved: false,
+        };
+    };
+
+    match evaluator.evaluate_template(name, locals) {
+        Some(BicepValue::String(resolved)) => DeploymentInstance {
+            index,
+            name: resolved,
+            resolved: true,
+        },
+        Some(value) => DeploymentInstance {
+            index,
+            name: value.pretty_format(),
+            resolved: true,
+        },
+        None => DeploymentInstance {
+            index,
+            name: name.to_string(),
+            resolved: false,
+        },
+    }
+}
+
+impl Expansion {
+    /// Get the declarations that deploy for certain
+    pub fn deployed(&self) -> impl Iterator<Item = &ExpandedDeployment> {
+        self.deployments
+            .iter()
+            .filter(|d| d.status == DeploymentStatus::Deployed)
+    }
+}
+
+impl fmt::Display for DeploymentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeploymentKind::Resource => write!(f, "resource"),
+            DeploymentKind::Module => write!(f, "module"),
+        }
+    }
+}
+
+impl fmt::Display for Expansion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.missing_parameters.is_empty() {
+            writeln!(
+                f,
+                "Missing parameters: {}\n",
+                self.missing_parameters.join(", ")
+            )?;
+        }
+
+        for (status, heading) in [
+            (DeploymentStatus::Deployed, "Deployed"),
+            (DeploymentStatus::Unknown, "Not statically knowable"),
+            (DeploymentStatus::Skipped, "Skipped"),
+        ] {
+            let deployments = self
+                .deployments
+                .iter()
+                .filter(|d| d.status == status)
+                .collect::<Vec<_>>();
+            if deployments.is_empty() {
+                continue;
+            }
+
+            writeln!(f, "{heading}:")?;
+            for deployment in deployments {
+                writeln!(
+                    f,
+                    "  {} {} ({})",
+                    deployment.kind, deployment.symbol, deployment.deployment_type
+                )?;
+                for instance in &deployment.instances {
+                    let index = instance
+                        .index
+                        .map(|i| format!("[{i}] "))
+                        .unwrap_or_default();
+                    let name = match (instance.name.is_empty(), instance.resolved) {
+                        (true, _) => "(generated name)".to_string(),
+                        (false, true) => instance.name.clone(),
+                        (false, false) => format!("{} (unresolved)", instance.name),
+                    };
+                    writeln!(f, "    {index}{name}")?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{
+        BicepModule, BicepParameter, BicepResource, BicepType, BicepVariable, ModuleSource,
+    };
+
+    fn sample_document() -> BicepDocument {
+        let mut document = BicepDocument::default();
+        for (name, default_value) in [
+            ("environment", None),
+            ("deployVault", Some(BicepValue::Bool(false))),
+            ("regions", Some(BicepValue::Array(vec![]))),
+        ] {
+            document.parameters.insert(
+                name.to_string(),
+                BicepParameter {
+                    parameter_type: BicepType::String,
+                    default_value,
+                    ..Default::default()
+                },
+            );
+        }
+        document.variables.insert(
+            "prefix".to_string(),
+            BicepVariable {
+                description: None,
+                value: BicepValue::String("contoso-${environment}".to_string()),
+                is_exported: false,
+            },
+        );
+        document.resources.insert(
+            "vault".to_string(),
+            BicepResource {
+                name: "${prefix}-kv".to_string(),
+                resource_type: "Microsoft.KeyVault/vaults".to_string(),
+                condition: Some("(deployVault)".to_string()),
+                ..Default::default()
+            },
+        );
+        document.resources.insert(
+            "vault::secret".to_string(),
+            BicepResource {
+                name: "admin".to_string(),
+                resource_type: "Microsoft.KeyVault/vaults/secrets".to_string(),
+                ..Default::default()
+            },
+        );
+        document.resources.insert(
+            "storage".to_string(),
+            BicepResource {
+                name: "${toLower('st${region}${i}')}".to_string(),
+                resource_type: "Microsoft.Storage/storageAccounts".to_string(),
+                loop_statement: Some("for (region, i) in regions".to_string()),
+                ..Default::default()
+            },
+        );
+        document.resources.insert(
+            "logs".to_string(),
+            BicepResource {
+                name: "${prefix}-logs".to_string(),
+                resource_type: "Microsoft.OperationalInsights/workspaces".to_string(),
+                condition: Some("(resourceGroup().location == 'westeurope')".to_string()),
+                ..Default::default()
+            },
+        );
+        document.modules.insert(
+            "network".to_string(),
+            BicepModule {
+                description: None,
+                name: "network".to_string(),
+                deployment_name: Some("${prefix}-vnet".to_string()),
+                source: ModuleSource::LocalPath("./network.bicep".to_string()),
+                depends_on: None,
+                condition: None,
+                loop_statement: None,
+                batch_size: None,
+                params: IndexMap::new(),
+            },
+        );
+        document
+    }
+
+    #[test]
+    fn test_parse_bicepparam() {
+        let source = r#"
+using './main.bicep'
+
+// Shared prefix
+var prefix = 'contoso'
+
+param environment = 'prod'
+param name = '${prefix}-${toUpper('app')}' /* inline comment */
+param regions = [
+  'WestEurope'
+  'NorthEurope'
+]
+param tags = {
+  owner: 'platform'
+  'cost-center': 42
+}
+param secret = az.getSecret('sub', 'rg', 'kv', 'secret')
+"#;
+        let parameters = parse_bicepparam(source).unwrap();
+
+        assert_eq!(
+            parameters.keys().collect::<Vec<_>>(),
+            vec!["environment", "name", "regions", "tags"]
+        );
+        assert_eq!(
+            parameters["name"],
+            BicepValue::String("contoso-APP".to_string())
+        );
+        assert_eq!(
+            parameters["regions"],
+            BicepValue::Array(vec![
+                BicepValue::String("WestEurope".to_string()),
+                BicepValue::String("NorthEurope".to_string()),
+            ])
+        );
+        let BicepValue::Object(tags) = &parameters["tags"] else {
+            panic!("Expected tags to be an object");
+        };
+        assert_eq!(tags["cost-center"], BicepValue::Int(42));
+    }
+
+    #[test]
+    fn test_parse_parameters_json() {
+        let source = r#"{
+  "$schema": "https://schema.management.azure.com/schemas/2019-04-01/deploymentParameters.json#",
+  "contentVersion": "1.0.0.0",
+  "parameters": {
+    "environment": { "value": "prod" },
+    "count": { "value": 3 },
+    "secret": { "reference": { "keyVault": { "id": "/subscriptions/x" }, "secretName": "s" } }
+  }
+}"#;
+        let parameters = parse_parameters_json(source).unwrap();
+
+        assert_eq!(parameters.len(), 2);
+        assert_eq!(parameters["count"], BicepValue::Int(3));
+        assert!(parse_parameters_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_expand_document() {
+        let document = sample_document();
+        let mut parameters = IndexMap::new();
+        parameters.insert(
+            "environment".to_string(),
+            BicepValue::String("prod".to_string()),
+        );
+        parameters.insert(
+            "regions".to_string(),
+            BicepValue::Array(vec![
+                BicepValue::String("WestEurope".to_string()),
+                BicepValue::String("NorthEurope".to_string()),
+            ]),
+        );
+        let expansion = expand_document(&document, parameters);
+
+        assert!(expansion.missing_parameters.is_empty());
+
+        let status = |symbol: &str| {
+            expansion
+                .deployments
+                .iter()
+                .find(|d| d.symbol == symbol)
+                .map(|d| d.status)
+        };
+        assert_eq!(status("vault"), Some(DeploymentStatus::Skipped));
+        assert_eq!(status("vault::secret"), Some(DeploymentStatus::Skipped));
+        assert_eq!(status("logs"), Some(DeploymentStatus::Unknown));
+
+        let storage = expansion.deployed().next().unwrap();
+        assert_eq!(storage.symbol, "storage");
+        assert_eq!(
+            storage
+                .instances
+                .iter()
+                .map(|i| i.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["stwesteurope0", "stnortheurope1"]
+        );
+
+        let logs = &expansion.deployments[3];
+        assert_eq!(logs.instances[0].name, "contoso-prod-logs");
+
+        // Modules deploy under the name in their body, not their symbolic name
+        let network = &expansion.deployments[4];
+        assert_eq!(network.symbol, "network");
+        assert_eq!(network.instances[0].name, "contoso-prod-vnet");
+        assert!(network.instances[0].resolved);
+    }
+
+    #[test]
+    fn test_expand_parent_property() {
+        let mut document = sample_document();
+        // Declared before the parent it names, which is itself a nested child
+        document.resources.shift_insert(
+            0,
+            "secretAccess".to_string(),
+            BicepResource {
+                name: "access".to_string(),
+                resource_type: "Microsoft.KeyVault/vaults/secrets/accessPolicies".to_string(),
+                parent: Some("vault::secret".to_string()),
+                ..Default::default()
+            },
+        );
+        document.resources.insert(
+            "diagnostics".to_string(),
+            BicepResource {
+                name: "default".to_string(),
+                resource_type: "Microsoft.OperationalInsights/workspaces/tables".to_string(),
+                parent: Some("logs".to_string()),
+                ..Default::default()
+            },
+        );
+        document.resources.insert(
+            "blobs".to_string(),
+            BicepResource {
+                name: "default".to_string(),
+                resource_type: "Microsoft.Storage/storageAccounts/blobServices".to_string(),
+                parent: Some("storage[0]".to_string()),
+                ..Default::default()
+            },
+        );
+        let mut parameters = IndexMap::new();
+        parameters.insert(
+            "environment".to_string(),
+            BicepValue::String("prod".to_string()),
+        );
+        parameters.insert(
+            "regions".to_string(),
+            BicepValue::Array(vec![BicepValue::String("WestEurope".to_string())]),
+        );
+        let expansion = expand_document(&document, parameters);
+
+        let deployment = |symbol: &str| {
+            expansion
+                .deployments
+                .iter()
+                .find(|d| d.symbol == symbol)
+                .unwrap()
+        };
+        assert_eq!(deployment("secretAccess").status, DeploymentStatus::Skipped);
+        assert!(deployment("secretAccess").instances.is_empty());
+        assert_eq!(deployment("diagnostics").status, DeploymentStatus::Unknown);
+        assert_eq!(deployment("blobs").status, DeploymentStatus::Deployed);
+    }
+
+    #[test]
+    fn test_expand_module_without_name() {
+        let mut document = sample_document();
+        document.modules["network"].deployment_name = None;
+        let expansion = expand_document(&document, IndexMap::new());
+
+        let network = &expansion.deployments[4];
+        assert_eq!(network.instances[0].name, "");
+        assert!(!network.instances[0].resolved);
+        assert!(expansion.to_string().contains("(generated name)"));
+    }
+
+    #[test]
+    fn test_expand_document_missing_parameters() {
+        let document = sample_document();
+        let expansion = expand_document(&document, IndexMap::new());
+
+        assert_eq!(expansion.missing_parameters, vec!["environment"]);
+        // An empty loop deploys nothing
+        assert_eq!(expansion.deployments[2].status, DeploymentStatus::Skipped);
+        assert!(expansion
+            .to_string()
+            .contains("Missing parameters: environment"));
+    }
+}
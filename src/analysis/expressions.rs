@@ -0,0 +1,593 @@
+//! Expression tokenization for Bicep values.
+//!
+//! The parser stores expressions (e.g. `toLower(prefix)` or `'${name}-kv'`) as the
+//! text they were written with. This module turns that text back into tokens so that
+//! the analysis passes can find the symbols an expression refers to.
+//!
+//! Values are not tagged with their origin, so a literal string and an expression
+//! look the same once parsed. Text that does not lex as an expression, or that reads
+//! as prose (two operands in a row, e.g. `Name of the storage account`), is treated
+//! as a literal and yields no references.
+
+use crate::error::BicepError;
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// A single token of a Bicep expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    /// An identifier, such as a symbol name, function name or property name
+    Identifier(String),
+    /// An integer literal
+    Integer(i64),
+    /// A string literal, split into literal text and interpolated expressions
+    String(Vec<StringSegment>),
+    /// An operator or punctuation symbol
+    Symbol(&'static str),
+}
+
+/// A segment of a (possibly interpolated) string literal
+#[derive(Debug, Clone, PartialEq)]
+pub enum StringSegment {
+    /// Literal text with escape sequences already resolved
+    Literal(String),
+    /// The tokens of a `${...}` interpolation
+    Interpolation(Vec<Token>),
+}
+
+/// How the text of a stored value reads once tokenized
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueText {
+    /// Plain literal text
+    Literal,
+    /// An expression
+    Expression(Vec<Token>),
+    /// The content of an interpolated string, without its quotes
+    Template(Vec<StringSegment>),
+}
+
+/// Symbols recognised by the lexer, longest first so that multi-character
+/// operators win over their single-character prefixes
+const SYMBOLS: &[&str] = &[
+    "::", "?.", "??", "==", "!=", "<=", ">=", "&&", "||", "=>", "=~", "!~", "(", ")", "[", "]",
+    "{", "}", ",", ".", ":", "?", "!", "+", "-", "*", "/", "%", "<", ">", "=", "@", "|", ";",
+];
+
+/// Keywords that can appear inside expressions but never name a symbol
+const KEYWORDS: &[&str] = &["for", "in", "if", "true", "false", "null"];
+
+/// Character-level lexer state
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+impl Lexer {
+    fn new(text: &str) -> Self {
+        Lexer {
+            chars: text.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn starts_with(&self, pattern: &str) -> bool {
+        pattern
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.peek(i) == Some(c))
+    }
+
+    /// Lex tokens until the end of input, or until the closing brace of an
+    /// interpolation when `in_interpolation` is set
+    fn lex_tokens(&mut self, in_interpolation: bool) -> Result<Vec<Token>, BicepError> {
+        let mut tokens = Vec::new();
+        let mut depth = 0usize;
+
+        while let Some(c) = self.peek(0) {
+            if c.is_whitespace() {
+                self.pos += 1;
+            } else if self.starts_with("//") {
+                while let Some(c) = self.peek(0) {
+                    if c == '\n' {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+            } else if self.starts_with("/*") {
+                self.pos += 2;
+                while !self.starts_with("*/") {
+                    if self.peek(0).is_none() {
+                        return Err(syntax_error("Unterminated comment"));
+                    }
+                    self.pos += 1;
+                }
+                self.pos += 2;
+            } else if c == '\'' {
+                tokens.push(Token::String(self.lex_string()?));
+            } else if c.is_ascii_digit() {
+                let start = self.pos;
+                while self.peek(0).is_some_and(|c| c.is_ascii_digit()) {
+                    self.pos += 1;
+                }
+                let text: String = self.chars[start..self.pos].iter().collect();
+                tokens.push(Token::Integer(text.parse()?));
+            } else if c.is_alphabetic() || c == '_' {
+                let start = self.pos;
+                while self
+                    .peek(0)
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_')
+                {
+                    self.pos += 1;
+                }
+                tokens.push(Token::Identifier(
+                    self.chars[start..self.pos].iter().collect(),
+                ));
+            } else if let Some(symbol) = SYMBOLS.iter().find(|s| self.starts_with(s)) {
+                if *symbol == "{" {
+                    depth += 1;
+                } else if *symbol == "}" {
+                    if depth == 0 && in_interpolation {
+                        self.pos += 1;
+                        return Ok(tokens);
+                    }
+                    depth = depth.saturating_sub(1);
+                }
+                self.pos += symbol.len();
+                tokens.push(Token::Symbol(symbol));
+            } else {
+                return Err(syntax_error(format!(
+                    "Unexpected character '{c}' in expression"
+                )));
+            }
+        }
+
+        if in_interpolation {
+            return Err(syntax_error("Unterminated string interpolation"));
+        }
+        Ok(tokens)
+    }
+
+    /// Lex a string literal starting at the opening quote
+    fn lex_string(&mut self) -> Result<Vec<StringSegment>, BicepError> {
+        if self.starts_with("'''") {
+            // Multi-line strings have neither escapes nor interpolation
+            self.pos += 3;
+            let start = self.pos;
+            while !self.starts_with("'''") {
+                if self.peek(0).is_none() {
+                    return Err(syntax_error("Unterminated multi-line string"));
+                }
+                self.pos += 1;
+            }
+            let text: String = self.chars[start..self.pos].iter().collect();
+            self.pos += 3;
+            return Ok(vec![StringSegment::Literal(
+                text.strip_prefix('\n').unwrap_or(&text).to_string(),
+            )]);
+        }
+
+        self.pos += 1;
+        let segments = self.lex_string_body(true)?;
+        Ok(segments)
+    }
+
+    /// Lex the body of a single-line string, up to the closing quote when
+    /// `quoted` is set or up to the end of input otherwise
+    fn lex_string_body(&mut self, quoted: bool) -> Result<Vec<StringSegment>, BicepError> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+
+        loop {
+            match self.peek(0) {
+                None if quoted => return Err(syntax_error("Unterminated string")),
+                None => break,
+                Some('\'') if quoted => {
+                    self.pos += 1;
+                    break;
+                },
+                Some('\\') => {
+                    let escaped = self
+                        .peek(1)
+                        .ok_or_else(|| syntax_error("Unterminated escape sequence"))?;
+                    self.pos += 2;
+                    match escaped {
+                        'n' => literal.push('\n'),
+                        'r' => literal.push('\r'),
+                        't' => literal.push('\t'),
+                        'u' if self.peek(0) == Some('{') => {
+                            let start = self.pos + 1;
+                            while self.peek(0).is_some_and(|c| c != '}') {
+                                self.pos += 1;
+                            }
+                            let hex: String = self.chars[start..self.pos].iter().collect();
+                            self.pos += 1;
+                            let code = u32::from_str_radix(&hex, 16)?;
+                            literal.push(
+                                char::from_u32(code)
+                                    .ok_or_else(|| syntax_error("Invalid unicode escape"))?,
+                            );
+                        },
+                        other => literal.push(other),
+                    }
+                },
+                Some('$') if self.peek(1) == Some('{') => {
+                    self.pos += 2;
+                    if !literal.is_empty() {
+                        segments.push(StringSegment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(StringSegment::Interpolation(self.lex_tokens(true)?));
+                },
+                Some(c) => {
+                    literal.push(c);
+                    self.pos += 1;
+                },
+            }
+        }
+
+        if !literal.is_empty() || segments.is_empty() {
+            segments.push(StringSegment::Literal(literal));
+        }
+        Ok(segments)
+    }
+}
+
+/// Tokenize a Bicep expression
+///
+/// # Arguments
+///
+/// * `text` - The expression text, e.g. `toLower('${prefix}kv')`
+///
+/// # Returns
+///
+/// A Result containing the tokens of the expression
+///
+/// # Errors
+///
+/// Returns an error if the text contains characters that cannot appear in an
+/// expression or has unterminated strings, interpolations or comments
+pub fn tokenize(text: &str) -> Result<Vec<Token>, BicepError> {
+    Lexer::new(text).lex_tokens(false)
+}
+
+/// Tokenize the content of an interpolated string without its surrounding quotes
+///
+/// The parser keeps interpolated strings as their template text (e.g. `${prefix}-kv`),
+/// so this reads the text as the body of a string literal.
+///
+/// # Arguments
+///
+/// * `text` - The template text
+///
+/// # Returns
+///
+/// A Result containing the literal and interpolated segments of the template
+///
+/// # Errors
+///
+/// Returns an error if an interpolation is malformed
+pub fn tokenize_template(text: &str) -> Result<Vec<StringSegment>, BicepError> {
+    Lexer::new(text).lex_string_body(false)
+}
+
+/// Create an error for expression text that does not lex
+fn syntax_error(message: impl Into<String>) -> BicepError {
+    BicepError::Syntax {
+        message: message.into(),
+        span: None,
+    }
+}
+
+/// Classify the text of a stored value
+///
+/// # Arguments
+///
+/// * `text` - The value text as stored by the parser
+///
+/// # Returns
+///
+/// Whether the text is an expression, an interpolated string template or a plain literal
+pub fn classify_value(text: &str) -> ValueText {
+    match tokenize(text) {
+        Ok(tokens) if !is_prose(&tokens) => ValueText::Expression(tokens),
+        Ok(_) => ValueText::Literal,
+        Err(_) if text.contains("${") => match tokenize_template(text) {
+            Ok(segments) => ValueText::Template(segments),
+            Err(_) => ValueText::Literal,
+        },
+        Err(_) => ValueText::Literal,
+    }
+}
+
+/// Tokenize a stored value, deciding whether it is an expression or a template
+///
+/// # Arguments
+///
+/// * `text` - The value text as stored by the parser
+///
+/// # Returns
+///
+/// The tokens of the value, or None if the text is a plain literal
+pub fn tokenize_value(text: &str) -> Option<Vec<Token>> {
+    match classify_value(text) {
+        ValueText::Expression(tokens) => Some(tokens),
+        ValueText::Template(segments) => Some(vec![Token::String(segments)]),
+        ValueText::Literal => None,
+    }
+}
+
+/// Whether a token is an operand (a value rather than an operator)
+fn is_operand(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Identifier(_) | Token::Integer(_) | Token::String(_)
+    )
+}
+
+/// Whether the tokens read as literal text rather than an expression
+///
+/// Expressions never contain two operands in a row outside of `for ... in` loops,
+/// whereas prose almost always does.
+fn is_prose(tokens: &[Token]) -> bool {
+    tokens.windows(2).any(|pair| {
+        let keyword = |token: &Token| {
+            matches!(token, Token::Identifier(name) if KEYWORDS.contains(&name.as_str()))
+        };
+        is_operand(&pair[0]) && is_operand(&pair[1]) && !keyword(&pair[0]) && !keyword(&pair[1])
+    })
+}
+
+/// Collect the names bound locally within an expression
+///
+/// Lambda parameters (`x => ...`, `(x, i) => ...`) and loop variables
+/// (`for x in ...`, `for (x, i) in ...`) shadow template symbols of the same name.
+fn local_bindings(tokens: &[Token]) -> Vec<String> {
+    let mut bindings = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Symbol("=>") if i > 0 => match &tokens[i - 1] {
+                Token::Identifier(name) => bindings.push(name.clone()),
+                Token::Symbol(")") => {
+                    for previous in tokens[..i - 1].iter().rev() {
+                        match previous {
+                            Token::Identifier(name) => bindings.push(name.clone()),
+                            Token::Symbol("(") => break,
+                            _ => {},
+                        }
+                    }
+                },
+                _ => {},
+            },
+            Token::Identifier(keyword) if keyword == "for" => {
+                for next in &tokens[i + 1..] {
+                    match next {
+                        Token::Identifier(name) if name == "in" => break,
+                        Token::Identifier(name) => bindings.push(name.clone()),
+                        _ => {},
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+
+    bindings
+}
+
+/// Find the variables bound by a loop statement
+///
+/// # Arguments
+///
+/// * `loop_statement` - The loop text, e.g. `for (name, i) in names`
+///
+/// # Returns
+///
+/// The loop item and index variable names
+pub fn loop_variables(loop_statement: &str) -> Vec<String> {
+    tokenize(loop_statement)
+        .map(|tokens| local_bindings(&tokens))
+        .unwrap_or_default()
+}
+
+/// Collect the identifiers referenced by a token stream
+///
+/// `outer_bindings` carries the local bindings of enclosing token streams into
+/// string interpolations.
+fn collect_identifiers(tokens: &[Token], outer_bindings: &[String], references: &mut Vec<String>) {
+    let mut bindings = local_bindings(tokens);
+    bindings.extend_from_slice(outer_bindings);
+    let mut i = 0;
+
+    while i < tokens.len() {
+        match &tokens[i] {
+            Token::Identifier(name) => {
+                let previous = i.checked_sub(1).map(|p| &tokens[p]);
+                let next = tokens.get(i + 1);
+
+                let is_member = matches!(previous, Some(Token::Symbol("." | "?.")));
+                let is_object_key = matches!(previous, Some(Token::Symbol("{" | ",")))
+                    && matches!(next, Some(Token::Symbol(":")));
+
+                // Join nested resource accessors such as parent::child
+                let mut full_name = name.clone();
+                while let (Some(Token::Symbol("::")), Some(Token::Identifier(child))) =
+                    (tokens.get(i + 1), tokens.get(i + 2))
+                {
+                    full_name = format!("{full_name}::{child}");
+                    i += 2;
+                }
+
+                if !is_member
+                    && !is_object_key
+                    && !KEYWORDS.contains(&name.as_str())
+                    && !bindings.contains(name)
+                    && !references.contains(&full_name)
+                {
+                    references.push(full_name);
+                }
+            },
+            Token::String(segments) => {
+                for segment in segments {
+                    if let StringSegment::Interpolation(inner) = segment {
+                        collect_identifiers(inner, &bindings, references);
+                    }
+                }
+            },
+            _ => {},
+        }
+        i += 1;
+    }
+}
+
+/// Find the identifiers an expression refers to
+///
+/// Property accesses (`a.b`), object keys, keywords and locally bound lambda or loop
+/// variables are excluded. Nested resource accessors are returned joined (`parent::child`).
+///
+/// # Arguments
+///
+/// * `text` - The expression or template text as stored by the parser
+///
+/// # Returns
+///
+/// The referenced identifiers in order of first appearance, or an empty vector if the
+/// text is a plain literal
+pub fn referenced_identifiers(text: &str) -> Vec<String> {
+    let mut references = Vec::new();
+    if let Some(tokens) = tokenize_value(text) {
+        collect_identifiers(&tokens, &[], &mut references);
+    }
+    references
+}
+
+/// Find the identifiers the interpolations of a string literal refer to
+///
+/// Unlike [`referenced_identifiers`], the text is never read as an expression, so a
+/// literal such as `storage` refers to nothing even when a symbol has that name.
+///
+/// # Arguments
+///
+/// * `text` - The content of the string, without its quotes
+///
+/// # Returns
+///
+/// The referenced identifiers in order of first appearance, or an empty vector if the
+/// string has no interpolations
+pub fn interpolated_identifiers(text: &str) -> Vec<String> {
+    let mut references = Vec::new();
+    if text.contains("${") {
+        if let Ok(segments) = tokenize_template(text) {
+            collect_identifiers(&[Token::String(segments)], &[], &mut references);
+        }
+    }
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_expression() {
+        let tokens = tokenize("toLower(prefix) == 'abc'").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Identifier("toLower".to_string()),
+                Token::Symbol("("),
+                Token::Identifier("prefix".to_string()),
+                Token::Symbol(")"),
+                Token::Symbol("=="),
+                Token::String(vec![StringSegment::Literal("abc".to_string())]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_interpolated_string() {
+        let tokens = tokenize("'${a}-\\'${b.c}\\''").unwrap();
+        assert_eq!(
+            tokens,
+            vec![Token::String(vec![
+                StringSegment::Interpolation(vec![Token::Identifier("a".to_string())]),
+                StringSegment::Literal("-'".to_string()),
+                StringSegment::Interpolation(vec![
+                    Token::Identifier("b".to_string()),
+                    Token::Symbol("."),
+                    Token::Identifier("c".to_string()),
+                ]),
+                StringSegment::Literal("'".to_string()),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_template() {
+        let segments = tokenize_template("${prefix}-kv").unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                StringSegment::Interpolation(vec![Token::Identifier("prefix".to_string())]),
+                StringSegment::Literal("-kv".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_referenced_identifiers() {
+        assert_eq!(
+            referenced_identifiers("concat(prefix, storage.properties.name)"),
+            vec!["concat", "prefix", "storage"]
+        );
+        assert_eq!(
+            referenced_identifiers("${prefix}-${environment}-kv"),
+            vec!["prefix", "environment"]
+        );
+        assert_eq!(
+            referenced_identifiers("enabled ? { name: siteName } : null"),
+            vec!["enabled", "siteName"]
+        );
+        assert_eq!(
+            referenced_identifiers("vnet::subnet.id"),
+            vec!["vnet::subnet"]
+        );
+    }
+
+    #[test]
+    fn test_referenced_identifiers_excludes_local_bindings() {
+        assert_eq!(
+            referenced_identifiers("map(items, item => item.name)"),
+            vec!["map", "items"]
+        );
+        assert_eq!(
+            referenced_identifiers("[for (name, i) in names: '${name}${i}${suffix}']"),
+            vec!["names", "suffix"]
+        );
+    }
+
+    #[test]
+    fn test_loop_variables() {
+        assert_eq!(loop_variables("for site in sites"), vec!["site"]);
+        assert_eq!(
+            loop_variables("for (site, index) in sites"),
+            vec!["site", "index"]
+        );
+    }
+
+    #[test]
+    fn test_referenced_identifiers_ignores_literals() {
+        assert!(referenced_identifiers("The name of the storage account").is_empty());
+        assert!(referenced_identifiers("10.0.0.0/16").is_empty());
+        assert!(referenced_identifiers("").is_empty());
+    }
+}
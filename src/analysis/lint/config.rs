@@ -0,0 +1,340 @@
+//! Lint configuration files.
+//!
+//! A configuration file, in YAML or JSON, changes the severity of rules or turns
+//! them off, and defines rules of the configurable kinds:
+//!
+//! ```yaml
+//! rules:
+//!   resource-tags: error
+//!   parameter-name-case: off
+//! custom:
+//!   - id: storage-account-prefix
+//!     kind: name-pattern
+//!     symbols: [resource]
+//!     pattern: "st*"
+//!   - id: resource-location
+//!     kind: required-properties
+//!     resourceTypes: ["Microsoft.Storage/*"]
+//!     properties: [location]
+//!   - id: secure-allowed
+//!     kind: forbidden-decorators
+//!     severity: error
+//!     symbols: [parameter]
+//!     decorators: [secure, allowed]
+//! ```
+
+use std::{fmt, fs, path::Path};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use super::{
+    super::references::SymbolKind,
+    rules::{
+        ForbiddenDecoratorsRule, NameCase, NamePatternRule, RequiredPropertiesRule, Rule,
+        DECORATORS,
+    },
+    Severity,
+};
+use crate::error::{BicepError, Span};
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// The content of a lint configuration file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    /// The level of rules, keyed by rule ID
+    #[serde(default)]
+    pub rules: IndexMap<String, RuleLevel>,
+    /// Rules defined in the configuration
+    #[serde(default)]
+    pub custom: Vec<CustomRuleConfig>,
+}
+
+/// Whether a rule runs, and the severity of its diagnostics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    /// The rule does not run
+    Off,
+    /// Diagnostics are informational
+    Info,
+    /// Diagnostics are warnings
+    Warning,
+    /// Diagnostics are errors
+    Error,
+}
+
+/// A rule defined in a configuration file
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomRuleConfig {
+    /// The ID of the rule
+    pub id: String,
+    /// What the rule requires, or None to describe it from its settings
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The severity of the rule's diagnostics
+    #[serde(default = "default_severity")]
+    pub severity: Severity,
+    /// The kind of rule and its settings
+    #[serde(flatten)]
+    pub check: CustomCheck,
+}
+
+/// The kinds of rule a configuration file can define
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum CustomCheck {
+    /// Declared names follow a case or match a pattern
+    NamePattern {
+        /// The kinds of declaration to check, or empty for every kind
+        #[serde(default)]
+        symbols: Vec<SymbolKind>,
+        /// The case names must be in
+        #[serde(default)]
+        case: Option<NameCase>,
+        /// A pattern names must match
+        #[serde(default)]
+        pattern: Option<String>,
+    },
+    /// Resources set properties
+    #[serde(rename_all = "camelCase")]
+    RequiredProperties {
+        /// Patterns of the resource types to check, or empty for every type
+        #[serde(default)]
+        resource_types: Vec<String>,
+        /// Patterns of resource types not to check
+        #[serde(default)]
+        exclude: Vec<String>,
+        /// The properties each resource must set
+        properties: Vec<String>,
+    },
+    /// Declarations do not combine a set of decorators
+    ForbiddenDecorators {
+        /// The kinds of declaration to check, or empty for every kind
+        #[serde(default)]
+        symbols: Vec<SymbolKind>,
+        /// The decorators that may not all be used on one declaration
+        decorators: Vec<String>,
+    },
+}
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+fn default_severity() -> Severity {
+    Severity::Warning
+}
+
+impl LintConfig {
+    /// Read a configuration file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the YAML or JSON file
+    ///
+    /// # Returns
+    ///
+    /// The configuration, or an error if the file cannot be read or is not a valid configuration
+    pub fn from_file(path: &Path) -> Result<Self, BicepError> {
+        let content = fs::read_to_string(path).map_err(|e| BicepError::io(path, e))?;
+        Self::parse(&content).map_err(|error| match error {
+            BicepError::InvalidValue { kind, reason, span } => BicepError::InvalidValue {
+                kind,
+                reason: format!("{}: {reason}", path.display()),
+                span,
+            },
+            error => error,
+        })
+    }
+
+    /// Read a configuration from its YAML or JSON text
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text of the configuration
+    pub fn parse(content: &str) -> Result<Self, BicepError> {
+        serde_yaml::from_str(content).map_err(|error| {
+            let span = error
+                .location()
+                .map(|location| Span::at(location.line(), location.column()));
+            let error = config_error(error);
+            match span {
+                Some(span) => error.with_span(span),
+                None => error,
+            }
+        })
+    }
+}
+
+impl RuleLevel {
+    /// The severity of diagnostics at this level, or None when the rule is off
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            RuleLevel::Off => None,
+            RuleLevel::Info => Some(Severity::Info),
+            RuleLevel::Warning => Some(Severity::Warning),
+            RuleLevel::Error => Some(Severity::Error),
+        }
+    }
+}
+
+impl CustomRuleConfig {
+    /// Build the rule the configuration defines
+    ///
+    /// # Returns
+    ///
+    /// The rule, or an error if its settings cannot check anything
+    pub fn into_rule(self) -> Result<Box<dyn Rule>, BicepError> {
+        let CustomRuleConfig {
+            id,
+            description,
+            severity,
+            check,
+        } = self;
+        Ok(match check {
+            CustomCheck::NamePattern {
+                symbols,
+                case,
+                pattern,
+            } => {
+                if case.is_none() && pattern.is_none() {
+                    return Err(config_error(format!("rule {id} needs a case or a pattern")));
+                }
+                Box::new(NamePatternRule {
+                    id,
+                    description,
+                    severity,
+                    symbols,
+                    case,
+                    pattern,
+                })
+            },
+            CustomCheck::RequiredProperties {
+                resource_types,
+                exclude,
+                properties,
+            } => {
+                if properties.is_empty() {
+                    return Err(config_error(format!(
+                        "rule {id} needs at least one property"
+                    )));
+                }
+                Box::new(RequiredPropertiesRule {
+                    id,
+                    description,
+                    severity,
+                    resource_types,
+                    exclude,
+                    properties,
+                })
+            },
+            CustomCheck::ForbiddenDecorators {
+                symbols,
+                decorators,
+            } => {
+                if decorators.len() < 2 {
+                    return Err(config_error(format!(
+                        "rule {id} needs at least two decorators"
+                    )));
+                }
+                if let Some(unknown) = decorators
+                    .iter()
+                    .find(|decorator| !DECORATORS.contains(&decorator.as_str()))
+                {
+                    return Err(config_error(format!(
+                        "rule {id} names unknown decorator {unknown} (known: {})",
+                        DECORATORS.join(", ")
+                    )));
+                }
+                Box::new(ForbiddenDecoratorsRule {
+                    id,
+                    description,
+                    severity,
+                    symbols,
+                    decorators,
+                })
+            },
+        })
+    }
+}
+
+/// Create an error for a configuration that is not valid
+pub(super) fn config_error(reason: impl fmt::Display) -> BicepError {
+    BicepError::InvalidValue {
+        kind: "lint configuration".to_string(),
+        reason: reason.to_string(),
+        span: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = LintConfig::parse(
+            "\
+rules:
+  resource-tags: error
+  parameter-name-case: off
+custom:
+  - id: storage-account-prefix
+    kind: name-pattern
+    symbols: [resource]
+    pattern: 'st*'
+  - id: resource-location
+    kind: required-properties
+    resourceTypes: ['Microsoft.Storage/*']
+    properties: [location]
+",
+        )
+        .unwrap();
+        assert_eq!(config.rules["resource-tags"], RuleLevel::Error);
+        assert_eq!(config.rules["parameter-name-case"].severity(), None);
+
+        let rules: Vec<_> = config
+            .custom
+            .into_iter()
+            .map(|custom| custom.into_rule().unwrap())
+            .collect();
+        assert_eq!(rules[0].id(), "storage-account-prefix");
+        assert_eq!(rules[0].description(), "Names match `st*`");
+        assert_eq!(rules[1].default_severity(), Severity::Warning);
+        assert_eq!(rules[1].description(), "Resources set `location`");
+
+        // JSON is read as YAML
+        let config = LintConfig::parse(r#"{"rules": {"no-secret-outputs": "info"}}"#).unwrap();
+        assert_eq!(config.rules["no-secret-outputs"], RuleLevel::Info);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(LintConfig::parse("rule:\n  resource-tags: error\n").is_err());
+        let error = LintConfig::parse("rules:\n  resource-tags: loud\n").unwrap_err();
+        assert_eq!(error.code(), "BD1003");
+        assert_eq!(error.span().map(|span| span.start_line), Some(2));
+
+        let custom = |text: &str| {
+            LintConfig::parse(text)
+                .unwrap()
+                .custom
+                .remove(0)
+                .into_rule()
+        };
+        assert!(custom("custom:\n  - id: names\n    kind: name-pattern\n").is_err());
+        assert!(custom(
+            "custom:\n  - id: secure\n    kind: forbidden-decorators\n    decorators: [secure, optional]\n"
+        )
+        .is_err());
+        assert!(custom(
+            "custom:\n  - id: secure\n    kind: forbidden-decorators\n    decorators: [secure, allowed]\n"
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,339 @@
+//! House-rule linting of Bicep documents.
+//!
+//! A [`Linter`] runs a set of [`Rule`]s against a parsed [`BicepDocument`] and
+//! reports a [`Diagnostic`] for each declaration that breaks one. It starts with
+//! the [built-in rules](rules::built_in_rules), and a [`LintConfig`] can change
+//! their severity, turn them off, or add rules of the configurable kinds. Given
+//! the source of the template, diagnostics are placed on the line of the
+//! declaration and can be suppressed with a comment, as described in [`source`].
+
+pub mod config;
+pub mod rules;
+pub mod source;
+
+pub use super::diagnostics::{Diagnostic, Severity};
+pub use config::{CustomCheck, CustomRuleConfig, LintConfig, RuleLevel};
+pub use rules::{built_in_rules, Finding, NameCase, Rule};
+pub use source::SourceMap;
+
+use crate::{error::BicepError, parsing::BicepDocument};
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// A rule and the severity it runs at, or None when it is off
+struct ConfiguredRule {
+    rule: Box<dyn Rule>,
+    severity: Option<Severity>,
+    /// Whether the severity was set rather than left at the rule's default
+    configured: bool,
+}
+
+/// Runs rules against documents
+pub struct Linter {
+    rules: Vec<ConfiguredRule>,
+}
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+impl Linter {
+    /// Create a linter with the built-in rules at their default severity
+    ///
+    /// Rules that are not enabled by default start off.
+    pub fn new() -> Self {
+        Linter {
+            rules: built_in_rules()
+                .into_iter()
+                .map(|rule| ConfiguredRule {
+                    severity: rule.enabled_by_default().then(|| rule.default_severity()),
+                    configured: false,
+                    rule,
+                })
+                .collect(),
+        }
+    }
+
+    /// Create a linter with the built-in rules and the rules of a configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration to apply
+    ///
+    /// # Returns
+    ///
+    /// The linter, or an error if the configuration defines an invalid rule,
+    /// repeats a rule ID or sets the level of a rule that does not exist
+    pub fn with_config(config: LintConfig) -> Result<Self, BicepError> {
+        let mut linter = Linter::new();
+        for custom in config.custom {
+            linter.add_rule(custom.into_rule()?)?;
+        }
+        for (id, level) in config.rules {
+            linter.set_severity(&id, level.severity())?;
+        }
+        Ok(linter)
+    }
+
+    /// Add a rule at its default severity, or off if it is not enabled by default
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The rule to add
+    ///
+    /// # Returns
+    ///
+    /// An error if the linter already has a rule with the same ID
+    pub fn add_rule(&mut self, rule: Box<dyn Rule>) -> Result<(), BicepError> {
+        if self.rule(rule.id()).is_some() {
+            return Err(config::config_error(format!(
+                "rule {} is defined more than once",
+                rule.id()
+            )));
+        }
+        self.rules.push(ConfiguredRule {
+            severity: rule.enabled_by_default().then(|| rule.default_severity()),
+            configured: false,
+            rule,
+        });
+        Ok(())
+    }
+
+    /// Change the severity of a rule, or turn it off
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the rule
+    /// * `severity` - The severity of its diagnostics, or None to turn it off
+    ///
+    /// # Returns
+    ///
+    /// An error if the linter has no rule with that ID
+    pub fn set_severity(&mut self, id: &str, severity: Option<Severity>) -> Result<(), BicepError> {
+        let known: Vec<_> = self
+            .rules
+            .iter()
+            .map(|entry| entry.rule.id().to_string())
+            .collect();
+        let entry = self
+            .rules
+            .iter_mut()
+            .find(|entry| entry.rule.id() == id)
+            .ok_or_else(|| {
+                config::config_error(format!("unknown rule {id} (known: {})", known.join(", ")))
+            })?;
+        entry.severity = severity;
+        entry.configured = true;
+        Ok(())
+    }
+
+    /// Turn on the rules that are off by default and whose severity was not set
+    ///
+    /// Code scanning dashboards filter the results of a SARIF log by level, so the
+    /// lint command runs these rules at a low severity when it writes one, which
+    /// reports documentation coverage gaps without failing the command.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity to run the rules at
+    pub fn enable_default_off_rules(&mut self, severity: Severity) {
+        for entry in &mut self.rules {
+            if !entry.configured && !entry.rule.enabled_by_default() {
+                entry.severity = Some(severity);
+            }
+        }
+    }
+
+    /// The rules of the linter in the order they run, with their severity or None when off
+    pub fn rules(&self) -> impl Iterator<Item = (&dyn Rule, Option<Severity>)> {
+        self.rules
+            .iter()
+            .map(|entry| (entry.rule.as_ref(), entry.severity))
+    }
+
+    /// Find a rule by ID
+    fn rule(&self, id: &str) -> Option<&dyn Rule> {
+        self.rules()
+            .map(|(rule, _)| rule)
+            .find(|rule| rule.id() == id)
+    }
+
+    /// Check a document against the rules that are on
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The document to check
+    /// * `source` - The Bicep source of the document, to place and suppress diagnostics
+    ///
+    /// # Returns
+    ///
+    /// The diagnostics that are not suppressed, in rule order
+    pub fn lint(&self, document: &BicepDocument, source: Option<&str>) -> Vec<Diagnostic> {
+        let source_map = source.map(SourceMap::scan).unwrap_or_default();
+
+        let mut diagnostics = Vec::new();
+        for (rule, severity) in self.rules() {
+            let Some(severity) = severity else {
+                continue;
+            };
+            for finding in rule.check(document) {
+                let span = source_map.span(&finding.symbol);
+                if span.is_some_and(|span| source_map.is_suppressed(rule.id(), span.start_line)) {
+                    continue;
+                }
+                diagnostics.push(Diagnostic {
+                    rule: rule.id().to_string(),
+                    severity,
+                    message: finding.message,
+                    symbol: Some(finding.symbol),
+                    span,
+                });
+            }
+        }
+        diagnostics
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Linter::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{BicepParameter, BicepResource};
+
+    const SOURCE: &str = "\
+param Location string
+
+// bicep-docs-disable-next-line parameter-name-case
+param Name string
+
+resource account 'Microsoft.Storage/storageAccounts@2023-01-01' = { // bicep-docs-disable-line resource-tags
+  name: Name
+  location: Location
+}
+resource vault 'Microsoft.KeyVault/vaults@2023-07-01' = {
+  name: Name
+  location: Location
+}
+";
+
+    fn document() -> BicepDocument {
+        let mut document = BicepDocument::default();
+        for name in ["Location", "Name"] {
+            document.parameters.insert(
+                name.to_string(),
+                BicepParameter {
+                    description: Some(format!("The {name} of the account")),
+                    ..Default::default()
+                },
+            );
+        }
+        for (name, resource_type) in [
+            ("account", "Microsoft.Storage/storageAccounts"),
+            ("vault", "Microsoft.KeyVault/vaults"),
+        ] {
+            document.resources.insert(
+                name.to_string(),
+                BicepResource {
+                    resource_type: resource_type.to_string(),
+                    ..Default::default()
+                },
+            );
+        }
+        document
+    }
+
+    #[test]
+    fn test_lint_with_suppressions() {
+        let diagnostics = Linter::new().lint(&document(), Some(SOURCE));
+        let found: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.rule.as_str(),
+                    diagnostic.span.map(|span| span.start_line),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("parameter-name-case", Some(1)),
+                ("resource-tags", Some(10)),
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "parameter-name-case: Parameter `Location` is not in camelCase"
+        );
+
+        // Without the source, nothing is placed or suppressed
+        let diagnostics = Linter::new().lint(&document(), None);
+        assert_eq!(diagnostics.len(), 4);
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.span.is_none()));
+    }
+
+    #[test]
+    fn test_lint_with_config() {
+        let config = LintConfig::parse(
+            "\
+rules:
+  parameter-name-case: off
+  resource-tags: error
+  missing-description: info
+custom:
+  - id: pascal-case
+    kind: name-pattern
+    severity: info
+    symbols: [parameter]
+    case: PascalCase
+",
+        )
+        .unwrap();
+        let linter = Linter::with_config(config).unwrap();
+        assert_eq!(
+            linter
+                .rules()
+                .map(|(rule, severity)| (rule.id(), severity))
+                .collect::<Vec<_>>(),
+            vec![
+                ("parameter-name-case", None),
+                ("resource-tags", Some(Severity::Error)),
+                ("secure-parameter-default", Some(Severity::Warning)),
+                ("no-secret-outputs", Some(Severity::Warning)),
+                ("missing-description", Some(Severity::Info)),
+                ("pascal-case", Some(Severity::Info)),
+            ]
+        );
+        let diagnostics = linter.lint(&document(), None);
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.rule == "resource-tags"
+                && diagnostic.severity == Severity::Error));
+        // Off by default, on when configured
+        assert_eq!(
+            Linter::new()
+                .rules()
+                .find(|(rule, _)| rule.id() == "missing-description")
+                .map(|(_, severity)| severity),
+            Some(None)
+        );
+
+        let unknown = LintConfig::parse("rules:\n  no-such-rule: error\n").unwrap();
+        let error = Linter::with_config(unknown).err().unwrap();
+        assert!(error.to_string().contains("unknown rule no-such-rule"));
+        let duplicate = LintConfig::parse(
+            "custom:\n  - id: resource-tags\n    kind: required-properties\n    properties: [tags]\n",
+        )
+        .unwrap();
+        assert!(Linter::with_config(duplicate).is_err());
+    }
+}
@@ -0,0 +1,1057 @@
+//! Lint rules.
+//!
+//! Every rule implements [`Rule`]. The built-in rules are listed by
+//! [`built_in_rules`]; the configurable kinds, [`NamePatternRule`],
+//! [`RequiredPropertiesRule`] and [`ForbiddenDecoratorsRule`], back both
+//! built-in rules and the rules defined in a lint configuration file.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::super::{
+    expressions::{tokenize, tokenize_template, StringSegment, Token},
+    references::{analyze_references, SymbolId, SymbolKind},
+};
+use super::Severity;
+use crate::parsing::{BicepDocument, BicepValue};
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// A check run against a document
+pub trait Rule: Send + Sync {
+    /// The stable ID of the rule, such as `resource-tags`
+    fn id(&self) -> &str;
+
+    /// What the rule requires
+    fn description(&self) -> String;
+
+    /// The severity of the rule's diagnostics unless configured otherwise
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    /// Whether the rule runs unless configured otherwise
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    /// Find the declarations that break the rule
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The document to check
+    ///
+    /// # Returns
+    ///
+    /// A finding for each declaration that breaks the rule
+    fn check(&self, document: &BicepDocument) -> Vec<Finding>;
+}
+
+/// A declaration that breaks a rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// The declaration, with nested resources named `parent::child`
+    pub symbol: SymbolId,
+    /// What is wrong
+    pub message: String,
+}
+
+/// A naming convention for declared names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NameCase {
+    /// `storageAccountName`
+    #[serde(rename = "camelCase")]
+    Camel,
+    /// `StorageAccountName`
+    #[serde(rename = "PascalCase")]
+    Pascal,
+    /// `storage_account_name`
+    #[serde(rename = "snake_case")]
+    Snake,
+}
+
+/// Requires declared names to follow a case and match a pattern
+#[derive(Debug, Clone)]
+pub struct NamePatternRule {
+    /// The ID of the rule
+    pub id: String,
+    /// What the rule requires, or None to describe the pattern
+    pub description: Option<String>,
+    /// The severity of the rule's diagnostics
+    pub severity: Severity,
+    /// The kinds of declaration to check, or empty for every kind
+    pub symbols: Vec<SymbolKind>,
+    /// The case names must be in
+    pub case: Option<NameCase>,
+    /// A pattern names must match, where `*` matches any text and `?` one character
+    pub pattern: Option<String>,
+}
+
+/// Requires resources to set properties
+#[derive(Debug, Clone)]
+pub struct RequiredPropertiesRule {
+    /// The ID of the rule
+    pub id: String,
+    /// What the rule requires, or None to list the properties
+    pub description: Option<String>,
+    /// The severity of the rule's diagnostics
+    pub severity: Severity,
+    /// Patterns of the resource types to check, or empty for every type
+    pub resource_types: Vec<String>,
+    /// Patterns of resource types not to check
+    pub exclude: Vec<String>,
+    /// The properties each resource must set
+    pub properties: Vec<String>,
+}
+
+/// Forbids declarations from combining a set of decorators
+#[derive(Debug, Clone)]
+pub struct ForbiddenDecoratorsRule {
+    /// The ID of the rule
+    pub id: String,
+    /// What the rule requires, or None to list the decorators
+    pub description: Option<String>,
+    /// The severity of the rule's diagnostics
+    pub severity: Severity,
+    /// The kinds of declaration to check, or empty for every kind
+    pub symbols: Vec<SymbolKind>,
+    /// The decorators that may not all be used on one declaration
+    pub decorators: Vec<String>,
+}
+
+/// Forbids default values on `@secure()` parameters
+#[derive(Debug, Clone, Copy)]
+pub struct SecureParameterDefaultRule;
+
+/// Forbids outputs that are not `@secure()` from exposing secure values
+#[derive(Debug, Clone, Copy)]
+pub struct NoSecretOutputsRule;
+
+/// Requires descriptions on the declarations that make up a template's interface
+///
+/// The rule is off unless a lint configuration sets its level.
+#[derive(Debug, Clone, Copy)]
+pub struct MissingDescriptionRule;
+
+/// The decorators that forbidden decorator rules can name
+pub const DECORATORS: &[&str] = &[
+    "allowed",
+    "batchSize",
+    "description",
+    "discriminator",
+    "export",
+    "maxLength",
+    "maxValue",
+    "metadata",
+    "minLength",
+    "minValue",
+    "sealed",
+    "secure",
+];
+
+/// The ARM `list*()` functions, which return keys, credentials and other secrets
+const LIST_FUNCTIONS: &[&str] = &[
+    "list",
+    "listAccountSas",
+    "listAdminKeys",
+    "listAuthKeys",
+    "listCallbackUrl",
+    "listChannelWithKeys",
+    "listClusterAdminCredential",
+    "listClusterMonitoringUserCredential",
+    "listClusterUserCredential",
+    "listConnectionStrings",
+    "listCredential",
+    "listCredentials",
+    "listDomainSecrets",
+    "listKeys",
+    "listKeyValue",
+    "listNotificationKeys",
+    "listQueryKeys",
+    "listSecrets",
+    "listServiceSas",
+    "listSyncFunctionTriggerStatus",
+];
+
+/// Resource types that do not take tags, for the built-in `resource-tags` rule
+const UNTAGGED_RESOURCE_TYPES: &[&str] = &[
+    "*/*/*",
+    "Microsoft.Authorization/*",
+    "Microsoft.Insights/diagnosticSettings",
+    "Microsoft.Resources/deployments",
+];
+
+// ---------------------------------------------------------------
+// Rules
+// ---------------------------------------------------------------
+
+/// The rules that every linter starts with
+///
+/// | ID | Requires |
+/// |----|----------|
+/// | `parameter-name-case` | Parameter names in camelCase |
+/// | `resource-tags` | Resources that take tags to set `tags` |
+/// | `secure-parameter-default` | No default value on a `@secure()` parameter |
+/// | `no-secret-outputs` | No secure value in an output that is not `@secure()` |
+/// | `missing-description` | A description on every parameter, output and export (off by default) |
+pub fn built_in_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(NamePatternRule {
+            id: "parameter-name-case".to_string(),
+            description: Some("Parameter names are in camelCase".to_string()),
+            severity: Severity::Warning,
+            symbols: vec![SymbolKind::Parameter],
+            case: Some(NameCase::Camel),
+            pattern: None,
+        }),
+        Box::new(RequiredPropertiesRule {
+            id: "resource-tags".to_string(),
+            description: Some("Resources that take tags set `tags`".to_string()),
+            severity: Severity::Warning,
+            resource_types: Vec::new(),
+            exclude: UNTAGGED_RESOURCE_TYPES
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+            properties: vec!["tags".to_string()],
+        }),
+        Box::new(SecureParameterDefaultRule),
+        Box::new(NoSecretOutputsRule),
+        Box::new(MissingDescriptionRule),
+    ]
+}
+
+impl Rule for NamePatternRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn description(&self) -> String {
+        if let Some(description) = &self.description {
+            return description.clone();
+        }
+        match (&self.case, &self.pattern) {
+            (Some(case), Some(pattern)) => {
+                format!("Names are in {} and match `{pattern}`", case.name())
+            },
+            (Some(case), None) => format!("Names are in {}", case.name()),
+            (None, Some(pattern)) => format!("Names match `{pattern}`"),
+            (None, None) => "Names are not checked".to_string(),
+        }
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn check(&self, document: &BicepDocument) -> Vec<Finding> {
+        declared_symbols(document)
+            .into_iter()
+            .filter(|symbol| applies_to(&self.symbols, symbol.kind))
+            .filter_map(|symbol| {
+                // Nested resources are checked by their own name
+                let name = symbol.name.rsplit("::").next().unwrap_or(&symbol.name);
+                let message = if let Some(case) = self.case.filter(|case| !case.matches(name)) {
+                    format!(
+                        "{} `{name}` is not in {}",
+                        kind_name(symbol.kind),
+                        case.name()
+                    )
+                } else if let Some(pattern) = self
+                    .pattern
+                    .as_deref()
+                    .filter(|pattern| !glob_match(pattern, name, false))
+                {
+                    format!(
+                        "{} `{name}` does not match `{pattern}`",
+                        kind_name(symbol.kind)
+                    )
+                } else {
+                    return None;
+                };
+                Some(Finding { symbol, message })
+            })
+            .collect()
+    }
+}
+
+impl Rule for RequiredPropertiesRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn description(&self) -> String {
+        self.description.clone().unwrap_or_else(|| {
+            let properties: Vec<_> = self
+                .properties
+                .iter()
+                .map(|property| format!("`{property}`"))
+                .collect();
+            format!("Resources set {}", properties.join(", "))
+        })
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn check(&self, document: &BicepDocument) -> Vec<Finding> {
+        let matches_any = |patterns: &[String], resource_type: &str| {
+            patterns
+                .iter()
+                .any(|pattern| glob_match(pattern, resource_type, true))
+        };
+
+        document
+            .resources
+            .iter()
+            .filter(|(_, resource)| !resource.existing)
+            .filter(|(_, resource)| {
+                (self.resource_types.is_empty()
+                    || matches_any(&self.resource_types, &resource.resource_type))
+                    && !matches_any(&self.exclude, &resource.resource_type)
+            })
+            .flat_map(|(name, resource)| {
+                self.properties
+                    .iter()
+                    .filter(|property| !resource.properties.contains_key(property.as_str()))
+                    .map(move |property| Finding {
+                        symbol: SymbolId::new(SymbolKind::Resource, name),
+                        message: format!(
+                            "Resource `{name}` of type `{}` does not set `{property}`",
+                            resource.resource_type
+                        ),
+                    })
+            })
+            .collect()
+    }
+}
+
+impl Rule for ForbiddenDecoratorsRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn description(&self) -> String {
+        self.description
+            .clone()
+            .unwrap_or_else(|| format!("Declarations do not combine {}", self.decorator_list()))
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn check(&self, document: &BicepDocument) -> Vec<Finding> {
+        declared_symbols(document)
+            .into_iter()
+            .filter(|symbol| applies_to(&self.symbols, symbol.kind))
+            .filter(|symbol| {
+                let used = decorators(document, symbol);
+                !self.decorators.is_empty()
+                    && self
+                        .decorators
+                        .iter()
+                        .all(|decorator| used.contains(&decorator.as_str()))
+            })
+            .map(|symbol| Finding {
+                message: format!(
+                    "{} `{}` combines {}",
+                    kind_name(symbol.kind),
+                    symbol.name,
+                    self.decorator_list()
+                ),
+                symbol,
+            })
+            .collect()
+    }
+}
+
+impl ForbiddenDecoratorsRule {
+    /// The decorators, as they are written
+    fn decorator_list(&self) -> String {
+        let decorators: Vec<_> = self
+            .decorators
+            .iter()
+            .map(|decorator| format!("`@{decorator}()`"))
+            .collect();
+        decorators.join(" and ")
+    }
+}
+
+impl Rule for SecureParameterDefaultRule {
+    fn id(&self) -> &str {
+        "secure-parameter-default"
+    }
+
+    fn description(&self) -> String {
+        "`@secure()` parameters have no default value".to_string()
+    }
+
+    fn check(&self, document: &BicepDocument) -> Vec<Finding> {
+        document
+            .parameters
+            .iter()
+            .filter(|(_, parameter)| parameter.is_secure)
+            .filter(|(_, parameter)| match &parameter.default_value {
+                None => false,
+                // An empty string makes a secret optional, and newGuid() is never known
+                Some(BicepValue::String(value)) => !value.is_empty(),
+                Some(BicepValue::Identifier(expression)) => expression != "newGuid()",
+                Some(_) => true,
+            })
+            .map(|(name, _)| Finding {
+                symbol: SymbolId::new(SymbolKind::Parameter, name),
+                message: format!("Secure parameter `{name}` has a default value"),
+            })
+            .collect()
+    }
+}
+
+impl Rule for NoSecretOutputsRule {
+    fn id(&self) -> &str {
+        "no-secret-outputs"
+    }
+
+    fn description(&self) -> String {
+        "Outputs that are not `@secure()` do not expose secure parameters or `list*()` results"
+            .to_string()
+    }
+
+    fn check(&self, document: &BicepDocument) -> Vec<Finding> {
+        let references = analyze_references(document);
+        let mut findings = Vec::new();
+
+        for (name, output) in &document.outputs {
+            if output.secure {
+                continue;
+            }
+            let symbol = SymbolId::new(SymbolKind::Output, name);
+
+            // Follow references through variables to the parameters they come from
+            let mut seen = HashSet::new();
+            let mut pending = vec![symbol.clone()];
+            let mut secrets = Vec::new();
+            while let Some(current) = pending.pop() {
+                for dependency in references.depends_on(current.kind, &current.name) {
+                    if !seen.insert(dependency.clone()) {
+                        continue;
+                    }
+                    match dependency.kind {
+                        SymbolKind::Parameter
+                            if document
+                                .parameters
+                                .get(&dependency.name)
+                                .is_some_and(|parameter| parameter.is_secure) =>
+                        {
+                            secrets.push(format!("secure parameter `{}`", dependency.name));
+                        },
+                        SymbolKind::Variable => pending.push(dependency.clone()),
+                        _ => {},
+                    }
+                }
+            }
+            if let Some(function) = list_function(&output.value) {
+                secrets.push(format!("`{function}()`"));
+            }
+
+            if !secrets.is_empty() {
+                secrets.sort();
+                findings.push(Finding {
+                    symbol,
+                    message: format!(
+                        "Output `{name}` exposes {}; mark it `@secure()`",
+                        secrets.join(", ")
+                    ),
+                });
+            }
+        }
+        findings
+    }
+}
+
+impl Rule for MissingDescriptionRule {
+    fn id(&self) -> &str {
+        "missing-description"
+    }
+
+    fn description(&self) -> String {
+        "Parameters, outputs and exported types, functions and variables have a description"
+            .to_string()
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, document: &BicepDocument) -> Vec<Finding> {
+        declared_symbols(document)
+            .into_iter()
+            .filter(|symbol| needs_description(document, symbol.kind, &symbol.name))
+            .map(|symbol| Finding {
+                message: format!(
+                    "{} `{}` has no description; add a `@description()` decorator",
+                    kind_name(symbol.kind),
+                    symbol.name
+                ),
+                symbol,
+            })
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+/// Whether a declaration is expected to have a description and has none
+///
+/// Parameters and outputs are the interface of every template; types, functions
+/// and variables are only part of it when they are exported. A description in
+/// any language, including one from `@metadata`, counts.
+///
+/// # Arguments
+///
+/// * `document` - The document that declares the symbol
+/// * `kind` - The kind of declaration
+/// * `name` - The declared name
+pub fn needs_description(document: &BicepDocument, kind: SymbolKind, name: &str) -> bool {
+    match kind {
+        SymbolKind::Parameter => document.parameters.get(name).is_some_and(|parameter| {
+            parameter.description.is_none() && parameter.descriptions.is_empty()
+        }),
+        SymbolKind::Output => document
+            .outputs
+            .get(name)
+            .is_some_and(|output| output.description.is_none() && output.descriptions.is_empty()),
+        SymbolKind::Type => document.types.get(name).is_some_and(|custom_type| {
+            custom_type.is_exported
+                && custom_type.description.is_none()
+                && custom_type.descriptions.is_empty()
+        }),
+        SymbolKind::Function => document
+            .functions
+            .get(name)
+            .is_some_and(|function| function.is_exported && function.description.is_none()),
+        SymbolKind::Variable => document
+            .variables
+            .get(name)
+            .is_some_and(|variable| variable.is_exported && variable.description.is_none()),
+        SymbolKind::Resource | SymbolKind::Module => false,
+    }
+}
+
+impl NameCase {
+    /// The name of the case, as written in configuration
+    pub fn name(self) -> &'static str {
+        match self {
+            NameCase::Camel => "camelCase",
+            NameCase::Pascal => "PascalCase",
+            NameCase::Snake => "snake_case",
+        }
+    }
+
+    /// Whether a name is in this case
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The declared name
+    pub fn matches(self, name: &str) -> bool {
+        let mut chars = name.chars();
+        let Some(first) = chars.next() else {
+            return false;
+        };
+        let rest = chars.as_str();
+        match self {
+            NameCase::Camel => {
+                first.is_ascii_lowercase() && rest.chars().all(|c| c.is_ascii_alphanumeric())
+            },
+            NameCase::Pascal => {
+                first.is_ascii_uppercase() && rest.chars().all(|c| c.is_ascii_alphanumeric())
+            },
+            NameCase::Snake => {
+                first.is_ascii_lowercase()
+                    && rest
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            },
+        }
+    }
+}
+
+/// Whether text matches a pattern where `*` matches any text and `?` one character
+///
+/// # Arguments
+///
+/// * `pattern` - The pattern
+/// * `text` - The text to match
+/// * `ignore_case` - Whether to compare letters without regard to case
+pub fn glob_match(pattern: &str, text: &str, ignore_case: bool) -> bool {
+    let normalize = |value: &str| -> Vec<char> {
+        if ignore_case {
+            value.to_lowercase().chars().collect()
+        } else {
+            value.chars().collect()
+        }
+    };
+    let (pattern, text) = (normalize(pattern), normalize(text));
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            },
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            },
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                },
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Every declaration in a document
+fn declared_symbols(document: &BicepDocument) -> Vec<SymbolId> {
+    let names = [
+        (SymbolKind::Type, document.types.keys().collect::<Vec<_>>()),
+        (SymbolKind::Function, document.functions.keys().collect()),
+        (SymbolKind::Parameter, document.parameters.keys().collect()),
+        (SymbolKind::Variable, document.variables.keys().collect()),
+        (SymbolKind::Resource, document.resources.keys().collect()),
+        (SymbolKind::Module, document.modules.keys().collect()),
+        (SymbolKind::Output, document.outputs.keys().collect()),
+    ];
+    names
+        .into_iter()
+        .flat_map(|(kind, names)| names.into_iter().map(move |name| SymbolId::new(kind, name)))
+        .collect()
+}
+
+/// Whether a rule limited to some kinds of declaration applies to a kind
+fn applies_to(kinds: &[SymbolKind], kind: SymbolKind) -> bool {
+    kinds.is_empty() || kinds.contains(&kind)
+}
+
+/// The name of a kind of declaration, to start a message with
+pub fn kind_name(kind: SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::Type => "Type",
+        SymbolKind::Function => "Function",
+        SymbolKind::Parameter => "Parameter",
+        SymbolKind::Variable => "Variable",
+        SymbolKind::Resource => "Resource",
+        SymbolKind::Module => "Module",
+        SymbolKind::Output => "Output",
+    }
+}
+
+/// The decorators a declaration uses, as far as the document model records them
+///
+/// # Arguments
+///
+/// * `document` - The document that declares the symbol
+/// * `symbol` - The declaration
+///
+/// # Returns
+///
+/// The names of the decorators, without the `@`
+pub fn decorators(document: &BicepDocument, symbol: &SymbolId) -> Vec<&'static str> {
+    let name = symbol.name.as_str();
+    let flags: Vec<(&'static str, bool)> = match symbol.kind {
+        SymbolKind::Type => document.types.get(name).map_or_else(Vec::new, |custom| {
+            vec![
+                ("description", custom.description.is_some()),
+                ("export", custom.is_exported),
+                ("secure", custom.is_secure),
+            ]
+        }),
+        SymbolKind::Function => document
+            .functions
+            .get(name)
+            .map_or_else(Vec::new, |function| {
+                vec![
+                    ("description", function.description.is_some()),
+                    ("export", function.is_exported),
+                    ("metadata", !function.metadata.is_empty()),
+                ]
+            }),
+        SymbolKind::Parameter => document
+            .parameters
+            .get(name)
+            .map_or_else(Vec::new, |parameter| {
+                vec![
+                    ("allowed", parameter.allowed_values.is_some()),
+                    ("description", parameter.description.is_some()),
+                    ("discriminator", parameter.discriminator.is_some()),
+                    ("maxLength", parameter.max_length.is_some()),
+                    ("maxValue", parameter.max_value.is_some()),
+                    ("metadata", !parameter.metadata.is_empty()),
+                    ("minLength", parameter.min_length.is_some()),
+                    ("minValue", parameter.min_value.is_some()),
+                    ("sealed", parameter.is_sealed),
+                    ("secure", parameter.is_secure),
+                ]
+            }),
+        SymbolKind::Variable => document
+            .variables
+            .get(name)
+            .map_or_else(Vec::new, |variable| {
+                vec![
+                    ("description", variable.description.is_some()),
+                    ("export", variable.is_exported),
+                ]
+            }),
+        SymbolKind::Resource => document
+            .resources
+            .get(name)
+            .map_or_else(Vec::new, |resource| {
+                vec![
+                    ("batchSize", resource.batch_size.is_some()),
+                    ("description", resource.description.is_some()),
+                ]
+            }),
+        SymbolKind::Module => document.modules.get(name).map_or_else(Vec::new, |module| {
+            vec![
+                ("batchSize", module.batch_size.is_some()),
+                ("description", module.description.is_some()),
+            ]
+        }),
+        SymbolKind::Output => document.outputs.get(name).map_or_else(Vec::new, |output| {
+            vec![
+                ("description", output.description.is_some()),
+                ("discriminator", output.discriminator.is_some()),
+                ("maxLength", output.max_length.is_some()),
+                ("maxValue", output.max_value.is_some()),
+                ("metadata", output.metadata.is_some()),
+                ("minLength", output.min_length.is_some()),
+                ("minValue", output.min_value.is_some()),
+                ("sealed", output.sealed),
+                ("secure", output.secure),
+            ]
+        }),
+    };
+    flags
+        .into_iter()
+        .filter(|(_, used)| *used)
+        .map(|(decorator, _)| decorator)
+        .collect()
+}
+
+/// The first `list*()` function, such as `listKeys`, that a value calls
+///
+/// # Arguments
+///
+/// * `value` - The value to search
+pub fn list_function(value: &BicepValue) -> Option<String> {
+    called_functions(value)
+        .into_iter()
+        .find(|name| is_list_function(name))
+}
+
+/// Whether a function is one of the ARM `list*()` functions, such as `listKeys`
+///
+/// Functions of other names that start with `list`, such as a user-defined
+/// `listNames()`, are not.
+///
+/// # Arguments
+///
+/// * `name` - The name of the function, without a namespace or resource
+pub fn is_list_function(name: &str) -> bool {
+    LIST_FUNCTIONS
+        .iter()
+        .any(|function| function.eq_ignore_ascii_case(name))
+}
+
+/// The functions a value calls, including methods such as `vault.getSecret()`
+///
+/// # Arguments
+///
+/// * `value` - The value to search
+///
+/// # Returns
+///
+/// The names of the functions in the order they are called in the text
+pub fn called_functions(value: &BicepValue) -> Vec<String> {
+    let mut calls = Vec::new();
+    value_calls(value, &mut calls);
+    calls
+}
+
+/// Collect the functions called in a value
+fn value_calls(value: &BicepValue, calls: &mut Vec<String>) {
+    match value {
+        // Only the interpolations of a string literal can call functions
+        BicepValue::String(text) => {
+            if let Ok(segments) = tokenize_template(text) {
+                token_calls(&[Token::String(segments)], calls);
+            }
+        },
+        BicepValue::Identifier(text) => {
+            if let Ok(tokens) = tokenize(text) {
+                token_calls(&tokens, calls);
+            }
+        },
+        BicepValue::Array(items) => items.iter().for_each(|item| value_calls(item, calls)),
+        BicepValue::Object(properties) => properties
+            .values()
+            .for_each(|property| value_calls(property, calls)),
+        BicepValue::Int(_) | BicepValue::Bool(_) => {},
+    }
+}
+
+/// Collect the functions called in a sequence of tokens
+fn token_calls(tokens: &[Token], calls: &mut Vec<String>) {
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Identifier(name)
+                if matches!(tokens.get(index + 1), Some(Token::Symbol("("))) =>
+            {
+                calls.push(name.clone());
+            },
+            Token::String(segments) => {
+                for segment in segments {
+                    if let StringSegment::Interpolation(tokens) = segment {
+                        token_calls(tokens, calls);
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+    use crate::parsing::{BicepOutput, BicepParameter, BicepResource, BicepType, BicepVariable};
+
+    fn resource(resource_type: &str, properties: &[&str]) -> BicepResource {
+        BicepResource {
+            resource_type: resource_type.to_string(),
+            properties: properties
+                .iter()
+                .map(|name| (name.to_string(), BicepValue::Identifier(name.to_string())))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn output(value: BicepValue) -> BicepOutput {
+        BicepOutput {
+            description: None,
+            descriptions: IndexMap::new(),
+            output_type: BicepType::String,
+            value,
+            discriminator: None,
+            min_length: None,
+            max_length: None,
+            min_value: None,
+            max_value: None,
+            metadata: None,
+            sealed: false,
+            secure: false,
+        }
+    }
+
+    fn ids(findings: &[Finding]) -> Vec<&str> {
+        findings
+            .iter()
+            .map(|finding| finding.symbol.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("st*", "stlogs", false));
+        assert!(!glob_match("st*", "logs", false));
+        assert!(glob_match("*Name", "storageAccountName", false));
+        assert!(glob_match("a?c*", "abcdef", false));
+        assert!(glob_match(
+            "*/*/*",
+            "Microsoft.Storage/storageAccounts/blobServices",
+            true
+        ));
+        assert!(!glob_match(
+            "*/*/*",
+            "Microsoft.Storage/storageAccounts",
+            true
+        ));
+        assert!(glob_match(
+            "microsoft.authorization/*",
+            "Microsoft.Authorization/locks",
+            true
+        ));
+        assert!(!glob_match(
+            "microsoft.authorization/*",
+            "Microsoft.Authorization/locks",
+            false
+        ));
+    }
+
+    #[test]
+    fn test_name_case() {
+        assert!(NameCase::Camel.matches("storageAccountName"));
+        assert!(!NameCase::Camel.matches("StorageAccountName"));
+        assert!(!NameCase::Camel.matches("storage_account"));
+        assert!(NameCase::Pascal.matches("StorageAccount"));
+        assert!(NameCase::Snake.matches("storage_account_2"));
+        assert!(!NameCase::Snake.matches("storageAccount"));
+    }
+
+    #[test]
+    fn test_built_in_rules() {
+        let mut document = BicepDocument::default();
+        document
+            .parameters
+            .insert("Location".to_string(), BicepParameter::default());
+        document.parameters.insert(
+            "adminPassword".to_string(),
+            BicepParameter {
+                is_secure: true,
+                default_value: Some(BicepValue::String("P@ssw0rd".to_string())),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "optionalSecret".to_string(),
+            BicepParameter {
+                is_secure: true,
+                default_value: Some(BicepValue::String(String::new())),
+                descriptions: IndexMap::from([(
+                    "fr".to_string(),
+                    "Un secret facultatif".to_string(),
+                )]),
+                ..Default::default()
+            },
+        );
+        document.resources.insert(
+            "account".to_string(),
+            resource("Microsoft.Storage/storageAccounts", &["location"]),
+        );
+        document.resources.insert(
+            "account::blobs".to_string(),
+            resource("Microsoft.Storage/storageAccounts/blobServices", &[]),
+        );
+        document.resources.insert(
+            "lock".to_string(),
+            resource("Microsoft.Authorization/locks", &[]),
+        );
+        document.variables.insert(
+            "connection".to_string(),
+            BicepVariable {
+                description: None,
+                value: BicepValue::String("Password=${adminPassword}".to_string()),
+                is_exported: false,
+            },
+        );
+        document.outputs.insert(
+            "connection".to_string(),
+            output(BicepValue::Identifier("connection".to_string())),
+        );
+        document.outputs.insert(
+            "keys".to_string(),
+            output(BicepValue::Identifier(
+                "account.listKeys().keys[0].value".to_string(),
+            )),
+        );
+        document.outputs.insert(
+            "id".to_string(),
+            output(BicepValue::Identifier("account.id".to_string())),
+        );
+        // A user-defined function is not an ARM list*() function
+        document.outputs.insert(
+            "names".to_string(),
+            output(BicepValue::Identifier("listNames('app')".to_string())),
+        );
+
+        let findings: Vec<(String, Vec<Finding>)> = built_in_rules()
+            .iter()
+            .map(|rule| (rule.id().to_string(), rule.check(&document)))
+            .collect();
+        assert_eq!(ids(&findings[0].1), vec!["Location"]);
+        assert_eq!(ids(&findings[1].1), vec!["account"]);
+        assert_eq!(
+            findings[1].1[0].message,
+            "Resource `account` of type `Microsoft.Storage/storageAccounts` does not set `tags`"
+        );
+        assert_eq!(ids(&findings[2].1), vec!["adminPassword"]);
+        assert_eq!(ids(&findings[3].1), vec!["connection", "keys"]);
+        assert_eq!(
+            findings[3].1[0].message,
+            "Output `connection` exposes secure parameter `adminPassword`; mark it `@secure()`"
+        );
+        assert_eq!(
+            findings[3].1[1].message,
+            "Output `keys` exposes `listKeys()`; mark it `@secure()`"
+        );
+        assert_eq!(
+            ids(&findings[4].1),
+            vec![
+                "Location",
+                "adminPassword",
+                "connection",
+                "keys",
+                "id",
+                "names"
+            ]
+        );
+        assert_eq!(
+            findings[4].1[0].message,
+            "Parameter `Location` has no description; add a `@description()` decorator"
+        );
+    }
+
+    #[test]
+    fn test_forbidden_decorators() {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "sku".to_string(),
+            BicepParameter {
+                is_secure: true,
+                allowed_values: Some(vec![BicepValue::String("Standard".to_string())]),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "password".to_string(),
+            BicepParameter {
+                is_secure: true,
+                ..Default::default()
+            },
+        );
+
+        let rule = ForbiddenDecoratorsRule {
+            id: "secure-allowed".to_string(),
+            description: None,
+            severity: Severity::Error,
+            symbols: vec![SymbolKind::Parameter],
+            decorators: vec!["secure".to_string(), "allowed".to_string()],
+        };
+        let findings = rule.check(&document);
+        assert_eq!(ids(&findings), vec!["sku"]);
+        assert_eq!(
+            findings[0].message,
+            "Parameter `sku` combines `@secure()` and `@allowed()`"
+        );
+        assert_eq!(
+            rule.description(),
+            "Declarations do not combine `@secure()` and `@allowed()`"
+        );
+    }
+}
@@ -0,0 +1,304 @@
+//! Source positions and suppression comments for lint diagnostics.
+//!
+//! The document model does not record where declarations are, so the source text
+//! is scanned once for the lines that declare each symbol and for suppression
+//! comments. The scan understands strings, interpolations and comments well
+//! enough to tell a declaration or a comment from text that only looks like one.
+//!
+//! Two comments suppress diagnostics on a single line:
+//!
+//! ```bicep
+//! // bicep-docs-disable-next-line parameter-name-case
+//! param Location string
+//!
+//! param Name string // bicep-docs-disable-line
+//! ```
+//!
+//! Rule IDs after the directive limit it to those rules; without any it
+//! suppresses every rule.
+
+use std::collections::HashMap;
+
+use super::super::references::{SymbolId, SymbolKind};
+use crate::error::Span;
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// Comment directive suppressing diagnostics on the line after the comment
+pub const DISABLE_NEXT_LINE: &str = "bicep-docs-disable-next-line";
+/// Comment directive suppressing diagnostics on the line of the comment
+pub const DISABLE_LINE: &str = "bicep-docs-disable-line";
+
+/// Where the symbols of a template are declared and which lines are suppressed
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    /// The span of the name of each declaration
+    declarations: HashMap<SymbolId, Span>,
+    /// The rules suppressed on each line, with an empty list for every rule
+    suppressions: HashMap<usize, Vec<String>>,
+}
+
+/// What the scanner is reading
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// Expressions and declarations
+    Code,
+    /// A `${...}` interpolation, with the number of braces opened inside it
+    Interpolation(usize),
+    /// A single-quoted string
+    String,
+    /// A `'''` multi-line string
+    Verbatim,
+    /// A `/* ... */` comment
+    BlockComment,
+}
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+impl SourceMap {
+    /// Scan the source of a Bicep template
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The Bicep source text
+    ///
+    /// # Returns
+    ///
+    /// The declarations and suppressions found in the source
+    pub fn scan(source: &str) -> Self {
+        let mut map = SourceMap::default();
+        let mut modes = vec![Mode::Code];
+        let mut depth = 0;
+        // Resources whose body is open, with the depth their declaration is at
+        let mut parents: Vec<(String, usize)> = Vec::new();
+        let mut line_start = 0;
+
+        for (index, line) in source.split_inclusive('\n').enumerate() {
+            let line_number = index + 1;
+            if modes == [Mode::Code] {
+                if let Some((kind, name, column)) = declaration(line) {
+                    parents.retain(|(_, parent_depth)| *parent_depth < depth);
+                    let key = if kind == SymbolKind::Resource && depth > 0 {
+                        parents
+                            .last()
+                            .map(|(parent, _)| format!("{parent}::{name}"))
+                    } else {
+                        (depth == 0).then(|| name.to_string())
+                    };
+                    if let Some(key) = key {
+                        if kind == SymbolKind::Resource {
+                            parents.push((key.clone(), depth));
+                        }
+                        let start_byte = line_start + column;
+                        map.declarations.insert(
+                            SymbolId::new(kind, &key),
+                            Span {
+                                start_byte,
+                                end_byte: start_byte + name.len(),
+                                start_line: line_number,
+                                start_column: column + 1,
+                                end_line: line_number,
+                                end_column: column + name.len() + 1,
+                            },
+                        );
+                    }
+                }
+            }
+
+            let bytes = line.as_bytes();
+            let mut i = 0;
+            while i < bytes.len() {
+                let rest = &line[i..];
+                match modes.last_mut().expect("the scanner always has a mode") {
+                    Mode::Code | Mode::Interpolation(_) if rest.starts_with("//") => {
+                        map.suppress(line_number, &rest[2..]);
+                        break;
+                    },
+                    Mode::Code | Mode::Interpolation(_) if rest.starts_with("/*") => {
+                        modes.push(Mode::BlockComment);
+                        i += 1;
+                    },
+                    Mode::Code | Mode::Interpolation(_) if rest.starts_with("'''") => {
+                        modes.push(Mode::Verbatim);
+                        i += 2;
+                    },
+                    Mode::Code | Mode::Interpolation(_) if rest.starts_with('\'') => {
+                        modes.push(Mode::String);
+                    },
+                    Mode::Code if rest.starts_with('{') => depth += 1,
+                    Mode::Code if rest.starts_with('}') => depth = depth.saturating_sub(1),
+                    Mode::Interpolation(braces) if rest.starts_with('{') => *braces += 1,
+                    Mode::Interpolation(0) if rest.starts_with('}') => {
+                        modes.pop();
+                    },
+                    Mode::Interpolation(braces) if rest.starts_with('}') => *braces -= 1,
+                    Mode::String if rest.starts_with('\\') => i += 1,
+                    Mode::String if rest.starts_with("${") => {
+                        modes.push(Mode::Interpolation(0));
+                        i += 1;
+                    },
+                    Mode::String if rest.starts_with('\'') => {
+                        modes.pop();
+                    },
+                    Mode::Verbatim if rest.starts_with("'''") => {
+                        modes.pop();
+                        i += 2;
+                    },
+                    Mode::BlockComment if rest.starts_with("*/") => {
+                        modes.pop();
+                        i += 1;
+                    },
+                    _ => {},
+                }
+                i += rest.chars().next().map_or(1, char::len_utf8);
+            }
+            line_start += line.len();
+        }
+        map
+    }
+
+    /// The span of the name of a declaration
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - The declaration, with nested resources named `parent::child`
+    pub fn span(&self, symbol: &SymbolId) -> Option<Span> {
+        self.declarations.get(symbol).copied()
+    }
+
+    /// Whether a comment suppresses a rule on a line
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The ID of the rule
+    /// * `line` - The one-based line of the diagnostic
+    pub fn is_suppressed(&self, rule: &str, line: usize) -> bool {
+        self.suppressions
+            .get(&line)
+            .is_some_and(|rules| rules.is_empty() || rules.iter().any(|id| id == rule))
+    }
+
+    /// Record the suppression in a line comment, if it is one
+    fn suppress(&mut self, line: usize, comment: &str) {
+        let mut words = comment.split_whitespace();
+        let line = match words.next() {
+            Some(DISABLE_NEXT_LINE) => line + 1,
+            Some(DISABLE_LINE) => line,
+            _ => return,
+        };
+        self.suppressions
+            .entry(line)
+            .or_default()
+            .extend(words.map(str::to_string));
+    }
+}
+
+/// Read the declaration that a line starts with
+///
+/// # Returns
+///
+/// The kind of declaration, its name and the byte column the name starts at
+fn declaration(line: &str) -> Option<(SymbolKind, &str, usize)> {
+    let indent = line.len() - line.trim_start().len();
+    let mut rest = &line[indent..];
+    if let Some(after) = rest.strip_prefix("@export()") {
+        rest = after.trim_start();
+    }
+    let (keyword, after) = rest.split_once(|c: char| c.is_whitespace())?;
+    let kind = match keyword {
+        "type" => SymbolKind::Type,
+        "func" => SymbolKind::Function,
+        "param" => SymbolKind::Parameter,
+        "var" => SymbolKind::Variable,
+        "resource" => SymbolKind::Resource,
+        "module" => SymbolKind::Module,
+        "output" => SymbolKind::Output,
+        _ => return None,
+    };
+    let name_start = after.len() - after.trim_start().len();
+    let name = &after[name_start..];
+    let name_len = name
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(name.len());
+    let name = &name[..name_len];
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let column = line.len() - after.len() + name_start;
+    Some((kind, name, column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+@description('''Multi-line description
+param notADeclaration string
+''')
+param location string = '${resourceGroup().location}' // bicep-docs-disable-line
+
+/* param hidden string */
+var settings = {
+  type: 'Standard'
+  name: '}'
+}
+
+// bicep-docs-disable-next-line resource-tags parameter-name-case
+resource account 'Microsoft.Storage/storageAccounts@2023-01-01' = {
+  name: 'st${uniqueString('{')}'
+  resource blobs 'blobServices' = {
+    name: 'default'
+    resource container 'containers' = {
+      name: 'logs'
+    }
+  }
+}
+
+@export()
+output accountId string = account.id
+";
+
+    #[test]
+    fn test_declarations() {
+        let map = SourceMap::scan(SOURCE);
+        let line = |kind, name| {
+            map.span(&SymbolId::new(kind, name))
+                .map(|span| span.start_line)
+        };
+
+        assert_eq!(line(SymbolKind::Parameter, "location"), Some(4));
+        assert_eq!(line(SymbolKind::Parameter, "notADeclaration"), None);
+        assert_eq!(line(SymbolKind::Parameter, "hidden"), None);
+        assert_eq!(line(SymbolKind::Variable, "settings"), Some(7));
+        assert_eq!(line(SymbolKind::Resource, "account"), Some(13));
+        assert_eq!(line(SymbolKind::Resource, "account::blobs"), Some(15));
+        assert_eq!(
+            line(SymbolKind::Resource, "account::blobs::container"),
+            Some(17)
+        );
+        assert_eq!(line(SymbolKind::Output, "accountId"), Some(24));
+
+        let span = map
+            .span(&SymbolId::new(SymbolKind::Parameter, "location"))
+            .unwrap();
+        assert_eq!((span.start_column, span.end_column), (7, 15));
+        assert_eq!(&SOURCE[span.start_byte..span.end_byte], "location");
+    }
+
+    #[test]
+    fn test_suppressions() {
+        let map = SourceMap::scan(SOURCE);
+        assert!(map.is_suppressed("parameter-name-case", 4));
+        assert!(map.is_suppressed("resource-tags", 4));
+        assert!(map.is_suppressed("resource-tags", 13));
+        assert!(map.is_suppressed("parameter-name-case", 13));
+        assert!(!map.is_suppressed("no-secret-outputs", 13));
+        assert!(!map.is_suppressed("resource-tags", 12));
+        assert!(!map.is_suppressed("resource-tags", 14));
+    }
+}
@@ -0,0 +1,28 @@
+//! Static analysis of parsed Bicep documents.
+//!
+//! This module contains analysis passes that run over a [`crate::BicepDocument`]
+//! after parsing, such as working out which declarations reference each other,
+//! evaluating the values that are known at compile time, checking a module
+//! against house rules or the Azure Verified Modules interface, or reviewing how it
+//! handles secrets.
+
+pub mod avm;
+pub mod diagnostics;
+pub mod evaluator;
+pub mod expand;
+pub mod expressions;
+pub mod lint;
+pub mod references;
+pub mod sarif;
+pub mod security;
+
+pub use avm::{AvmRule, AvmViolation, RuleSet, RULE_SET_VERSIONS};
+pub use diagnostics::{Diagnostic, Severity};
+pub use evaluator::Evaluator;
+pub use expand::{expand_document, read_parameters_file, Expansion};
+pub use lint::{LintConfig, Linter};
+pub use references::{
+    analyze_references, value_references, ReferenceIndex, SymbolId, SymbolKind, SymbolReferences,
+};
+pub use sarif::SarifLog;
+pub use security::{SecurityReport, SecurityRule, SECURITY_RULES};
@@ -0,0 +1,547 @@
+//! Symbol reference analysis for Bicep documents.
+//!
+//! This module walks every value and expression in a parsed [`BicepDocument`] and
+//! records which symbols (parameters, variables, functions, types, resources and
+//! modules) each declaration refers to. The resulting [`ReferenceIndex`] answers
+//! both directions of the question: what a declaration depends on, and what is
+//! affected when a symbol changes.
+
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use super::expressions::{interpolated_identifiers, loop_variables, referenced_identifiers};
+use crate::parsing::{BicepDocument, BicepType, BicepValue};
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// The kind of declaration a symbol refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolKind {
+    /// A user-defined type
+    Type,
+    /// A user-defined function
+    Function,
+    /// A template parameter
+    Parameter,
+    /// A template variable
+    Variable,
+    /// A resource declaration
+    Resource,
+    /// A module declaration
+    Module,
+    /// A template output
+    Output,
+}
+
+/// Identifies a declaration within a document
+///
+/// Outputs live in their own namespace, so the kind is part of the identity.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolId {
+    /// The kind of declaration
+    pub kind: SymbolKind,
+    /// The declared name
+    pub name: String,
+}
+
+/// The references recorded for a single symbol
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolReferences {
+    /// Symbols referenced by this declaration
+    pub depends_on: Vec<SymbolId>,
+    /// Declarations that reference this symbol
+    pub used_by: Vec<SymbolId>,
+}
+
+/// Symbol table with references for a whole document
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReferenceIndex {
+    symbols: IndexMap<SymbolId, SymbolReferences>,
+}
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+impl fmt::Display for SymbolId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl SymbolId {
+    /// Create a new symbol identifier
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of declaration
+    /// * `name` - The declared name
+    pub fn new(kind: SymbolKind, name: &str) -> Self {
+        SymbolId {
+            kind,
+            name: name.to_string(),
+        }
+    }
+}
+
+impl ReferenceIndex {
+    /// Get the references recorded for a symbol
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of declaration
+    /// * `name` - The declared name
+    ///
+    /// # Returns
+    ///
+    /// The symbol's references, or None if the document does not declare it
+    pub fn get(&self, kind: SymbolKind, name: &str) -> Option<&SymbolReferences> {
+        self.symbols.get(&SymbolId::new(kind, name))
+    }
+
+    /// Get the declarations that reference a symbol
+    pub fn used_by(&self, kind: SymbolKind, name: &str) -> &[SymbolId] {
+        self.get(kind, name)
+            .map(|refs| refs.used_by.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get the symbols a declaration references
+    pub fn depends_on(&self, kind: SymbolKind, name: &str) -> &[SymbolId] {
+        self.get(kind, name)
+            .map(|refs| refs.depends_on.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Iterate over all symbols and their references in declaration order
+    pub fn symbols(&self) -> impl Iterator<Item = (&SymbolId, &SymbolReferences)> {
+        self.symbols.iter()
+    }
+
+    /// Whether a symbol is declared but never referenced
+    pub fn is_unused(&self, kind: SymbolKind, name: &str) -> bool {
+        self.get(kind, name)
+            .is_some_and(|refs| refs.used_by.is_empty())
+    }
+
+    /// Names of the parameters that are never referenced
+    pub fn unused_parameters(&self) -> Vec<&str> {
+        self.unused_of_kind(SymbolKind::Parameter)
+    }
+
+    /// Names of the variables that are never referenced
+    pub fn unused_variables(&self) -> Vec<&str> {
+        self.unused_of_kind(SymbolKind::Variable)
+    }
+
+    fn unused_of_kind(&self, kind: SymbolKind) -> Vec<&str> {
+        self.symbols
+            .iter()
+            .filter(|(id, refs)| id.kind == kind && refs.used_by.is_empty())
+            .map(|(id, _)| id.name.as_str())
+            .collect()
+    }
+
+    /// Record that `from` references `to`
+    fn add_reference(&mut self, from: &SymbolId, to: SymbolId) {
+        if *from == to {
+            return;
+        }
+
+        if let Some(refs) = self.symbols.get_mut(&to) {
+            if !refs.used_by.contains(from) {
+                refs.used_by.push(from.clone());
+            }
+        }
+        if let Some(refs) = self.symbols.get_mut(from) {
+            if !refs.depends_on.contains(&to) {
+                refs.depends_on.push(to);
+            }
+        }
+    }
+}
+
+/// Collects the identifiers referenced by values, types and expressions
+#[derive(Default)]
+struct ReferenceCollector {
+    names: Vec<String>,
+    locals: Vec<String>,
+}
+
+impl ReferenceCollector {
+    fn push(&mut self, name: String) {
+        if !self.locals.contains(&name) && !self.names.contains(&name) {
+            self.names.push(name);
+        }
+    }
+
+    fn expression(&mut self, text: &str) {
+        for name in referenced_identifiers(text) {
+            self.push(name);
+        }
+    }
+
+    /// Only the interpolations of string literals are scanned, so that a literal
+    /// that happens to equal a symbol name is not a reference
+    fn template(&mut self, text: &str) {
+        for name in interpolated_identifiers(text) {
+            self.push(name);
+        }
+    }
+
+    fn value(&mut self, value: &BicepValue) {
+        match value {
+            BicepValue::String(text) => self.template(text),
+            BicepValue::Identifier(text) => self.expression(text),
+            BicepValue::Array(items) => items.iter().for_each(|item| self.value(item)),
+            BicepValue::Object(props) => props.values().for_each(|prop| self.value(prop)),
+            BicepValue::Int(_) | BicepValue::Bool(_) => {},
+        }
+    }
+
+    fn bicep_type(&mut self, bicep_type: &BicepType) {
+        match bicep_type {
+            BicepType::CustomType(name) => self.push(name.clone()),
+            BicepType::Array(inner) => self.bicep_type(inner),
+            BicepType::Object(Some(props)) => {
+                for prop in props.values() {
+                    self.bicep_type(&prop.parameter_type);
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Resolve a referenced name to a declared symbol
+///
+/// Nested resource accessors (`parent::child`) resolve to the child resource when it
+/// is declared, or to the parent resource otherwise.
+fn resolve(declared: &IndexMap<String, SymbolKind>, name: &str) -> Option<SymbolId> {
+    if let Some(kind) = declared.get(name) {
+        return Some(SymbolId::new(*kind, name));
+    }
+
+    let root = name.split("::").next()?;
+    declared.get(root).map(|kind| SymbolId::new(*kind, root))
+}
+
+/// Build the reference index for a document
+///
+/// Every declaration's values and expressions are scanned for identifiers, which are
+/// resolved against the document's declared symbols. Names that do not resolve (for
+/// example built-in functions or loop variables) are ignored.
+///
+/// # Arguments
+///
+/// * `document` - The parsed Bicep document
+///
+/// # Returns
+///
+/// The reference index for the document
+pub fn analyze_references(document: &BicepDocument) -> ReferenceIndex {
+    let mut index = ReferenceIndex::default();
+    let mut declared: IndexMap<String, SymbolKind> = IndexMap::new();
+    let mut collected: Vec<(SymbolId, ReferenceCollector)> = Vec::new();
+
+    for (name, custom_type) in &document.types {
+        let mut collector = ReferenceCollector::default();
+        collector.bicep_type(&custom_type.definition);
+        collected.push((SymbolId::new(SymbolKind::Type, name), collector));
+    }
+
+    for (name, function) in &document.functions {
+        let mut collector = ReferenceCollector {
+            locals: function
+                .arguments
+                .iter()
+                .map(|arg| arg.name.clone())
+                .collect(),
+            ..Default::default()
+        };
+        for argument in &function.arguments {
+            collector.bicep_type(&argument.argument_type);
+        }
+        collector.bicep_type(&function.return_type);
+        collector.expression(&function.expression);
+        collected.push((SymbolId::new(SymbolKind::Function, name), collector));
+    }
+
+    for (name, parameter) in &document.parameters {
+        let mut collector = ReferenceCollector::default();
+        collector.bicep_type(&parameter.parameter_type);
+        if let Some(default_value) = &parameter.default_value {
+            collector.value(default_value);
+        }
+        collected.push((SymbolId::new(SymbolKind::Parameter, name), collector));
+    }
+
+    for (name, variable) in &document.variables {
+        let mut collector = ReferenceCollector::default();
+        collector.value(&variable.value);
+        collected.push((SymbolId::new(SymbolKind::Variable, name), collector));
+    }
+
+    for (name, resource) in &document.resources {
+        let mut collector = ReferenceCollector::default();
+        if let Some(loop_statement) = &resource.loop_statement {
+            collector.expression(loop_statement);
+            collector.locals = loop_variables(loop_statement);
+        }
+        if let Some(condition) = &resource.condition {
+            collector.expression(condition);
+        }
+        collector.template(&resource.name);
+        if let Some(scope) = &resource.scope {
+            collector.value(scope);
+        }
+        if let Some(parent) = &resource.parent {
+            collector.expression(parent);
+        }
+        for dependency in resource.depends_on.iter().flatten() {
+            collector.expression(dependency);
+        }
+        for value in resource.properties.values() {
+            collector.value(value);
+        }
+        collected.push((SymbolId::new(SymbolKind::Resource, name), collector));
+    }
+
+    for (name, module) in &document.modules {
+        let mut collector = ReferenceCollector::default();
+        if let Some(loop_statement) = &module.loop_statement {
+            collector.expression(loop_statement);
+            collector.locals = loop_variables(loop_statement);
+        }
+        if let Some(condition) = &module.condition {
+            collector.expression(condition);
+        }
+        if let Some(deployment_name) = &module.deployment_name {
+            collector.template(deployment_name);
+        }
+        for dependency in module.depends_on.iter().flatten() {
+            collector.expression(dependency);
+        }
+        for value in module.params.values() {
+            collector.value(value);
+        }
+        collected.push((SymbolId::new(SymbolKind::Module, name), collector));
+    }
+
+    for (name, output) in &document.outputs {
+        let mut collector = ReferenceCollector::default();
+        collector.bicep_type(&output.output_type);
+        collector.value(&output.value);
+        collected.push((SymbolId::new(SymbolKind::Output, name), collector));
+    }
+
+    // Register every symbol before resolving so that forward references work
+    for (id, _) in &collected {
+        if id.kind != SymbolKind::Output {
+            declared.insert(id.name.clone(), id.kind);
+        }
+        index
+            .symbols
+            .insert(id.clone(), SymbolReferences::default());
+    }
+
+    for (id, collector) in &collected {
+        for name in &collector.names {
+            if let Some(target) = resolve(&declared, name) {
+                index.add_reference(id, target);
+            }
+        }
+    }
+
+    index
+}
+
+/// Get the names a single value refers to
+///
+/// Unlike [`analyze_references`], the names are not resolved against a document, so
+/// callers can tell which part of a declaration, such as one module parameter,
+/// refers to a symbol.
+///
+/// # Arguments
+///
+/// * `value` - The value to scan
+///
+/// # Returns
+///
+/// The referenced names in the order they first appear
+pub fn value_references(value: &BicepValue) -> Vec<String> {
+    let mut collector = ReferenceCollector::default();
+    collector.value(value);
+    collector.names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{BicepOutput, BicepParameter, BicepResource, BicepVariable};
+
+    fn variable(value: &str) -> BicepVariable {
+        BicepVariable {
+            description: None,
+            value: BicepValue::String(value.to_string()),
+            is_exported: false,
+        }
+    }
+
+    fn sample_document() -> BicepDocument {
+        let mut document = BicepDocument::default();
+        for name in ["prefix", "environment", "unusedParam"] {
+            document.parameters.insert(
+                name.to_string(),
+                BicepParameter {
+                    parameter_type: BicepType::String,
+                    ..Default::default()
+                },
+            );
+        }
+        document.variables.insert(
+            "vaultName".to_string(),
+            variable("${prefix}-${environment}-kv"),
+        );
+        document
+            .variables
+            .insert("unusedVar".to_string(), variable("constant value"));
+
+        let mut properties = IndexMap::new();
+        properties.insert(
+            "location".to_string(),
+            BicepValue::Identifier("resourceGroup().location".to_string()),
+        );
+        document.resources.insert(
+            "vault".to_string(),
+            BicepResource {
+                resource_type: "Microsoft.KeyVault/vaults".to_string(),
+                api_version: "2023-07-01".to_string(),
+                name: "${vaultName}".to_string(),
+                properties,
+                ..Default::default()
+            },
+        );
+        document.outputs.insert(
+            "vaultName".to_string(),
+            BicepOutput {
+                description: None,
+                descriptions: IndexMap::new(),
+                output_type: BicepType::String,
+                value: BicepValue::Identifier("vault.name".to_string()),
+                discriminator: None,
+                min_length: None,
+                max_length: None,
+                min_value: None,
+                max_value: None,
+                metadata: None,
+                sealed: false,
+                secure: false,
+            },
+        );
+        document
+    }
+
+    #[test]
+    fn test_analyze_references_used_by() {
+        let index = analyze_references(&sample_document());
+
+        assert_eq!(
+            index.used_by(SymbolKind::Parameter, "prefix"),
+            &[SymbolId::new(SymbolKind::Variable, "vaultName")]
+        );
+        assert_eq!(
+            index.used_by(SymbolKind::Variable, "vaultName"),
+            &[SymbolId::new(SymbolKind::Resource, "vault")]
+        );
+        assert_eq!(
+            index.used_by(SymbolKind::Resource, "vault"),
+            &[SymbolId::new(SymbolKind::Output, "vaultName")]
+        );
+    }
+
+    #[test]
+    fn test_analyze_references_depends_on() {
+        let index = analyze_references(&sample_document());
+
+        assert_eq!(
+            index.depends_on(SymbolKind::Variable, "vaultName"),
+            &[
+                SymbolId::new(SymbolKind::Parameter, "prefix"),
+                SymbolId::new(SymbolKind::Parameter, "environment"),
+            ]
+        );
+        // Outputs are not referenceable, so the output does not shadow the variable
+        assert_eq!(
+            index.depends_on(SymbolKind::Output, "vaultName"),
+            &[SymbolId::new(SymbolKind::Resource, "vault")]
+        );
+    }
+
+    #[test]
+    fn test_unused_symbols() {
+        let index = analyze_references(&sample_document());
+
+        assert_eq!(index.unused_parameters(), vec!["unusedParam"]);
+        assert_eq!(index.unused_variables(), vec!["unusedVar"]);
+        assert!(index.is_unused(SymbolKind::Parameter, "unusedParam"));
+        assert!(!index.is_unused(SymbolKind::Parameter, "prefix"));
+    }
+
+    #[test]
+    fn test_loop_variables_shadow_symbols() {
+        let mut document = sample_document();
+        let mut properties = IndexMap::new();
+        properties.insert(
+            "location".to_string(),
+            BicepValue::Identifier("prefix.location".to_string()),
+        );
+        document.resources.insert(
+            "sites".to_string(),
+            BicepResource {
+                resource_type: "Microsoft.Web/sites".to_string(),
+                api_version: "2022-03-01".to_string(),
+                name: "${prefix.name}".to_string(),
+                loop_statement: Some("for prefix in items".to_string()),
+                properties,
+                ..Default::default()
+            },
+        );
+
+        let index = analyze_references(&document);
+        assert!(index.depends_on(SymbolKind::Resource, "sites").is_empty());
+    }
+
+    #[test]
+    fn test_literals_equal_to_symbol_names() {
+        let mut document = sample_document();
+        document
+            .variables
+            .insert("label".to_string(), variable("unusedParam"));
+        let mut properties = IndexMap::new();
+        properties.insert(
+            "kind".to_string(),
+            BicepValue::String("environment".to_string()),
+        );
+        document.resources.insert(
+            "storage".to_string(),
+            BicepResource {
+                resource_type: "Microsoft.Storage/storageAccounts".to_string(),
+                api_version: "2023-01-01".to_string(),
+                name: "vault".to_string(),
+                properties,
+                ..Default::default()
+            },
+        );
+
+        let index = analyze_references(&document);
+        assert!(index.depends_on(SymbolKind::Variable, "label").is_empty());
+        assert!(index.depends_on(SymbolKind::Resource, "storage").is_empty());
+        assert_eq!(index.unused_parameters(), vec!["unusedParam"]);
+    }
+}
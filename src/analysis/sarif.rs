@@ -0,0 +1,411 @@
+//! SARIF 2.1.0 logs of analysis results.
+//!
+//! A [`SarifLog`] collects the rules an analysis ran and the [`Diagnostic`]s it
+//! reported for each template, and writes them as a single run of the
+//! [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! format read by code scanning dashboards such as GitHub's. Source spans, which
+//! count bytes, are converted to the UTF-16 columns that SARIF uses by default
+//! when the source of the template is known.
+
+use std::path::{Component, Path};
+
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+use super::{
+    avm::RuleSet,
+    diagnostics::{Diagnostic, Severity, ERROR_RULES},
+    lint::Linter,
+    references::SymbolKind,
+    security::SECURITY_RULES,
+};
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// The version of SARIF that logs are written in
+pub const SARIF_VERSION: &str = "2.1.0";
+/// The JSON schema of SARIF 2.1.0 logs
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+/// Where to find out about the tool that produced a log
+const INFORMATION_URI: &str = "https://github.com/oWretch/bicep-docs";
+
+/// A rule that results can refer to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMetadata {
+    /// The ID of the rule
+    pub id: String,
+    /// What the rule requires
+    pub description: String,
+    /// The severity of the rule's results unless configured otherwise
+    pub severity: Severity,
+}
+
+/// A SARIF log being built from the results of one or more analyses
+#[derive(Debug, Clone, Default)]
+pub struct SarifLog {
+    /// The rules results refer to, in the order they were added
+    rules: IndexMap<String, RuleMetadata>,
+    /// The SARIF result objects
+    results: Vec<Value>,
+}
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+impl SarifLog {
+    /// Create a log without rules or results
+    pub fn new() -> Self {
+        SarifLog::default()
+    }
+
+    /// Add a rule, unless one with the same ID was already added
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The rule to add
+    pub fn add_rule(&mut self, rule: RuleMetadata) {
+        self.rules.entry(rule.id.clone()).or_insert(rule);
+    }
+
+    /// Add the rules of a linter that are on, at their configured severity
+    ///
+    /// # Arguments
+    ///
+    /// * `linter` - The linter whose rules to add
+    pub fn add_linter_rules(&mut self, linter: &Linter) {
+        for (rule, severity) in linter.rules() {
+            if let Some(severity) = severity {
+                self.add_rule(RuleMetadata {
+                    id: rule.id().to_string(),
+                    description: rule.description(),
+                    severity,
+                });
+            }
+        }
+    }
+
+    /// Add the rules of a version of the Azure Verified Modules rule set
+    ///
+    /// # Arguments
+    ///
+    /// * `rule_set` - The rule set whose rules to add
+    pub fn add_avm_rules(&mut self, rule_set: &RuleSet) {
+        for rule in &rule_set.rules {
+            self.add_rule(RuleMetadata {
+                id: rule.id.to_string(),
+                description: rule.summary.to_string(),
+                severity: Severity::Error,
+            });
+        }
+    }
+
+    /// Add the rules of the security review
+    pub fn add_security_rules(&mut self) {
+        for rule in SECURITY_RULES {
+            self.add_rule(RuleMetadata {
+                id: rule.id.to_string(),
+                description: rule.summary.to_string(),
+                severity: rule.severity,
+            });
+        }
+    }
+
+    /// Add the rules for templates that cannot be read or parsed
+    pub fn add_error_rules(&mut self) {
+        for (code, description) in ERROR_RULES {
+            self.add_rule(RuleMetadata {
+                id: code.to_string(),
+                description: description.to_string(),
+                severity: Severity::Error,
+            });
+        }
+    }
+
+    /// Add the diagnostics reported for a template
+    ///
+    /// Diagnostics of rules that were not added first add a rule named after their ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the template, relative to the root of the repository
+    /// * `source` - The source of the template, to convert columns to UTF-16
+    /// * `diagnostics` - The diagnostics reported for the template
+    pub fn add_results(&mut self, path: &Path, source: Option<&str>, diagnostics: &[Diagnostic]) {
+        let uri = artifact_uri(path);
+        for diagnostic in diagnostics {
+            self.add_rule(RuleMetadata {
+                id: diagnostic.rule.clone(),
+                description: diagnostic.rule.clone(),
+                severity: diagnostic.severity,
+            });
+            let rule_index = self.rules.get_index_of(&diagnostic.rule);
+
+            let mut physical_location = json!({ "artifactLocation": { "uri": uri } });
+            if let Some(span) = diagnostic.span {
+                physical_location["region"] = json!({
+                    "startLine": span.start_line,
+                    "startColumn": utf16_column(source, span.start_line, span.start_column),
+                    "endLine": span.end_line,
+                    "endColumn": utf16_column(source, span.end_line, span.end_column),
+                });
+            }
+            let mut location = json!({ "physicalLocation": physical_location });
+            if let Some(symbol) = &diagnostic.symbol {
+                location["logicalLocations"] = json!([{
+                    "name": symbol.name,
+                    "kind": logical_kind(symbol.kind),
+                }]);
+            }
+
+            self.results.push(json!({
+                "ruleId": diagnostic.rule,
+                "ruleIndex": rule_index,
+                "level": level(diagnostic.severity),
+                "message": { "text": diagnostic.message },
+                "locations": [location],
+            }));
+        }
+    }
+
+    /// The number of results in the log
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether the log has no results
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// The log as a SARIF JSON object
+    pub fn to_json(&self) -> Value {
+        let rules: Vec<Value> = self
+            .rules
+            .values()
+            .map(|rule| {
+                json!({
+                    "id": rule.id,
+                    "shortDescription": { "text": rule.description },
+                    "defaultConfiguration": { "level": level(rule.severity) },
+                })
+            })
+            .collect();
+
+        json!({
+            "$schema": SARIF_SCHEMA,
+            "version": SARIF_VERSION,
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "bicep-docs",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": INFORMATION_URI,
+                        "rules": rules,
+                    },
+                },
+                "columnKind": "utf16CodeUnits",
+                "results": self.results,
+            }],
+        })
+    }
+
+    /// The log as indented SARIF JSON text
+    pub fn to_string_pretty(&self) -> String {
+        let mut text =
+            serde_json::to_string_pretty(&self.to_json()).expect("SARIF logs serialize to JSON");
+        text.push('\n');
+        text
+    }
+}
+
+/// The SARIF level of a severity
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// The SARIF logical location kind of a declaration
+fn logical_kind(kind: SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::Type => "type",
+        SymbolKind::Function => "function",
+        SymbolKind::Parameter => "parameter",
+        SymbolKind::Variable => "variable",
+        SymbolKind::Resource => "resource",
+        SymbolKind::Module => "module",
+        SymbolKind::Output => "output",
+    }
+}
+
+/// The URI of a template: relative paths with `/` separators, or a `file` URI for absolute paths
+fn artifact_uri(path: &Path) -> String {
+    let segments: Vec<String> = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(segment) => Some(percent_encode(&segment.to_string_lossy())),
+            Component::ParentDir => Some("..".to_string()),
+            Component::Prefix(prefix) => Some(prefix.as_os_str().to_string_lossy().into_owned()),
+            Component::RootDir | Component::CurDir => None,
+        })
+        .collect();
+    let relative = segments.join("/");
+    if path.is_absolute() {
+        format!("file:///{relative}")
+    } else {
+        relative
+    }
+}
+
+/// Escape the characters of a path segment that may not appear in a URI
+fn percent_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            },
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Convert a one-based byte column to a one-based UTF-16 column
+fn utf16_column(source: Option<&str>, line: usize, byte_column: usize) -> usize {
+    let text = source.and_then(|source| source.lines().nth(line.saturating_sub(1)));
+    match text.and_then(|text| text.get(..byte_column.saturating_sub(1))) {
+        Some(prefix) => prefix.encode_utf16().count() + 1,
+        None => byte_column,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        analysis::{lint::LintConfig, references::SymbolId},
+        error::Span,
+        parsing::{BicepDocument, BicepParameter},
+    };
+
+    #[test]
+    fn test_sarif_log() {
+        let mut log = SarifLog::new();
+        log.add_linter_rules(&Linter::new());
+        log.add_error_rules();
+        let source = "// Über\nparam Location string\n";
+        log.add_results(
+            Path::new("./infra/main.bicep"),
+            Some(source),
+            &[Diagnostic {
+                rule: "parameter-name-case".to_string(),
+                severity: Severity::Warning,
+                message: "Parameter `Location` is not in camelCase".to_string(),
+                symbol: Some(SymbolId::new(SymbolKind::Parameter, "Location")),
+                span: Some(Span {
+                    start_byte: 15,
+                    end_byte: 23,
+                    start_line: 2,
+                    start_column: 7,
+                    end_line: 2,
+                    end_column: 15,
+                }),
+            }],
+        );
+        log.add_results(
+            Path::new("broken file.json"),
+            None,
+            &[Diagnostic {
+                rule: "custom-check".to_string(),
+                severity: Severity::Info,
+                message: "Something to know".to_string(),
+                symbol: None,
+                span: None,
+            }],
+        );
+        assert_eq!(log.len(), 2);
+
+        let sarif = log.to_json();
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules[0]["id"], "parameter-name-case");
+        assert_eq!(rules[0]["defaultConfiguration"]["level"], "warning");
+        assert!(rules.iter().any(|rule| rule["id"] == "BD1001"));
+
+        let result = &run["results"][0];
+        assert_eq!(result["ruleIndex"], 0);
+        assert_eq!(result["level"], "warning");
+        let location = &result["locations"][0];
+        assert_eq!(
+            location["physicalLocation"]["artifactLocation"]["uri"],
+            "infra/main.bicep"
+        );
+        assert_eq!(
+            location["physicalLocation"]["region"],
+            json!({ "startLine": 2, "startColumn": 7, "endLine": 2, "endColumn": 15 })
+        );
+        assert_eq!(
+            location["logicalLocations"][0],
+            json!({ "name": "Location", "kind": "parameter" })
+        );
+
+        let result = &run["results"][1];
+        assert_eq!(result["level"], "note");
+        assert_eq!(
+            rules[result["ruleIndex"].as_u64().unwrap() as usize]["id"],
+            "custom-check"
+        );
+        assert_eq!(
+            result["locations"][0]["physicalLocation"],
+            json!({ "artifactLocation": { "uri": "broken%20file.json" } })
+        );
+    }
+
+    #[test]
+    fn test_sarif_coverage_gaps() {
+        let mut document = BicepDocument::default();
+        document
+            .parameters
+            .insert("location".to_string(), BicepParameter::default());
+        let coverage = |linter: &Linter| {
+            let mut log = SarifLog::new();
+            log.add_linter_rules(linter);
+            log.add_results(Path::new("main.bicep"), None, &linter.lint(&document, None));
+            log.to_json()["runs"][0]["results"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|result| result["ruleId"] == "missing-description")
+                .map(|result| result["level"].clone())
+                .collect::<Vec<_>>()
+        };
+
+        // The default configuration reports coverage gaps as notes in SARIF
+        let mut linter = Linter::new();
+        assert!(coverage(&linter).is_empty());
+        linter.enable_default_off_rules(Severity::Info);
+        assert_eq!(coverage(&linter), vec![json!("note")]);
+
+        // A configured level is kept
+        let config = LintConfig::parse("rules:\n  missing-description: off\n").unwrap();
+        let mut linter = Linter::with_config(config).unwrap();
+        linter.enable_default_off_rules(Severity::Info);
+        assert!(coverage(&linter).is_empty());
+    }
+
+    #[test]
+    fn test_utf16_column() {
+        let source = "var a = 'Ünïcode' // 😀 x\n";
+        let emoji = source.find('😀').unwrap();
+        assert_eq!(utf16_column(Some(source), 1, emoji + 1), 22);
+        assert_eq!(utf16_column(Some(source), 1, emoji + 5), 24);
+        assert_eq!(utf16_column(None, 1, 40), 40);
+        assert_eq!(utf16_column(Some(source), 3, 5), 5);
+    }
+}
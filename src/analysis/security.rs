@@ -0,0 +1,896 @@
+//! Security review of secrets and sensitive data.
+//!
+//! [`review`] lists the secure parameters and outputs of a template, traces the
+//! declarations that the value of each secure parameter reaches, directly or
+//! through variables, and reports each place a secret may leak or be written into
+//! the template as a [`Diagnostic`] of one of the [`SECURITY_RULES`]:
+//!
+//! - outputs that are not `@secure()` but receive a secure value,
+//! - module parameters that are not `@secure()` but receive a secure value,
+//! - variables that hold a secure value, which loses its protection,
+//! - calls to `list*()` functions and `getSecret()`, to review where they go,
+//! - default values of secure parameters, and defaults of other parameters that
+//!   look like secrets.
+//!
+//! Outputs and secure parameter defaults are checked by the `no-secret-outputs`
+//! and `secure-parameter-default` lint rules, and reported under their IDs, so that
+//! the lint and the review report the same problem once. Diagnostics can be
+//! suppressed with the comments described in [`source`](super::lint::source), and
+//! their messages are written in the language of the review.
+
+use std::{collections::HashSet, fs, path::Path};
+
+use indexmap::IndexMap;
+use rust_i18n::t;
+use tracing::debug;
+
+use super::{
+    diagnostics::{Diagnostic, Severity},
+    lint::{
+        rules::{
+            called_functions, is_list_function, NoSecretOutputsRule, SecureParameterDefaultRule,
+        },
+        Rule, SourceMap,
+    },
+    references::{analyze_references, value_references, ReferenceIndex, SymbolId, SymbolKind},
+};
+use crate::{
+    localization::Language,
+    parsing::{BicepDocument, BicepValue, ModuleSource},
+};
+
+// ---------------------------------------------------------------
+// Structs, Enums & Types
+// ---------------------------------------------------------------
+
+/// A rule of the security review
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecurityRule {
+    /// The ID diagnostics of the rule are reported with
+    pub id: &'static str,
+    /// What the rule requires
+    pub summary: &'static str,
+    /// The severity of the rule's diagnostics
+    pub severity: Severity,
+}
+
+/// The rules of the security review
+pub const SECURITY_RULES: &[SecurityRule] = &[
+    SecurityRule {
+        id: "no-secret-outputs",
+        summary: "Outputs that receive secure values are `@secure()`",
+        severity: Severity::Error,
+    },
+    SecurityRule {
+        id: "secret-module-parameter",
+        summary: "Module parameters that receive secure values are `@secure()`",
+        severity: Severity::Warning,
+    },
+    SecurityRule {
+        id: "secret-variable",
+        summary: "Variables do not hold secure values",
+        severity: Severity::Warning,
+    },
+    SecurityRule {
+        id: "secret-function",
+        summary: "Secrets read with `list*()` or `getSecret()` are reviewed",
+        severity: Severity::Info,
+    },
+    SecurityRule {
+        id: "secure-parameter-default",
+        summary: "`@secure()` parameters have no default value",
+        severity: Severity::Error,
+    },
+    SecurityRule {
+        id: "hard-coded-secret",
+        summary: "Defaults of parameters that are not `@secure()` do not contain secrets",
+        severity: Severity::Error,
+    },
+];
+
+/// What the security review found in a template
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SecurityReport {
+    /// The `@secure()` parameters, in declaration order
+    pub secure_parameters: Vec<SecureParameter>,
+    /// The names of the `@secure()` outputs, in declaration order
+    pub secure_outputs: Vec<String>,
+    /// The places a secret may leak or is hard-coded
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// A secure parameter and where its value goes
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecureParameter {
+    /// The name of the parameter
+    pub name: String,
+    /// Whether the parameter has a default value
+    pub has_default: bool,
+    /// The declarations that receive the value, directly or through variables
+    pub flows_to: Vec<SymbolId>,
+}
+
+/// Connection string settings whose value is a secret, in lowercase
+const SECRET_SETTINGS: &[&str] = &[
+    "accountkey=",
+    "password=",
+    "pwd=",
+    "sharedaccesskey=",
+    "sharedaccesssignature=",
+    "sig=",
+    "clientsecret=",
+];
+
+/// The shortest base64 or hex text that is taken for a key
+const MIN_KEY_LENGTH: usize = 32;
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+/// Review a document for secrets and sensitive data
+///
+/// # Arguments
+///
+/// * `document` - The document to review
+/// * `source` - The Bicep source of the document, to place diagnostics
+/// * `modules` - The documents of the document's modules, keyed by module name, to
+///   check the parameters they are passed; modules without one are not checked
+/// * `language` - The language to write the messages of diagnostics in
+///
+/// # Returns
+///
+/// The secure parameters and outputs, and diagnostics in declaration order
+pub fn review(
+    document: &BicepDocument,
+    source: Option<&str>,
+    modules: &IndexMap<String, BicepDocument>,
+    language: Language,
+) -> SecurityReport {
+    let locale = language.code();
+    let references = analyze_references(document);
+    let source_map = source.map(SourceMap::scan).unwrap_or_default();
+    let mut findings: Vec<(&'static str, SymbolId, String)> = Vec::new();
+
+    // The secure parameters that reach each declaration
+    let mut received: IndexMap<SymbolId, Vec<String>> = IndexMap::new();
+    let mut secure_parameters = Vec::new();
+    for (name, parameter) in &document.parameters {
+        if !parameter.is_secure {
+            continue;
+        }
+        let flows_to = flows(&references, SymbolId::new(SymbolKind::Parameter, name));
+        for symbol in &flows_to {
+            received
+                .entry(symbol.clone())
+                .or_default()
+                .push(name.clone());
+        }
+        secure_parameters.push(SecureParameter {
+            name: name.clone(),
+            has_default: parameter.default_value.is_some(),
+            flows_to,
+        });
+    }
+
+    // The lint rules decide which secure defaults and outputs are reported
+    let secure_defaults = flagged(&SecureParameterDefaultRule, document);
+    let exposing_outputs = flagged(&NoSecretOutputsRule, document);
+
+    for (name, parameter) in &document.parameters {
+        let Some(default_value) = &parameter.default_value else {
+            continue;
+        };
+        let symbol = SymbolId::new(SymbolKind::Parameter, name);
+        if secure_defaults.contains(&symbol) {
+            findings.push((
+                "secure-parameter-default",
+                symbol,
+                t!("security.secure_default", locale = locale, name = name).to_string(),
+            ));
+        } else if let Some(secret) = secret_text(default_value).filter(|_| !parameter.is_secure) {
+            findings.push((
+                "hard-coded-secret",
+                symbol,
+                t!(
+                    "security.default_looks_like_secret",
+                    locale = locale,
+                    name = name,
+                    secret = t!(secret, locale = locale)
+                )
+                .to_string(),
+            ));
+        }
+    }
+
+    for (name, variable) in &document.variables {
+        let symbol = SymbolId::new(SymbolKind::Variable, name);
+        if let Some(parameters) = received.get(&symbol) {
+            findings.push((
+                "secret-variable",
+                symbol.clone(),
+                t!(
+                    "security.variable_holds_secret",
+                    locale = locale,
+                    name = name,
+                    secrets = secure_parameter_list(parameters, language)
+                )
+                .to_string(),
+            ));
+        }
+        push_secret_functions(&mut findings, symbol, [&variable.value], language);
+    }
+
+    for (name, resource) in &document.resources {
+        push_secret_functions(
+            &mut findings,
+            SymbolId::new(SymbolKind::Resource, name),
+            resource.properties.values(),
+            language,
+        );
+    }
+
+    for (name, module) in &document.modules {
+        let symbol = SymbolId::new(SymbolKind::Module, name);
+        let target = modules.get(name);
+        for (parameter, value) in &module.params {
+            let is_secure = target
+                .and_then(|target| target.parameters.get(parameter))
+                .map(|parameter| parameter.is_secure);
+            let secrets = secrets_in(document, &received, value, language);
+            if is_secure == Some(false) && !secrets.is_empty() {
+                findings.push((
+                    "secret-module-parameter",
+                    symbol.clone(),
+                    t!(
+                        "security.module_passes_secret",
+                        locale = locale,
+                        name = name,
+                        secrets = secrets.join(", "),
+                        parameter = parameter
+                    )
+                    .to_string(),
+                ));
+            }
+        }
+        push_secret_functions(&mut findings, symbol, module.params.values(), language);
+    }
+
+    let mut secure_outputs = Vec::new();
+    for (name, output) in &document.outputs {
+        let symbol = SymbolId::new(SymbolKind::Output, name);
+        if output.secure {
+            secure_outputs.push(name.clone());
+        } else if exposing_outputs.contains(&symbol) {
+            let mut secrets: Vec<String> = received
+                .get(&symbol)
+                .map(|parameters| {
+                    parameters
+                        .iter()
+                        .map(|parameter| {
+                            secure_parameter_list(std::slice::from_ref(parameter), language)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            secrets.extend(secret_calls(&output.value));
+            findings.push((
+                "no-secret-outputs",
+                symbol.clone(),
+                t!(
+                    "security.output_receives_secret",
+                    locale = locale,
+                    name = name,
+                    secrets = secrets.join(", ")
+                )
+                .to_string(),
+            ));
+        }
+        push_secret_functions(&mut findings, symbol, [&output.value], language);
+    }
+
+    let diagnostics = findings
+        .into_iter()
+        .filter_map(|(rule, symbol, message)| {
+            let span = source_map.span(&symbol);
+            if span.is_some_and(|span| source_map.is_suppressed(rule, span.start_line)) {
+                return None;
+            }
+            Some(Diagnostic {
+                rule: rule.to_string(),
+                severity: rule_severity(rule),
+                message,
+                span,
+                symbol: Some(symbol),
+            })
+        })
+        .collect();
+
+    SecurityReport {
+        secure_parameters,
+        secure_outputs,
+        diagnostics,
+    }
+}
+
+/// Read the local modules of a template, to check the parameters they are passed
+///
+/// Modules from registries, and local modules that cannot be read or parsed, are
+/// left out.
+///
+/// # Arguments
+///
+/// * `document` - The document declaring the modules
+/// * `template` - The path of the template, which local module paths are relative to
+///
+/// # Returns
+///
+/// The documents of the modules, keyed by module name
+pub fn load_local_modules(
+    document: &BicepDocument,
+    template: &Path,
+) -> IndexMap<String, BicepDocument> {
+    let directory = template.parent().unwrap_or(Path::new(""));
+    let mut modules = IndexMap::new();
+    for (name, module) in &document.modules {
+        let ModuleSource::LocalPath(path) = &module.source else {
+            continue;
+        };
+        let path = directory.join(path);
+        let parsed = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|source| crate::parse_template(&source, &path).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(module_document) => {
+                modules.insert(name.clone(), module_document);
+            },
+            Err(e) => debug!("Not checking module {name} ({}): {e}", path.display()),
+        }
+    }
+    modules
+}
+
+/// The declarations a lint rule reports in a document
+fn flagged(rule: &dyn Rule, document: &BicepDocument) -> HashSet<SymbolId> {
+    rule.check(document)
+        .into_iter()
+        .map(|finding| finding.symbol)
+        .collect()
+}
+
+/// The severity of a security rule
+fn rule_severity(id: &str) -> Severity {
+    SECURITY_RULES
+        .iter()
+        .find(|rule| rule.id == id)
+        .map_or(Severity::Warning, |rule| rule.severity)
+}
+
+/// The declarations a symbol's value reaches, following variables
+fn flows(references: &ReferenceIndex, symbol: SymbolId) -> Vec<SymbolId> {
+    let mut seen = HashSet::new();
+    let mut reached = Vec::new();
+    let mut pending = vec![symbol];
+    while let Some(current) = pending.pop() {
+        for user in references.used_by(current.kind, &current.name) {
+            if !seen.insert(user.clone()) {
+                continue;
+            }
+            reached.push(user.clone());
+            if user.kind == SymbolKind::Variable {
+                pending.push(user.clone());
+            }
+        }
+    }
+    reached
+}
+
+/// The secure parameters and secret reads that a value passes on
+fn secrets_in(
+    document: &BicepDocument,
+    received: &IndexMap<SymbolId, Vec<String>>,
+    value: &BicepValue,
+    language: Language,
+) -> Vec<String> {
+    let mut parameters: Vec<String> = Vec::new();
+    for name in value_references(value) {
+        let name = name.split("::").next().unwrap_or(&name).to_string();
+        if document
+            .parameters
+            .get(&name)
+            .is_some_and(|parameter| parameter.is_secure)
+        {
+            parameters.push(name);
+        } else if let Some(through) = received.get(&SymbolId::new(SymbolKind::Variable, &name)) {
+            parameters.extend(through.iter().cloned());
+        }
+    }
+    let mut seen = HashSet::new();
+    parameters.retain(|parameter| seen.insert(parameter.clone()));
+
+    let mut secrets: Vec<String> = parameters
+        .iter()
+        .map(|parameter| secure_parameter_list(std::slice::from_ref(parameter), language))
+        .collect();
+    secrets.extend(secret_calls(value));
+    secrets
+}
+
+/// The secret reads in a value, written as `` `listKeys()` ``
+fn secret_calls(value: &BicepValue) -> Vec<String> {
+    let mut calls: Vec<String> = Vec::new();
+    for function in called_functions(value) {
+        if is_secret_function(&function) && !calls.contains(&function) {
+            calls.push(function);
+        }
+    }
+    calls
+        .into_iter()
+        .map(|function| format!("`{function}()`"))
+        .collect()
+}
+
+/// Add a finding for each secret read in the values of a declaration
+fn push_secret_functions<'a>(
+    findings: &mut Vec<(&'static str, SymbolId, String)>,
+    symbol: SymbolId,
+    values: impl IntoIterator<Item = &'a BicepValue>,
+    language: Language,
+) {
+    let mut functions: Vec<String> = Vec::new();
+    for value in values {
+        for function in called_functions(value) {
+            if is_secret_function(&function) && !functions.contains(&function) {
+                functions.push(function);
+            }
+        }
+    }
+    for function in functions {
+        let message = t!(
+            "security.reads_secret",
+            locale = language.code(),
+            name = symbol.name,
+            function = function
+        );
+        findings.push(("secret-function", symbol.clone(), message.to_string()));
+    }
+}
+
+/// Whether a function returns secrets, like `listKeys()` or `vault.getSecret()`
+fn is_secret_function(name: &str) -> bool {
+    name == "getSecret" || is_list_function(name)
+}
+
+/// Describe a list of secure parameters, e.g. "secure parameter `password`"
+fn secure_parameter_list(parameters: &[String], language: Language) -> String {
+    let locale = language.code();
+    match parameters {
+        [name] => t!(
+            "security.secure_parameter_named",
+            locale = locale,
+            name = name
+        )
+        .to_string(),
+        _ => {
+            let names: Vec<_> = parameters.iter().map(|name| format!("`{name}`")).collect();
+            t!(
+                "security.secure_parameters_named",
+                locale = locale,
+                names = names.join(", ")
+            )
+            .to_string()
+        },
+    }
+}
+
+/// What secret the text in a value looks like, if any, as the key of its description
+fn secret_text(value: &BicepValue) -> Option<&'static str> {
+    match value {
+        BicepValue::String(text) => looks_like_secret(text),
+        BicepValue::Array(items) => items.iter().find_map(secret_text),
+        BicepValue::Object(properties) => properties.values().find_map(secret_text),
+        BicepValue::Identifier(_) | BicepValue::Int(_) | BicepValue::Bool(_) => None,
+    }
+}
+
+/// What secret a text looks like: a connection string with a key or password, or a key
+///
+/// Returns the key of the description of the secret.
+fn looks_like_secret(text: &str) -> Option<&'static str> {
+    let lower = text.to_ascii_lowercase();
+    let has_secret_setting = lower.split(';').any(|setting| {
+        let setting = setting.trim_start();
+        SECRET_SETTINGS.iter().any(|name| {
+            setting
+                .strip_prefix(name)
+                .is_some_and(|value| !value.is_empty() && !value.starts_with("${"))
+        })
+    });
+    if has_secret_setting {
+        return Some("security.connection_string_secret");
+    }
+
+    let is_key_character = |c: char| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=');
+    let key = text.trim_end_matches('=');
+    let is_key = key.len() >= MIN_KEY_LENGTH
+        && key.chars().all(is_key_character)
+        && key.chars().any(|c| c.is_ascii_digit())
+        && key.chars().any(|c| c.is_ascii_alphabetic())
+        && !key.contains('=')
+        && !is_path(key);
+    is_key.then_some("security.key_secret")
+}
+
+/// Whether a text that could be base64 is shaped like a path, e.g. `/etc/ssl/server2024`
+///
+/// Paths start or end with a separator, or have a directory named in lowercase
+/// letters, which base64 text of a random key rarely does.
+fn is_path(text: &str) -> bool {
+    if !text.contains('/') {
+        return false;
+    }
+    text.starts_with('/')
+        || text.ends_with('/')
+        || text.contains("//")
+        || text
+            .split('/')
+            .any(|segment| segment.len() >= 3 && segment.chars().all(|c| c.is_ascii_lowercase()))
+}
+
+impl SecurityReport {
+    /// Render the reports of several templates as a Markdown document
+    ///
+    /// # Arguments
+    ///
+    /// * `reports` - The path of each template and its report
+    /// * `language` - The language to write headings and labels in
+    ///
+    /// # Returns
+    ///
+    /// The Markdown document, with a section for each template
+    pub fn to_markdown(reports: &[(String, SecurityReport)], language: Language) -> String {
+        let locale = language.code();
+        let label = |key: &str| t!(key, locale = locale).to_string();
+        let none = format!("{}\n\n", label("security.none"));
+
+        let mut markdown = format!("# {}\n\n", label("security.title"));
+        for (path, report) in reports {
+            markdown.push_str(&format!("## {path}\n\n"));
+
+            markdown.push_str(&format!("### {}\n\n", label("security.secure_parameters")));
+            if report.secure_parameters.is_empty() {
+                markdown.push_str(&none);
+            } else {
+                markdown.push_str(&format!(
+                    "| {} | {} | {} |\n| --- | --- | --- |\n",
+                    label("security.parameter"),
+                    label("security.has_default"),
+                    label("security.flows_to")
+                ));
+                for parameter in &report.secure_parameters {
+                    let flows_to = if parameter.flows_to.is_empty() {
+                        label("security.none")
+                    } else {
+                        let names: Vec<_> = parameter
+                            .flows_to
+                            .iter()
+                            .map(|symbol| format!("`{}`", symbol.name))
+                            .collect();
+                        names.join(", ")
+                    };
+                    let has_default = if parameter.has_default {
+                        label("common.yes")
+                    } else {
+                        label("common.no")
+                    };
+                    markdown.push_str(&format!(
+                        "| `{}` | {has_default} | {flows_to} |\n",
+                        parameter.name
+                    ));
+                }
+                markdown.push('\n');
+            }
+
+            markdown.push_str(&format!("### {}\n\n", label("security.secure_outputs")));
+            if report.secure_outputs.is_empty() {
+                markdown.push_str(&none);
+            } else {
+                for output in &report.secure_outputs {
+                    markdown.push_str(&format!("- `{output}`\n"));
+                }
+                markdown.push('\n');
+            }
+
+            markdown.push_str(&format!("### {}\n\n", label("security.findings")));
+            if report.diagnostics.is_empty() {
+                markdown.push_str(&none);
+            } else {
+                markdown.push_str(&format!(
+                    "| {} | {} | {} | {} |\n| --- | --- | --- | --- |\n",
+                    label("security.severity"),
+                    label("security.rule"),
+                    label("security.line"),
+                    label("security.message")
+                ));
+                for diagnostic in &report.diagnostics {
+                    let line = diagnostic
+                        .span
+                        .map(|span| span.start_line.to_string())
+                        .unwrap_or_default();
+                    markdown.push_str(&format!(
+                        "| {} | `{}` | {line} | {} |\n",
+                        label(&format!("security.{}", diagnostic.severity)),
+                        diagnostic.rule,
+                        diagnostic.message.replace('|', "\\|")
+                    ));
+                }
+                markdown.push('\n');
+            }
+        }
+        markdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{BicepModule, BicepOutput, BicepParameter, BicepType, BicepVariable};
+
+    fn output(value: &str, secure: bool) -> BicepOutput {
+        BicepOutput {
+            description: None,
+            descriptions: IndexMap::new(),
+            output_type: BicepType::String,
+            value: BicepValue::Identifier(value.to_string()),
+            discriminator: None,
+            min_length: None,
+            max_length: None,
+            min_value: None,
+            max_value: None,
+            metadata: None,
+            sealed: false,
+            secure,
+        }
+    }
+
+    fn document() -> BicepDocument {
+        let mut document = BicepDocument::default();
+        let parameters = [
+            ("adminPassword", true, None),
+            (
+                "apiKey",
+                true,
+                Some(BicepValue::String(
+                    "correct horse battery staple".to_string(),
+                )),
+            ),
+            (
+                "sessionToken",
+                true,
+                Some(BicepValue::Identifier("newGuid()".to_string())),
+            ),
+            (
+                "storageConnection",
+                false,
+                Some(BicepValue::String(
+                    "DefaultEndpointsProtocol=https;AccountName=st;AccountKey=abc123==".to_string(),
+                )),
+            ),
+            (
+                "location",
+                false,
+                Some(BicepValue::Identifier(
+                    "resourceGroup().location".to_string(),
+                )),
+            ),
+        ];
+        for (name, is_secure, default_value) in parameters {
+            document.parameters.insert(
+                name.to_string(),
+                BicepParameter {
+                    description: Some(format!("The {name}")),
+                    is_secure,
+                    default_value,
+                    ..Default::default()
+                },
+            );
+        }
+        document.variables.insert(
+            "credentials".to_string(),
+            BicepVariable {
+                description: None,
+                value: BicepValue::Object(IndexMap::from([(
+                    "password".to_string(),
+                    BicepValue::Identifier("adminPassword".to_string()),
+                )])),
+                is_exported: false,
+            },
+        );
+        document.modules.insert(
+            "app".to_string(),
+            BicepModule {
+                description: None,
+                name: "app".to_string(),
+                deployment_name: None,
+                source: ModuleSource::LocalPath("./app.bicep".to_string()),
+                depends_on: None,
+                condition: None,
+                loop_statement: None,
+                batch_size: None,
+                params: IndexMap::from([
+                    (
+                        "settings".to_string(),
+                        BicepValue::Identifier("credentials".to_string()),
+                    ),
+                    (
+                        "password".to_string(),
+                        BicepValue::Identifier("adminPassword".to_string()),
+                    ),
+                    (
+                        "secret".to_string(),
+                        BicepValue::Identifier("vault.getSecret('admin')".to_string()),
+                    ),
+                ]),
+            },
+        );
+        document
+            .outputs
+            .insert("password".to_string(), output("adminPassword", false));
+        document
+            .outputs
+            .insert("token".to_string(), output("sessionToken", true));
+        document.outputs.insert(
+            "keys".to_string(),
+            output("account.listKeys().keys[0].value", false),
+        );
+        document
+    }
+
+    fn app_module() -> BicepDocument {
+        let mut module = BicepDocument::default();
+        for (name, is_secure) in [("settings", false), ("password", true), ("secret", false)] {
+            module.parameters.insert(
+                name.to_string(),
+                BicepParameter {
+                    is_secure,
+                    ..Default::default()
+                },
+            );
+        }
+        module
+    }
+
+    #[test]
+    fn test_review() {
+        let modules = IndexMap::from([("app".to_string(), app_module())]);
+        let report = review(&document(), None, &modules, Language::English);
+
+        let names: Vec<_> = report
+            .secure_parameters
+            .iter()
+            .map(|parameter| (parameter.name.as_str(), parameter.has_default))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("adminPassword", false),
+                ("apiKey", true),
+                ("sessionToken", true)
+            ]
+        );
+        let flows: Vec<_> = report.secure_parameters[0]
+            .flows_to
+            .iter()
+            .map(|symbol| symbol.to_string())
+            .collect();
+        assert_eq!(flows.len(), 3);
+        for name in ["credentials", "app", "password"] {
+            assert!(flows.contains(&name.to_string()), "{name} in {flows:?}");
+        }
+        assert_eq!(report.secure_outputs, vec!["token"]);
+
+        let found: Vec<_> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.rule.as_str(),
+                    diagnostic.symbol.as_ref().unwrap().name.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("secure-parameter-default", "apiKey"),
+                ("hard-coded-secret", "storageConnection"),
+                ("secret-variable", "credentials"),
+                ("secret-module-parameter", "app"),
+                ("secret-module-parameter", "app"),
+                ("secret-function", "app"),
+                ("no-secret-outputs", "password"),
+                ("no-secret-outputs", "keys"),
+                ("secret-function", "keys"),
+            ]
+        );
+        assert!(report.diagnostics[3].message.contains("`settings`"));
+        assert!(report.diagnostics[4].message.contains("`getSecret()`"));
+        assert_eq!(report.diagnostics[6].severity, Severity::Error);
+
+        // Without the module, its parameters are not checked
+        let report = review(&document(), None, &IndexMap::new(), Language::English);
+        assert!(!report
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.rule == "secret-module-parameter"));
+    }
+
+    #[test]
+    fn test_looks_like_secret() {
+        assert!(looks_like_secret("Server=tcp:sql;User ID=admin;Password=Pa55w0rd!").is_some());
+        assert!(looks_like_secret("Endpoint=sb://ns/;SharedAccessKey=${key}").is_none());
+        assert!(looks_like_secret("Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==").is_some());
+        assert!(looks_like_secret("0123456789abcdef0123456789abcdef").is_some());
+        assert!(looks_like_secret("00000000-0000-0000-0000-000000000000").is_none());
+        assert!(looks_like_secret("Microsoft.Storage/storageAccounts").is_none());
+        assert!(looks_like_secret("resourceGroup().location").is_none());
+
+        // Paths are made of the same characters as base64 text
+        assert!(looks_like_secret("home/azureuser/certificates/server2024certificate").is_none());
+        assert!(looks_like_secret("/etc/ssl/private/server2024").is_none());
+        assert!(looks_like_secret("Server2024Certificate/Intermediate2024CA/").is_none());
+        assert_eq!(
+            looks_like_secret("AccountName=st;AccountKey=abc123=="),
+            Some("security.connection_string_secret")
+        );
+    }
+
+    #[test]
+    fn test_review_language_and_suppression() {
+        let source = "\
+param apiKey string = 'correct horse battery staple'
+// bicep-docs-disable-next-line no-secret-outputs
+output password string = adminPassword
+";
+        let report = review(
+            &document(),
+            Some(source),
+            &IndexMap::new(),
+            Language::French,
+        );
+        let found: Vec<_> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.rule.as_str(),
+                    diagnostic.symbol.as_ref().unwrap().name.as_str(),
+                )
+            })
+            .collect();
+        assert!(!found.contains(&("no-secret-outputs", "password")));
+        assert!(found.contains(&("no-secret-outputs", "keys")));
+
+        let english = review(&document(), None, &IndexMap::new(), Language::English);
+        assert_eq!(english.diagnostics[0].rule, "secure-parameter-default");
+        assert_eq!(
+            english.diagnostics[0].message,
+            "Secure parameter `apiKey` has a default value written into the template"
+        );
+        assert_ne!(
+            report.diagnostics[0].message,
+            english.diagnostics[0].message
+        );
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let report = review(&document(), None, &IndexMap::new(), Language::English);
+        let markdown =
+            SecurityReport::to_markdown(&[("main.bicep".to_string(), report)], Language::English);
+        assert!(markdown.starts_with("# Security Review\n\n## main.bicep\n"));
+        assert!(markdown.contains("| `adminPassword` | No | "));
+        assert!(markdown.contains("- `token`\n"));
+        assert!(markdown.contains("| Error | `no-secret-outputs` |  | Output `password` receives"));
+    }
+}
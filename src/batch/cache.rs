@@ -0,0 +1,186 @@
+/// Content-hash cache of generated documentation
+///
+/// Each entry records the key a template was last documented with. The key hashes
+/// the template bytes together with the export options and the tool build, so a
+/// template is only regenerated when its source, the options or the tool change.
+/// The build is identified by the package version and the commit it was built from,
+/// since development builds all share the version `0.0.0-dev`.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::error::BicepError;
+
+/// The default location of the cache file, relative to the working directory
+pub const DEFAULT_CACHE_FILE: &str = ".bicep-docs-cache.json";
+
+/// The build of the tool, such as `0.0.0-dev+1a2b3c4d5e6f`
+const BUILD: &str = concat!(env!("CARGO_PKG_VERSION"), "+", env!("BICEP_DOCS_BUILD_ID"));
+
+/// Compute the cache key for a template
+///
+/// The hash is 64-bit FNV-1a, which is stable across platforms and Rust releases
+/// so a cache file stays valid between runs of the same tool build.
+///
+/// # Arguments
+///
+/// * `content` - The bytes of the template
+/// * `fingerprint` - A description of the export options
+///
+/// # Returns
+///
+/// The key as a hexadecimal string
+pub fn cache_key(content: &[u8], fingerprint: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    // Separate the parts with a zero byte so that moving bytes between them changes the key
+    for part in [
+        BUILD.as_bytes(),
+        &[0],
+        fingerprint.as_bytes(),
+        &[0],
+        content,
+    ] {
+        for byte in part {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+/// The keys templates were last documented with, by template path
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cache {
+    version: String,
+    entries: BTreeMap<String, String>,
+}
+
+impl Cache {
+    /// Load a cache file
+    ///
+    /// A missing file gives an empty cache. So does a file written by another build of
+    /// the tool, or one that cannot be read, since its keys would never match.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the cache file
+    ///
+    /// # Returns
+    ///
+    /// The cache
+    pub fn load(path: &Path) -> Self {
+        let Ok(content) = fs::read_to_string(path) else {
+            return Cache::default();
+        };
+        match serde_json::from_str::<Cache>(&content) {
+            Ok(cache) if cache.version == BUILD => cache,
+            Ok(_) => Cache::default(),
+            Err(e) => {
+                warn!("Ignoring unreadable cache {}: {}", path.display(), e);
+                Cache::default()
+            },
+        }
+    }
+
+    /// Write the cache file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the cache file
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure of the write
+    pub fn save(&self, path: &Path) -> Result<(), BicepError> {
+        let cache = Cache {
+            version: BUILD.to_string(),
+            entries: self.entries.clone(),
+        };
+        fs::write(path, serde_json::to_string_pretty(&cache)?)
+            .map_err(|e| BicepError::io(path, e))?;
+        Ok(())
+    }
+
+    /// Get the key a template was last documented with
+    pub fn get(&self, template: &Path) -> Option<&str> {
+        self.entries
+            .get(&template.to_string_lossy().into_owned())
+            .map(String::as_str)
+    }
+
+    /// Record the key a template was documented with
+    pub fn insert(&mut self, template: &Path, key: String) {
+        self.entries
+            .insert(template.to_string_lossy().into_owned(), key);
+    }
+
+    /// Forget a template, so that it is regenerated on the next run
+    pub fn remove(&mut self, template: &Path) {
+        self.entries
+            .remove(&template.to_string_lossy().into_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key() {
+        let key = cache_key(b"param a string\n", "md");
+        assert_eq!(key.len(), 16);
+        assert_eq!(key, cache_key(b"param a string\n", "md"));
+        assert_ne!(key, cache_key(b"param b string\n", "md"));
+        assert_ne!(key, cache_key(b"param a string\n", "md,json"));
+        // The boundary between the options and the content is part of the key
+        assert_ne!(cache_key(b"ab", "x"), cache_key(b"b", "xa"));
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("bicep-docs-cache-{}.json", std::process::id()));
+        let template = Path::new("infra/main.bicep");
+
+        let mut cache = Cache::load(&path);
+        assert_eq!(cache.get(template), None);
+        cache.insert(template, "0123456789abcdef".to_string());
+        cache.save(&path).unwrap();
+
+        let mut loaded = Cache::load(&path);
+        assert_eq!(loaded.get(template), Some("0123456789abcdef"));
+        loaded.remove(template);
+        assert_eq!(loaded.get(template), None);
+
+        // A cache from another version of the tool is discarded
+        fs::write(
+            &path,
+            r#"{"version":"0.0.0-other","entries":{"infra/main.bicep":"0123456789abcdef"}}"#,
+        )
+        .unwrap();
+        assert_eq!(Cache::load(&path).get(template), None);
+
+        // So is one from another build of the same version
+        assert!(BUILD.starts_with(concat!(env!("CARGO_PKG_VERSION"), "+")));
+        fs::write(
+            &path,
+            format!(
+                r#"{{"version":"{}","entries":{{"infra/main.bicep":"0123456789abcdef"}}}}"#,
+                env!("CARGO_PKG_VERSION")
+            ),
+        )
+        .unwrap();
+        assert_eq!(Cache::load(&path).get(template), None);
+
+        fs::write(&path, "not json").unwrap();
+        assert_eq!(Cache::load(&path).get(template), None);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,370 @@
+/// Parallel, cached documentation of many templates
+///
+/// The batch engine documents each template next to its source in every requested
+/// format. Templates are shared between worker threads, each of which reuses its
+/// own parser. A content-hash cache skips templates whose source, options and tool
+/// version are unchanged since the last run and whose outputs still exist.
+pub mod cache;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use tracing::{debug, debug_span};
+
+pub use cache::{cache_key, Cache, DEFAULT_CACHE_FILE};
+
+use crate::error::BicepError;
+use crate::exports::{find_exporter, ExportOptions, Exporter};
+use crate::preview::watched_files;
+
+/// Options for a batch run
+#[derive(Clone, Debug)]
+pub struct BatchOptions {
+    /// The names of the registered formats to generate next to each template
+    pub formats: Vec<String>,
+    /// The options to export each format with
+    pub export: ExportOptions,
+    /// The number of worker threads, or 0 for one per available CPU
+    pub jobs: usize,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        BatchOptions {
+            formats: vec!["markdown".to_string()],
+            export: ExportOptions::default(),
+            jobs: 0,
+        }
+    }
+}
+
+impl BatchOptions {
+    /// Look up the exporter for each format
+    fn exporters(&self) -> Result<Vec<Arc<dyn Exporter>>, BicepError> {
+        self.formats
+            .iter()
+            .map(|name| {
+                find_exporter(name)
+                    .ok_or_else(|| BicepError::export(name, "the format is not registered"))
+            })
+            .collect()
+    }
+
+    /// Describe the options that affect the generated files, for the cache key
+    fn fingerprint(&self) -> String {
+        format!("formats={};{:?}", self.formats.join(","), self.export)
+    }
+
+    /// The number of worker threads to use for a number of templates
+    fn worker_count(&self, templates: usize) -> usize {
+        let jobs = match self.jobs {
+            0 => thread::available_parallelism().map_or(1, |count| count.get()),
+            jobs => jobs,
+        };
+        jobs.min(templates).max(1)
+    }
+}
+
+/// What happened to a template in a batch run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FileStatus {
+    /// The documentation was generated
+    Generated,
+    /// The template and options were unchanged, so the documentation was kept
+    Unchanged,
+    /// The template could not be documented
+    Failed(String),
+}
+
+/// The outcome of a batch run for one template
+#[derive(Clone, Debug)]
+pub struct FileReport {
+    /// The template
+    pub template: PathBuf,
+    /// What happened to it
+    pub status: FileStatus,
+    /// The files generated for it, or that would have been
+    pub outputs: Vec<PathBuf>,
+}
+
+/// The outcome of a batch run, in the order the templates were given
+#[derive(Clone, Debug, Default)]
+pub struct BatchReport {
+    pub files: Vec<FileReport>,
+}
+
+impl BatchReport {
+    /// The number of templates whose documentation was generated
+    pub fn generated(&self) -> usize {
+        self.count(|status| *status == FileStatus::Generated)
+    }
+
+    /// The number of templates skipped because they were unchanged
+    pub fn unchanged(&self) -> usize {
+        self.count(|status| *status == FileStatus::Unchanged)
+    }
+
+    /// The number of templates that could not be documented
+    pub fn failed(&self) -> usize {
+        self.count(|status| matches!(status, FileStatus::Failed(_)))
+    }
+
+    fn count(&self, predicate: impl Fn(&FileStatus) -> bool) -> usize {
+        self.files
+            .iter()
+            .filter(|file| predicate(&file.status))
+            .count()
+    }
+}
+
+/// Find the templates to document under a set of paths
+///
+/// Files are used as given. Directories are searched recursively for `.bicep` files,
+/// skipping hidden directories.
+///
+/// # Arguments
+///
+/// * `paths` - Templates and directories
+///
+/// # Returns
+///
+/// The templates, without duplicates, in the order they were found
+pub fn find_templates(paths: &[PathBuf]) -> Result<Vec<PathBuf>, BicepError> {
+    let mut templates = Vec::new();
+    for path in paths {
+        let found = if path.is_dir() {
+            watched_files(path)?
+                .into_iter()
+                .filter(|file| {
+                    file.extension()
+                        .is_some_and(|extension| extension == "bicep")
+                })
+                .collect()
+        } else {
+            vec![path.clone()]
+        };
+        for template in found {
+            if !templates.contains(&template) {
+                templates.push(template);
+            }
+        }
+    }
+    Ok(templates)
+}
+
+/// Document a set of templates in parallel
+///
+/// # Arguments
+///
+/// * `templates` - The templates to document
+/// * `options` - The formats and export options
+/// * `cache` - The cache to skip unchanged templates with and update, if any
+///
+/// # Returns
+///
+/// A report of what happened to each template. Templates that fail are reported
+/// rather than stopping the run, so an error means a format is not registered.
+pub fn run(
+    templates: &[PathBuf],
+    options: &BatchOptions,
+    mut cache: Option<&mut Cache>,
+) -> Result<BatchReport, BicepError> {
+    let exporters = options.exporters()?;
+    let fingerprint = options.fingerprint();
+    let workers = options.worker_count(templates.len());
+    debug!(
+        "Documenting {} template(s) with {} worker(s)",
+        templates.len(),
+        workers
+    );
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(templates.len()));
+    let previous = cache.as_deref();
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(template) = templates.get(index) else {
+                    break;
+                };
+                let outcome = process(template, &exporters, options, &fingerprint, previous);
+                results
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push((index, outcome));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut report = BatchReport::default();
+    for (_, (file, key)) in results {
+        if let Some(cache) = cache.as_deref_mut() {
+            match key {
+                Some(key) => cache.insert(&file.template, key),
+                None => cache.remove(&file.template),
+            }
+        }
+        report.files.push(file);
+    }
+    Ok(report)
+}
+
+/// Document one template
+///
+/// # Returns
+///
+/// The report for the template, and the key to cache it under if it succeeded
+fn process(
+    template: &Path,
+    exporters: &[Arc<dyn Exporter>],
+    options: &BatchOptions,
+    fingerprint: &str,
+    cache: Option<&Cache>,
+) -> (FileReport, Option<String>) {
+    let _span = debug_span!("batch", template = %template.display()).entered();
+    let outputs = exporters
+        .iter()
+        .map(|exporter| template.with_extension(exporter.extension()))
+        .collect::<Vec<_>>();
+    let report = |status| FileReport {
+        template: template.to_path_buf(),
+        status,
+        outputs: outputs.clone(),
+    };
+
+    let content = match fs::read(template) {
+        Ok(content) => content,
+        Err(e) => return (report(FileStatus::Failed(e.to_string())), None),
+    };
+    let key = cache_key(&content, fingerprint);
+    let cached = cache.and_then(|cache| cache.get(template)) == Some(key.as_str());
+    if cached && outputs.iter().all(|output| output.is_file()) {
+        debug!("Skipping unchanged template");
+        return (report(FileStatus::Unchanged), Some(key));
+    }
+
+    match generate(template, &content, exporters, &options.export, &outputs) {
+        Ok(()) => (report(FileStatus::Generated), Some(key)),
+        Err(e) => (report(FileStatus::Failed(e.to_string())), None),
+    }
+}
+
+/// Parse a template and write each of its outputs
+fn generate(
+    template: &Path,
+    content: &[u8],
+    exporters: &[Arc<dyn Exporter>],
+    options: &ExportOptions,
+    outputs: &[PathBuf],
+) -> Result<(), BicepError> {
+    let source = std::str::from_utf8(content)?;
+    let document = crate::parse_template(source, template)?;
+
+    for (exporter, output) in exporters.iter().zip(outputs) {
+        if output == template {
+            return Err(BicepError::export(
+                exporter.name(),
+                format!("output path {} is the input template", output.display()),
+            ));
+        }
+        let generated = exporter.export_to_string(&document, options)?;
+        // Leave files that have not changed alone, so their modification times are kept
+        if fs::read_to_string(output).is_ok_and(|existing| existing == generated) {
+            continue;
+        }
+        fs::write(output, generated).map_err(|e| BicepError::io(output, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_templates() {
+        let root = std::env::temp_dir().join(format!("bicep-docs-batch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("modules")).unwrap();
+        fs::write(root.join("main.bicep"), "").unwrap();
+        fs::write(root.join("main.json"), "{}").unwrap();
+        fs::write(root.join("modules").join("storage.bicep"), "").unwrap();
+
+        let templates = find_templates(&[
+            root.join("modules").join("storage.bicep"),
+            root.clone(),
+            root.join("main.json"),
+        ])
+        .unwrap();
+        assert_eq!(
+            templates,
+            vec![
+                root.join("modules").join("storage.bicep"),
+                root.join("main.bicep"),
+                root.join("main.json"),
+            ]
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_run_reports_failures() {
+        let missing = std::env::temp_dir()
+            .join("bicep-docs-batch-missing")
+            .join("main.bicep");
+        let mut cache = Cache::default();
+        cache.insert(&missing, "0123456789abcdef".to_string());
+
+        let options = BatchOptions {
+            formats: vec!["markdown".to_string(), "json".to_string()],
+            jobs: 4,
+            ..Default::default()
+        };
+        let report = run(std::slice::from_ref(&missing), &options, Some(&mut cache)).unwrap();
+        assert_eq!(report.failed(), 1);
+        assert_eq!(
+            report.files[0].outputs,
+            vec![missing.with_extension("md"), missing.with_extension("json")]
+        );
+        // A failed template is regenerated on the next run
+        assert_eq!(cache.get(&missing), None);
+
+        let unknown = BatchOptions {
+            formats: vec!["html".to_string()],
+            ..Default::default()
+        };
+        assert!(run(std::slice::from_ref(&missing), &unknown, None).is_err());
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let options = BatchOptions::default();
+        let json = BatchOptions {
+            formats: vec!["json".to_string()],
+            ..Default::default()
+        };
+        let emoji = BatchOptions {
+            export: ExportOptions::builder().use_emoji(true).build(),
+            ..Default::default()
+        };
+        assert_ne!(options.fingerprint(), json.fingerprint());
+        assert_ne!(options.fingerprint(), emoji.fingerprint());
+        assert_eq!(options.worker_count(0), 1);
+        assert_eq!(
+            BatchOptions {
+                jobs: 8,
+                ..Default::default()
+            }
+            .worker_count(3),
+            3
+        );
+    }
+}
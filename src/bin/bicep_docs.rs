@@ -1,25 +1,45 @@
 use std::{
     error::Error,
+    ffi::OsString,
     fs::{self, File},
+    io::{self, BufWriter, Read, Write},
     path::{Path, PathBuf},
-    process,
+    process, thread,
 };
 
 use bicep_docs::{
-    export_bicep_document_to_asciidoc, export_bicep_document_to_asciidoc_string,
-    export_bicep_document_to_json, export_bicep_document_to_json_string,
-    export_bicep_document_to_markdown, export_bicep_document_to_yaml,
-    export_bicep_document_to_yaml_string,
-    localization::{detect_system_locale, init_localization, Language},
+    analysis::{
+        security, Diagnostic, LintConfig, Linter, RuleSet, SarifLog, SecurityReport, Severity,
+        RULE_SET_VERSIONS,
+    },
+    batch::{self, BatchOptions, BatchReport, Cache, FileStatus, DEFAULT_CACHE_FILE},
+    error::BicepError,
+    exports::{exporter_names, find_exporter, usage, ExportOptions},
+    localization::{
+        detect_system_locale, init_localization, load_locale_dir, try_translate, Catalog, Language,
+    },
+    preview::{PreviewServer, ServeOptions, Watcher, BICEP_CONFIG, POLL_INTERVAL},
+};
+use clap::{
+    self, Arg, ArgAction, Args, Command, CommandFactory, FromArgMatches, Parser, Subcommand,
+    ValueEnum,
 };
-use clap::{self, Args, Parser, Subcommand, ValueEnum};
-use tracing::{debug, debug_span, error, trace, Level};
+use indexmap::IndexSet;
+use rust_i18n::t;
+use tracing::{debug, debug_span, error, info, trace, warn, Level};
 use tracing_subscriber::{
     filter::EnvFilter,
     fmt::{self, format::FmtSpan},
     prelude::*,
 };
 
+// The CLI messages come from the same catalogs as the library's
+rust_i18n::i18n!(
+    "locales",
+    fallback = "en",
+    backend = bicep_docs::localization::RuntimeCatalogs
+);
+
 /// Bicep Documentation Generator
 ///
 /// Parse Azure Bicep files and export documentation in a range of formats
@@ -48,9 +68,14 @@ struct Cli {
     #[arg(long)]
     log_file: Option<PathBuf>,
 
-    /// Set the language for CLI messages and generated documentation (use locale codes like 'en', 'es', 'fr', 'de', 'ja', 'zh')
-    #[arg(long)]
-    language: Option<String>,
+    /// Set the language for CLI messages and generated documentation (use locale codes like 'en', 'es', 'fr', 'de', 'ja', 'zh'). Separate several with commas to write a file per language, e.g. 'en,fr,de'; CLI messages use the first.
+    #[arg(long, value_delimiter = ',')]
+    language: Vec<String>,
+
+    /// Directory of translation catalogs named after their locale, e.g. 'pt-BR.yml', to add languages or reword the built-in ones
+    #[arg(long, value_name = "DIR")]
+    #[arg(value_hint = clap::ValueHint::DirPath)]
+    locale_dir: Option<PathBuf>,
 
     #[command(subcommand)]
     command: Commands,
@@ -94,17 +119,256 @@ enum Commands {
         #[arg(short, long, default_value_t = false)]
         pretty: bool,
     },
+    /// Generate a JSON Schema for the parameters of a Bicep file
+    JsonSchema {
+        #[command(flatten)]
+        common: CommonExportOptions,
+
+        /// Format JSON with indentation for readability
+        #[arg(short, long, default_value_t = false)]
+        pretty: bool,
+    },
+    /// Generate an Azure portal createUiDefinition.json for a Bicep file
+    UiDefinition {
+        #[command(flatten)]
+        common: CommonExportOptions,
+
+        /// Format JSON with indentation for readability
+        #[arg(short, long, default_value_t = false)]
+        pretty: bool,
+    },
+    /// Write a Bicep file or ARM JSON template as canonically formatted Bicep
+    Bicep {
+        #[command(flatten)]
+        common: CommonExportOptions,
+    },
+    /// Document Bicep file in any registered format
+    Export {
+        #[command(flatten)]
+        common: CommonExportOptions,
+
+        /// Name of the format to generate
+        #[arg(short, long, value_parser = parse_format)]
+        format: String,
+
+        /// Format structured output such as JSON with indentation for readability
+        #[arg(short, long, default_value_t = false)]
+        pretty: bool,
+    },
+    /// List the resources and modules that would deploy for a set of parameter values
+    Expand {
+        /// Path to the Bicep file or ARM JSON template to parse, or - to read from stdin
+        #[arg(value_name = "BICEP FILE", required = true)]
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        input: PathBuf,
+
+        /// Parameters file (.bicepparam or parameters.json) to take parameter values from
+        #[arg(long, value_name = "PARAMS FILE")]
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        params: Option<PathBuf>,
+
+        /// Output file path. Defaults to printing to stdout.
+        #[arg(short, long)]
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        output: Option<PathBuf>,
+
+        /// Output the expansion as JSON
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+    /// Generate a parameters file skeleton for a Bicep file
+    Params {
+        /// Path to the Bicep file or ARM JSON template to parse, or - to read from stdin
+        #[arg(value_name = "BICEP FILE", required = true)]
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        input: PathBuf,
+
+        /// Output file path, or - for stdout. Defaults to input filename with a .bicepparam or .parameters.json extension, or stdout when reading from stdin.
+        #[arg(short, long)]
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        output: Option<PathBuf>,
+
+        /// Generate an ARM parameters.json file instead of a .bicepparam file
+        #[arg(long, default_value_t = false)]
+        json: bool,
+
+        /// Fill in sample values that satisfy each parameter's constraints
+        #[arg(long, default_value_t = false)]
+        sample_values: bool,
+    },
+    /// Document every Bicep file under a set of paths in parallel, skipping unchanged files
+    Batch {
+        /// Bicep files and directories of Bicep files to document
+        #[arg(value_name = "PATH", default_value = ".")]
+        #[arg(value_hint = clap::ValueHint::AnyPath)]
+        paths: Vec<PathBuf>,
+
+        /// Formats to generate next to each Bicep file (comma-separated)
+        #[arg(short, long, value_delimiter = ',', default_value = "markdown")]
+        #[arg(value_parser = parse_format)]
+        format: Vec<String>,
+
+        /// Number of files to process at once [default: number of CPUs]
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// File recording which templates are up to date
+        #[arg(long, value_name = "FILE", default_value = DEFAULT_CACHE_FILE)]
+        cache: PathBuf,
+
+        /// Regenerate every file without reading or writing the cache
+        #[arg(long, default_value_t = false)]
+        no_cache: bool,
+
+        /// Enable emoji usage in documentation output
+        #[arg(long, default_value_t = false)]
+        emoji: bool,
+
+        /// Skip exporting empty sections in the documentation
+        #[arg(long, default_value_t = false)]
+        exclude_empty: bool,
+
+        /// Show which symbols each declaration depends on and is used by
+        #[arg(long, default_value_t = false)]
+        show_references: bool,
+    },
+    /// Regenerate documentation whenever a Bicep file or bicepconfig.json changes
+    Watch {
+        /// Bicep file or directory of Bicep files to watch
+        #[arg(value_name = "PATH", required = true)]
+        #[arg(value_hint = clap::ValueHint::AnyPath)]
+        path: PathBuf,
+
+        /// Formats to generate next to each Bicep file (comma-separated)
+        #[arg(short, long, value_delimiter = ',', default_value = "markdown")]
+        #[arg(value_parser = parse_format)]
+        format: Vec<String>,
+
+        /// Enable emoji usage in documentation output
+        #[arg(long, default_value_t = false)]
+        emoji: bool,
+
+        /// Skip exporting empty sections in the documentation
+        #[arg(long, default_value_t = false)]
+        exclude_empty: bool,
+
+        /// Show which symbols each declaration depends on and is used by
+        #[arg(long, default_value_t = false)]
+        show_references: bool,
+    },
+    /// Serve rendered documentation on localhost, reloading pages when files change
+    Serve {
+        /// Bicep file or directory of Bicep files to serve
+        #[arg(value_name = "PATH", default_value = ".")]
+        #[arg(value_hint = clap::ValueHint::AnyPath)]
+        path: PathBuf,
+
+        /// Port to listen on
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+
+        /// Enable emoji usage in documentation output
+        #[arg(long, default_value_t = false)]
+        emoji: bool,
+
+        /// Skip exporting empty sections in the documentation
+        #[arg(long, default_value_t = false)]
+        exclude_empty: bool,
+
+        /// Show which symbols each declaration depends on and is used by
+        #[arg(long, default_value_t = false)]
+        show_references: bool,
+
+        /// Add a usage section with a module snippet and parameters file that reference the template from SOURCE: a local path, or a br: or ts: reference. {file}, {name} and {version} are replaced with the template's file name, file name without extension, and version metadata. Defaults to ./{file}
+        #[arg(long, value_name = "SOURCE", num_args = 0..=1, require_equals = true)]
+        #[arg(default_missing_value = "./{file}")]
+        usage: Option<String>,
+    },
+    /// Run a language server over stdio with hover documentation and an outline
+    Lsp {
+        /// Accepted for clients that pass --stdio; stdio is the only transport
+        #[arg(long = "stdio", hide = true)]
+        _stdio: bool,
+    },
+    /// Check Bicep files against built-in and configured house rules
+    Lint {
+        /// Bicep files and directories of Bicep files to check
+        #[arg(value_name = "PATH", default_value = ".")]
+        #[arg(value_hint = clap::ValueHint::AnyPath)]
+        paths: Vec<PathBuf>,
+
+        /// YAML or JSON file that sets rule levels and defines rules
+        #[arg(long, value_name = "FILE")]
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        config: Option<PathBuf>,
+
+        /// Also write the diagnostics as a SARIF 2.1.0 log to FILE, or - for stdout
+        #[arg(long, value_name = "FILE")]
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        sarif: Option<PathBuf>,
+    },
+    /// Check Bicep modules against the Azure Verified Modules interface rules
+    AvmCheck {
+        /// Bicep files and directories of Bicep files to check
+        #[arg(value_name = "PATH", default_value = ".")]
+        #[arg(value_hint = clap::ValueHint::AnyPath)]
+        paths: Vec<PathBuf>,
+
+        /// Version of the rule set to check against [default: the newest]
+        #[arg(long, value_name = "VERSION")]
+        #[arg(value_parser = parse_rule_set)]
+        rule_set: Option<RuleSet>,
+
+        /// Also write the violations as a SARIF 2.1.0 log to FILE, or - for stdout
+        #[arg(long, value_name = "FILE")]
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        sarif: Option<PathBuf>,
+    },
+    /// Review Bicep files for secure parameters, where their values go, and leaked or hard-coded secrets
+    SecurityReport {
+        /// Bicep files and directories of Bicep files to review
+        #[arg(value_name = "PATH", default_value = ".")]
+        #[arg(value_hint = clap::ValueHint::AnyPath)]
+        paths: Vec<PathBuf>,
+
+        /// Output file path for the Markdown report, or - for stdout
+        #[arg(short, long, value_name = "FILE", default_value = STDIO)]
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        output: PathBuf,
+
+        /// Also write the findings as a SARIF 2.1.0 log to FILE, or - for stdout
+        #[arg(long, value_name = "FILE")]
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        sarif: Option<PathBuf>,
+    },
+    /// Work with translation catalogs
+    Locales {
+        #[command(subcommand)]
+        command: LocalesCommand,
+    },
+}
+
+/// Commands for translation catalogs
+#[derive(Subcommand)]
+enum LocalesCommand {
+    /// Report keys that catalogs are missing, or have and English does not
+    Check {
+        /// Catalog files and directories of catalogs to check [default: the built-in catalogs and --locale-dir]
+        #[arg(value_name = "PATH")]
+        #[arg(value_hint = clap::ValueHint::AnyPath)]
+        paths: Vec<PathBuf>,
+    },
 }
 
 /// Common options shared between export formats
 #[derive(Args)]
 struct CommonExportOptions {
-    /// Path to the Bicep file to parse
+    /// Path to the Bicep file or ARM JSON template to parse, or - to read from stdin
     #[arg(value_name = "BICEP FILE", required = true)]
     #[arg(value_hint = clap::ValueHint::FilePath)]
     input: PathBuf,
 
-    /// Output file path. Defaults to input filename with appropriate extension.
+    /// Output file path, or - for stdout. Defaults to input filename with appropriate extension, or stdout when reading from stdin.
     #[arg(short, long)]
     #[arg(value_hint = clap::ValueHint::FilePath)]
     output: Option<PathBuf>,
@@ -120,10 +384,120 @@ struct CommonExportOptions {
     /// Check if generated documentation matches existing file and exit with appropriate code
     #[arg(long, default_value_t = false)]
     check: bool,
+
+    /// Show which symbols each declaration depends on and is used by, and warn about unused parameters and variables
+    #[arg(long, default_value_t = false)]
+    show_references: bool,
+
+    /// Add a usage section with a module snippet and parameters file that reference the template from SOURCE: a local path, or a br: or ts: reference. {file}, {name} and {version} are replaced with the template's file name, file name without extension, and version metadata. Defaults to ./{file}
+    #[arg(long, value_name = "SOURCE", num_args = 0..=1, require_equals = true)]
+    #[arg(default_missing_value = "./{file}")]
+    usage: Option<String>,
+
+    /// Write the default values of secure parameters instead of redacting them
+    #[arg(long, default_value_t = false)]
+    show_secure_defaults: bool,
+}
+
+/// The path that stands for stdin as an input and stdout as an output
+const STDIO: &str = "-";
+
+/// Whether a path stands for stdin or stdout
+fn is_stdio(path: &Path) -> bool {
+    path == Path::new(STDIO)
+}
+
+/// Read and parse a template from a file, or from stdin for `-`
+///
+/// Templates from stdin are read as ARM JSON when they start with an object, since
+/// Bicep source never does, and as Bicep otherwise.
+fn read_template(input: &Path) -> Result<bicep_docs::BicepDocument, BicepError> {
+    if !is_stdio(input) {
+        let source_code = fs::read_to_string(input).map_err(|e| BicepError::io(input, e))?;
+        debug!(
+            "Successfully read Bicep file: {} ({} bytes)",
+            input.display(),
+            source_code.len()
+        );
+        return bicep_docs::parse_template(&source_code, input);
+    }
+
+    let mut source_code = String::new();
+    io::stdin().read_to_string(&mut source_code)?;
+    debug!(
+        "Successfully read template from stdin ({} bytes)",
+        source_code.len()
+    );
+    if source_code.trim_start().starts_with('{') {
+        bicep_docs::parse_arm_document(&source_code)
+    } else {
+        bicep_docs::parse_bicep_document(&source_code)
+    }
+}
+
+/// Write generated content to a file, or to stdout for `-`
+fn write_output(output_path: &Path, content: &str) -> Result<(), Box<dyn Error>> {
+    if is_stdio(output_path) {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(content.as_bytes())?;
+        stdout.flush()?;
+    } else {
+        fs::write(output_path, content)?;
+    }
+    Ok(())
 }
 
-/// Compare generated content with existing file and exit with appropriate code
-fn check_file_diff(generated_content: &str, output_path: &Path) -> Result<(), Box<dyn Error>> {
+/// Whether a command writes its result to stdout, which then cannot carry logs
+fn writes_to_stdout(command: &Commands) -> bool {
+    let export_to_stdout = |common: &CommonExportOptions| match &common.output {
+        Some(output) => is_stdio(output),
+        None => is_stdio(&common.input),
+    };
+    match command {
+        Commands::Markdown { common }
+        | Commands::Asciidoc { common }
+        | Commands::Yaml { common }
+        | Commands::Json { common, .. }
+        | Commands::JsonSchema { common, .. }
+        | Commands::UiDefinition { common, .. }
+        | Commands::Bicep { common }
+        | Commands::Export { common, .. } => export_to_stdout(common),
+        Commands::Expand { output, .. } => output.as_deref().is_none_or(is_stdio),
+        Commands::Params { input, output, .. } => match output {
+            Some(output) => is_stdio(output),
+            None => is_stdio(input),
+        },
+        Commands::Lint { sarif, .. } | Commands::AvmCheck { sarif, .. } => {
+            sarif.as_deref().is_some_and(is_stdio)
+        },
+        Commands::SecurityReport { output, sarif, .. } => {
+            is_stdio(output) || sarif.as_deref().is_some_and(is_stdio)
+        },
+        Commands::Lsp { .. } => true,
+        Commands::Batch { .. }
+        | Commands::Watch { .. }
+        | Commands::Serve { .. }
+        | Commands::Locales { .. } => false,
+    }
+}
+
+/// Refuse to write output over the input template, e.g. when documenting `main.json` as JSON
+fn ensure_output_is_not_input(input: &Path, output_path: &Path) -> Result<(), Box<dyn Error>> {
+    if is_stdio(input) || is_stdio(output_path) {
+        return Ok(());
+    }
+    if std::path::absolute(input)? == std::path::absolute(output_path)? {
+        return Err(t!("cli.output_is_input", path = output_path.display()).into());
+    }
+    Ok(())
+}
+
+/// Compare generated content with an existing file, printing a diff if they differ
+///
+/// # Returns
+///
+/// Whether the file is up to date
+fn check_file_diff(generated_content: &str, output_path: &Path) -> bool {
     let existing_content = match fs::read_to_string(output_path) {
         Ok(content) => content,
         Err(_) => {
@@ -136,13 +510,13 @@ fn check_file_diff(generated_content: &str, output_path: &Path) -> Result<(), Bo
                 println!("+{line}");
             }
 
-            process::exit(1);
+            return false;
         },
     };
 
     if generated_content == existing_content {
         // Files match
-        process::exit(0);
+        true
     } else {
         // Files differ, output unified diff
         let generated_lines: Vec<&str> = generated_content.lines().collect();
@@ -204,7 +578,7 @@ fn check_file_diff(generated_content: &str, output_path: &Path) -> Result<(), Bo
             );
         }
 
-        process::exit(1);
+        false
     }
 }
 
@@ -235,180 +609,721 @@ fn print_diff_hunk(
     }
 }
 
-/// Generic export handler to reduce duplication
-fn handle_export<F, G>(
+/// Parse an AVM rule set version from the command line
+fn parse_rule_set(version: &str) -> Result<RuleSet, String> {
+    RuleSet::version(version).ok_or_else(|| {
+        t!(
+            "cli.unknown_rule_set",
+            version = version,
+            versions = RULE_SET_VERSIONS.join(", ")
+        )
+        .into()
+    })
+}
+
+/// Accept the name of a registered export format
+fn parse_format(name: &str) -> Result<String, String> {
+    let names = exporter_names();
+    if names.iter().any(|known| known == name) {
+        Ok(name.to_string())
+    } else {
+        Err(t!(
+            "cli.unknown_format",
+            format = name,
+            formats = names.join(", ")
+        )
+        .into())
+    }
+}
+
+/// Export a Bicep file with the registered exporter for a format
+///
+/// With several languages, a file is written for each, named like `main.fr.md`.
+fn handle_export(
     common: CommonExportOptions,
-    extension: &str,
-    export_fn: F,
-    export_to_string_fn: G,
-) -> Result<(), Box<dyn Error>>
-where
-    F: Fn(&bicep_docs::parsing::BicepDocument, &Path, bool, bool) -> Result<(), Box<dyn Error>>,
-    G: Fn(&bicep_docs::parsing::BicepDocument, bool, bool) -> Result<String, Box<dyn Error>>,
-{
+    format: &str,
+    pretty: bool,
+    languages: &[Language],
+) -> Result<(), Box<dyn Error>> {
+    let exporter = find_exporter(format).ok_or_else(|| {
+        t!(
+            "cli.unknown_format",
+            format = format,
+            formats = exporter_names().join(", ")
+        )
+        .to_string()
+    })?;
     debug!(
         "Beginning {} export for file: {}",
-        extension.to_uppercase(),
+        exporter.name(),
         common.input.display()
     );
 
-    // Read the Bicep file
-    let source_code = fs::read_to_string(&common.input)?;
-    debug!(
-        "Successfully read Bicep file: {} ({} bytes)",
-        common.input.display(),
-        source_code.len()
-    );
-
-    // Parse the Bicep file
-    let document = bicep_docs::parse_bicep_document(&source_code)?;
+    // Read and parse the Bicep file
+    let document = read_template(&common.input)?;
     debug!("Successfully parsed Bicep document");
 
+    if common.show_references {
+        warn_unused_symbols(&document);
+    }
+
     // Determine output path
-    let output_path = common
-        .output
-        .clone()
-        .unwrap_or_else(|| common.input.with_extension(extension));
+    let output_path = match &common.output {
+        Some(output) => output.clone(),
+        None if is_stdio(&common.input) => PathBuf::from(STDIO),
+        None => common.input.with_extension(exporter.extension()),
+    };
     debug!("Using output path: {}", output_path.display());
 
+    // One language is written to the output path, and several to a file each
+    let outputs = match languages {
+        [language] => vec![(*language, output_path)],
+        _ if is_stdio(&output_path) => {
+            return Err(t!("cli.languages_need_output").into());
+        },
+        _ => languages
+            .iter()
+            .map(|language| (*language, localized_path(&output_path, *language)))
+            .collect(),
+    };
+
+    let mut builder = ExportOptions::builder()
+        .use_emoji(common.emoji)
+        .exclude_empty(common.exclude_empty)
+        .show_references(common.show_references)
+        .show_secure_defaults(common.show_secure_defaults)
+        .pretty(pretty);
+    if let Some(template) = &common.usage {
+        builder = builder.module_source(usage::module_source(template, &common.input, &document)?);
+    }
+
+    // Check mode compares every output before exiting, so no stale file is missed
+    let mut up_to_date = true;
+    for (language, output_path) in outputs {
+        ensure_output_is_not_input(&common.input, &output_path)?;
+        let options = builder.clone().language(language).build();
+
+        if common.check {
+            if is_stdio(&output_path) {
+                return Err(t!("cli.check_needs_output").into());
+            }
+            // Check mode: compare generated content with existing file
+            let generated_content = exporter.export_to_string(&document, &options)?;
+            up_to_date &= check_file_diff(&generated_content, &output_path);
+        } else if is_stdio(&output_path) {
+            // Stream the document to stdout so it can be piped to another command
+            let mut writer = BufWriter::new(io::stdout().lock());
+            exporter.export_to_writer(&document, &mut writer, &options)?;
+            writer.flush()?;
+        } else {
+            // Normal mode: export the document
+            exporter.export_to_file(&document, &output_path, &options)?;
+            debug!("{} exported to: {}", exporter.name(), output_path.display());
+        }
+    }
+
     if common.check {
-        // Check mode: compare generated content with existing file
-        let generated_content = export_to_string_fn(&document, common.emoji, common.exclude_empty)?;
-        check_file_diff(&generated_content, &output_path)?;
+        process::exit(if up_to_date { 0 } else { 1 });
+    }
+    Ok(())
+}
+
+/// Insert a language code before the extension of an output path, e.g. `main.fr.md`
+fn localized_path(path: &Path, language: Language) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = match path.extension() {
+        Some(extension) => format!("{stem}.{language}.{}", extension.to_string_lossy()),
+        None => format!("{stem}.{language}"),
+    };
+    path.with_file_name(file_name)
+}
+
+/// Log a warning for each parameter and variable that is never referenced
+fn warn_unused_symbols(document: &bicep_docs::parsing::BicepDocument) {
+    let references = bicep_docs::analysis::analyze_references(document);
+
+    for name in references.unused_parameters() {
+        warn!("{}", t!("cli.unused_parameter", name = name));
+    }
+    for name in references.unused_variables() {
+        warn!("{}", t!("cli.unused_variable", name = name));
+    }
+}
+
+/// Handle the JSON export command
+fn handle_json_export(
+    mut common: CommonExportOptions,
+    pretty: bool,
+    languages: &[Language],
+) -> Result<(), Box<dyn Error>> {
+    // JSON output is written to the working directory unless a path is given
+    if common.output.is_none() && !is_stdio(&common.input) {
+        let file_stem = common
+            .input
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("output");
+        common.output = Some(Path::new(file_stem).with_extension("json"));
+    }
+    handle_export(common, "json", pretty, languages)
+}
+
+/// Handle the createUiDefinition.json export command
+fn handle_ui_definition_export(
+    mut common: CommonExportOptions,
+    pretty: bool,
+    languages: &[Language],
+) -> Result<(), Box<dyn Error>> {
+    // The portal expects this exact file name next to the template
+    if common.output.is_none() && !is_stdio(&common.input) {
+        common.output = Some(common.input.with_file_name("createUiDefinition.json"));
+    }
+    handle_export(common, "ui-definition", pretty, languages)
+}
+
+/// Handle the expand command
+fn handle_expand(
+    input: &Path,
+    params: Option<&Path>,
+    output: Option<&Path>,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    debug!("Beginning expansion for file: {}", input.display());
+
+    let document = read_template(input)?;
+    debug!("Successfully parsed Bicep document");
+
+    let parameters = match params {
+        Some(params) => {
+            let parameters = bicep_docs::analysis::read_parameters_file(params)?;
+            debug!(
+                "Read {} parameter values from: {}",
+                parameters.len(),
+                params.display()
+            );
+            parameters
+        },
+        None => Default::default(),
+    };
+
+    let expansion = bicep_docs::analysis::expand_document(&document, parameters);
+    for name in &expansion.missing_parameters {
+        warn!("{}", t!("cli.parameter_without_value", name = name));
+    }
+
+    let content = if json {
+        serde_json::to_string_pretty(&expansion)?
     } else {
-        // Normal mode: export the document
-        export_fn(&document, &output_path, common.emoji, common.exclude_empty)?;
-        debug!(
-            "{} exported to: {}",
-            extension.to_uppercase(),
-            output_path.display()
-        );
+        expansion.to_string()
+    };
+
+    let output = output.unwrap_or(Path::new(STDIO));
+    write_output(output, &content)?;
+    debug!("Expansion written to: {}", output.display());
+
+    Ok(())
+}
+
+/// Handle the params command
+fn handle_params(
+    input: &Path,
+    output: Option<&Path>,
+    json: bool,
+    sample_values: bool,
+) -> Result<(), Box<dyn Error>> {
+    debug!(
+        "Generating parameters file for: {} (sample values: {})",
+        input.display(),
+        sample_values
+    );
+
+    // A .bicepparam file names its template in a `using` statement
+    if is_stdio(input) && !json {
+        return Err(t!("cli.bicepparam_needs_json").into());
     }
 
+    let document = read_template(input)?;
+    debug!("Successfully parsed Bicep document");
+
+    let output_path = match output {
+        Some(output) => output.to_path_buf(),
+        None if is_stdio(input) => PathBuf::from(STDIO),
+        None if json => input.with_extension("parameters.json"),
+        None => input.with_extension("bicepparam"),
+    };
+
+    let content = if json {
+        bicep_docs::exports::parameters_json::export_to_string(&document, sample_values)?
+    } else {
+        let template_path = relative_template_path(input, &output_path);
+        bicep_docs::exports::bicepparam::export_to_string(&document, &template_path, sample_values)?
+    };
+    write_output(&output_path, &content)?;
+    debug!("Parameters file written to: {}", output_path.display());
+
     Ok(())
 }
 
-/// Handle the YAML export command
-fn handle_yaml_export(common: CommonExportOptions) -> Result<(), Box<dyn Error>> {
-    if common.check {
-        // YAML export doesn't use emoji parameter, so handle separately
-        debug!("Beginning YAML check for file: {}", common.input.display());
+/// Get the path of a template relative to the directory of a parameters file
+///
+/// Falls back to the template path as given when no relative path can be found.
+fn relative_template_path(template: &Path, parameters_file: &Path) -> String {
+    let absolute = |path: &Path| {
+        std::path::absolute(path)
+            .map(|path| {
+                path.components()
+                    .map(|part| part.as_os_str().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .ok()
+    };
+    let (Some(template_parts), Some(parameters_parts)) =
+        (absolute(template), absolute(parameters_file))
+    else {
+        return template.display().to_string().replace('\\', "/");
+    };
 
-        // Read the Bicep file
-        let source_code = fs::read_to_string(&common.input)?;
-        debug!(
-            "Successfully read Bicep file: {} ({} bytes)",
-            common.input.display(),
-            source_code.len()
-        );
+    let directory = &parameters_parts[..parameters_parts.len().saturating_sub(1)];
+    let common = template_parts
+        .iter()
+        .zip(directory)
+        .take_while(|(a, b)| a == b)
+        .count();
 
-        // Parse the Bicep file
-        let document = bicep_docs::parse_bicep_document(&source_code)?;
-        debug!("Successfully parsed Bicep document");
+    let mut parts = Vec::new();
+    if common == directory.len() {
+        parts.push(".".to_string());
+    }
+    parts.extend(std::iter::repeat_n(
+        "..".to_string(),
+        directory.len() - common,
+    ));
+    parts.extend(template_parts[common..].iter().cloned());
+    parts.join("/")
+}
 
-        // Determine output path
-        let output_path = common
-            .output
-            .clone()
-            .unwrap_or_else(|| common.input.with_extension("yaml"));
-        debug!("Using output path: {}", output_path.display());
+/// Handle the batch command
+fn handle_batch(
+    paths: &[PathBuf],
+    options: &BatchOptions,
+    cache_path: &Path,
+    no_cache: bool,
+) -> Result<(), Box<dyn Error>> {
+    let templates = batch::find_templates(paths)?;
+    let mut cache = (!no_cache).then(|| Cache::load(cache_path));
+
+    let report = batch::run(&templates, options, cache.as_mut())?;
+    log_batch_report(&report);
+    if let Some(cache) = cache {
+        cache.save(cache_path)?;
+    }
 
-        // Generate content and check diff
-        let generated_content =
-            export_bicep_document_to_yaml_string(&document, common.exclude_empty)?;
-        check_file_diff(&generated_content, &output_path)?;
+    if report.failed() > 0 {
+        return Err(t!("cli.batch_failed", count = report.failed()).into());
+    }
+    Ok(())
+}
 
-        Ok(())
-    } else {
-        handle_export(
-            common,
-            "yaml",
-            |doc, path, _emoji, exclude_empty| {
-                export_bicep_document_to_yaml(doc, path, exclude_empty)
+/// Log what happened to each template in a batch run
+fn log_batch_report(report: &BatchReport) {
+    for file in &report.files {
+        match &file.status {
+            FileStatus::Generated => {
+                for output in &file.outputs {
+                    info!("{}", t!("cli.generated_file", path = output.display()));
+                }
             },
-            |doc, _emoji, exclude_empty| export_bicep_document_to_yaml_string(doc, exclude_empty),
-        )
+            FileStatus::Unchanged => debug!("Unchanged {}", file.template.display()),
+            FileStatus::Failed(e) => {
+                error!(
+                    "{}",
+                    t!(
+                        "cli.document_failed",
+                        path = file.template.display(),
+                        error = e
+                    )
+                )
+            },
+        }
     }
+    info!(
+        "{}",
+        t!(
+            "cli.batch_summary",
+            total = report.files.len(),
+            generated = report.generated(),
+            unchanged = report.unchanged(),
+            failed = report.failed()
+        )
+    );
 }
 
-/// Handle the JSON export command
-fn handle_json_export(common: CommonExportOptions, pretty: bool) -> Result<(), Box<dyn Error>> {
-    debug!(
-        "Beginning JSON export for file: {} (pretty: {})",
-        common.input.display(),
-        pretty
+/// Handle the watch command
+///
+/// Documentation is generated for every Bicep file at start, then for each Bicep
+/// file that changes. A change to `bicepconfig.json` regenerates every file.
+fn handle_watch(path: &Path, options: &BatchOptions) -> Result<(), Box<dyn Error>> {
+    let mut watcher = Watcher::new(path)?;
+    info!(
+        "{}",
+        t!("cli.watching", path = path.display(), config = BICEP_CONFIG)
     );
 
-    // Read the Bicep file
-    let source_code = fs::read_to_string(&common.input)?;
-    debug!(
-        "Successfully read Bicep file: {} ({} bytes)",
-        common.input.display(),
-        source_code.len()
+    let mut templates = watcher.files();
+    loop {
+        templates.retain(|file| {
+            file.is_file()
+                && file
+                    .extension()
+                    .is_some_and(|extension| extension == "bicep")
+        });
+        log_batch_report(&batch::run(&templates, options, None)?);
+
+        let changed = loop {
+            thread::sleep(POLL_INTERVAL);
+            let changed = watcher.changes()?;
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+        templates = if changed
+            .iter()
+            .any(|file| file.file_name().is_some_and(|name| name == BICEP_CONFIG))
+        {
+            watcher.files()
+        } else {
+            changed
+        };
+    }
+}
+
+/// Handle the serve command
+fn handle_serve(path: &Path, port: u16, options: ServeOptions) -> Result<(), Box<dyn Error>> {
+    let server = PreviewServer::bind(path, port, options)?;
+    info!(
+        "{}",
+        t!(
+            "cli.serving",
+            path = path.display(),
+            address = server.local_addr()?
+        )
     );
+    Ok(server.run()?)
+}
 
-    // Parse the Bicep file
-    let document = bicep_docs::parse_bicep_document(&source_code)?;
-    debug!("Successfully parsed Bicep document");
+/// A template that an analysis ran over, and what it reported
+struct AnalyzedTemplate {
+    path: PathBuf,
+    /// The source of the template, or None if it could not be read
+    source: Option<String>,
+    diagnostics: Vec<Diagnostic>,
+}
 
-    // Determine output path
-    let output_path = common.output.clone().unwrap_or_else(|| {
-        let file_stem = common
-            .input
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("output");
-        Path::new(file_stem).with_extension("json")
-    });
+/// Run an analysis over every template under some paths
+///
+/// Templates that cannot be read or parsed are reported as an error diagnostic
+/// instead of stopping the run, so that one broken template does not hide the
+/// results of the others.
+///
+/// # Arguments
+///
+/// * `paths` - Templates and directories of templates
+/// * `analyze` - The analysis, given the path of a template, its document, and its
+///   source when it is Bicep
+fn analyze_templates(
+    paths: &[PathBuf],
+    mut analyze: impl FnMut(&Path, &bicep_docs::BicepDocument, Option<&str>) -> Vec<Diagnostic>,
+) -> Result<Vec<AnalyzedTemplate>, Box<dyn Error>> {
+    let mut analyzed = Vec::new();
+    for template in batch::find_templates(paths)? {
+        let (source, diagnostics) = match fs::read_to_string(&template) {
+            Ok(source_code) => {
+                let diagnostics = match bicep_docs::parse_template(&source_code, &template) {
+                    Ok(document) => {
+                        // Only Bicep source has the comments and declarations diagnostics are placed by
+                        let is_bicep = !template
+                            .extension()
+                            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+                        analyze(
+                            &template,
+                            &document,
+                            is_bicep.then_some(source_code.as_str()),
+                        )
+                    },
+                    Err(e) => vec![Diagnostic::from_error(&e)],
+                };
+                (Some(source_code), diagnostics)
+            },
+            Err(e) => (
+                None,
+                vec![Diagnostic::from_error(&BicepError::io(&template, e))],
+            ),
+        };
+        analyzed.push(AnalyzedTemplate {
+            path: template,
+            source,
+            diagnostics,
+        });
+    }
+    Ok(analyzed)
+}
 
-    if common.check {
-        // Check mode: compare generated content with existing file
-        let generated_content =
-            export_bicep_document_to_json_string(&document, pretty, common.exclude_empty)?;
-        check_file_diff(&generated_content, &output_path)?;
-    } else {
-        // Normal mode: export the document
-        export_bicep_document_to_json(&document, &output_path, pretty, common.exclude_empty)?;
-        debug!("JSON exported to: {}", output_path.display());
-        if pretty {
-            debug!("Output is formatted with indentation");
+/// Log the diagnostics of analysed templates, and write them to a SARIF log if asked
+///
+/// # Arguments
+///
+/// * `templates` - The analysed templates
+/// * `log` - A SARIF log with the rules of the analysis
+/// * `sarif` - Where to write the SARIF log, or - for stdout
+/// * `clean` - The message for a template without diagnostics
+///
+/// # Returns
+///
+/// The number of templates with an error diagnostic
+fn report_diagnostics(
+    templates: &[AnalyzedTemplate],
+    mut log: SarifLog,
+    sarif: Option<&Path>,
+    clean: impl Fn(&Path) -> String,
+) -> Result<usize, Box<dyn Error>> {
+    log.add_error_rules();
+    let mut failed = 0;
+    for template in templates {
+        for diagnostic in &template.diagnostics {
+            let location = match diagnostic.span {
+                Some(span) => format!(
+                    "{}:{}:{}",
+                    template.path.display(),
+                    span.start_line,
+                    span.start_column
+                ),
+                None => template.path.display().to_string(),
+            };
+            let message = t!(
+                "cli.diagnostic",
+                location = location,
+                rule = diagnostic.rule,
+                message = diagnostic.message
+            );
+            match diagnostic.severity {
+                Severity::Error => error!("{message}"),
+                Severity::Warning => warn!("{message}"),
+                Severity::Info => info!("{message}"),
+            }
+        }
+        if template.diagnostics.is_empty() {
+            info!("{}", clean(&template.path));
+        }
+        if template
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+        {
+            failed += 1;
         }
+        log.add_results(
+            &template.path,
+            template.source.as_deref(),
+            &template.diagnostics,
+        );
+    }
+
+    if let Some(sarif) = sarif {
+        write_output(sarif, &log.to_string_pretty())?;
+        info!(
+            "{}",
+            t!(
+                "cli.sarif_written",
+                count = log.len(),
+                path = sarif.display()
+            )
+        );
+    }
+    Ok(failed)
+}
+
+/// Handle the lint command
+///
+/// Diagnostics are logged at their severity, and the command fails if any template
+/// has an error or cannot be parsed.
+fn handle_lint(
+    paths: &[PathBuf],
+    config: Option<&Path>,
+    sarif: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let mut linter = match config {
+        Some(config) => Linter::with_config(LintConfig::from_file(config)?)?,
+        None => Linter::new(),
+    };
+    // SARIF logs also carry the documentation coverage gaps
+    if sarif.is_some() {
+        linter.enable_default_off_rules(Severity::Info);
+    }
+
+    let templates = analyze_templates(paths, |_, document, source| linter.lint(document, source))?;
+    let mut log = SarifLog::new();
+    log.add_linter_rules(&linter);
+    let failed = report_diagnostics(&templates, log, sarif, |path| {
+        t!("cli.lint_clean", path = path.display()).to_string()
+    })?;
+
+    if failed > 0 {
+        return Err(t!("cli.lint_failed", count = failed).into());
     }
+    Ok(())
+}
+
+/// Handle the avm-check command
+///
+/// Every template is checked, and the command fails if any breaks a rule or cannot
+/// be parsed.
+fn handle_avm_check(
+    paths: &[PathBuf],
+    rule_set: &RuleSet,
+    sarif: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let templates = analyze_templates(paths, |_, document, source| {
+        rule_set.diagnostics(document, source)
+    })?;
+    let mut log = SarifLog::new();
+    log.add_avm_rules(rule_set);
+    let failed = report_diagnostics(&templates, log, sarif, |path| {
+        t!(
+            "cli.avm_conformant",
+            path = path.display(),
+            version = rule_set.version
+        )
+        .to_string()
+    })?;
 
+    if failed > 0 {
+        return Err(t!("cli.avm_check_failed", count = failed).into());
+    }
     Ok(())
 }
 
-/// Handle the Markdown export command
-fn handle_markdown_export(common: CommonExportOptions) -> Result<(), Box<dyn Error>> {
-    handle_export(
-        common,
-        "md",
-        |doc, path, emoji, exclude_empty| {
-            export_bicep_document_to_markdown(doc, path, emoji, exclude_empty)
-        },
-        |doc, emoji, exclude_empty| {
-            // Use the localized version for string export
-            bicep_docs::exports::markdown::export_to_string(doc, emoji, exclude_empty)
-        },
-    )
+/// Handle the security-report command
+///
+/// The report is written as Markdown, findings are logged at their severity, and the
+/// command fails if any template has an error, such as a secret that an output exposes.
+fn handle_security_report(
+    paths: &[PathBuf],
+    output: &Path,
+    sarif: Option<&Path>,
+    language: Language,
+) -> Result<(), Box<dyn Error>> {
+    if is_stdio(output) && sarif.is_some_and(is_stdio) {
+        return Err(t!("cli.one_stdout_output").into());
+    }
+
+    let mut reviewed = Vec::new();
+    let templates = analyze_templates(paths, |template, document, source| {
+        let modules = security::load_local_modules(document, template);
+        let report = security::review(document, source, &modules, language);
+        let diagnostics = report.diagnostics.clone();
+        reviewed.push((template.to_path_buf(), report));
+        diagnostics
+    })?;
+
+    // Templates that could not be reviewed are listed with the error that stopped them
+    let reports: Vec<_> = templates
+        .iter()
+        .map(|template| {
+            let report = reviewed
+                .iter()
+                .find(|(path, _)| *path == template.path)
+                .map(|(_, report)| report.clone())
+                .unwrap_or_else(|| SecurityReport {
+                    diagnostics: template.diagnostics.clone(),
+                    ..Default::default()
+                });
+            (template.path.display().to_string(), report)
+        })
+        .collect();
+
+    let mut log = SarifLog::new();
+    log.add_security_rules();
+    let failed = report_diagnostics(&templates, log, sarif, |path| {
+        t!("cli.security_clean", path = path.display()).to_string()
+    })?;
+    write_output(output, &SecurityReport::to_markdown(&reports, language))?;
+
+    if failed > 0 {
+        return Err(t!("cli.security_report_failed", count = failed).into());
+    }
+    Ok(())
 }
 
-/// Handle the AsciiDoc export command
-fn handle_asciidoc_export(common: CommonExportOptions) -> Result<(), Box<dyn Error>> {
-    handle_export(
-        common,
-        "adoc",
-        |doc, path, emoji, exclude_empty| {
-            export_bicep_document_to_asciidoc(doc, path, emoji, exclude_empty)
-        },
-        |doc, emoji, exclude_empty| {
-            export_bicep_document_to_asciidoc_string(doc, emoji, exclude_empty)
-        },
-    )
+/// Handle the locales check command
+///
+/// Each catalog is compared with the built-in English catalog. Catalogs that reword
+/// only some keys are reported as missing the rest.
+fn handle_locales_check(
+    paths: &[PathBuf],
+    locale_dir: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let mut catalogs = Vec::new();
+    if paths.is_empty() {
+        catalogs.extend(Catalog::built_ins());
+        if let Some(locale_dir) = locale_dir {
+            catalogs.extend(bicep_docs::localization::catalog::read_catalogs(
+                locale_dir,
+            )?);
+        }
+    }
+    for path in paths {
+        if path.is_dir() {
+            catalogs.extend(bicep_docs::localization::catalog::read_catalogs(path)?);
+        } else {
+            catalogs.push(Catalog::from_file(path)?);
+        }
+    }
+
+    let english = Catalog::built_in(bicep_docs::localization::catalog::REFERENCE_LOCALE)
+        .ok_or("the built-in English catalog is missing")?;
+    let mut failed = 0;
+    for catalog in &catalogs {
+        let differences = catalog.compare(&english);
+        for key in &differences.missing {
+            warn!(
+                "{}",
+                t!(
+                    "cli.catalog_missing_key",
+                    catalog = catalog.locale,
+                    key = key
+                )
+            );
+        }
+        for key in &differences.extra {
+            warn!(
+                "{}",
+                t!("cli.catalog_extra_key", catalog = catalog.locale, key = key)
+            );
+        }
+        if differences.is_empty() {
+            info!("{}", t!("cli.catalog_complete", catalog = catalog.locale));
+        } else {
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        return Err(t!("cli.catalogs_differ", count = failed).into());
+    }
+    Ok(())
 }
 
 /// Configure the tracing subscriber based on command line options
-fn setup_tracing(verbose: u8, quiet: bool, log_format: LogFormat, log_file: Option<PathBuf>) {
+///
+/// When `stdout_reserved` is set, e.g. because stdout carries language server
+/// messages or a generated document, logs that would go to stdout go to stderr instead.
+fn setup_tracing(
+    verbose: u8,
+    quiet: bool,
+    log_format: LogFormat,
+    log_file: Option<PathBuf>,
+    stdout_reserved: bool,
+) {
     // Set default filter level based on verbosity
     let filter_level = match (verbose, quiet) {
         (_, true) => Level::ERROR, // When quiet is enabled, only show errors
@@ -459,7 +1374,13 @@ fn setup_tracing(verbose: u8, quiet: bool, log_format: LogFormat, log_file: Opti
                         .with(
                             fmt::Layer::default()
                                 .json()
-                                .with_writer(std::io::stdout)
+                                .with_writer(move || -> Box<dyn std::io::Write> {
+                                    if stdout_reserved {
+                                        Box::new(std::io::stderr())
+                                    } else {
+                                        Box::new(std::io::stdout())
+                                    }
+                                })
                                 .with_target(true)
                                 .with_span_events(FmtSpan::CLOSE),
                         )
@@ -482,65 +1403,216 @@ fn setup_tracing(verbose: u8, quiet: bool, log_format: LogFormat, log_file: Opti
 }
 
 pub fn main() -> Result<(), Box<dyn Error>> {
-    let cli = Cli::parse();
+    // Help and parse errors are shown before the options are parsed, so the
+    // catalogs and language they use are found by looking ahead
+    let args: Vec<OsString> = std::env::args_os().collect();
+    let catalogs = early_option(&args, "--locale-dir").map(|dir| load_locale_dir(Path::new(dir)));
+    let help_language = requested_language(&args);
+    init_localization(help_language);
+    let matches = localized_command(help_language).get_matches_from(args);
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     // Setup tracing with the appropriate verbosity and format
-    setup_tracing(cli.verbose, cli.quiet, cli.log_format, cli.log_file);
+    let stdout_reserved = writes_to_stdout(&cli.command);
+    setup_tracing(
+        cli.verbose,
+        cli.quiet,
+        cli.log_format,
+        cli.log_file,
+        stdout_reserved,
+    );
 
     trace!("Starting Bicep-Docs with verbosity level: {}", cli.verbose);
     debug!("Parsed command line arguments");
 
-    // Determine the language to use
-    let language = if let Some(lang_str) = cli.language {
-        match Language::from_code(&lang_str) {
-            Some(lang) => {
-                debug!("Successfully parsed language: {lang_str} -> {lang:?}");
-                lang
-            },
-            None => {
-                // Check if it's a valid locale format but not supported
-                if is_valid_locale_format(&lang_str) {
-                    error!("Locale '{lang_str}' is not yet supported. Supported locales: en, es, fr, de, ja, zh");
-                    process::exit(1);
-                } else {
-                    error!(
-                        "Invalid locale format '{lang_str}'. Expected format: 'en', 'es-es', 'fr-ca', etc."
-                    );
-                    process::exit(1);
-                }
-            },
-        }
-    } else {
+    match catalogs {
+        Some(Ok(locales)) => debug!("Loaded translation catalogs: {}", locales.join(", ")),
+        Some(Err(e)) => {
+            error!("{}", t!("cli.command_failed", error = e));
+            return Err(e.into());
+        },
+        None => {},
+    }
+
+    // Determine the languages to use, keeping the first occurrence of each
+    let mut languages: Vec<Language> = cli
+        .language
+        .iter()
+        .map(|code| parse_language(code))
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .collect();
+    if languages.is_empty() {
         let system_locale = detect_system_locale();
         debug!("Detected system locale: {:?}", system_locale);
-        system_locale
-    };
+        languages.push(system_locale);
+    }
+    let language = languages[0];
 
     debug!("Using language: {language}");
 
-    // Initialize localization
+    // Initialize localization for CLI messages
     init_localization(language);
 
     // Create a top-level span for the command execution
     let command_name = match &cli.command {
         Commands::Yaml { .. } => "yaml",
         Commands::Json { .. } => "json",
+        Commands::JsonSchema { .. } => "json-schema",
+        Commands::UiDefinition { .. } => "ui-definition",
+        Commands::Bicep { .. } => "bicep",
+        Commands::Export { .. } => "export",
         Commands::Markdown { .. } => "markdown",
         Commands::Asciidoc { .. } => "asciidoc",
+        Commands::Expand { .. } => "expand",
+        Commands::Params { .. } => "params",
+        Commands::Batch { .. } => "batch",
+        Commands::Watch { .. } => "watch",
+        Commands::Serve { .. } => "serve",
+        Commands::Lsp { .. } => "lsp",
+        Commands::Lint { .. } => "lint",
+        Commands::AvmCheck { .. } => "avm-check",
+        Commands::SecurityReport { .. } => "security-report",
+        Commands::Locales { .. } => "locales",
     };
 
+    // Commands other than the exports write a single language rather than a file each
+    let writes_one_language = matches!(
+        cli.command,
+        Commands::Batch { .. }
+            | Commands::Watch { .. }
+            | Commands::Serve { .. }
+            | Commands::Lsp { .. }
+            | Commands::SecurityReport { .. }
+    );
+    if writes_one_language && languages.len() > 1 {
+        let message = t!("cli.single_language", command = command_name);
+        error!("{message}");
+        return Err(message.into());
+    }
+
     let span = debug_span!("bicep_docs_command", command = command_name);
     let _guard = span.enter();
 
     let result = match cli.command {
-        Commands::Yaml { common } => handle_yaml_export(common),
-        Commands::Json { common, pretty } => handle_json_export(common, pretty),
-        Commands::Markdown { common } => handle_markdown_export(common),
-        Commands::Asciidoc { common } => handle_asciidoc_export(common),
+        Commands::Yaml { common } => handle_export(common, "yaml", false, &languages),
+        Commands::Json { common, pretty } => handle_json_export(common, pretty, &languages),
+        Commands::JsonSchema { common, pretty } => {
+            handle_export(common, "json-schema", pretty, &languages)
+        },
+        Commands::UiDefinition { common, pretty } => {
+            handle_ui_definition_export(common, pretty, &languages)
+        },
+        Commands::Bicep { common } => handle_export(common, "bicep", false, &languages),
+        Commands::Export {
+            common,
+            format,
+            pretty,
+        } => handle_export(common, &format, pretty, &languages),
+        Commands::Markdown { common } => handle_export(common, "markdown", false, &languages),
+        Commands::Asciidoc { common } => handle_export(common, "asciidoc", false, &languages),
+        Commands::Expand {
+            input,
+            params,
+            output,
+            json,
+        } => handle_expand(&input, params.as_deref(), output.as_deref(), json),
+        Commands::Params {
+            input,
+            output,
+            json,
+            sample_values,
+        } => handle_params(&input, output.as_deref(), json, sample_values),
+        Commands::Batch {
+            paths,
+            format,
+            jobs,
+            cache,
+            no_cache,
+            emoji,
+            exclude_empty,
+            show_references,
+        } => handle_batch(
+            &paths,
+            &BatchOptions {
+                formats: format,
+                export: ExportOptions::builder()
+                    .use_emoji(emoji)
+                    .exclude_empty(exclude_empty)
+                    .show_references(show_references)
+                    .pretty(true)
+                    .language(language)
+                    .build(),
+                jobs: jobs.unwrap_or(0),
+            },
+            &cache,
+            no_cache,
+        ),
+        Commands::Watch {
+            path,
+            format,
+            emoji,
+            exclude_empty,
+            show_references,
+        } => handle_watch(
+            &path,
+            &BatchOptions {
+                formats: format,
+                export: ExportOptions::builder()
+                    .use_emoji(emoji)
+                    .exclude_empty(exclude_empty)
+                    .show_references(show_references)
+                    .pretty(true)
+                    .language(language)
+                    .build(),
+                jobs: 0,
+            },
+        ),
+        Commands::Serve {
+            path,
+            port,
+            emoji,
+            exclude_empty,
+            show_references,
+            usage,
+        } => handle_serve(
+            &path,
+            port,
+            ServeOptions {
+                use_emoji: emoji,
+                exclude_empty,
+                show_references,
+                language,
+                usage,
+            },
+        ),
+        Commands::Lsp { .. } => bicep_docs::lsp::run_stdio(language).map_err(Into::into),
+        Commands::Lint {
+            paths,
+            config,
+            sarif,
+        } => handle_lint(&paths, config.as_deref(), sarif.as_deref()),
+        Commands::AvmCheck {
+            paths,
+            rule_set,
+            sarif,
+        } => handle_avm_check(
+            &paths,
+            &rule_set.unwrap_or_else(RuleSet::latest),
+            sarif.as_deref(),
+        ),
+        Commands::SecurityReport {
+            paths,
+            output,
+            sarif,
+        } => handle_security_report(&paths, &output, sarif.as_deref(), language),
+        Commands::Locales {
+            command: LocalesCommand::Check { paths },
+        } => handle_locales_check(&paths, cli.locale_dir.as_deref()),
     };
 
     if let Err(ref e) = result {
-        error!("Command failed: {}", e);
+        error!("{}", t!("cli.command_failed", error = e));
     } else {
         debug!("Command completed successfully");
     }
@@ -548,6 +1620,127 @@ pub fn main() -> Result<(), Box<dyn Error>> {
     result
 }
 
+/// Parse a language code from the command line, exiting if it is not supported
+fn parse_language(lang_str: &str) -> Language {
+    match Language::from_code(lang_str) {
+        Some(lang) => {
+            debug!("Successfully parsed language: {lang_str} -> {lang:?}");
+            lang
+        },
+        None => {
+            // Check if it's a valid locale format but not supported
+            if is_valid_locale_format(lang_str) {
+                error!(
+                    "{}",
+                    t!(
+                        "cli.locale_not_supported",
+                        code = lang_str,
+                        locales = Language::available_codes().join(", ")
+                    )
+                );
+            } else {
+                error!("{}", t!("cli.invalid_locale_format", code = lang_str));
+            }
+            process::exit(1);
+        },
+    }
+}
+
+/// Find the value of an option on the command line before it is parsed
+fn early_option<'a>(args: &'a [OsString], name: &str) -> Option<&'a str> {
+    let mut args = args.iter().filter_map(|arg| arg.to_str());
+    let mut value = None;
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        } else if arg == name {
+            value = args.next();
+        } else if let Some(option) = arg
+            .strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            value = Some(option);
+        }
+    }
+    value
+}
+
+/// Find the language asked for on the command line before it is parsed
+///
+/// Only the first of several languages is used, and a missing or unsupported
+/// language falls back to the system locale so that help can still be shown.
+fn requested_language(args: &[OsString]) -> Language {
+    early_option(args, "--language")
+        .and_then(|codes| codes.split(',').next())
+        .and_then(Language::from_code)
+        .unwrap_or_else(detect_system_locale)
+}
+
+/// Build the command line interface with its help in a language
+///
+/// The doc comments on [`Cli`] are the English help. Each command is described by
+/// the `cli.<command>_command_desc` key, and each argument by
+/// `cli.<command>_<argument>_help` or, when that is not defined, `cli.<argument>_help`.
+fn localized_command(language: Language) -> Command {
+    let locale = language.code();
+    localize_command(Cli::command(), language)
+        .about(t!("cli.app_description", locale = locale).to_string())
+        .subcommand_help_heading(t!("cli.formats_heading", locale = locale).to_string())
+        .disable_version_flag(true)
+        .arg(
+            Arg::new("version")
+                .short('V')
+                .long("version")
+                .action(ArgAction::Version)
+                .help(t!("cli.version_help", locale = locale).to_string())
+                .help_heading(t!("cli.options_heading", locale = locale).to_string()),
+        )
+}
+
+/// Localize the help of a command and its subcommands
+fn localize_command(mut command: Command, language: Language) -> Command {
+    let locale = language.code();
+    let text = |key: &str| try_translate(language, &format!("cli.{key}"));
+    let name = command.get_name().replace('-', "_");
+
+    if let Some(about) = text(&format!("{name}_command_desc")) {
+        command = command.about(about);
+    }
+
+    // Clap's own help flag and usage heading are replaced to carry translations
+    command
+        .mut_args(|arg| {
+            if arg.is_hide_set() {
+                return arg;
+            }
+            let id = arg.get_id().to_string();
+            let heading = if arg.is_positional() {
+                t!("cli.arguments_heading", locale = locale)
+            } else {
+                t!("cli.options_heading", locale = locale)
+            };
+            let arg = arg.help_heading(heading.to_string());
+            match text(&format!("{name}_{id}_help")).or_else(|| text(&format!("{id}_help"))) {
+                Some(help) => arg.help(help),
+                None => arg,
+            }
+        })
+        .disable_help_flag(true)
+        .arg(
+            Arg::new("help")
+                .short('h')
+                .long("help")
+                .action(ArgAction::Help)
+                .help(t!("cli.help_help", locale = locale).to_string())
+                .help_heading(t!("cli.options_heading", locale = locale).to_string()),
+        )
+        .help_template(format!(
+            "{{before-help}}{{about-with-newline}}\n{} {{usage}}\n\n{{all-args}}{{after-help}}",
+            t!("cli.usage_heading", locale = locale)
+        ))
+        .mut_subcommands(|subcommand| localize_command(subcommand, language))
+}
+
 /// Check if a string has a valid locale format
 /// Accepts formats like: en, en-US, fr-CA, etc.
 fn is_valid_locale_format(s: &str) -> bool {
@@ -629,4 +1822,156 @@ mod tests {
             panic!("Expected Markdown command");
         }
     }
+
+    #[test]
+    fn test_language_list_parsing() {
+        let args = vec![
+            "bicep-docs",
+            "--language",
+            "en,fr,de",
+            "markdown",
+            "main.bicep",
+        ];
+        let cli = Cli::parse_from(args);
+        assert_eq!(cli.language, vec!["en", "fr", "de"]);
+
+        assert_eq!(
+            localized_path(Path::new("docs/main.md"), Language::French),
+            PathBuf::from("docs/main.fr.md")
+        );
+        assert_eq!(
+            localized_path(Path::new("README"), Language::German),
+            PathBuf::from("README.de")
+        );
+    }
+
+    #[test]
+    fn test_requested_language() {
+        let args = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+
+        assert_eq!(
+            requested_language(&args(&["bicep-docs", "--language", "fr,de", "markdown"])),
+            Language::French
+        );
+        assert_eq!(
+            requested_language(&args(&["bicep-docs", "--language=ja", "markdown"])),
+            Language::Japanese
+        );
+        assert_eq!(
+            requested_language(&args(&["bicep-docs", "--language", "xx", "markdown"])),
+            detect_system_locale()
+        );
+    }
+
+    #[test]
+    fn test_localized_help() {
+        let command = localized_command(Language::French);
+        assert_eq!(
+            command.get_about().unwrap().to_string(),
+            t!("cli.app_description", locale = "fr")
+        );
+
+        let markdown = command.find_subcommand("markdown").unwrap();
+        assert_eq!(
+            markdown.get_about().unwrap().to_string(),
+            t!("cli.markdown_command_desc", locale = "fr")
+        );
+        let input = markdown
+            .get_arguments()
+            .find(|arg| arg.get_id() == "input")
+            .unwrap();
+        assert_eq!(
+            input.get_help().unwrap().to_string(),
+            t!("cli.input_help", locale = "fr")
+        );
+
+        // Per-command keys take precedence over the shared argument keys
+        let expand = command.find_subcommand("expand").unwrap();
+        let output = expand
+            .get_arguments()
+            .find(|arg| arg.get_id() == "output")
+            .unwrap();
+        assert_eq!(
+            output.get_help().unwrap().to_string(),
+            t!("cli.expand_output_help", locale = "fr")
+        );
+    }
+
+    #[test]
+    fn test_every_command_and_argument_has_help_text() {
+        fn assert_help_text(command: &Command) {
+            for subcommand in command.get_subcommands() {
+                let name = subcommand.get_name().replace('-', "_");
+                assert!(
+                    try_translate(Language::English, &format!("cli.{name}_command_desc")).is_some(),
+                    "no description for {name}"
+                );
+                for arg in subcommand.get_arguments().filter(|arg| !arg.is_hide_set()) {
+                    let id = arg.get_id();
+                    assert!(
+                        try_translate(Language::English, &format!("cli.{name}_{id}_help"))
+                            .or_else(|| try_translate(Language::English, &format!("cli.{id}_help")))
+                            .is_some(),
+                        "no help for {name} --{id}"
+                    );
+                }
+                assert_help_text(subcommand);
+            }
+        }
+
+        let command = Cli::command();
+        assert_help_text(&command);
+        for arg in command.get_arguments() {
+            let id = arg.get_id();
+            assert!(
+                try_translate(Language::English, &format!("cli.{id}_help")).is_some(),
+                "no help for --{id}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_command_parsing() {
+        let args = vec![
+            "bicep-docs",
+            "expand",
+            "main.bicep",
+            "--params",
+            "prod.bicepparam",
+        ];
+        let cli = Cli::parse_from(args);
+
+        if let Commands::Expand {
+            input,
+            params,
+            output,
+            json,
+        } = cli.command
+        {
+            assert_eq!(input, PathBuf::from("main.bicep"));
+            assert_eq!(params, Some(PathBuf::from("prod.bicepparam")));
+            assert_eq!(output, None);
+            assert!(!json);
+        } else {
+            panic!("Expected Expand command");
+        }
+    }
+
+    #[test]
+    fn test_relative_template_path() {
+        assert_eq!(
+            relative_template_path(
+                Path::new("infra/main.bicep"),
+                Path::new("infra/main.bicepparam")
+            ),
+            "./main.bicep"
+        );
+        assert_eq!(
+            relative_template_path(
+                Path::new("infra/main.bicep"),
+                Path::new("params/prod.bicepparam")
+            ),
+            "../infra/main.bicep"
+        );
+    }
 }
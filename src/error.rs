@@ -0,0 +1,356 @@
+/// Errors returned by the library
+///
+/// Every fallible function of the library returns a [`BicepError`], so callers
+/// can tell a missing file from a syntax error or an unsupported construct by
+/// matching on the variant. Each error has a stable
+/// [code](BicepError::code) for tooling to key on, and errors that come from a
+/// template carry the [`Span`] of the source they refer to.
+use std::{
+    error::Error,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use tree_sitter::Node;
+
+/// A Result whose error is a [`BicepError`]
+pub type Result<T, E = BicepError> = std::result::Result<T, E>;
+
+/// A range of source text
+///
+/// Lines and columns are one-based, as editors and compilers show them. Columns
+/// count bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Byte offset of the start of the range
+    pub start_byte: usize,
+    /// Byte offset of the end of the range, exclusive
+    pub end_byte: usize,
+    /// Line of the start of the range
+    pub start_line: usize,
+    /// Column of the start of the range
+    pub start_column: usize,
+    /// Line of the end of the range
+    pub end_line: usize,
+    /// Column of the end of the range, exclusive
+    pub end_column: usize,
+}
+
+impl Span {
+    /// The span covered by a tree-sitter node
+    pub fn from_node(node: &Node) -> Self {
+        let start = node.start_position();
+        let end = node.end_position();
+        Span {
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+            start_line: start.row + 1,
+            start_column: start.column + 1,
+            end_line: end.row + 1,
+            end_column: end.column + 1,
+        }
+    }
+
+    /// A span covering a single position
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The one-based line of the position
+    /// * `column` - The one-based column of the position
+    pub fn at(line: usize, column: usize) -> Self {
+        Span {
+            start_byte: 0,
+            end_byte: 0,
+            start_line: line,
+            start_column: column,
+            end_line: line,
+            end_column: column,
+        }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.start_line, self.start_column)
+    }
+}
+
+/// An error from parsing, exporting or localizing a template
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BicepError {
+    /// A file could not be read or written
+    Io {
+        /// The file involved, when known
+        path: Option<PathBuf>,
+        /// The underlying I/O error
+        source: io::Error,
+    },
+    /// The template is not valid Bicep or ARM JSON, or another input such as a
+    /// parameters file or language server message is malformed
+    Syntax {
+        /// What is wrong with the source
+        message: String,
+        /// Where in the source the problem is
+        span: Option<Span>,
+    },
+    /// The template uses a construct this tool does not understand
+    Unsupported {
+        /// The construct, such as a node kind or module source
+        construct: String,
+        /// Where in the source the construct is
+        span: Option<Span>,
+    },
+    /// A value in the template or a lint configuration is not valid for its kind
+    InvalidValue {
+        /// The kind of value, such as `number` or `module source`
+        kind: String,
+        /// Why the value is invalid
+        reason: String,
+        /// Where in the source the value is
+        span: Option<Span>,
+    },
+    /// A document could not be exported
+    Export {
+        /// The name of the format being exported
+        format: String,
+        /// What went wrong
+        message: String,
+    },
+    /// A translation catalog could not be loaded or used
+    Localization {
+        /// The locale involved
+        locale: String,
+        /// What went wrong
+        message: String,
+    },
+}
+
+impl BicepError {
+    /// Create a syntax error for a node
+    pub fn syntax(message: impl Into<String>, node: &Node) -> Self {
+        BicepError::Syntax {
+            message: message.into(),
+            span: Some(Span::from_node(node)),
+        }
+    }
+
+    /// Create an unsupported construct error for a node
+    pub fn unsupported(construct: impl Into<String>, node: &Node) -> Self {
+        BicepError::Unsupported {
+            construct: construct.into(),
+            span: Some(Span::from_node(node)),
+        }
+    }
+
+    /// Create an invalid value error for a node
+    pub fn invalid_value(kind: impl Into<String>, reason: impl Into<String>, node: &Node) -> Self {
+        BicepError::InvalidValue {
+            kind: kind.into(),
+            reason: reason.into(),
+            span: Some(Span::from_node(node)),
+        }
+    }
+
+    /// Create an export error
+    pub fn export(format: impl Into<String>, message: impl fmt::Display) -> Self {
+        BicepError::Export {
+            format: format.into(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Create an I/O error for a file
+    pub fn io(path: impl AsRef<Path>, source: io::Error) -> Self {
+        BicepError::Io {
+            path: Some(path.as_ref().to_path_buf()),
+            source,
+        }
+    }
+
+    /// Attach a source span to an error that refers to a template
+    ///
+    /// Errors that are not about template source are returned unchanged.
+    pub fn with_span(mut self, new_span: Span) -> Self {
+        if let BicepError::Syntax { span, .. }
+        | BicepError::Unsupported { span, .. }
+        | BicepError::InvalidValue { span, .. } = &mut self
+        {
+            *span = Some(new_span);
+        }
+        self
+    }
+
+    /// The stable code of the error, such as `BD1001`
+    ///
+    /// Codes never change meaning between releases, so they are safe to match on
+    /// in scripts and to look up in documentation.
+    pub fn code(&self) -> &'static str {
+        match self {
+            BicepError::Io { .. } => "BD0001",
+            BicepError::Syntax { .. } => "BD1001",
+            BicepError::Unsupported { .. } => "BD1002",
+            BicepError::InvalidValue { .. } => "BD1003",
+            BicepError::Export { .. } => "BD2001",
+            BicepError::Localization { .. } => "BD3001",
+        }
+    }
+
+    /// The source span the error refers to, if it comes from a template
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            BicepError::Syntax { span, .. }
+            | BicepError::Unsupported { span, .. }
+            | BicepError::InvalidValue { span, .. } => *span,
+            _ => None,
+        }
+    }
+
+    /// Whether the error is a file that does not exist
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, BicepError::Io { source, .. } if source.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+impl fmt::Display for BicepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] ", self.code())?;
+        match self {
+            BicepError::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "{}: {source}", path.display())?,
+            BicepError::Io { path: None, source } => write!(f, "{source}")?,
+            BicepError::Syntax { message, .. } => write!(f, "Syntax error: {message}")?,
+            BicepError::Unsupported { construct, .. } => {
+                write!(f, "Unsupported construct: {construct}")?
+            },
+            BicepError::InvalidValue { kind, reason, .. } => {
+                write!(f, "Invalid {kind} value: {reason}")?
+            },
+            BicepError::Export { format, message } => {
+                write!(f, "Could not export {format}: {message}")?
+            },
+            BicepError::Localization { locale, message } => {
+                write!(f, "Localization error for '{locale}': {message}")?
+            },
+        }
+        if let Some(span) = self.span() {
+            write!(f, " at {span}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for BicepError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BicepError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BicepError {
+    fn from(source: io::Error) -> Self {
+        BicepError::Io { path: None, source }
+    }
+}
+
+impl From<fmt::Error> for BicepError {
+    fn from(_: fmt::Error) -> Self {
+        BicepError::Io {
+            path: None,
+            source: io::Error::other("formatter error"),
+        }
+    }
+}
+
+impl From<std::str::Utf8Error> for BicepError {
+    fn from(error: std::str::Utf8Error) -> Self {
+        BicepError::Syntax {
+            message: format!("Source is not valid UTF-8: {error}"),
+            span: None,
+        }
+    }
+}
+
+impl From<std::string::FromUtf8Error> for BicepError {
+    fn from(error: std::string::FromUtf8Error) -> Self {
+        BicepError::Export {
+            format: "text".to_string(),
+            message: format!("Output is not valid UTF-8: {error}"),
+        }
+    }
+}
+
+impl From<std::num::ParseIntError> for BicepError {
+    fn from(error: std::num::ParseIntError) -> Self {
+        BicepError::InvalidValue {
+            kind: "number".to_string(),
+            reason: error.to_string(),
+            span: None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for BicepError {
+    fn from(error: serde_json::Error) -> Self {
+        match error.classify() {
+            serde_json::error::Category::Io => BicepError::Io {
+                path: None,
+                source: error.into(),
+            },
+            serde_json::error::Category::Syntax | serde_json::error::Category::Eof => {
+                BicepError::Syntax {
+                    span: Some(Span::at(error.line(), error.column())),
+                    message: error.to_string(),
+                }
+            },
+            serde_json::error::Category::Data => BicepError::export("json", error),
+        }
+    }
+}
+
+impl From<serde_yaml::Error> for BicepError {
+    fn from(error: serde_yaml::Error) -> Self {
+        BicepError::export("yaml", error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_and_display() {
+        let error = BicepError::InvalidValue {
+            kind: "number".to_string(),
+            reason: "Could not parse 'x' as integer".to_string(),
+            span: Some(Span::at(3, 7)),
+        };
+        assert_eq!(error.code(), "BD1003");
+        assert_eq!(error.span(), Some(Span::at(3, 7)));
+        assert_eq!(
+            error.to_string(),
+            "[BD1003] Invalid number value: Could not parse 'x' as integer at 3:7"
+        );
+
+        let error = BicepError::io(
+            "missing.bicep",
+            io::Error::new(io::ErrorKind::NotFound, "not found"),
+        );
+        assert!(error.is_not_found());
+        assert_eq!(error.span(), None);
+        assert_eq!(error.to_string(), "[BD0001] missing.bicep: not found");
+    }
+
+    #[test]
+    fn test_json_syntax_error_has_span() {
+        let error: BicepError = serde_json::from_str::<serde_json::Value>("{\n  \"a\": }")
+            .unwrap_err()
+            .into();
+        assert_eq!(error.code(), "BD1001");
+        assert_eq!(error.span().map(|span| span.start_line), Some(2));
+    }
+}
@@ -2,15 +2,35 @@
 ///
 /// This module provides functions to export parsed Bicep documents
 /// to AsciiDoc format with structured documentation layout.
-use std::error::Error as StdError;
-use std::{fs, path::Path};
+use crate::error::BicepError;
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use indexmap::IndexMap;
 
 use crate::{
+    analysis::{analyze_references, Evaluator, ReferenceIndex, SymbolKind},
     exports::utils::{
-        common::{format_yes_no, generate_metadata_display_asciidoc},
+        common::{format_yes_no, generate_metadata_display_asciidoc, localized_description},
+        conventions::{
+            example_source, is_hidden, parameter_sections, without_conventions, DocConventions,
+        },
         formatting::{escape_asciidoc, format_bicep_array_as_list},
     },
-    parsing::{BicepDocument, BicepFunctionArgument, BicepImport, BicepType},
+    exports::{
+        usage::{write_module_snippet, write_parameters_snippet},
+        ExportOptions, Exporter,
+    },
+    localization::Language,
+    parsing::{
+        BicepDocument, BicepFunctionArgument, BicepImport, BicepParameter, BicepType, BicepValue,
+        ModuleSource,
+    },
+    t,
 };
 
 /// Export a Bicep document to an AsciiDoc file
@@ -19,7 +39,7 @@ use crate::{
 ///
 /// * `document` - The BicepDocument to export
 /// * `file_path` - Path where the AsciiDoc file should be written
-/// * `use_emoji` - Whether to use emoji symbols (✅/❌) for Yes/No values
+/// * `options` - How to write the document, such as its language and layout
 ///
 /// # Returns
 ///
@@ -31,11 +51,12 @@ use crate::{
 pub fn export_to_file<P: AsRef<Path>>(
     document: &BicepDocument,
     file_path: P,
-    use_emoji: bool,
-    exclude_empty: bool,
-) -> Result<(), Box<dyn StdError>> {
-    let asciidoc_content = export_to_string(document, use_emoji, exclude_empty)?;
-    fs::write(file_path, asciidoc_content)?;
+    options: &ExportOptions,
+) -> Result<(), BicepError> {
+    let mut writer =
+        BufWriter::new(File::create(&file_path).map_err(|e| BicepError::io(&file_path, e))?);
+    export_to_writer(document, &mut writer, options)?;
+    writer.flush()?;
     Ok(())
 }
 
@@ -44,8 +65,7 @@ pub fn export_to_file<P: AsRef<Path>>(
 /// # Arguments
 ///
 /// * `document` - The BicepDocument to export
-/// * `use_emoji` - Whether to use emoji symbols (✅/❌) for Yes/No values
-/// * `exclude_empty` - Whether to exclude empty sections from the output
+/// * `options` - How to write the document, such as its language and layout
 ///
 /// # Returns
 ///
@@ -56,51 +76,110 @@ pub fn export_to_file<P: AsRef<Path>>(
 /// Returns an error if serialization fails
 pub fn export_to_string(
     document: &BicepDocument,
-    use_emoji: bool,
-    exclude_empty: bool,
-) -> Result<String, Box<dyn StdError>> {
-    let mut asciidoc = String::new();
+    options: &ExportOptions,
+) -> Result<String, BicepError> {
+    let mut buffer = Vec::new();
+    export_to_writer(document, &mut buffer, options)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// Write a Bicep document as AsciiDoc
+///
+/// The document is written section by section as it is generated, so the writer
+/// should be buffered when it is a file or socket.
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to export
+/// * `writer` - Where to write the AsciiDoc
+/// * `options` - How to write the document, such as its language and layout
+///
+/// # Returns
+///
+/// Result indicating success or failure of the export operation
+///
+/// # Errors
+///
+/// Returns an error if writing fails
+pub fn export_to_writer(
+    document: &BicepDocument,
+    writer: &mut dyn Write,
+    options: &ExportOptions,
+) -> Result<(), BicepError> {
+    let document = &*options.redact(document);
+    let use_emoji = options.use_emoji;
+    let exclude_empty = options.exclude_empty;
+    let language = options.language;
+    let references = options
+        .show_references
+        .then(|| analyze_references(document));
+    let references = references.as_ref();
+    let evaluator = Evaluator::new(document);
 
     // Title and document attributes
     if let Some(name) = &document.name {
-        asciidoc.push_str(&format!("= {name}\n"));
+        writeln!(writer, "= {name}")?;
     } else {
-        asciidoc.push_str("= Bicep Template\n");
+        writeln!(
+            writer,
+            "= {}",
+            t!("export.bicep_template", locale = language.code())
+        )?;
     }
 
     // Document attributes
-    asciidoc.push_str(":noheader:\n");
-    asciidoc.push_str(":source-language: bicep\n");
-    asciidoc.push_str(":table-caption!:\n");
-    asciidoc.push_str(":toc: preamble\n");
-    asciidoc.push_str(":toclevels: 2\n\n");
+    writer.write_all(b":noheader:\n")?;
+    writer.write_all(b":source-language: bicep\n")?;
+    writer.write_all(b":table-caption!:\n")?;
+    writer.write_all(b":toc: preamble\n")?;
+    writer.write_all(b":toclevels: 2\n\n")?;
 
     // Description
-    if let Some(description) = &document.description {
-        asciidoc.push_str(&format!("{}\n\n", escape_asciidoc(description)));
+    if let Some(description) = localized_description(
+        document.description.as_deref(),
+        &document.descriptions,
+        language,
+    ) {
+        write!(writer, "{}\n\n", escape_asciidoc(description))?;
     }
 
     // Target scope in table format
     if let Some(target_scope) = &document.target_scope {
-        asciidoc.push_str("[%autowidth,cols=\"h,1\",frame=none]\n");
-        asciidoc.push_str("|===\n");
-        asciidoc.push_str("| Target Scope\n");
-        asciidoc.push_str(&format!("| {}\n", escape_asciidoc(target_scope)));
-        asciidoc.push_str("|===\n\n");
+        writer.write_all(b"[%autowidth,cols=\"h,1\",frame=none]\n")?;
+        writer.write_all(b"|===\n")?;
+        writeln!(
+            writer,
+            "| {}",
+            t!("export.target_scope", locale = language.code())
+        )?;
+        writeln!(writer, "| {}", escape_asciidoc(target_scope))?;
+        writer.write_all(b"|===\n\n")?;
     }
 
     // Additional metadata
     if !document.metadata.is_empty() {
-        asciidoc.push_str(".Additional Metadata\n");
-        asciidoc.push_str("[%autowidth,cols=\"h,1\",frame=none]\n");
-        generate_metadata_display_asciidoc(&mut asciidoc, &document.metadata);
+        writeln!(
+            writer,
+            ".{}",
+            t!("export.additional_metadata", locale = language.code())
+        )?;
+        writer.write_all(b"[%autowidth,cols=\"h,1\",frame=none]\n")?;
+        generate_metadata_display_asciidoc(writer, &document.metadata)?;
     }
 
-    asciidoc.push('\n');
+    writer.write_all(b"\n")?;
+
+    if let Some(source) = &options.module_source {
+        generate_usage_section(writer, document, source, language)?;
+    }
 
     // Imports section
     if !document.imports.is_empty() || !exclude_empty {
-        asciidoc.push_str("== Imports\n\n");
+        write!(
+            writer,
+            "== {}\n\n",
+            t!("export.imports", locale = language.code())
+        )?;
         if !document.imports.is_empty() {
             // Separate namespace and module imports
             let namespace_imports: Vec<_> = document
@@ -115,27 +194,43 @@ pub fn export_to_string(
                 .collect();
 
             if !namespace_imports.is_empty() {
-                asciidoc.push_str("=== Namespace Imports\n\n");
-                asciidoc.push_str("|===\n");
-                asciidoc.push_str("| Namespace | Version\n\n");
+                write!(
+                    writer,
+                    "=== {}\n\n",
+                    t!("export.namespace_imports", locale = language.code())
+                )?;
+                writer.write_all(b"|===\n")?;
+                write!(
+                    writer,
+                    "| {} | {}\n\n",
+                    t!("export.namespace_header", locale = language.code()),
+                    t!("export.version_header", locale = language.code())
+                )?;
 
                 for import in namespace_imports {
                     if let BicepImport::Namespace { namespace, version } = import {
-                        let version_str = version.as_deref().unwrap_or("N/A");
-                        asciidoc.push_str(&format!(
-                            "| {} | {}\n",
+                        let version_str = version.as_deref().map(Cow::from).unwrap_or_else(|| {
+                            t!("export.not_applicable", locale = language.code())
+                        });
+                        writeln!(
+                            writer,
+                            "| {} | {}",
                             escape_asciidoc(namespace),
-                            escape_asciidoc(version_str)
-                        ));
+                            escape_asciidoc(&version_str)
+                        )?;
                     }
                 }
-                asciidoc.push_str("|===\n\n");
+                writer.write_all(b"|===\n\n")?;
             }
 
             if !module_imports.is_empty() {
-                asciidoc.push_str("=== Module Imports\n\n");
-                asciidoc.push_str("[%autowidth,cols=\"m,1\",frame=none]\n");
-                asciidoc.push_str("|===\n");
+                write!(
+                    writer,
+                    "=== {}\n\n",
+                    t!("export.module_imports", locale = language.code())
+                )?;
+                writer.write_all(b"[%autowidth,cols=\"m,1\",frame=none]\n")?;
+                writer.write_all(b"|===\n")?;
 
                 for import in module_imports {
                     if let BicepImport::Module {
@@ -164,58 +259,137 @@ pub fn export_to_string(
                         } else {
                             String::new()
                         };
-                        asciidoc.push_str(&format!(
-                            "| {} \n| {}{}\n",
+                        writeln!(
+                            writer,
+                            "| {} \n| {}{}",
                             escape_asciidoc(&source.to_string()),
                             escape_asciidoc(&symbols_str),
                             escape_asciidoc(&wildcard_str)
-                        ));
+                        )?;
                     }
                 }
-                asciidoc.push_str("|===\n\n");
-                asciidoc.push('\n');
+                writer.write_all(b"|===\n\n")?;
+                writer.write_all(b"\n")?;
             }
         } else if !exclude_empty {
-            asciidoc.push_str("_No imports defined_\n\n");
+            write!(
+                writer,
+                "_{}_\n\n",
+                t!("export.no_imports_defined", locale = language.code())
+            )?;
         }
     }
 
     // Types section
     if !document.types.is_empty() || !exclude_empty {
-        generate_types_section(&mut asciidoc, document, use_emoji, exclude_empty);
+        generate_types_section(
+            writer,
+            document,
+            references,
+            use_emoji,
+            exclude_empty,
+            language,
+        )?;
     }
 
     // Functions section
     if !document.functions.is_empty() || !exclude_empty {
-        generate_functions_section(&mut asciidoc, document, use_emoji, exclude_empty);
+        generate_functions_section(
+            writer,
+            document,
+            references,
+            use_emoji,
+            exclude_empty,
+            language,
+        )?;
     }
 
     // Parameters section
     if !document.parameters.is_empty() || !exclude_empty {
-        generate_parameters_section(&mut asciidoc, document, use_emoji, exclude_empty);
+        generate_parameters_section(
+            writer,
+            document,
+            references,
+            &evaluator,
+            use_emoji,
+            exclude_empty,
+            language,
+        )?;
     }
 
     // Variables section
     if !document.variables.is_empty() || !exclude_empty {
-        generate_variables_section(&mut asciidoc, document, use_emoji, exclude_empty);
+        generate_variables_section(
+            writer,
+            document,
+            references,
+            &evaluator,
+            use_emoji,
+            exclude_empty,
+            language,
+        )?;
     }
 
     // Resources section
     if !document.resources.is_empty() || !exclude_empty {
-        generate_resources_section(&mut asciidoc, document, use_emoji, exclude_empty);
+        generate_resources_section(
+            writer,
+            document,
+            references,
+            &evaluator,
+            use_emoji,
+            exclude_empty,
+            language,
+        )?;
     }
 
     // Modules section
     if !document.modules.is_empty() || !exclude_empty {
-        generate_modules_section(&mut asciidoc, document, exclude_empty);
+        generate_modules_section(writer, document, references, exclude_empty, language)?;
     }
 
     // Outputs section
     if !document.outputs.is_empty() || !exclude_empty {
-        generate_outputs_section(&mut asciidoc, document, use_emoji, exclude_empty);
+        generate_outputs_section(
+            writer,
+            document,
+            references,
+            &evaluator,
+            use_emoji,
+            exclude_empty,
+            language,
+        )?;
     }
 
-    Ok(asciidoc)
+    writer.flush()?;
+    Ok(())
+}
+
+/// The asciidoc format for the exporter registry
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsciidocExporter;
+
+impl Exporter for AsciidocExporter {
+    fn name(&self) -> &str {
+        "asciidoc"
+    }
+
+    fn extension(&self) -> &str {
+        "adoc"
+    }
+
+    fn description(&self) -> &str {
+        "Document Bicep file in AsciiDoc format"
+    }
+
+    fn export_to_writer(
+        &self,
+        document: &BicepDocument,
+        writer: &mut dyn Write,
+        options: &ExportOptions,
+    ) -> Result<(), BicepError> {
+        export_to_writer(document, writer, options)
+    }
 }
 
 /// Parse a Bicep file and export it to AsciiDoc
@@ -236,10 +410,14 @@ pub fn parse_and_export<P: AsRef<Path>, Q: AsRef<Path>>(
     file_path: P,
     output_path: Q,
     exclude_empty: bool,
-) -> Result<(), Box<dyn StdError>> {
-    let content = std::fs::read_to_string(file_path)?;
+) -> Result<(), BicepError> {
+    let content = std::fs::read_to_string(&file_path).map_err(|e| BicepError::io(&file_path, e))?;
     let document = crate::parse_bicep_document(&content)?;
-    export_to_file(&document, output_path, true, exclude_empty)?;
+    let options = ExportOptions::builder()
+        .use_emoji(true)
+        .exclude_empty(exclude_empty)
+        .build();
+    export_to_file(&document, output_path, &options)?;
     Ok(())
 }
 
@@ -248,312 +426,655 @@ pub fn test_parse_and_export<P: AsRef<Path>, Q: AsRef<Path>>(
     file_path: P,
     output_path: Q,
     exclude_empty: bool,
-) -> Result<(), Box<dyn StdError>> {
+) -> Result<(), BicepError> {
     parse_and_export(file_path, output_path, exclude_empty)
 }
 
+/// Generate the Usage section of the AsciiDoc
+///
+/// # Arguments
+///
+/// * `asciidoc` - The string buffer to append AsciiDoc content to
+/// * `document` - The template being documented
+/// * `source` - Where consumers reference the template from
+/// * `language` - The language to write labels in
+fn generate_usage_section(
+    asciidoc: &mut dyn Write,
+    document: &BicepDocument,
+    source: &ModuleSource,
+    language: Language,
+) -> Result<(), BicepError> {
+    write!(
+        asciidoc,
+        "== {}\n\n",
+        t!("export.usage", locale = language.code())
+    )?;
+    writeln!(
+        asciidoc,
+        ".{}",
+        t!("export.module", locale = language.code())
+    )?;
+    asciidoc.write_all(b"[source]\n----\n")?;
+    write_module_snippet(asciidoc, document, source)?;
+    asciidoc.write_all(b"----\n")?;
+    writeln!(
+        asciidoc,
+        "\n.{}",
+        t!("export.parameters_file", locale = language.code())
+    )?;
+    asciidoc.write_all(b"[source]\n----\n")?;
+    write_parameters_snippet(asciidoc, document, source)?;
+    asciidoc.write_all(b"----\n")?;
+    asciidoc.write_all(b"\n")?;
+    Ok(())
+}
+
 /// Generate the Types section of the AsciiDoc
 fn generate_types_section(
-    asciidoc: &mut String,
+    asciidoc: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
     use_emoji: bool,
     exclude_empty: bool,
-) {
-    asciidoc.push_str("== Types\n\n");
+    language: Language,
+) -> io::Result<()> {
+    write!(
+        asciidoc,
+        "== {}\n\n",
+        t!("export.types", locale = language.code())
+    )?;
 
     if document.types.is_empty() {
         if !exclude_empty {
-            asciidoc.push_str("_No custom types defined_\n\n");
+            write!(
+                asciidoc,
+                "_{}_\n\n",
+                t!("export.no_types_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
     for (name, custom_type) in &document.types {
-        asciidoc.push_str(&format!("=== `{name}`\n\n"));
-
-        if let Some(description) = &custom_type.description {
-            asciidoc.push_str(&format!("{}\n\n", escape_asciidoc(description)));
+        write!(asciidoc, "=== `{name}`\n\n")?;
+
+        if let Some(description) = localized_description(
+            custom_type.description.as_deref(),
+            &custom_type.descriptions,
+            language,
+        ) {
+            write!(asciidoc, "{}\n\n", escape_asciidoc(description))?;
         }
 
         // Basic information table with properties label
-        asciidoc.push_str(".Properties\n");
-        let items = vec![
+        writeln!(
+            asciidoc,
+            ".{}",
+            t!("export.properties", locale = language.code())
+        )?;
+        let mut items = vec![
+            (
+                t!("export.exported", locale = language.code()),
+                format_yes_no(custom_type.is_exported, use_emoji, language),
+            ),
             (
-                "Exported",
-                format_yes_no(custom_type.is_exported, use_emoji),
+                t!("export.secure", locale = language.code()),
+                format_yes_no(custom_type.is_secure, use_emoji, language),
             ),
-            ("Secure", format_yes_no(custom_type.is_secure, use_emoji)),
         ];
+        items.extend(reference_items(
+            references,
+            SymbolKind::Type,
+            name,
+            language,
+        ));
 
-        generate_key_value_display(asciidoc, &items, "h,1");
+        generate_key_value_display(asciidoc, &items, "h,1")?;
 
         // Check if this is an object type with properties and add object properties section
         if let BicepType::Object(Some(properties)) = &custom_type.definition {
             if !properties.is_empty() {
-                asciidoc.push_str("\n*Object Definition*\n\n");
+                write!(
+                    asciidoc,
+                    "\n*{}*\n\n",
+                    t!("export.object_definition", locale = language.code())
+                )?;
 
                 for (prop_name, prop_param) in properties {
-                    asciidoc.push_str(&format!("==== `{prop_name}`\n\n"));
-
-                    if let Some(description) = &prop_param.description {
-                        asciidoc.push_str(&format!("{}\n\n", escape_asciidoc(description)));
+                    let conventions = DocConventions::from_metadata(&prop_param.metadata);
+                    if conventions.hidden {
+                        continue;
+                    }
+                    write!(asciidoc, "==== `{prop_name}`\n\n")?;
+                    generate_deprecation_notice(asciidoc, &conventions, language)?;
+
+                    if let Some(description) = localized_description(
+                        prop_param.description.as_deref(),
+                        &prop_param.descriptions,
+                        language,
+                    ) {
+                        write!(asciidoc, "{}\n\n", escape_asciidoc(description))?;
                     }
 
-                    asciidoc.push_str(".Properties\n");
+                    writeln!(
+                        asciidoc,
+                        ".{}",
+                        t!("export.properties", locale = language.code())
+                    )?;
                     let prop_items = vec![
-                        ("Type", format!("m| {}", prop_param.parameter_type)),
-                        ("Nullable", format_yes_no(prop_param.is_nullable, use_emoji)),
-                        ("Secure", format_yes_no(prop_param.is_secure, use_emoji)),
+                        (
+                            t!("export.type_header", locale = language.code()),
+                            format!("m| {}", prop_param.parameter_type),
+                        ),
+                        (
+                            t!("export.nullable", locale = language.code()),
+                            format_yes_no(prop_param.is_nullable, use_emoji, language),
+                        ),
+                        (
+                            t!("export.secure", locale = language.code()),
+                            format_yes_no(prop_param.is_secure, use_emoji, language),
+                        ),
                     ];
 
-                    generate_key_value_display(asciidoc, &prop_items, "h,1");
+                    generate_key_value_display(asciidoc, &prop_items, "h,1")?;
 
                     // Add constraints section if there are any constraints
                     let mut constraints = Vec::new();
                     if let Some(min_value) = prop_param.min_value {
-                        constraints.push(("Minimum Value", min_value.to_string()));
+                        constraints.push((
+                            t!("export.minimum_value", locale = language.code()),
+                            min_value.to_string(),
+                        ));
                     }
                     if let Some(max_value) = prop_param.max_value {
-                        constraints.push(("Maximum Value", max_value.to_string()));
+                        constraints.push((
+                            t!("export.maximum_value", locale = language.code()),
+                            max_value.to_string(),
+                        ));
                     }
                     if let Some(min_length) = prop_param.min_length {
-                        constraints.push(("Minimum Length", min_length.to_string()));
+                        constraints.push((
+                            t!("export.minimum_length", locale = language.code()),
+                            min_length.to_string(),
+                        ));
                     }
                     if let Some(max_length) = prop_param.max_length {
-                        constraints.push(("Maximum Length", max_length.to_string()));
+                        constraints.push((
+                            t!("export.maximum_length", locale = language.code()),
+                            max_length.to_string(),
+                        ));
                     }
                     if let Some(allowed_values) = &prop_param.allowed_values {
                         if !allowed_values.is_empty() {
                             constraints.push((
-                                "Allowed Values",
+                                t!("export.allowed_values", locale = language.code()),
                                 format_bicep_array_as_list(allowed_values),
                             ));
                         }
                     }
 
                     if !constraints.is_empty() {
-                        asciidoc.push_str("\n.Constraints\n");
-                        generate_key_value_display(asciidoc, &constraints, "h,>m");
+                        writeln!(
+                            asciidoc,
+                            "\n.{}",
+                            t!("export.constraints", locale = language.code())
+                        )?;
+                        generate_key_value_display(asciidoc, &constraints, "h,>m")?;
                     }
 
                     // Handle nested object properties recursively
                     if let BicepType::Object(Some(nested_props)) = &prop_param.parameter_type {
                         if !nested_props.is_empty() {
-                            generate_nested_object_properties(asciidoc, nested_props, 5, use_emoji);
+                            generate_nested_object_properties(
+                                asciidoc,
+                                nested_props,
+                                5,
+                                use_emoji,
+                                language,
+                            )?;
                         }
                     }
 
                     if let Some(default_value) = &prop_param.default_value {
-                        asciidoc.push_str("\n.Default Value\n");
-                        asciidoc.push_str(&format_code_block(&default_value.pretty_format()));
+                        writeln!(
+                            asciidoc,
+                            "\n.{}",
+                            t!("export.default_value", locale = language.code())
+                        )?;
+                        asciidoc.write_all(
+                            format_code_block(&default_value.pretty_format()).as_bytes(),
+                        )?;
                     }
 
-                    if !prop_param.metadata.is_empty() {
-                        asciidoc.push_str("\n.Metadata\n");
-                        asciidoc.push_str("[%autowidth,cols=\"h,1\",frame=none]\n");
-                        generate_metadata_display_asciidoc(asciidoc, &prop_param.metadata);
+                    generate_example(asciidoc, &conventions, language)?;
+
+                    let metadata = without_conventions(&prop_param.metadata);
+                    if !metadata.is_empty() {
+                        writeln!(
+                            asciidoc,
+                            "\n.{}",
+                            t!("export.metadata", locale = language.code())
+                        )?;
+                        asciidoc.write_all(b"[%autowidth,cols=\"h,1\",frame=none]\n")?;
+                        generate_metadata_display_asciidoc(asciidoc, &metadata)?;
                     }
 
-                    asciidoc.push('\n');
+                    asciidoc.write_all(b"\n")?;
                 }
             }
         }
 
-        asciidoc.push('\n');
+        asciidoc.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Functions section of the AsciiDoc
 fn generate_functions_section(
-    asciidoc: &mut String,
+    asciidoc: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
     use_emoji: bool,
     exclude_empty: bool,
-) {
-    asciidoc.push_str("== Functions\n\n");
-
-    if document.functions.is_empty() {
+    language: Language,
+) -> io::Result<()> {
+    write!(
+        asciidoc,
+        "== {}\n\n",
+        t!("export.functions", locale = language.code())
+    )?;
+
+    if document
+        .functions
+        .values()
+        .all(|function| is_hidden(&function.metadata))
+    {
         if !exclude_empty {
-            asciidoc.push_str("_No user-defined functions_\n\n");
+            write!(
+                asciidoc,
+                "_{}_\n\n",
+                t!("export.no_user_defined_functions", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
     for (name, function) in &document.functions {
-        asciidoc.push_str(&format!("=== `{name}`\n\n"));
+        let conventions = DocConventions::from_metadata(&function.metadata);
+        if conventions.hidden {
+            continue;
+        }
+        write!(asciidoc, "=== `{name}`\n\n")?;
+        generate_deprecation_notice(asciidoc, &conventions, language)?;
 
         if let Some(description) = &function.description {
-            asciidoc.push_str(&format!("{}\n\n", escape_asciidoc(description)));
+            write!(asciidoc, "{}\n\n", escape_asciidoc(description))?;
         }
 
         // Basic information table
-        asciidoc.push_str(".Properties\n");
-        let items = vec![
-            ("Return Type", format!("m| {}", function.return_type)),
-            ("Exported", format_yes_no(function.is_exported, use_emoji)),
+        writeln!(
+            asciidoc,
+            ".{}",
+            t!("export.properties", locale = language.code())
+        )?;
+        let mut items = vec![
+            (
+                t!("export.return_type", locale = language.code()),
+                format!("m| {}", function.return_type),
+            ),
+            (
+                t!("export.exported", locale = language.code()),
+                format_yes_no(function.is_exported, use_emoji, language),
+            ),
         ];
-        generate_key_value_display(asciidoc, &items, "h,1");
+        items.extend(reference_items(
+            references,
+            SymbolKind::Function,
+            name,
+            language,
+        ));
+        generate_key_value_display(asciidoc, &items, "h,1")?;
 
         // Parameters
         if !function.arguments.is_empty() {
-            asciidoc.push_str("\n.Parameters\n");
-            generate_function_arguments_display(asciidoc, &function.arguments, use_emoji);
+            writeln!(
+                asciidoc,
+                "\n.{}",
+                t!("export.parameters", locale = language.code())
+            )?;
+            generate_function_arguments_display(
+                asciidoc,
+                &function.arguments,
+                use_emoji,
+                language,
+            )?;
         }
 
         // Function definition
-        asciidoc.push_str("\n.Definition\n");
-        asciidoc.push_str(&format_code_block(&function.expression));
+        writeln!(
+            asciidoc,
+            "\n.{}",
+            t!("export.definition", locale = language.code())
+        )?;
+        asciidoc.write_all(format_code_block(&function.expression).as_bytes())?;
+
+        generate_example(asciidoc, &conventions, language)?;
 
-        asciidoc.push('\n');
+        asciidoc.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Parameters section of the AsciiDoc
 fn generate_parameters_section(
-    asciidoc: &mut String,
+    asciidoc: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
+    evaluator: &Evaluator,
     use_emoji: bool,
     exclude_empty: bool,
-) {
-    asciidoc.push_str("== Parameters\n\n");
-
-    if document.parameters.is_empty() {
+    language: Language,
+) -> io::Result<()> {
+    write!(
+        asciidoc,
+        "== {}\n\n",
+        t!("export.parameters", locale = language.code())
+    )?;
+
+    let sections = parameter_sections(&document.parameters);
+    if sections.is_empty() {
         if !exclude_empty {
-            asciidoc.push_str("_No parameters defined_\n\n");
+            write!(
+                asciidoc,
+                "_{}_\n\n",
+                t!("export.no_parameters_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
-    for (name, parameter) in &document.parameters {
-        asciidoc.push_str(&format!("=== `{name}`\n\n"));
-
-        if let Some(description) = &parameter.description {
-            asciidoc.push_str(&format!("{}\n\n", escape_asciidoc(description)));
-        }
-
-        // Handle metadata at the top if it contains description
-        if !parameter.metadata.is_empty() {
-            // Check if metadata has description that should be shown as the main description
-            if let Some(metadata_desc) = parameter.metadata.get("description") {
-                if parameter.description.is_none() {
-                    asciidoc.push_str(&format!("{}\n\n", &metadata_desc.to_string()));
-                }
-            }
-
-            // Show other metadata
-            let mut other_metadata = parameter.metadata.clone();
-            other_metadata.shift_remove("description");
-            if !other_metadata.is_empty() {
-                asciidoc.push_str(".Metadata\n");
-                asciidoc.push_str("[%autowidth,cols=\"h,1\",frame=none]\n");
-                generate_metadata_display_asciidoc(asciidoc, &other_metadata);
-                asciidoc.push('\n');
-            }
+    for (group, parameters) in sections {
+        let level = match group {
+            Some(group) => {
+                write!(asciidoc, "=== {}\n\n", escape_asciidoc(group))?;
+                4
+            },
+            None => 3,
+        };
+        for (name, parameter) in parameters {
+            generate_parameter(
+                asciidoc, name, parameter, level, references, evaluator, use_emoji, language,
+            )?;
         }
+    }
+    Ok(())
+}
 
-        // Basic information table
-        asciidoc.push_str(".Properties\n");
-        let items = vec![
-            ("Type", format!("m| {}", parameter.parameter_type)),
-            ("Nullable", format_yes_no(parameter.is_nullable, use_emoji)),
-            ("Secure", format_yes_no(parameter.is_secure, use_emoji)),
-            ("Sealed", format_yes_no(parameter.is_sealed, use_emoji)),
-        ];
-
-        generate_key_value_display(asciidoc, &items, "h,1");
+/// Generate the documentation of one parameter
+///
+/// # Arguments
+///
+/// * `asciidoc` - The string buffer to append AsciiDoc content to
+/// * `name` - The name of the parameter
+/// * `parameter` - The parameter to document
+/// * `level` - The section level of the parameter name (3 for ===, 4 for ====)
+/// * `references` - The reference index, or None when references are not shown
+/// * `evaluator` - The evaluator for effective default values
+/// * `use_emoji` - Whether to use emoji symbols (✅/❌) for Yes/No values
+/// * `language` - The language to write labels in
+#[allow(clippy::too_many_arguments)]
+fn generate_parameter(
+    asciidoc: &mut dyn Write,
+    name: &str,
+    parameter: &BicepParameter,
+    level: usize,
+    references: Option<&ReferenceIndex>,
+    evaluator: &Evaluator,
+    use_emoji: bool,
+    language: Language,
+) -> io::Result<()> {
+    let conventions = DocConventions::from_metadata(&parameter.metadata);
+    write!(asciidoc, "{} `{name}`\n\n", "=".repeat(level))?;
+    generate_deprecation_notice(asciidoc, &conventions, language)?;
+
+    if let Some(description) = localized_description(
+        parameter.description.as_deref(),
+        &parameter.descriptions,
+        language,
+    ) {
+        write!(asciidoc, "{}\n\n", escape_asciidoc(description))?;
+    }
 
-        // Add constraints section if there are any constraints
-        let mut constraints = Vec::new();
-        if let Some(min_value) = parameter.min_value {
-            constraints.push(("Minimum Value", min_value.to_string()));
-        }
-        if let Some(max_value) = parameter.max_value {
-            constraints.push(("Maximum Value", max_value.to_string()));
-        }
-        if let Some(min_length) = parameter.min_length {
-            constraints.push(("Minimum Length", min_length.to_string()));
-        }
-        if let Some(max_length) = parameter.max_length {
-            constraints.push(("Maximum Length", max_length.to_string()));
-        }
-        if let Some(allowed_values) = &parameter.allowed_values {
-            if !allowed_values.is_empty() {
-                constraints.push(("Allowed Values", format_bicep_array_as_list(allowed_values)));
+    // Handle metadata at the top if it contains description
+    if !parameter.metadata.is_empty() {
+        // Check if metadata has description that should be shown as the main description
+        if let Some(metadata_desc) = parameter.metadata.get("description") {
+            if parameter.description.is_none() {
+                write!(asciidoc, "{}\n\n", &metadata_desc.to_string())?;
             }
         }
 
-        if !constraints.is_empty() {
-            asciidoc.push_str("\n.Constraints\n");
-            generate_key_value_display(asciidoc, &constraints, "h,>m");
+        // Show other metadata
+        let mut other_metadata = without_conventions(&parameter.metadata);
+        other_metadata.shift_remove("description");
+        if !other_metadata.is_empty() {
+            writeln!(
+                asciidoc,
+                ".{}",
+                t!("export.metadata", locale = language.code())
+            )?;
+            asciidoc.write_all(b"[%autowidth,cols=\"h,1\",frame=none]\n")?;
+            generate_metadata_display_asciidoc(asciidoc, &other_metadata)?;
+            asciidoc.write_all(b"\n")?;
         }
+    }
 
-        // Default value
-        if let Some(default_value) = &parameter.default_value {
-            asciidoc.push_str("\n.Default Value\n");
-            asciidoc.push_str(&format_code_block(&default_value.pretty_format()));
+    // Basic information table
+    writeln!(
+        asciidoc,
+        ".{}",
+        t!("export.properties", locale = language.code())
+    )?;
+    let mut items = vec![
+        (
+            t!("export.type_header", locale = language.code()),
+            format!("m| {}", parameter.parameter_type),
+        ),
+        (
+            t!("export.nullable", locale = language.code()),
+            format_yes_no(parameter.is_nullable, use_emoji, language),
+        ),
+        (
+            t!("export.secure", locale = language.code()),
+            format_yes_no(parameter.is_secure, use_emoji, language),
+        ),
+        (
+            t!("export.sealed", locale = language.code()),
+            format_yes_no(parameter.is_sealed, use_emoji, language),
+        ),
+    ];
+    items.extend(reference_items(
+        references,
+        SymbolKind::Parameter,
+        name,
+        language,
+    ));
+
+    generate_key_value_display(asciidoc, &items, "h,1")?;
+
+    // Add constraints section if there are any constraints
+    let mut constraints = Vec::new();
+    if let Some(min_value) = parameter.min_value {
+        constraints.push((
+            t!("export.minimum_value", locale = language.code()),
+            min_value.to_string(),
+        ));
+    }
+    if let Some(max_value) = parameter.max_value {
+        constraints.push((
+            t!("export.maximum_value", locale = language.code()),
+            max_value.to_string(),
+        ));
+    }
+    if let Some(min_length) = parameter.min_length {
+        constraints.push((
+            t!("export.minimum_length", locale = language.code()),
+            min_length.to_string(),
+        ));
+    }
+    if let Some(max_length) = parameter.max_length {
+        constraints.push((
+            t!("export.maximum_length", locale = language.code()),
+            max_length.to_string(),
+        ));
+    }
+    if let Some(allowed_values) = &parameter.allowed_values {
+        if !allowed_values.is_empty() {
+            constraints.push((
+                t!("export.allowed_values", locale = language.code()),
+                format_bicep_array_as_list(allowed_values),
+            ));
         }
+    }
 
-        // Object definition for object types
-        if let BicepType::Object(Some(properties)) = &parameter.parameter_type {
-            if !properties.is_empty() {
-                asciidoc.push_str("\n*Object Definition*\n\n");
-
-                for (prop_name, prop_param) in properties {
-                    asciidoc.push_str(&format!("==== `{prop_name}`\n\n"));
-
-                    if let Some(description) = &prop_param.description {
-                        asciidoc.push_str(&format!("{}\n\n", escape_asciidoc(description)));
-                    }
+    if !constraints.is_empty() {
+        writeln!(
+            asciidoc,
+            "\n.{}",
+            t!("export.constraints", locale = language.code())
+        )?;
+        generate_key_value_display(asciidoc, &constraints, "h,>m")?;
+    }
 
-                    asciidoc.push_str(".Properties\n");
-                    let prop_items = vec![
-                        ("Type", format!("m| {}", prop_param.parameter_type)),
-                        ("Nullable", format_yes_no(prop_param.is_nullable, use_emoji)),
-                        ("Secure", format_yes_no(prop_param.is_secure, use_emoji)),
-                    ];
+    // Default value
+    if let Some(default_value) = &parameter.default_value {
+        writeln!(
+            asciidoc,
+            "\n.{}",
+            t!("export.default_value", locale = language.code())
+        )?;
+        asciidoc.write_all(format_code_block(&default_value.pretty_format()).as_bytes())?;
+
+        if let Some(effective) = evaluator.effective_value(default_value) {
+            writeln!(
+                asciidoc,
+                "\n.{}",
+                t!("export.effective_default_value", locale = language.code())
+            )?;
+            asciidoc.write_all(format_code_block(&effective.pretty_format()).as_bytes())?;
+        }
+    }
 
-                    generate_key_value_display(asciidoc, &prop_items, "h,1");
+    generate_example(asciidoc, &conventions, language)?;
+
+    // Object definition for object types
+    if let BicepType::Object(Some(properties)) = &parameter.parameter_type {
+        if !properties.is_empty() {
+            write!(
+                asciidoc,
+                "\n*{}*\n\n",
+                t!("export.object_definition", locale = language.code())
+            )?;
+
+            for (prop_name, prop_param) in properties {
+                let conventions = DocConventions::from_metadata(&prop_param.metadata);
+                if conventions.hidden {
+                    continue;
+                }
+                write!(asciidoc, "{} `{prop_name}`\n\n", "=".repeat(level + 1))?;
+                generate_deprecation_notice(asciidoc, &conventions, language)?;
+
+                if let Some(description) = localized_description(
+                    prop_param.description.as_deref(),
+                    &prop_param.descriptions,
+                    language,
+                ) {
+                    write!(asciidoc, "{}\n\n", escape_asciidoc(description))?;
+                }
 
-                    // Add constraints for properties
-                    let mut prop_constraints = Vec::new();
-                    if let Some(min_value) = prop_param.min_value {
-                        prop_constraints.push(("Minimum Value", min_value.to_string()));
-                    }
-                    if let Some(max_value) = prop_param.max_value {
-                        prop_constraints.push(("Maximum Value", max_value.to_string()));
-                    }
-                    if let Some(min_length) = prop_param.min_length {
-                        prop_constraints.push(("Minimum Length", min_length.to_string()));
-                    }
-                    if let Some(max_length) = prop_param.max_length {
-                        prop_constraints.push(("Maximum Length", max_length.to_string()));
-                    }
+                writeln!(
+                    asciidoc,
+                    ".{}",
+                    t!("export.properties", locale = language.code())
+                )?;
+                let prop_items = vec![
+                    (
+                        t!("export.type_header", locale = language.code()),
+                        format!("m| {}", prop_param.parameter_type),
+                    ),
+                    (
+                        t!("export.nullable", locale = language.code()),
+                        format_yes_no(prop_param.is_nullable, use_emoji, language),
+                    ),
+                    (
+                        t!("export.secure", locale = language.code()),
+                        format_yes_no(prop_param.is_secure, use_emoji, language),
+                    ),
+                ];
+
+                generate_key_value_display(asciidoc, &prop_items, "h,1")?;
+
+                // Add constraints for properties
+                let mut prop_constraints = Vec::new();
+                if let Some(min_value) = prop_param.min_value {
+                    prop_constraints.push((
+                        t!("export.minimum_value", locale = language.code()),
+                        min_value.to_string(),
+                    ));
+                }
+                if let Some(max_value) = prop_param.max_value {
+                    prop_constraints.push((
+                        t!("export.maximum_value", locale = language.code()),
+                        max_value.to_string(),
+                    ));
+                }
+                if let Some(min_length) = prop_param.min_length {
+                    prop_constraints.push((
+                        t!("export.minimum_length", locale = language.code()),
+                        min_length.to_string(),
+                    ));
+                }
+                if let Some(max_length) = prop_param.max_length {
+                    prop_constraints.push((
+                        t!("export.maximum_length", locale = language.code()),
+                        max_length.to_string(),
+                    ));
+                }
 
-                    if !prop_constraints.is_empty() {
-                        asciidoc.push_str("\n.Constraints\n");
-                        generate_key_value_display(asciidoc, &prop_constraints, "h,>m");
-                    }
+                if !prop_constraints.is_empty() {
+                    writeln!(
+                        asciidoc,
+                        "\n.{}",
+                        t!("export.constraints", locale = language.code())
+                    )?;
+                    generate_key_value_display(asciidoc, &prop_constraints, "h,>m")?;
+                }
 
-                    // Recursively handle nested object properties
-                    if let BicepType::Object(Some(nested_properties)) = &prop_param.parameter_type {
-                        if !nested_properties.is_empty() {
-                            asciidoc.push_str("\n*Object Definition*\n\n");
-                            generate_nested_object_properties(
-                                asciidoc,
-                                nested_properties,
-                                5,
-                                use_emoji,
-                            );
-                        }
+                // Recursively handle nested object properties
+                if let BicepType::Object(Some(nested_properties)) = &prop_param.parameter_type {
+                    if !nested_properties.is_empty() {
+                        write!(
+                            asciidoc,
+                            "\n*{}*\n\n",
+                            t!("export.object_definition", locale = language.code())
+                        )?;
+                        generate_nested_object_properties(
+                            asciidoc,
+                            nested_properties,
+                            level + 2,
+                            use_emoji,
+                            language,
+                        )?;
                     }
-
-                    asciidoc.push('\n');
                 }
+
+                asciidoc.write_all(b"\n")?;
             }
         }
-
-        asciidoc.push('\n');
     }
+
+    asciidoc.write_all(b"\n")?;
+    Ok(())
 }
 
 /// Generate nested object properties recursively for AsciiDoc
@@ -563,331 +1084,663 @@ fn generate_parameters_section(
 /// * `asciidoc` - The string buffer to append AsciiDoc content to
 /// * `properties` - The object properties to document
 /// * `header_level` - The header level to use (4 for ==== level, 5 for ===== level, etc.)
+/// * `language` - The language to write labels in
 fn generate_nested_object_properties(
-    asciidoc: &mut String,
+    asciidoc: &mut dyn Write,
     properties: &indexmap::IndexMap<String, crate::parsing::BicepParameter>,
     header_level: usize,
     use_emoji: bool,
-) {
+    language: Language,
+) -> io::Result<()> {
     let header_prefix = "=".repeat(header_level);
 
     for (prop_name, prop_param) in properties {
-        asciidoc.push_str(&format!("{header_prefix} `{prop_name}`\n\n"));
-
-        if let Some(description) = &prop_param.description {
-            asciidoc.push_str(&format!("{}\n\n", escape_asciidoc(description)));
+        let conventions = DocConventions::from_metadata(&prop_param.metadata);
+        if conventions.hidden {
+            continue;
+        }
+        write!(asciidoc, "{header_prefix} `{prop_name}`\n\n")?;
+        generate_deprecation_notice(asciidoc, &conventions, language)?;
+
+        if let Some(description) = localized_description(
+            prop_param.description.as_deref(),
+            &prop_param.descriptions,
+            language,
+        ) {
+            write!(asciidoc, "{}\n\n", escape_asciidoc(description))?;
         }
 
-        asciidoc.push_str(".Properties\n");
+        writeln!(
+            asciidoc,
+            ".{}",
+            t!("export.properties", locale = language.code())
+        )?;
         let prop_items = vec![
-            ("Type", format!("m| {}", prop_param.parameter_type)),
-            ("Nullable", format_yes_no(prop_param.is_nullable, use_emoji)),
-            ("Secure", format_yes_no(prop_param.is_secure, use_emoji)),
+            (
+                t!("export.type_header", locale = language.code()),
+                format!("m| {}", prop_param.parameter_type),
+            ),
+            (
+                t!("export.nullable", locale = language.code()),
+                format_yes_no(prop_param.is_nullable, use_emoji, language),
+            ),
+            (
+                t!("export.secure", locale = language.code()),
+                format_yes_no(prop_param.is_secure, use_emoji, language),
+            ),
         ];
 
-        generate_key_value_display(asciidoc, &prop_items, "h,1");
+        generate_key_value_display(asciidoc, &prop_items, "h,1")?;
 
         // Add constraints for properties
         let mut prop_constraints = Vec::new();
         if let Some(min_value) = prop_param.min_value {
-            prop_constraints.push(("Minimum Value", min_value.to_string()));
+            prop_constraints.push((
+                t!("export.minimum_value", locale = language.code()),
+                min_value.to_string(),
+            ));
         }
         if let Some(max_value) = prop_param.max_value {
-            prop_constraints.push(("Maximum Value", max_value.to_string()));
+            prop_constraints.push((
+                t!("export.maximum_value", locale = language.code()),
+                max_value.to_string(),
+            ));
         }
         if let Some(min_length) = prop_param.min_length {
-            prop_constraints.push(("Minimum Length", min_length.to_string()));
+            prop_constraints.push((
+                t!("export.minimum_length", locale = language.code()),
+                min_length.to_string(),
+            ));
         }
         if let Some(max_length) = prop_param.max_length {
-            prop_constraints.push(("Maximum Length", max_length.to_string()));
+            prop_constraints.push((
+                t!("export.maximum_length", locale = language.code()),
+                max_length.to_string(),
+            ));
         }
 
         if !prop_constraints.is_empty() {
-            asciidoc.push_str("\n.Constraints\n");
-            generate_key_value_display(asciidoc, &prop_constraints, "h,>m");
+            writeln!(
+                asciidoc,
+                "\n.{}",
+                t!("export.constraints", locale = language.code())
+            )?;
+            generate_key_value_display(asciidoc, &prop_constraints, "h,>m")?;
         }
 
         // Recursively handle nested object properties (limit depth to avoid infinite recursion)
         if header_level < 7 {
             if let BicepType::Object(Some(nested_properties)) = &prop_param.parameter_type {
                 if !nested_properties.is_empty() {
-                    asciidoc.push_str("\n*Object Definition*\n\n");
+                    write!(
+                        asciidoc,
+                        "\n*{}*\n\n",
+                        t!("export.object_definition", locale = language.code())
+                    )?;
                     generate_nested_object_properties(
                         asciidoc,
                         nested_properties,
                         header_level + 1,
                         use_emoji,
-                    );
+                        language,
+                    )?;
                 }
             }
         }
 
-        asciidoc.push('\n');
+        asciidoc.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Variables section of the AsciiDoc
 fn generate_variables_section(
-    asciidoc: &mut String,
+    asciidoc: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
+    evaluator: &Evaluator,
     use_emoji: bool,
     exclude_empty: bool,
-) {
-    asciidoc.push_str("== Variables\n\n");
+    language: Language,
+) -> io::Result<()> {
+    write!(
+        asciidoc,
+        "== {}\n\n",
+        t!("export.variables", locale = language.code())
+    )?;
 
     if document.variables.is_empty() {
         if !exclude_empty {
-            asciidoc.push_str("_No variables defined_\n\n");
+            write!(
+                asciidoc,
+                "_{}_\n\n",
+                t!("export.no_variables_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
     for (name, variable) in &document.variables {
-        asciidoc.push_str(&format!("=== `{name}`\n\n"));
+        write!(asciidoc, "=== `{name}`\n\n")?;
 
         if let Some(description) = &variable.description {
-            asciidoc.push_str(&format!("{}\n\n", escape_asciidoc(description)));
+            write!(asciidoc, "{}\n\n", escape_asciidoc(description))?;
         }
 
         // Basic information table
-        asciidoc.push_str(".Properties\n");
-        let items = vec![("Exported", format_yes_no(variable.is_exported, use_emoji))];
-        generate_key_value_display(asciidoc, &items, "h,1");
+        writeln!(
+            asciidoc,
+            ".{}",
+            t!("export.properties", locale = language.code())
+        )?;
+        let mut items = vec![(
+            t!("export.exported", locale = language.code()),
+            format_yes_no(variable.is_exported, use_emoji, language),
+        )];
+        items.extend(reference_items(
+            references,
+            SymbolKind::Variable,
+            name,
+            language,
+        ));
+        generate_key_value_display(asciidoc, &items, "h,1")?;
 
         // Value section
-        asciidoc.push_str("\n.Value\n");
-        asciidoc.push_str(&format_code_block(&variable.value.pretty_format()));
+        writeln!(
+            asciidoc,
+            "\n.{}",
+            t!("export.value", locale = language.code())
+        )?;
+        asciidoc.write_all(format_code_block(&variable.value.pretty_format()).as_bytes())?;
+
+        if let Some(effective) = evaluator.effective_value(&variable.value) {
+            writeln!(
+                asciidoc,
+                "\n.{}",
+                t!("export.effective_value", locale = language.code())
+            )?;
+            asciidoc.write_all(format_code_block(&effective.pretty_format()).as_bytes())?;
+        }
 
-        asciidoc.push('\n');
+        asciidoc.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Resources section of the AsciiDoc
 fn generate_resources_section(
-    asciidoc: &mut String,
+    asciidoc: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
+    evaluator: &Evaluator,
     use_emoji: bool,
     exclude_empty: bool,
-) {
-    asciidoc.push_str("== Resources\n\n");
+    language: Language,
+) -> io::Result<()> {
+    write!(
+        asciidoc,
+        "== {}\n\n",
+        t!("export.resources", locale = language.code())
+    )?;
 
     if document.resources.is_empty() {
         if !exclude_empty {
-            asciidoc.push_str("_No resources defined_\n\n");
+            write!(
+                asciidoc,
+                "_{}_\n\n",
+                t!("export.no_resources_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
     for (name, resource) in &document.resources {
-        asciidoc.push_str(&format!("=== `{name}`\n\n"));
-
-        if let Some(description) = &resource.description {
-            asciidoc.push_str(&format!("{}\n\n", escape_asciidoc(description)));
+        write!(asciidoc, "=== `{name}`\n\n")?;
+
+        if let Some(description) = localized_description(
+            resource.description.as_deref(),
+            &resource.descriptions,
+            language,
+        ) {
+            write!(asciidoc, "{}\n\n", escape_asciidoc(description))?;
         }
 
         // Basic information table
-        asciidoc.push_str(".Properties\n");
-        let mut items: Vec<(&str, String)> = vec![
-            ("Name", resource.name.clone()),
-            ("Type", resource.resource_type.clone()),
-            ("API Version", resource.api_version.clone()),
+        writeln!(
+            asciidoc,
+            ".{}",
+            t!("export.properties", locale = language.code())
+        )?;
+        let mut items: Vec<(Cow<'static, str>, String)> = vec![
+            (
+                t!("export.name_header", locale = language.code()),
+                resource.name.clone(),
+            ),
+            (
+                t!("export.type_header", locale = language.code()),
+                resource.resource_type.clone(),
+            ),
+            (
+                t!("export.api_version", locale = language.code()),
+                resource.api_version.clone(),
+            ),
         ];
 
+        if let Some(effective) =
+            evaluator.effective_value(&BicepValue::String(resource.name.clone()))
+        {
+            items.insert(
+                1,
+                (
+                    t!("export.effective_name", locale = language.code()),
+                    effective.pretty_format(),
+                ),
+            );
+        }
+
         if let Some(scope) = &resource.scope {
-            items.push(("Scope", scope.to_string()));
+            items.push((
+                t!("export.scope", locale = language.code()),
+                scope.to_string(),
+            ));
         }
 
         if resource.existing {
-            items.push(("Existing", format!("d| {}", format_yes_no(true, use_emoji))));
+            items.push((
+                t!("export.existing", locale = language.code()),
+                format!("d| {}", format_yes_no(true, use_emoji, language)),
+            ));
         }
 
         if let Some(parent) = &resource.parent {
-            items.push(("Parent", parent.to_string()));
+            items.push((
+                t!("export.parent", locale = language.code()),
+                parent.to_string(),
+            ));
         }
 
-        if let Some(depends_on) = &resource.depends_on {
+        // The reference analysis includes explicit dependencies, so it replaces them when shown
+        if references.is_some() {
+            items.extend(reference_items(
+                references,
+                SymbolKind::Resource,
+                name,
+                language,
+            ));
+        } else if let Some(depends_on) = &resource.depends_on {
             if !depends_on.is_empty() {
                 let deps = depends_on.join(" +\n");
-                items.push(("Depends On", deps));
+                items.push((t!("export.depends_on", locale = language.code()), deps));
             }
         }
 
         if let Some(batch_size) = resource.batch_size {
-            items.push(("Batch Size", batch_size.to_string()));
+            items.push((
+                t!("export.batch_size", locale = language.code()),
+                batch_size.to_string(),
+            ));
         }
 
-        generate_key_value_display(asciidoc, &items, "h,m");
+        generate_key_value_display(asciidoc, &items, "h,m")?;
 
         // Condition section
         if let Some(condition) = &resource.condition {
-            asciidoc.push_str("\n.Condition\n");
-            asciidoc.push_str(&format_code_block(condition));
+            writeln!(
+                asciidoc,
+                "\n.{}",
+                t!("export.condition", locale = language.code())
+            )?;
+            asciidoc.write_all(format_code_block(condition).as_bytes())?;
         }
 
         // Loop section
         if let Some(loop_statement) = &resource.loop_statement {
-            asciidoc.push_str("\n.Loop\n");
-            asciidoc.push_str(&format_code_block(loop_statement));
+            writeln!(
+                asciidoc,
+                "\n.{}",
+                t!("export.loop", locale = language.code())
+            )?;
+            asciidoc.write_all(format_code_block(loop_statement).as_bytes())?;
         }
 
-        asciidoc.push('\n');
+        asciidoc.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Modules section of the AsciiDoc
-fn generate_modules_section(asciidoc: &mut String, document: &BicepDocument, exclude_empty: bool) {
-    asciidoc.push_str("== Modules\n\n");
+fn generate_modules_section(
+    asciidoc: &mut dyn Write,
+    document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
+    exclude_empty: bool,
+    language: Language,
+) -> io::Result<()> {
+    write!(
+        asciidoc,
+        "== {}\n\n",
+        t!("export.modules", locale = language.code())
+    )?;
 
     if document.modules.is_empty() {
         if !exclude_empty {
-            asciidoc.push_str("_No modules defined_\n\n");
+            write!(
+                asciidoc,
+                "_{}_\n\n",
+                t!("export.no_modules_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
     for (name, module) in &document.modules {
-        asciidoc.push_str(&format!("=== {name}\n\n"));
+        write!(asciidoc, "=== {name}\n\n")?;
 
         if let Some(description) = &module.description {
-            asciidoc.push_str(&format!("{}\n\n", escape_asciidoc(description)));
+            write!(asciidoc, "{}\n\n", escape_asciidoc(description))?;
         }
 
         // Basic information table
         let mut items = vec![
-            ("Source", format!(" `{}`", module.source)),
-            ("Name", module.name.clone()),
+            (
+                t!("export.source_header", locale = language.code()),
+                format!(" `{}`", module.source),
+            ),
+            (
+                t!("export.name_header", locale = language.code()),
+                module.name.clone(),
+            ),
         ];
 
-        if let Some(depends_on) = &module.depends_on {
+        if references.is_some() {
+            items.extend(reference_items(
+                references,
+                SymbolKind::Module,
+                name,
+                language,
+            ));
+        } else if let Some(depends_on) = &module.depends_on {
             if !depends_on.is_empty() {
                 let deps = depends_on.join(", ");
-                items.push(("Depends On", deps));
+                items.push((t!("export.depends_on", locale = language.code()), deps));
             }
         }
 
         if let Some(batch_size) = module.batch_size {
-            items.push(("Batch Size", format!("`{batch_size}`")));
+            items.push((
+                t!("export.batch_size", locale = language.code()),
+                format!("`{batch_size}`"),
+            ));
         }
 
-        generate_key_value_display(asciidoc, &items, "h,1");
+        generate_key_value_display(asciidoc, &items, "h,1")?;
 
         if let Some(condition) = &module.condition {
-            asciidoc.push_str("\n.Condition\n");
-            asciidoc.push_str(&format_code_block(condition));
+            writeln!(
+                asciidoc,
+                "\n.{}",
+                t!("export.condition", locale = language.code())
+            )?;
+            asciidoc.write_all(format_code_block(condition).as_bytes())?;
         }
 
         if let Some(loop_statement) = &module.loop_statement {
-            asciidoc.push_str("\n.Loop\n");
-            asciidoc.push_str(&format_code_block(loop_statement));
+            writeln!(
+                asciidoc,
+                "\n.{}",
+                t!("export.loop", locale = language.code())
+            )?;
+            asciidoc.write_all(format_code_block(loop_statement).as_bytes())?;
         }
 
-        asciidoc.push('\n');
+        asciidoc.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Outputs section of the AsciiDoc
 fn generate_outputs_section(
-    asciidoc: &mut String,
+    asciidoc: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
+    evaluator: &Evaluator,
     use_emoji: bool,
     exclude_empty: bool,
-) {
-    asciidoc.push_str("== Outputs\n\n");
-
-    if document.outputs.is_empty() {
+    language: Language,
+) -> io::Result<()> {
+    write!(
+        asciidoc,
+        "== {}\n\n",
+        t!("export.outputs", locale = language.code())
+    )?;
+
+    if document
+        .outputs
+        .values()
+        .all(|output| output.metadata.as_ref().is_some_and(is_hidden))
+    {
         if !exclude_empty {
-            asciidoc.push_str("_No outputs defined_\n\n");
+            write!(
+                asciidoc,
+                "_{}_\n\n",
+                t!("export.no_outputs_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
+    let no_metadata = IndexMap::new();
     for (name, output) in &document.outputs {
-        asciidoc.push_str(&format!("=== `{name}`\n\n"));
-
-        if let Some(description) = &output.description {
-            asciidoc.push_str(&format!("{}\n\n", escape_asciidoc(description)));
+        let conventions =
+            DocConventions::from_metadata(output.metadata.as_ref().unwrap_or(&no_metadata));
+        if conventions.hidden {
+            continue;
+        }
+        write!(asciidoc, "=== `{name}`\n\n")?;
+        generate_deprecation_notice(asciidoc, &conventions, language)?;
+
+        if let Some(description) = localized_description(
+            output.description.as_deref(),
+            &output.descriptions,
+            language,
+        ) {
+            write!(asciidoc, "{}\n\n", escape_asciidoc(description))?;
         }
 
         // Basic information table
-        asciidoc.push_str(".Properties\n");
+        writeln!(
+            asciidoc,
+            ".{}",
+            t!("export.properties", locale = language.code())
+        )?;
         let mut items = vec![
-            ("Type", format!("m| {}", output.output_type)),
-            ("Secure", format_yes_no(output.secure, use_emoji)),
+            (
+                t!("export.type_header", locale = language.code()),
+                format!("m| {}", output.output_type),
+            ),
+            (
+                t!("export.secure", locale = language.code()),
+                format_yes_no(output.secure, use_emoji, language),
+            ),
         ];
 
         if output.sealed {
-            items.push(("Sealed", format_yes_no(true, use_emoji)));
+            items.push((
+                t!("export.sealed", locale = language.code()),
+                format_yes_no(true, use_emoji, language),
+            ));
         }
 
         if let Some(discriminator) = &output.discriminator {
-            items.push(("Discriminator", discriminator.clone()));
+            items.push((
+                t!("export.discriminator", locale = language.code()),
+                discriminator.clone(),
+            ));
         }
 
-        generate_key_value_display(asciidoc, &items, "h,1");
+        items.extend(reference_items(
+            references,
+            SymbolKind::Output,
+            name,
+            language,
+        ));
+
+        generate_key_value_display(asciidoc, &items, "h,1")?;
 
         let mut prop_constraints = Vec::new();
         if let Some(min_value) = output.min_value {
-            prop_constraints.push(("Minimum Value", min_value.to_string()));
+            prop_constraints.push((
+                t!("export.minimum_value", locale = language.code()),
+                min_value.to_string(),
+            ));
         }
         if let Some(max_value) = output.max_value {
-            prop_constraints.push(("Maximum Value", max_value.to_string()));
+            prop_constraints.push((
+                t!("export.maximum_value", locale = language.code()),
+                max_value.to_string(),
+            ));
         }
         if let Some(min_length) = output.min_length {
-            prop_constraints.push(("Minimum Length", min_length.to_string()));
+            prop_constraints.push((
+                t!("export.minimum_length", locale = language.code()),
+                min_length.to_string(),
+            ));
         }
         if let Some(max_length) = output.max_length {
-            prop_constraints.push(("Maximum Length", max_length.to_string()));
+            prop_constraints.push((
+                t!("export.maximum_length", locale = language.code()),
+                max_length.to_string(),
+            ));
         }
 
         if !prop_constraints.is_empty() {
-            asciidoc.push_str("\n.Constraints\n");
-            generate_key_value_display(asciidoc, &prop_constraints, "h,>m");
+            writeln!(
+                asciidoc,
+                "\n.{}",
+                t!("export.constraints", locale = language.code())
+            )?;
+            generate_key_value_display(asciidoc, &prop_constraints, "h,>m")?;
         }
 
         // Value section
-        asciidoc.push_str("\n.Value\n");
-        asciidoc.push_str(&format_code_block(&output.value.pretty_format()));
+        writeln!(
+            asciidoc,
+            "\n.{}",
+            t!("export.value", locale = language.code())
+        )?;
+        asciidoc.write_all(format_code_block(&output.value.pretty_format()).as_bytes())?;
+
+        if let Some(effective) = evaluator.effective_value(&output.value) {
+            writeln!(
+                asciidoc,
+                "\n.{}",
+                t!("export.effective_value", locale = language.code())
+            )?;
+            asciidoc.write_all(format_code_block(&effective.pretty_format()).as_bytes())?;
+        }
+
+        generate_example(asciidoc, &conventions, language)?;
 
         // Additional metadata if present
         if let Some(metadata) = &output.metadata {
+            let metadata = without_conventions(metadata);
             if !metadata.is_empty() {
-                asciidoc.push_str("\n.Metadata\n");
-                asciidoc.push_str("[%autowidth,cols=\"h,1\",frame=none]\n");
-                generate_metadata_display_asciidoc(asciidoc, metadata);
+                writeln!(
+                    asciidoc,
+                    "\n.{}",
+                    t!("export.metadata", locale = language.code())
+                )?;
+                asciidoc.write_all(b"[%autowidth,cols=\"h,1\",frame=none]\n")?;
+                generate_metadata_display_asciidoc(asciidoc, &metadata)?;
             }
         }
 
-        asciidoc.push('\n');
+        asciidoc.write_all(b"\n")?;
     }
+    Ok(())
+}
+
+/// Build the "Depends On" and "Used By" rows for a symbol
+///
+/// Parameters and variables that nothing references are marked as unused.
+///
+/// # Arguments
+///
+/// * `references` - The reference index, or None when references are not shown
+/// * `kind` - The kind of the symbol
+/// * `name` - The name of the symbol
+/// * `language` - The language to write labels in
+fn reference_items(
+    references: Option<&ReferenceIndex>,
+    kind: SymbolKind,
+    name: &str,
+    language: Language,
+) -> Vec<(Cow<'static, str>, String)> {
+    let Some(references) = references else {
+        return Vec::new();
+    };
+
+    let mut items = Vec::new();
+    let depends_on = references.depends_on(kind, name);
+    if !depends_on.is_empty() {
+        let names = depends_on.iter().map(|s| s.name.as_str());
+        items.push((
+            t!("export.depends_on", locale = language.code()),
+            format!("m| {}", names.collect::<Vec<_>>().join("\n")),
+        ));
+    }
+
+    let used_by = references.used_by(kind, name);
+    if !used_by.is_empty() {
+        let names = used_by.iter().map(|s| s.name.as_str());
+        items.push((
+            t!("export.used_by", locale = language.code()),
+            format!("m| {}", names.collect::<Vec<_>>().join("\n")),
+        ));
+    } else if matches!(kind, SymbolKind::Parameter | SymbolKind::Variable) {
+        items.push((
+            t!("export.used_by", locale = language.code()),
+            format!("d| _{}_", t!("export.unused", locale = language.code())),
+        ));
+    }
+
+    items
 }
 
 /// Generate key-value property display
-fn generate_key_value_display(asciidoc: &mut String, items: &[(&str, String)], cols: &str) {
-    asciidoc.push_str(&format!("[%autowidth,cols=\"{cols}\",frame=none]\n"));
-    asciidoc.push_str("|===\n");
+fn generate_key_value_display(
+    asciidoc: &mut dyn Write,
+    items: &[(Cow<'static, str>, String)],
+    cols: &str,
+) -> io::Result<()> {
+    writeln!(asciidoc, "[%autowidth,cols=\"{cols}\",frame=none]")?;
+    asciidoc.write_all(b"|===\n")?;
     for (key, value) in items {
         match value.split_once("|") {
             Some((attr, split_value)) if !attr.ends_with('\\') => {
                 // We have an attribute.
                 // If statement catches escaped pipes in the value
-                asciidoc.push_str(&format!(
+                write!(
+                    asciidoc,
                     "| {}\n{}| {}\n\n",
                     escape_asciidoc(key),
                     escape_asciidoc(attr),
                     escape_asciidoc(split_value.trim())
-                ));
+                )?;
             },
             _ => {
                 // Otherwise, just display the key and value
-                asciidoc.push_str(&format!(
+                write!(
+                    asciidoc,
                     "| {}\n| {}\n\n",
                     escape_asciidoc(key),
                     escape_asciidoc(value)
-                ));
+                )?;
             },
         }
     }
-    asciidoc.push_str("|===\n");
+    asciidoc.write_all(b"|===\n")?;
+    Ok(())
 }
 
 /// Generate display for function arguments in table format
@@ -896,26 +1749,102 @@ fn generate_key_value_display(asciidoc: &mut String, items: &[(&str, String)], c
 ///
 /// * `asciidoc` - The string buffer to append AsciiDoc content to
 /// * `arguments` - The function arguments to display
+/// * `language` - The language to write labels in
 fn generate_function_arguments_display(
-    asciidoc: &mut String,
+    asciidoc: &mut dyn Write,
     arguments: &[BicepFunctionArgument],
     use_emoji: bool,
-) {
-    asciidoc.push_str("[%autowidth,cols=\"h,m,1\",frame=none]\n");
-    asciidoc.push_str("|===\n");
-    asciidoc.push_str("| Name\n| Type\n| Required\n\n");
+    language: Language,
+) -> io::Result<()> {
+    asciidoc.write_all(b"[%autowidth,cols=\"h,m,1\",frame=none]\n")?;
+    asciidoc.write_all(b"|===\n")?;
+    write!(
+        asciidoc,
+        "| {}\n| {}\n| {}\n\n",
+        t!("export.name_header", locale = language.code()),
+        t!("export.type_header", locale = language.code()),
+        t!("export.required_header", locale = language.code())
+    )?;
     for arg in arguments {
-        asciidoc.push_str(&format!(
+        write!(
+            asciidoc,
             "| {}\n| {}\n| {}\n\n",
             escape_asciidoc(&arg.name),
             escape_asciidoc(&arg.argument_type.to_string()),
-            format_yes_no(!arg.is_nullable, use_emoji)
-        ));
+            format_yes_no(!arg.is_nullable, use_emoji, language)
+        )?;
     }
-    asciidoc.push_str("|===\n");
+    asciidoc.write_all(b"|===\n")?;
+    Ok(())
 }
 
 /// Format a value as a code block for display in AsciiDoc
+/// Generate the deprecation warning of a declaration, if it is deprecated
+///
+/// # Arguments
+///
+/// * `asciidoc` - The string buffer to append AsciiDoc content to
+/// * `conventions` - The documentation conventions of the declaration
+/// * `language` - The language to write labels in
+fn generate_deprecation_notice(
+    asciidoc: &mut dyn Write,
+    conventions: &DocConventions,
+    language: Language,
+) -> io::Result<()> {
+    let Some(deprecation) = conventions.deprecation else {
+        return Ok(());
+    };
+
+    let label = match deprecation.since {
+        Some(version) => t!(
+            "export.deprecated_since",
+            locale = language.code(),
+            version = version
+        ),
+        None => t!("export.deprecated", locale = language.code()),
+    };
+    match deprecation.message {
+        Some(message) => write!(asciidoc, "WARNING: *{label}:* {}", escape_asciidoc(message))?,
+        None => write!(asciidoc, "WARNING: *{label}*")?,
+    }
+    if let Some(replacement) = deprecation.replacement {
+        let replacement = format!("`{replacement}`");
+        write!(
+            asciidoc,
+            " +\n{}",
+            t!(
+                "export.use_instead",
+                locale = language.code(),
+                replacement = replacement
+            )
+        )?;
+    }
+    asciidoc.write_all(b"\n\n")
+}
+
+/// Generate the usage example of a declaration, if it has one
+///
+/// # Arguments
+///
+/// * `asciidoc` - The string buffer to append AsciiDoc content to
+/// * `conventions` - The documentation conventions of the declaration
+/// * `language` - The language to write labels in
+fn generate_example(
+    asciidoc: &mut dyn Write,
+    conventions: &DocConventions,
+    language: Language,
+) -> io::Result<()> {
+    if let Some(example) = conventions.example {
+        writeln!(
+            asciidoc,
+            "\n.{}",
+            t!("export.example", locale = language.code())
+        )?;
+        asciidoc.write_all(format_code_block(&example_source(example)).as_bytes())?;
+    }
+    Ok(())
+}
+
 fn format_code_block(value: &str) -> String {
     format!("[source]\n----\n{value}\n----\n")
 }
@@ -934,7 +1863,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = export_to_string(&document, true, false);
+        let result = export_to_string(&document, &ExportOptions::builder().use_emoji(true).build());
         assert!(result.is_ok());
 
         let asciidoc = result.unwrap();
@@ -966,7 +1895,7 @@ mod tests {
             .parameters
             .insert("testParam".to_string(), parameter);
 
-        let result = export_to_string(&document, true, false);
+        let result = export_to_string(&document, &ExportOptions::builder().use_emoji(true).build());
         assert!(result.is_ok());
 
         let asciidoc = result.unwrap();
@@ -976,6 +1905,67 @@ mod tests {
         assert!(asciidoc.contains("default"));
     }
 
+    #[test]
+    fn test_export_to_string_in_french() {
+        let document = BicepDocument {
+            target_scope: Some("subscription".to_string()),
+            ..Default::default()
+        };
+
+        let asciidoc = export_to_string(
+            &document,
+            &ExportOptions::builder().language(Language::French).build(),
+        )
+        .unwrap();
+        assert!(asciidoc.starts_with("= Modèle Bicep\n"));
+        assert!(asciidoc.contains("| Portée Cible\n"));
+        assert!(asciidoc.contains("== Paramètres"));
+        assert!(!asciidoc.contains("No parameters defined"));
+    }
+
+    #[test]
+    fn test_export_to_string_with_metadata_conventions() {
+        let document = crate::parse_arm_document(
+            r#"{
+                "$schema": "https://schema.management.azure.com/schemas/2019-04-01/deploymentTemplate.json#",
+                "parameters": {
+                    "name": { "type": "string", "metadata": { "example": "'stcontoso'", "owner": "platform" } },
+                    "skuName": { "type": "string", "metadata": { "group": "Pricing" } },
+                    "location": { "type": "string", "metadata": { "order": 1 } },
+                    "legacySku": {
+                        "type": "string",
+                        "metadata": { "deprecated": "Set by policy", "deprecatedSince": "2.0", "replacement": "skuName", "group": "Pricing" }
+                    },
+                    "internalTag": { "type": "string", "metadata": { "docs": { "hidden": true } } }
+                },
+                "resources": [],
+                "outputs": {
+                    "debug": { "type": "string", "value": "x", "metadata": { "docs.hidden": true } }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let asciidoc = export_to_string(
+            &document,
+            &ExportOptions::builder()
+                .exclude_empty(true)
+                .language(Language::German)
+                .build(),
+        )
+        .unwrap();
+
+        let position = |text: &str| asciidoc.find(text).unwrap();
+        assert!(position("=== `location`") < position("=== `name`"));
+        assert!(position("=== Pricing") < position("==== `legacySku`"));
+        assert!(asciidoc.contains(".Beispiel\n[source]\n----\n'stcontoso'\n----"));
+        assert!(asciidoc.contains(
+            "WARNING: *Veraltet seit 2.0:* Set by policy +\nVerwenden Sie stattdessen `skuName`."
+        ));
+        assert!(!asciidoc.contains("internalTag"));
+        assert!(!asciidoc.contains("`debug`"));
+    }
+
     #[test]
     fn test_export_to_string_with_exclude_empty() {
         // Create a document with some empty collections and one non-empty collection
@@ -996,7 +1986,14 @@ mod tests {
             .insert("testParam".to_string(), parameter);
 
         // Test with exclude_empty = true
-        let result = export_to_string(&document, true, true).unwrap();
+        let result = export_to_string(
+            &document,
+            &ExportOptions::builder()
+                .use_emoji(true)
+                .exclude_empty(true)
+                .build(),
+        )
+        .unwrap();
 
         // Should contain the document name and the parameter section
         assert!(result.contains("= Test Template"));
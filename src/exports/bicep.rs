@@ -0,0 +1,950 @@
+/// Bicep source export functionality
+///
+/// This module emits Bicep source from a parsed Bicep document: declarations,
+/// decorators, types and values are written in canonical formatting with two-space
+/// indentation. It can be used to generate templates programmatically, to normalise
+/// the formatting of existing templates, or to convert ARM JSON templates to Bicep.
+///
+/// Declarations are written in a fixed order: target scope, metadata, imports, types,
+/// functions, parameters, variables, resources, modules and outputs. Values are written
+/// as they are, including the default values of secure parameters, so that the emitted
+/// template deploys the same way as the original.
+use std::{fs::File, io::Write, path::Path};
+
+use indexmap::IndexMap;
+
+use crate::error::BicepError;
+use crate::{
+    analysis::expressions::{tokenize_template, StringSegment},
+    exports::utils::formatting::{format_bicep_key, format_bicep_source, quote_bicep_string},
+    exports::{ExportOptions, Exporter},
+    parsing::{
+        utils::decorators::LOCALIZED_DESCRIPTIONS_KEY, BicepDocument, BicepImport, BicepModule,
+        BicepParameter, BicepResource, BicepType, BicepValue,
+    },
+};
+
+/// Export a Bicep document as Bicep source to a file
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to export
+/// * `output_path` - The path where the Bicep file should be written
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn export_to_file<P: AsRef<Path>>(
+    document: &BicepDocument,
+    output_path: P,
+) -> Result<(), BicepError> {
+    let mut file = File::create(&output_path).map_err(|e| BicepError::io(&output_path, e))?;
+    export_to_writer(document, &mut file)?;
+    Ok(())
+}
+
+/// Export a Bicep document as Bicep source
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to export
+///
+/// # Returns
+///
+/// A Result containing the Bicep source or an error
+pub fn export_to_string(document: &BicepDocument) -> Result<String, BicepError> {
+    let mut buffer = Vec::new();
+    export_to_writer(document, &mut buffer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// Write a Bicep document as Bicep source
+///
+/// The source is laid out in full before it is written, since whether a value fits
+/// on one line depends on the rendered width of everything nested inside it.
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to export
+/// * `writer` - Where to write the Bicep source
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn export_to_writer(
+    document: &BicepDocument,
+    writer: &mut dyn Write,
+) -> Result<(), BicepError> {
+    let emitter = Emitter { document };
+    writer.write_all(emitter.emit().as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// The bicep format for the exporter registry
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BicepExporter;
+
+impl Exporter for BicepExporter {
+    fn name(&self) -> &str {
+        "bicep"
+    }
+
+    fn extension(&self) -> &str {
+        "bicep"
+    }
+
+    fn description(&self) -> &str {
+        "Write a Bicep file or ARM JSON template as canonically formatted Bicep"
+    }
+
+    fn export_to_writer(
+        &self,
+        document: &BicepDocument,
+        writer: &mut dyn Write,
+        _options: &ExportOptions,
+    ) -> Result<(), BicepError> {
+        export_to_writer(document, writer)
+    }
+}
+
+/// Writes the declarations of a document as Bicep source
+struct Emitter<'a> {
+    document: &'a BicepDocument,
+}
+
+impl<'a> Emitter<'a> {
+    /// Emit every declaration of the document, separated by blank lines
+    fn emit(&self) -> String {
+        let document = self.document;
+        let mut declarations = Vec::new();
+
+        if let Some(scope) = &document.target_scope {
+            declarations.push(format!("targetScope = {}", quote_bicep_string(scope)));
+        }
+
+        let mut metadata = Vec::new();
+        if let Some(name) = &document.name {
+            metadata.push(format!("metadata name = {}", quote_bicep_string(name)));
+        }
+        if let Some(description) = &document.description {
+            metadata.push(format!(
+                "metadata description = {}",
+                quote_bicep_string(description)
+            ));
+        }
+        if !document.descriptions.is_empty() {
+            metadata.push(format!(
+                "metadata {LOCALIZED_DESCRIPTIONS_KEY} = {}",
+                self.value(&descriptions_value(&document.descriptions), 0)
+            ));
+        }
+        for (key, value) in &document.metadata {
+            metadata.push(format!("metadata {key} = {}", self.value(value, 0)));
+        }
+        if !metadata.is_empty() {
+            declarations.push(metadata.join("\n"));
+        }
+
+        if !document.imports.is_empty() {
+            declarations.push(
+                document
+                    .imports
+                    .iter()
+                    .map(format_import)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+
+        for (name, custom_type) in &document.types {
+            let mut source = String::new();
+            if custom_type.is_exported {
+                source.push_str("@export()\n");
+            }
+            if let Some(description) = &custom_type.description {
+                push_description(&mut source, description, 0);
+            }
+            self.push_metadata(
+                &mut source,
+                &with_descriptions(&custom_type.descriptions, &IndexMap::new()),
+                0,
+            );
+            if let Some(discriminator) = &custom_type.discriminator {
+                source.push_str(&format!(
+                    "@discriminator({})\n",
+                    quote_bicep_string(discriminator)
+                ));
+            }
+            if custom_type.is_sealed {
+                source.push_str("@sealed()\n");
+            }
+            if custom_type.is_secure {
+                source.push_str("@secure()\n");
+            }
+            source.push_str(&format!(
+                "type {name} = {}",
+                self.format_type(&custom_type.definition, 0)
+            ));
+            declarations.push(source);
+        }
+
+        for (name, function) in &document.functions {
+            let mut source = String::new();
+            if function.is_exported {
+                source.push_str("@export()\n");
+            }
+            if let Some(description) = &function.description {
+                push_description(&mut source, description, 0);
+            }
+            self.push_metadata(&mut source, &function.metadata, 0);
+            let arguments = function
+                .arguments
+                .iter()
+                .map(|argument| {
+                    format!(
+                        "{} {}{}",
+                        argument.name,
+                        self.format_type(&argument.argument_type, 0),
+                        if argument.is_nullable { "?" } else { "" }
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            source.push_str(&format!(
+                "func {name}({arguments}) {} => {}",
+                self.format_type(&function.return_type, 0),
+                function.expression
+            ));
+            declarations.push(source);
+        }
+
+        for (name, parameter) in &document.parameters {
+            let mut source = String::new();
+            self.push_parameter_decorators(&mut source, parameter, 0);
+            source.push_str(&format!(
+                "param {name} {}{}",
+                self.format_type(&parameter.parameter_type, 0),
+                if parameter.is_nullable { "?" } else { "" }
+            ));
+            if let Some(default_value) = &parameter.default_value {
+                source.push_str(&format!(" = {}", self.value(default_value, 0)));
+            }
+            declarations.push(source);
+        }
+
+        for (name, variable) in &document.variables {
+            let mut source = String::new();
+            if variable.is_exported {
+                source.push_str("@export()\n");
+            }
+            if let Some(description) = &variable.description {
+                push_description(&mut source, description, 0);
+            }
+            source.push_str(&format!("var {name} = {}", self.value(&variable.value, 0)));
+            declarations.push(source);
+        }
+
+        for (name, resource) in &document.resources {
+            // Child resources are written inside their parent
+            if !name.contains("::") {
+                declarations.push(self.format_resource(name, resource, 0));
+            }
+        }
+
+        for (name, module) in &document.modules {
+            declarations.push(self.format_module(name, module));
+        }
+
+        for (name, output) in &document.outputs {
+            let mut source = String::new();
+            if let Some(description) = &output.description {
+                push_description(&mut source, description, 0);
+            }
+            self.push_metadata(
+                &mut source,
+                &with_descriptions(
+                    &output.descriptions,
+                    output.metadata.as_ref().unwrap_or(&IndexMap::new()),
+                ),
+                0,
+            );
+            if let Some(discriminator) = &output.discriminator {
+                source.push_str(&format!(
+                    "@discriminator({})\n",
+                    quote_bicep_string(discriminator)
+                ));
+            }
+            if output.sealed {
+                source.push_str("@sealed()\n");
+            }
+            if output.secure {
+                source.push_str("@secure()\n");
+            }
+            push_bounds(
+                &mut source,
+                [
+                    ("minLength", output.min_length),
+                    ("maxLength", output.max_length),
+                    ("minValue", output.min_value),
+                    ("maxValue", output.max_value),
+                ],
+                0,
+            );
+            source.push_str(&format!(
+                "output {name} {} = {}",
+                self.format_type(&output.output_type, 0),
+                self.value(&output.value, 0)
+            ));
+            declarations.push(source);
+        }
+
+        let mut source = declarations.join("\n\n");
+        source.push('\n');
+        source
+    }
+
+    /// Format a value as Bicep source
+    fn value(&self, value: &BicepValue, indent: usize) -> String {
+        format_bicep_source(value, indent)
+    }
+
+    /// Add an `@metadata` decorator when there is metadata
+    fn push_metadata(
+        &self,
+        source: &mut String,
+        metadata: &IndexMap<String, BicepValue>,
+        indent: usize,
+    ) {
+        if !metadata.is_empty() {
+            source.push_str(&format!(
+                "{}@metadata({})\n",
+                " ".repeat(indent),
+                self.value(&BicepValue::Object(metadata.clone()), indent)
+            ));
+        }
+    }
+
+    /// Add the decorators of a parameter or object property
+    fn push_parameter_decorators(
+        &self,
+        source: &mut String,
+        parameter: &BicepParameter,
+        indent: usize,
+    ) {
+        let padding = " ".repeat(indent);
+        if let Some(description) = &parameter.description {
+            push_description(source, description, indent);
+        }
+        self.push_metadata(
+            source,
+            &with_descriptions(&parameter.descriptions, &parameter.metadata),
+            indent,
+        );
+        if let Some(discriminator) = &parameter.discriminator {
+            source.push_str(&format!(
+                "{padding}@discriminator({})\n",
+                quote_bicep_string(discriminator)
+            ));
+        }
+        if parameter.is_sealed {
+            source.push_str(&format!("{padding}@sealed()\n"));
+        }
+        if parameter.is_secure {
+            source.push_str(&format!("{padding}@secure()\n"));
+        }
+        if let Some(allowed_values) = &parameter.allowed_values {
+            source.push_str(&format!(
+                "{padding}@allowed({})\n",
+                self.value(&BicepValue::Array(allowed_values.clone()), indent)
+            ));
+        }
+        push_bounds(
+            source,
+            [
+                ("minLength", parameter.min_length),
+                ("maxLength", parameter.max_length),
+                ("minValue", parameter.min_value),
+                ("maxValue", parameter.max_value),
+            ],
+            indent,
+        );
+    }
+
+    /// Format a type expression
+    fn format_type(&self, bicep_type: &BicepType, indent: usize) -> String {
+        match bicep_type {
+            BicepType::String => "string".to_string(),
+            BicepType::Int => "int".to_string(),
+            BicepType::Bool => "bool".to_string(),
+            BicepType::CustomType(name) => name.clone(),
+            BicepType::Object(None) => "object".to_string(),
+            BicepType::Object(Some(properties)) if properties.is_empty() => "{}".to_string(),
+            BicepType::Object(Some(properties)) => {
+                let padding = " ".repeat(indent + 2);
+                let mut source = "{\n".to_string();
+                for (name, property) in properties {
+                    self.push_parameter_decorators(&mut source, property, indent + 2);
+                    source.push_str(&format!(
+                        "{padding}{}: {}{}\n",
+                        format_bicep_key(name),
+                        self.format_type(&property.parameter_type, indent + 2),
+                        if property.is_nullable { "?" } else { "" }
+                    ));
+                }
+                source.push_str(&format!("{}}}", " ".repeat(indent)));
+                source
+            },
+            BicepType::Array(item_type) => match item_type.as_ref() {
+                BicepType::Union(_) => format!("({})[]", self.format_type(item_type, indent)),
+                _ => format!("{}[]", self.format_type(item_type, indent)),
+            },
+            BicepType::Union(members) => members
+                .iter()
+                .map(|member| self.format_union_member(member))
+                .collect::<Vec<_>>()
+                .join(" | "),
+        }
+    }
+
+    /// Format a union member, quoting string literals
+    ///
+    /// Union members are stored without quotes, so members that name a declared
+    /// type or are numeric, boolean or null literals are written as is.
+    fn format_union_member(&self, member: &str) -> String {
+        let is_literal = member.parse::<i64>().is_ok()
+            || matches!(member, "true" | "false" | "null")
+            || self.document.types.contains_key(member);
+        if is_literal {
+            member.to_string()
+        } else {
+            quote_bicep_string(member)
+        }
+    }
+
+    /// Format a resource declaration, including its nested child resources
+    fn format_resource(&self, name: &str, resource: &BicepResource, indent: usize) -> String {
+        let padding = " ".repeat(indent);
+        let mut source = String::new();
+        if let Some(description) = &resource.description {
+            push_description(&mut source, description, indent);
+        }
+        self.push_metadata(
+            &mut source,
+            &with_descriptions(&resource.descriptions, &IndexMap::new()),
+            indent,
+        );
+        if let Some(batch_size) = resource.batch_size {
+            source.push_str(&format!("{padding}@batchSize({batch_size})\n"));
+        }
+
+        // Nested resources are declared with their type relative to the parent
+        let symbol = name.rsplit("::").next().unwrap_or(name);
+        let parent = self
+            .document
+            .resources
+            .get(name.rsplit_once("::").map_or("", |(p, _)| p));
+        let type_reference = match parent {
+            Some(parent) if indent > 0 => {
+                let relative_type = resource
+                    .resource_type
+                    .strip_prefix(&format!("{}/", parent.resource_type))
+                    .unwrap_or(&resource.resource_type);
+                if resource.api_version.is_empty() || resource.api_version == parent.api_version {
+                    relative_type.to_string()
+                } else {
+                    format!("{relative_type}@{}", resource.api_version)
+                }
+            },
+            _ if resource.api_version.is_empty() => resource.resource_type.clone(),
+            _ => format!("{}@{}", resource.resource_type, resource.api_version),
+        };
+        source.push_str(&format!(
+            "{padding}resource {symbol} {}{} = ",
+            quote_bicep_string(&type_reference),
+            if resource.existing { " existing" } else { "" }
+        ));
+
+        let mut body = IndexMap::new();
+        if !resource.name.is_empty() {
+            body.insert(
+                "name".to_string(),
+                self.resource_name(&resource.name, indent + 2),
+            );
+        }
+        if let Some(parent) = &resource.parent {
+            body.insert("parent".to_string(), parent.clone());
+        }
+        if let Some(scope) = &resource.scope {
+            body.insert("scope".to_string(), self.value(scope, indent + 2));
+        }
+        for (key, value) in &resource.properties {
+            body.insert(format_bicep_key(key), self.value(value, indent + 2));
+        }
+        if let Some(depends_on) = &resource.depends_on {
+            body.insert("dependsOn".to_string(), format_list(depends_on, indent + 2));
+        }
+
+        let children = self
+            .document
+            .resources
+            .iter()
+            .filter(|(child, _)| {
+                child
+                    .strip_prefix(name)
+                    .and_then(|rest| rest.strip_prefix("::"))
+                    .is_some_and(|rest| !rest.contains("::"))
+            })
+            .map(|(child, child_resource)| self.format_resource(child, child_resource, indent + 2))
+            .collect::<Vec<_>>();
+
+        source.push_str(&wrap_body(
+            format_body(&body, &children, indent),
+            resource.condition.as_deref(),
+            resource.loop_statement.as_deref(),
+        ));
+        source
+    }
+
+    /// Format a resource name, writing a name that is a single `${expression}` as the
+    /// plain expression
+    fn resource_name(&self, name: &str, indent: usize) -> String {
+        match tokenize_template(name).as_deref() {
+            Ok([StringSegment::Interpolation(_)]) => name[2..name.len() - 1].to_string(),
+            _ => self.value(&BicepValue::String(name.to_string()), indent),
+        }
+    }
+
+    /// Format a module declaration
+    fn format_module(&self, name: &str, module: &BicepModule) -> String {
+        let mut source = String::new();
+        if let Some(description) = &module.description {
+            push_description(&mut source, description, 0);
+        }
+        if let Some(batch_size) = module.batch_size {
+            source.push_str(&format!("@batchSize({batch_size})\n"));
+        }
+        source.push_str(&format!(
+            "module {name} {} = ",
+            quote_bicep_string(&module.source.to_string())
+        ));
+
+        let mut body = IndexMap::new();
+        if let Some(deployment_name) = &module.deployment_name {
+            body.insert("name".to_string(), self.resource_name(deployment_name, 2));
+        }
+        if !module.params.is_empty() {
+            body.insert(
+                "params".to_string(),
+                self.value(&BicepValue::Object(module.params.clone()), 2),
+            );
+        }
+        if let Some(depends_on) = &module.depends_on {
+            body.insert("dependsOn".to_string(), format_list(depends_on, 2));
+        }
+
+        source.push_str(&wrap_body(
+            format_body(&body, &[], 0),
+            module.condition.as_deref(),
+            module.loop_statement.as_deref(),
+        ));
+        source
+    }
+}
+
+/// The descriptions in other languages as a Bicep object keyed by locale code
+fn descriptions_value(descriptions: &IndexMap<String, String>) -> BicepValue {
+    BicepValue::Object(
+        descriptions
+            .iter()
+            .map(|(locale, text)| (locale.clone(), BicepValue::String(text.clone())))
+            .collect(),
+    )
+}
+
+/// Metadata with the descriptions in other languages added as a `descriptions` object
+fn with_descriptions(
+    descriptions: &IndexMap<String, String>,
+    metadata: &IndexMap<String, BicepValue>,
+) -> IndexMap<String, BicepValue> {
+    let mut combined = IndexMap::new();
+    if !descriptions.is_empty() {
+        combined.insert(
+            LOCALIZED_DESCRIPTIONS_KEY.to_string(),
+            descriptions_value(descriptions),
+        );
+    }
+    combined.extend(
+        metadata
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone())),
+    );
+    combined
+}
+
+/// Add a `@description` decorator
+fn push_description(source: &mut String, description: &str, indent: usize) {
+    source.push_str(&format!(
+        "{}@description({})\n",
+        " ".repeat(indent),
+        quote_bicep_string(description)
+    ));
+}
+
+/// Add the length and value bound decorators that are set
+fn push_bounds(source: &mut String, bounds: [(&str, Option<i64>); 4], indent: usize) {
+    for (decorator, bound) in bounds {
+        if let Some(bound) = bound {
+            source.push_str(&format!("{}@{decorator}({bound})\n", " ".repeat(indent)));
+        }
+    }
+}
+
+/// Format a list of symbol references or expressions, such as `dependsOn`
+fn format_list(items: &[String], indent: usize) -> String {
+    if items.is_empty() {
+        return "[]".to_string();
+    }
+    let mut source = "[\n".to_string();
+    for item in items {
+        source.push_str(&format!("{}{item}\n", " ".repeat(indent + 2)));
+    }
+    source.push_str(&format!("{}]", " ".repeat(indent)));
+    source
+}
+
+/// Format a declaration body from formatted properties and nested declarations
+fn format_body(body: &IndexMap<String, String>, children: &[String], indent: usize) -> String {
+    if body.is_empty() && children.is_empty() {
+        return "{}".to_string();
+    }
+    let padding = " ".repeat(indent + 2);
+    let mut source = "{\n".to_string();
+    for (key, value) in body {
+        source.push_str(&format!("{padding}{key}: {value}\n"));
+    }
+    for child in children {
+        source.push('\n');
+        source.push_str(child);
+        source.push('\n');
+    }
+    source.push_str(&format!("{}}}", " ".repeat(indent)));
+    source
+}
+
+/// Wrap a declaration body in its condition and loop
+fn wrap_body(body: String, condition: Option<&str>, loop_statement: Option<&str>) -> String {
+    let body = match condition {
+        Some(condition) if condition.starts_with('(') && condition.ends_with(')') => {
+            format!("if {condition} {body}")
+        },
+        Some(condition) => format!("if ({condition}) {body}"),
+        None => body,
+    };
+    match loop_statement {
+        Some(loop_statement) => format!("[{loop_statement}: {body}]"),
+        None => body,
+    }
+}
+
+/// Format an import statement
+fn format_import(import: &BicepImport) -> String {
+    match import {
+        BicepImport::Namespace { namespace, version } => match version {
+            Some(version) => format!(
+                "import {}",
+                quote_bicep_string(&format!("{namespace}@{version}"))
+            ),
+            None => format!("import {}", quote_bicep_string(namespace)),
+        },
+        BicepImport::Module {
+            source,
+            symbols,
+            wildcard_alias,
+        } => {
+            let source = quote_bicep_string(&source.to_string());
+            match (symbols, wildcard_alias) {
+                (_, Some(alias)) => format!("import * as {alias} from {source}"),
+                (Some(symbols), None) => {
+                    let symbols = symbols
+                        .iter()
+                        .map(|symbol| match &symbol.alias {
+                            Some(alias) => format!("{} as {alias}", symbol.name),
+                            None => symbol.name.clone(),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("import {{ {symbols} }} from {source}")
+                },
+                (None, None) => format!("import * from {source}"),
+            }
+        },
+    }
+}
+
+/// Parse a Bicep file and export it as canonically formatted Bicep source in one step
+///
+/// # Arguments
+///
+/// * `source_code` - The source code of the Bicep file
+/// * `output_path` - The path where the Bicep file should be written
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn parse_and_export<P: AsRef<Path>>(
+    source_code: &str,
+    output_path: P,
+) -> Result<(), BicepError> {
+    let document = crate::parse_bicep_document(source_code)?;
+    export_to_file(&document, output_path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{BicepCustomType, BicepOutput, BicepVariable, ModuleSource};
+
+    #[test]
+    fn test_export_to_string() {
+        let mut document = BicepDocument {
+            name: Some("Key Vault".to_string()),
+            descriptions: IndexMap::from([("de".to_string(), "Schlüsseltresor".to_string())]),
+            target_scope: Some("resourceGroup".to_string()),
+            ..Default::default()
+        };
+
+        let mut properties = IndexMap::new();
+        properties.insert(
+            "defaultAction".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Union(vec!["Allow".to_string(), "Deny".to_string()]),
+                ..Default::default()
+            },
+        );
+        properties.insert(
+            "ipRules".to_string(),
+            BicepParameter {
+                description: Some("Allowed IP ranges".to_string()),
+                parameter_type: BicepType::Array(Box::new(BicepType::String)),
+                is_nullable: true,
+                ..Default::default()
+            },
+        );
+        document.types.insert(
+            "networkAclsType".to_string(),
+            BicepCustomType {
+                description: None,
+                descriptions: IndexMap::new(),
+                definition: BicepType::Object(Some(properties)),
+                discriminator: None,
+                is_exported: true,
+                is_sealed: false,
+                is_secure: false,
+            },
+        );
+
+        document.parameters.insert(
+            "prefix".to_string(),
+            BicepParameter {
+                description: Some("Prefix for resource names".to_string()),
+                descriptions: IndexMap::from([(
+                    "fr".to_string(),
+                    "Préfixe des noms de ressources".to_string(),
+                )]),
+                min_length: Some(3),
+                max_length: Some(11),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "location".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::Identifier(
+                    "resourceGroup().location".to_string(),
+                )),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "sku".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::String("standard".to_string())),
+                allowed_values: Some(vec![
+                    BicepValue::String("standard".to_string()),
+                    BicepValue::String("premium".to_string()),
+                ]),
+                ..Default::default()
+            },
+        );
+
+        document.variables.insert(
+            "vaultName".to_string(),
+            BicepVariable {
+                description: None,
+                value: BicepValue::String("${prefix}-kv".to_string()),
+                is_exported: false,
+            },
+        );
+
+        let mut sku = IndexMap::new();
+        sku.insert(
+            "name".to_string(),
+            BicepValue::Identifier("sku".to_string()),
+        );
+        let mut vault_properties = IndexMap::new();
+        vault_properties.insert(
+            "location".to_string(),
+            BicepValue::Identifier("location".to_string()),
+        );
+        let mut nested = IndexMap::new();
+        nested.insert("sku".to_string(), BicepValue::Object(sku));
+        vault_properties.insert("properties".to_string(), BicepValue::Object(nested));
+        document.resources.insert(
+            "vault".to_string(),
+            BicepResource {
+                resource_type: "Microsoft.KeyVault/vaults".to_string(),
+                api_version: "2023-07-01".to_string(),
+                name: "${vaultName}".to_string(),
+                properties: vault_properties,
+                ..Default::default()
+            },
+        );
+        document.resources.insert(
+            "vault::secret".to_string(),
+            BicepResource {
+                resource_type: "Microsoft.KeyVault/vaults/secrets".to_string(),
+                api_version: "2023-07-01".to_string(),
+                name: "admin".to_string(),
+                ..Default::default()
+            },
+        );
+
+        document.modules.insert(
+            "diagnostics".to_string(),
+            BicepModule {
+                description: None,
+                name: "diagnostics".to_string(),
+                deployment_name: Some("${prefix}-diagnostics".to_string()),
+                source: ModuleSource::LocalPath("./diagnostics.bicep".to_string()),
+                depends_on: None,
+                condition: Some("(!empty(prefix))".to_string()),
+                loop_statement: None,
+                batch_size: None,
+                params: IndexMap::new(),
+            },
+        );
+
+        document.outputs.insert(
+            "vaultId".to_string(),
+            BicepOutput {
+                description: None,
+                descriptions: IndexMap::new(),
+                output_type: BicepType::String,
+                value: BicepValue::Identifier("vault.id".to_string()),
+                discriminator: None,
+                min_length: None,
+                max_length: None,
+                min_value: None,
+                max_value: None,
+                metadata: None,
+                sealed: false,
+                secure: false,
+            },
+        );
+
+        let result = export_to_string(&document).unwrap();
+        let expected = "\
+targetScope = 'resourceGroup'
+
+metadata name = 'Key Vault'
+metadata descriptions = {
+  de: 'Schlüsseltresor'
+}
+
+@export()
+type networkAclsType = {
+  defaultAction: 'Allow' | 'Deny'
+  @description('Allowed IP ranges')
+  ipRules: string[]?
+}
+
+@description('Prefix for resource names')
+@metadata({
+  descriptions: {
+    fr: 'Préfixe des noms de ressources'
+  }
+})
+@minLength(3)
+@maxLength(11)
+param prefix string
+
+param location string = resourceGroup().location
+
+@allowed([
+  'standard'
+  'premium'
+])
+param sku string = 'standard'
+
+var vaultName = '${prefix}-kv'
+
+resource vault 'Microsoft.KeyVault/vaults@2023-07-01' = {
+  name: vaultName
+  location: location
+  properties: {
+    sku: {
+      name: sku
+    }
+  }
+
+  resource secret 'secrets' = {
+    name: 'admin'
+  }
+}
+
+module diagnostics './diagnostics.bicep' = if (!empty(prefix)) {
+  name: '${prefix}-diagnostics'
+}
+
+output vaultId string = vault.id
+";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_wrap_body() {
+        assert_eq!(
+            wrap_body("{}".to_string(), Some("deploy"), Some("for name in names")),
+            "[for name in names: if (deploy) {}]"
+        );
+    }
+
+    #[test]
+    fn test_loop_variables_in_body() {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "names".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Array(Box::new(BicepType::String)),
+                ..Default::default()
+            },
+        );
+        document.resources.insert(
+            "accounts".to_string(),
+            BicepResource {
+                resource_type: "Microsoft.Storage/storageAccounts".to_string(),
+                api_version: "2023-01-01".to_string(),
+                name: "${toLower(name)}".to_string(),
+                loop_statement: Some("for name in names".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let result = export_to_string(&document).unwrap();
+        assert!(result.contains(
+            "resource accounts 'Microsoft.Storage/storageAccounts@2023-01-01' = [for name in names: {\n  name: toLower(name)\n}]"
+        ));
+    }
+}
@@ -0,0 +1,256 @@
+/// Bicep parameters file export functionality
+///
+/// This module generates `.bicepparam` skeletons from the parameters of a parsed
+/// Bicep document. Required parameters come first with a placeholder or sample value;
+/// optional parameters follow with their default values commented in, except that
+/// the defaults of secure parameters are redacted.
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::error::BicepError;
+use crate::{
+    exports::{
+        utils::{
+            formatting::{format_bicep_source, quote_bicep_string},
+            samples::{is_required, placeholder_value, required_first, sample_value},
+        },
+        REDACTED,
+    },
+    parsing::{BicepDocument, BicepParameter},
+};
+
+/// Export a `.bicepparam` skeleton for a Bicep document to a file
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate parameters for
+/// * `output_path` - The path where the parameters file should be written
+/// * `template_path` - The path of the template for the `using` statement, relative to the output
+/// * `sample_values` - Whether to fill in sample values that satisfy each parameter's constraints
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn export_to_file<P: AsRef<Path>>(
+    document: &BicepDocument,
+    output_path: P,
+    template_path: &str,
+    sample_values: bool,
+) -> Result<(), BicepError> {
+    let mut writer =
+        BufWriter::new(File::create(&output_path).map_err(|e| BicepError::io(&output_path, e))?);
+    export_to_writer(document, &mut writer, template_path, sample_values)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Export a `.bicepparam` skeleton for a Bicep document as a string
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate parameters for
+/// * `template_path` - The path of the template for the `using` statement
+/// * `sample_values` - Whether to fill in sample values that satisfy each parameter's constraints
+///
+/// # Returns
+///
+/// A Result containing the parameters file content or an error
+pub fn export_to_string(
+    document: &BicepDocument,
+    template_path: &str,
+    sample_values: bool,
+) -> Result<String, BicepError> {
+    let mut buffer = Vec::new();
+    export_to_writer(document, &mut buffer, template_path, sample_values)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// Write a `.bicepparam` skeleton for a Bicep document
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate parameters for
+/// * `writer` - Where to write the parameters file
+/// * `template_path` - The path of the template for the `using` statement
+/// * `sample_values` - Whether to fill in sample values that satisfy each parameter's constraints
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn export_to_writer(
+    document: &BicepDocument,
+    writer: &mut dyn Write,
+    template_path: &str,
+    sample_values: bool,
+) -> Result<(), BicepError> {
+    writeln!(writer, "using '{}'", template_path.replace('\'', "\\'"))?;
+
+    let parameters = required_first(document);
+    let (required, optional): (Vec<_>, Vec<_>) = parameters
+        .into_iter()
+        .partition(|(_, parameter)| is_required(parameter));
+
+    if !required.is_empty() {
+        writeln!(writer, "\n// Required parameters")?;
+        for (name, parameter) in required {
+            writeln!(writer)?;
+            write_comments(writer, parameter)?;
+
+            let value = if sample_values {
+                sample_value(parameter, document)
+            } else {
+                placeholder_value(&parameter.parameter_type, document)
+            };
+            writeln!(writer, "param {name} = {}", format_bicep_source(&value, 0))?;
+        }
+    }
+
+    if !optional.is_empty() {
+        writeln!(writer, "\n// Optional parameters")?;
+        for (name, parameter) in optional {
+            writeln!(writer)?;
+            write_comments(writer, parameter)?;
+
+            let value = match &parameter.default_value {
+                Some(_) if parameter.is_secure => quote_bicep_string(REDACTED),
+                Some(default_value) => format_bicep_source(default_value, 0),
+                None => "null".to_string(),
+            };
+            for line in format!("param {name} = {value}").lines() {
+                writeln!(writer, "// {line}")?;
+            }
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write a parameter's description and a note for secure parameters as comments
+fn write_comments(writer: &mut dyn Write, parameter: &BicepParameter) -> io::Result<()> {
+    if let Some(description) = &parameter.description {
+        for line in description.lines() {
+            writeln!(writer, "// {line}")?;
+        }
+    }
+    if parameter.is_secure {
+        writeln!(
+            writer,
+            "// Secure value: consider az.getSecret() or readEnvironmentVariable()"
+        )?;
+    }
+    Ok(())
+}
+
+/// Parse a Bicep file and export a `.bicepparam` skeleton in one step
+///
+/// # Arguments
+///
+/// * `source_code` - The source code of the Bicep file
+/// * `output_path` - The path where the parameters file should be written
+/// * `template_path` - The path of the template for the `using` statement
+/// * `sample_values` - Whether to fill in sample values
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn parse_and_export<P: AsRef<Path>>(
+    source_code: &str,
+    output_path: P,
+    template_path: &str,
+    sample_values: bool,
+) -> Result<(), BicepError> {
+    let document = crate::parse_bicep_document(source_code)?;
+    export_to_file(&document, output_path, template_path, sample_values)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{BicepType, BicepValue};
+
+    fn sample_document() -> BicepDocument {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "location".to_string(),
+            BicepParameter {
+                description: Some("Location for all resources".to_string()),
+                default_value: Some(BicepValue::Identifier(
+                    "resourceGroup().location".to_string(),
+                )),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "sku".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::String("Standard_LRS".to_string())),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "name".to_string(),
+            BicepParameter {
+                min_length: Some(3),
+                max_length: Some(5),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "adminPassword".to_string(),
+            BicepParameter {
+                is_secure: true,
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "instances".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Int,
+                min_value: Some(2),
+                ..Default::default()
+            },
+        );
+        document
+    }
+
+    #[test]
+    fn test_export_to_string_skeleton() {
+        let document = sample_document();
+        let result = export_to_string(&document, "./main.bicep", false).unwrap();
+
+        let expected = "\
+using './main.bicep'
+
+// Required parameters
+
+param name = ''
+
+// Secure value: consider az.getSecret() or readEnvironmentVariable()
+param adminPassword = ''
+
+param instances = 0
+
+// Optional parameters
+
+// Location for all resources
+// param location = resourceGroup().location
+
+// param sku = 'Standard_LRS'
+";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_export_to_string_sample_values() {
+        let document = sample_document();
+        let result = export_to_string(&document, "main.bicep", true).unwrap();
+
+        assert!(result.contains("param name = 'sampl'\n"));
+        assert!(result.contains("param instances = 2\n"));
+    }
+}
@@ -0,0 +1,734 @@
+/// Azure portal `createUiDefinition.json` export functionality
+///
+/// This module generates a portal UI definition for publishing a Bicep template as a
+/// managed application or template spec. Each parameter becomes a UI element chosen from
+/// its type and constraints, and its value is passed back through the outputs.
+///
+/// Parameters are placed on the Basics step unless their `@metadata` has a `group` key,
+/// which names the step they are placed on. Optional parameters whose default value is
+/// not statically knowable (e.g. `resourceGroup().location`) are shown without a
+/// default value. Optional parameters left blank are passed as `null`, so that the
+/// template default applies. Validation messages are written in the language of the
+/// export.
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+
+use crate::error::BicepError;
+use crate::{
+    analysis::Evaluator,
+    exports::utils::samples::{is_required, resolve_type, union_member_value},
+    exports::{ExportOptions, Exporter},
+    localization::Language,
+    parsing::{BicepDocument, BicepParameter, BicepType, BicepValue},
+    t,
+};
+
+/// Schema of portal UI definitions
+const UI_DEFINITION_SCHEMA: &str =
+    "https://schema.management.azure.com/schemas/0.1.2-preview/CreateUIDefinition.MultiVm.json#";
+
+/// Version of the UI definition schema
+const UI_DEFINITION_VERSION: &str = "0.1.2-preview";
+
+/// Metadata key naming the step a parameter is placed on
+const GROUP_METADATA_KEY: &str = "group";
+
+/// Export a `createUiDefinition.json` for a Bicep document to a file
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate the UI definition for
+/// * `output_path` - The path where the UI definition should be written
+/// * `options` - How to write the document, such as its language and layout
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn export_to_file<P: AsRef<Path>>(
+    document: &BicepDocument,
+    output_path: P,
+    options: &ExportOptions,
+) -> Result<(), BicepError> {
+    let mut writer =
+        BufWriter::new(File::create(&output_path).map_err(|e| BicepError::io(&output_path, e))?);
+    export_to_writer(document, &mut writer, options)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Export a `createUiDefinition.json` for a Bicep document as a string
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate the UI definition for
+/// * `options` - How to write the document, such as its language and layout
+///
+/// # Returns
+///
+/// A Result containing the UI definition or an error
+pub fn export_to_string(
+    document: &BicepDocument,
+    options: &ExportOptions,
+) -> Result<String, BicepError> {
+    let mut buffer = Vec::new();
+    export_to_writer(document, &mut buffer, options)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// Write the UI definition of a Bicep document
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate the UI definition for
+/// * `writer` - Where to write the UI definition
+/// * `options` - How to write the document, such as its language and layout
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn export_to_writer(
+    document: &BicepDocument,
+    writer: &mut dyn Write,
+    options: &ExportOptions,
+) -> Result<(), BicepError> {
+    let definition = generate_ui_definition(&options.redact(document), options.language);
+    if options.pretty {
+        serde_json::to_writer_pretty(&mut *writer, &definition)?;
+    } else {
+        serde_json::to_writer(&mut *writer, &definition)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// The ui-definition format for the exporter registry
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UiDefinitionExporter;
+
+impl Exporter for UiDefinitionExporter {
+    fn name(&self) -> &str {
+        "ui-definition"
+    }
+
+    fn extension(&self) -> &str {
+        "json"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a createUiDefinition.json for deploying from the Azure portal"
+    }
+
+    fn export_to_writer(
+        &self,
+        document: &BicepDocument,
+        writer: &mut dyn Write,
+        options: &ExportOptions,
+    ) -> Result<(), BicepError> {
+        export_to_writer(document, writer, options)
+    }
+}
+
+/// Generate the portal UI definition of a Bicep document
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate the UI definition for
+/// * `language` - The language to write validation messages in
+///
+/// # Returns
+///
+/// The UI definition as a JSON value
+pub fn generate_ui_definition(document: &BicepDocument, language: Language) -> Value {
+    let evaluator = Evaluator::new(document);
+
+    let mut basics = Vec::new();
+    let mut steps: IndexMap<String, (String, Vec<Value>)> = IndexMap::new();
+    let mut outputs = Map::new();
+
+    for (name, parameter) in &document.parameters {
+        // Defaults that depend on the deployment are not shown; the template applies
+        // them when the parameter is left blank
+        let default_value = parameter
+            .default_value
+            .as_ref()
+            .and_then(|value| evaluator.evaluate_value(value));
+
+        let group = match parameter.metadata.get(GROUP_METADATA_KEY) {
+            Some(BicepValue::String(group)) if !group.trim().is_empty() => Some(group),
+            _ => None,
+        };
+        let reference = match group {
+            Some(group) => format!("steps('{}').{name}", step_name(group)),
+            None => format!("basics('{name}')"),
+        };
+
+        let element = ui_element(
+            name,
+            &reference,
+            parameter,
+            default_value.as_ref(),
+            document,
+            language,
+        );
+        let output = output_expression(
+            &reference,
+            &element,
+            &resolve_type(&parameter.parameter_type, document),
+            is_required(parameter),
+        );
+        outputs.insert(name.clone(), json!(output));
+
+        match group {
+            Some(group) => steps
+                .entry(step_name(group))
+                .or_insert_with(|| (group.clone(), Vec::new()))
+                .1
+                .push(element),
+            None => basics.push(element),
+        }
+    }
+
+    let steps = steps
+        .into_iter()
+        .map(|(name, (label, elements))| {
+            json!({
+                "name": name,
+                "label": label,
+                "elements": elements,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": UI_DEFINITION_SCHEMA,
+        "handler": "Microsoft.Azure.CreateUIDef",
+        "version": UI_DEFINITION_VERSION,
+        "parameters": {
+            "basics": basics,
+            "steps": steps,
+            "outputs": outputs,
+        },
+    })
+}
+
+/// Build the UI element for a parameter
+///
+/// The reference is the expression that reads the element's value, such as
+/// `basics('name')`, for validations that check it.
+fn ui_element(
+    name: &str,
+    reference: &str,
+    parameter: &BicepParameter,
+    default_value: Option<&BicepValue>,
+    document: &BicepDocument,
+    language: Language,
+) -> Value {
+    let required = is_required(parameter);
+    let mut element = Map::new();
+    element.insert("name".to_string(), json!(name));
+    element.insert("label".to_string(), json!(label(name)));
+    if let Some(description) = &parameter.description {
+        element.insert("toolTip".to_string(), json!(description));
+    }
+
+    let resolved_type = resolve_type(&parameter.parameter_type, document);
+    let allowed_values = match (&parameter.allowed_values, &resolved_type) {
+        (Some(values), _) => Some(values.clone()),
+        (None, BicepType::Union(members)) => {
+            Some(members.iter().map(|m| union_member_value(m)).collect())
+        },
+        _ => None,
+    };
+
+    if let Some(allowed_values) = allowed_values {
+        element.insert("type".to_string(), json!("Microsoft.Common.DropDown"));
+        let options = allowed_values
+            .iter()
+            .map(|value| json!({ "label": display_value(value), "value": value }))
+            .collect::<Vec<_>>();
+        if let Some(default_value) = default_value {
+            element.insert(
+                "defaultValue".to_string(),
+                json!(display_value(default_value)),
+            );
+        }
+        element.insert(
+            "constraints".to_string(),
+            json!({ "allowedValues": options, "required": required }),
+        );
+        return Value::Object(element);
+    }
+
+    match resolved_type {
+        BicepType::Bool => {
+            element.insert("type".to_string(), json!("Microsoft.Common.CheckBox"));
+            if let Some(default_value) = default_value {
+                element.insert("defaultValue".to_string(), json!(default_value));
+            }
+            element.insert("constraints".to_string(), json!({ "required": false }));
+        },
+        BicepType::Int => match (parameter.min_value, parameter.max_value) {
+            (Some(min), Some(max)) => {
+                element.insert("type".to_string(), json!("Microsoft.Common.Slider"));
+                element.insert("min".to_string(), json!(min));
+                element.insert("max".to_string(), json!(max));
+                element.insert("showStepMarkers".to_string(), json!(false));
+                let default_value = match default_value {
+                    Some(BicepValue::Int(value)) => *value,
+                    _ => min,
+                };
+                element.insert("defaultValue".to_string(), json!(default_value));
+                element.insert("constraints".to_string(), json!({ "required": required }));
+            },
+            (min, max) => {
+                element.insert("type".to_string(), json!("Microsoft.Common.TextBox"));
+                if let Some(default_value) = default_value {
+                    element.insert(
+                        "defaultValue".to_string(),
+                        json!(display_value(default_value)),
+                    );
+                }
+                let locale = language.code();
+                let mut constraints = json!({
+                    "required": required,
+                    "regex": "^-?[0-9]+$",
+                    "validationMessage": t!("ui_definition.whole_number", locale = locale),
+                });
+                // A blank value is read as the bound itself, so that only a number out
+                // of bounds fails
+                let validation = match (min, max) {
+                    (Some(min), _) => Some(json!({
+                        "isValid": format!(
+                            "[greaterOrEquals(int(if(empty({reference}), '{min}', {reference})), {min})]"
+                        ),
+                        "message": t!("ui_definition.whole_number_min", locale = locale, min = min),
+                    })),
+                    (_, Some(max)) => Some(json!({
+                        "isValid": format!(
+                            "[lessOrEquals(int(if(empty({reference}), '{max}', {reference})), {max})]"
+                        ),
+                        "message": t!("ui_definition.whole_number_max", locale = locale, max = max),
+                    })),
+                    _ => None,
+                };
+                if let Some(validation) = validation {
+                    constraints["validations"] = json!([validation]);
+                }
+                element.insert("constraints".to_string(), constraints);
+            },
+        },
+        BicepType::String if parameter.is_secure => {
+            element.insert("type".to_string(), json!("Microsoft.Common.PasswordBox"));
+            element.insert(
+                "label".to_string(),
+                json!({
+                    "password": label(name),
+                    "confirmPassword": t!(
+                        "ui_definition.confirm",
+                        locale = language.code(),
+                        label = label(name).to_lowercase()
+                    ),
+                }),
+            );
+            let mut constraints = length_constraints(parameter, language);
+            constraints.insert("required".to_string(), json!(required));
+            element.insert("constraints".to_string(), Value::Object(constraints));
+            element.insert("options".to_string(), json!({ "hideConfirmation": false }));
+        },
+        BicepType::Array(_) | BicepType::Object(_) => {
+            element.insert("type".to_string(), json!("Microsoft.Common.TextBox"));
+            element.insert("multiLine".to_string(), json!(true));
+            if let Some(default_value) = default_value {
+                element.insert(
+                    "defaultValue".to_string(),
+                    json!(serde_json::to_string(default_value).unwrap_or_default()),
+                );
+            }
+            element.insert("constraints".to_string(), json!({ "required": required }));
+        },
+        _ => {
+            element.insert("type".to_string(), json!("Microsoft.Common.TextBox"));
+            if let Some(default_value) = default_value {
+                element.insert(
+                    "defaultValue".to_string(),
+                    json!(display_value(default_value)),
+                );
+            }
+            let mut constraints = length_constraints(parameter, language);
+            constraints.insert("required".to_string(), json!(required));
+            element.insert("constraints".to_string(), Value::Object(constraints));
+        },
+    }
+
+    Value::Object(element)
+}
+
+/// Build the output expression that passes an element's value to its parameter
+///
+/// Text boxes hold text, so numbers, arrays and objects are parsed from it. An
+/// optional parameter left blank is passed as `null`, so that the template default
+/// applies instead of an empty value.
+///
+/// # Arguments
+///
+/// * `reference` - The expression that reads the element's value
+/// * `element` - The UI element of the parameter
+/// * `parameter_type` - The resolved type of the parameter
+/// * `required` - Whether the parameter must be given a value
+fn output_expression(
+    reference: &str,
+    element: &Value,
+    parameter_type: &BicepType,
+    required: bool,
+) -> String {
+    let is_text = matches!(
+        element["type"].as_str(),
+        Some("Microsoft.Common.TextBox" | "Microsoft.Common.PasswordBox")
+    );
+    if !is_text {
+        return format!("[{reference}]");
+    }
+
+    let is_parsed = matches!(
+        parameter_type,
+        BicepType::Int | BicepType::Array(_) | BicepType::Object(_)
+    );
+    match (is_parsed, required) {
+        (false, true) => format!("[{reference}]"),
+        (false, false) => format!("[if(empty({reference}), parse('null'), {reference})]"),
+        (true, true) if matches!(parameter_type, BicepType::Int) => format!("[int({reference})]"),
+        (true, true) => format!("[parse({reference})]"),
+        // Parse the text only when there is some, since `parse('')` fails
+        (true, false) => format!("[parse(if(empty({reference}), 'null', {reference}))]"),
+    }
+}
+
+/// Build the regex constraint for a parameter's length bounds
+fn length_constraints(parameter: &BicepParameter, language: Language) -> Map<String, Value> {
+    let locale = language.code();
+    let mut constraints = Map::new();
+    let (regex, message) = match (parameter.min_length, parameter.max_length) {
+        (Some(min), Some(max)) => (
+            format!("^.{{{min},{max}}}$"),
+            t!(
+                "ui_definition.length_between",
+                locale = locale,
+                min = min,
+                max = max
+            ),
+        ),
+        (Some(min), None) => (
+            format!("^.{{{min},}}$"),
+            t!("ui_definition.length_min", locale = locale, min = min),
+        ),
+        (None, Some(max)) => (
+            format!("^.{{0,{max}}}$"),
+            t!("ui_definition.length_max", locale = locale, max = max),
+        ),
+        (None, None) => return constraints,
+    };
+    constraints.insert("regex".to_string(), json!(regex));
+    constraints.insert("validationMessage".to_string(), json!(message));
+    constraints
+}
+
+/// Format a value as the text shown in the portal
+fn display_value(value: &BicepValue) -> String {
+    match value {
+        BicepValue::String(text) | BicepValue::Identifier(text) => text.clone(),
+        other => serde_json::to_string(other).unwrap_or_default(),
+    }
+}
+
+/// Turn a camelCase parameter name into a label, e.g. `storageAccountName` into
+/// `Storage account name`
+fn label(name: &str) -> String {
+    let mut label = String::with_capacity(name.len() + 4);
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        match previous {
+            None => label.extend(c.to_uppercase()),
+            Some(p) if c.is_uppercase() && p.is_lowercase() => {
+                label.push(' ');
+                label.extend(c.to_lowercase());
+            },
+            Some(_) if c == '_' || c == '-' => label.push(' '),
+            Some(_) => label.push(c),
+        }
+        previous = Some(c);
+    }
+    label
+}
+
+/// Derive a step name from a group label, e.g. `Network settings` into `networkSettings`
+fn step_name(group: &str) -> String {
+    let mut name = String::with_capacity(group.len());
+    for (index, word) in group
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .enumerate()
+    {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if index == 0 {
+                name.extend(first.to_lowercase());
+            } else {
+                name.extend(first.to_uppercase());
+            }
+            name.push_str(chars.as_str());
+        }
+    }
+    name
+}
+
+/// Parse a Bicep file and export a `createUiDefinition.json` in one step
+///
+/// # Arguments
+///
+/// * `source_code` - The source code of the Bicep file
+/// * `output_path` - The path where the UI definition should be written
+/// * `pretty` - Whether to format the JSON with indentation for readability
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn parse_and_export<P: AsRef<Path>>(
+    source_code: &str,
+    output_path: P,
+    pretty: bool,
+) -> Result<(), BicepError> {
+    let document = crate::parse_bicep_document(source_code)?;
+    export_to_file(
+        &document,
+        output_path,
+        &ExportOptions::builder().pretty(pretty).build(),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_and_step_name() {
+        assert_eq!(label("storageAccountName"), "Storage account name");
+        assert_eq!(label("vm_size"), "Vm size");
+        assert_eq!(step_name("Network settings"), "networkSettings");
+        assert_eq!(step_name("  Monitoring  "), "monitoring");
+    }
+
+    #[test]
+    fn test_generate_ui_definition() {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "location".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::Identifier(
+                    "resourceGroup().location".to_string(),
+                )),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "name".to_string(),
+            BicepParameter {
+                description: Some("Name of the app".to_string()),
+                min_length: Some(3),
+                max_length: Some(24),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "sku".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::String("S1".to_string())),
+                allowed_values: Some(vec![
+                    BicepValue::String("S1".to_string()),
+                    BicepValue::String("P1".to_string()),
+                ]),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "adminPassword".to_string(),
+            BicepParameter {
+                is_secure: true,
+                ..Default::default()
+            },
+        );
+        let mut metadata = IndexMap::new();
+        metadata.insert(
+            GROUP_METADATA_KEY.to_string(),
+            BicepValue::String("Scale settings".to_string()),
+        );
+        document.parameters.insert(
+            "instances".to_string(),
+            BicepParameter {
+                metadata: metadata.clone(),
+                parameter_type: BicepType::Int,
+                min_value: Some(1),
+                max_value: Some(10),
+                default_value: Some(BicepValue::Int(2)),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "autoscale".to_string(),
+            BicepParameter {
+                metadata,
+                parameter_type: BicepType::Bool,
+                default_value: Some(BicepValue::Bool(true)),
+                ..Default::default()
+            },
+        );
+
+        let definition = generate_ui_definition(&document, Language::English);
+        let parameters = &definition["parameters"];
+
+        let basics = parameters["basics"].as_array().unwrap();
+        assert_eq!(basics.len(), 4);
+        // A default that depends on the deployment is not shown
+        assert_eq!(basics[0]["name"], "location");
+        assert!(basics[0].get("defaultValue").is_none());
+        assert_eq!(basics[1]["type"], "Microsoft.Common.TextBox");
+        assert_eq!(basics[1]["toolTip"], "Name of the app");
+        assert_eq!(basics[1]["constraints"]["regex"], "^.{3,24}$");
+        assert_eq!(
+            basics[1]["constraints"]["validationMessage"],
+            "Must be between 3 and 24 characters long."
+        );
+        assert_eq!(basics[1]["constraints"]["required"], true);
+        assert_eq!(basics[2]["type"], "Microsoft.Common.DropDown");
+        assert_eq!(basics[2]["defaultValue"], "S1");
+        assert_eq!(
+            basics[2]["constraints"]["allowedValues"][1],
+            json!({ "label": "P1", "value": "P1" })
+        );
+        assert_eq!(basics[3]["type"], "Microsoft.Common.PasswordBox");
+        assert_eq!(
+            basics[3]["label"]["confirmPassword"],
+            "Confirm admin password"
+        );
+
+        let steps = parameters["steps"].as_array().unwrap();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0]["name"], "scaleSettings");
+        assert_eq!(steps[0]["label"], "Scale settings");
+        assert_eq!(steps[0]["elements"][0]["type"], "Microsoft.Common.Slider");
+        assert_eq!(steps[0]["elements"][0]["defaultValue"], 2);
+        assert_eq!(steps[0]["elements"][1]["type"], "Microsoft.Common.CheckBox");
+
+        // Blank optional parameters are passed as null, so the template default applies
+        let outputs = &parameters["outputs"];
+        assert_eq!(
+            outputs["location"],
+            "[if(empty(basics('location')), parse('null'), basics('location'))]"
+        );
+        assert_eq!(outputs["name"], "[basics('name')]");
+        assert_eq!(outputs["sku"], "[basics('sku')]");
+        assert_eq!(outputs["instances"], "[steps('scaleSettings').instances]");
+    }
+
+    #[test]
+    fn test_generate_ui_definition_parsed_outputs() {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "count".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Int,
+                min_value: Some(1),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "retries".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Int,
+                max_value: Some(5),
+                default_value: Some(BicepValue::Int(3)),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "tags".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Object(None),
+                default_value: Some(BicepValue::Identifier("resourceGroup().tags".to_string())),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "zones".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Array(Box::new(BicepType::Int)),
+                ..Default::default()
+            },
+        );
+
+        let definition = generate_ui_definition(&document, Language::English);
+        let parameters = &definition["parameters"];
+        let basics = parameters["basics"].as_array().unwrap();
+
+        // Bounds of a number entered as text are checked by validations
+        assert_eq!(
+            basics[0]["constraints"]["validations"],
+            json!([{
+                "isValid": "[greaterOrEquals(int(if(empty(basics('count')), '1', basics('count'))), 1)]",
+                "message": "Must be a whole number of at least 1.",
+            }])
+        );
+        assert_eq!(
+            basics[1]["constraints"]["validations"][0]["isValid"],
+            "[lessOrEquals(int(if(empty(basics('retries')), '5', basics('retries'))), 5)]"
+        );
+
+        let outputs = &parameters["outputs"];
+        assert_eq!(outputs["count"], "[int(basics('count'))]");
+        assert_eq!(
+            outputs["retries"],
+            "[parse(if(empty(basics('retries')), 'null', basics('retries')))]"
+        );
+        assert_eq!(
+            outputs["tags"],
+            "[parse(if(empty(basics('tags')), 'null', basics('tags')))]"
+        );
+        assert_eq!(outputs["zones"], "[parse(basics('zones'))]");
+    }
+
+    #[test]
+    fn test_generate_ui_definition_localized() {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "name".to_string(),
+            BicepParameter {
+                min_length: Some(3),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "count".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Int,
+                ..Default::default()
+            },
+        );
+
+        let definition = generate_ui_definition(&document, Language::French);
+        let basics = definition["parameters"]["basics"].as_array().unwrap();
+        assert_eq!(
+            basics[0]["constraints"]["validationMessage"],
+            "Doit contenir au moins 3 caractères."
+        );
+        assert_eq!(
+            basics[1]["constraints"]["validationMessage"],
+            "Doit être un nombre entier."
+        );
+    }
+}
@@ -0,0 +1,268 @@
+/// The interface shared by every documentation format
+///
+/// An [`Exporter`] turns a parsed [`BicepDocument`] into the text of one format,
+/// configured by a shared set of [`ExportOptions`]. Exporters are looked up by name
+/// in an [`ExporterRegistry`](super::ExporterRegistry), which is how the command line
+/// finds both the built-in formats and any registered by other crates.
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::error::BicepError;
+use crate::localization::Language;
+use crate::parsing::{BicepDocument, BicepValue, ModuleSource};
+
+/// The value written in place of the default value of a secure parameter
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Options shared by all exporters
+///
+/// Exporters ignore options that do not apply to their format. Create options with
+/// [`ExportOptions::builder`] so that new options can be added without breaking callers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExportOptions {
+    /// Whether to use emoji symbols (✅/❌) for Yes/No values
+    pub use_emoji: bool,
+    /// Whether to exclude empty sections from the output
+    pub exclude_empty: bool,
+    /// Whether to show "Used By" and "Depends On" details for each symbol
+    pub show_references: bool,
+    /// Whether to indent structured output such as JSON
+    pub pretty: bool,
+    /// The language to write labels and headings in
+    pub language: Language,
+    /// Where consumers reference the template from, to add a usage section with a
+    /// module snippet and a parameters file
+    pub module_source: Option<ModuleSource>,
+    /// Whether documentation formats write the default values of secure parameters
+    /// instead of [`REDACTED`]
+    pub show_secure_defaults: bool,
+}
+
+impl ExportOptions {
+    /// Start building a set of options, with every option off and labels in English
+    pub fn builder() -> ExportOptionsBuilder {
+        ExportOptionsBuilder::default()
+    }
+
+    /// The document as it should be exported with these options
+    ///
+    /// Unless [`show_secure_defaults`](Self::show_secure_defaults) is set, the default
+    /// values of secure parameters are replaced with [`REDACTED`], so that secrets
+    /// written into a template do not spread to its documentation. Every built-in
+    /// documentation format writes the document this returns, including through the
+    /// `export_to_*` functions of its module. The Bicep, JSON and YAML formats write
+    /// the values as they are, since their output is read back as a template.
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The document to export
+    pub fn redact<'a>(&self, document: &'a BicepDocument) -> Cow<'a, BicepDocument> {
+        let has_secure_default = document
+            .parameters
+            .values()
+            .any(|parameter| parameter.is_secure && parameter.default_value.is_some());
+        if self.show_secure_defaults || !has_secure_default {
+            return Cow::Borrowed(document);
+        }
+
+        let mut redacted = document.clone();
+        for parameter in redacted.parameters.values_mut() {
+            if parameter.is_secure && parameter.default_value.is_some() {
+                parameter.default_value = Some(BicepValue::String(REDACTED.to_string()));
+            }
+        }
+        Cow::Owned(redacted)
+    }
+}
+
+/// Builder for [`ExportOptions`]
+#[derive(Clone, Debug, Default)]
+pub struct ExportOptionsBuilder {
+    options: ExportOptions,
+}
+
+impl ExportOptionsBuilder {
+    /// Use emoji symbols (✅/❌) for Yes/No values
+    pub fn use_emoji(mut self, use_emoji: bool) -> Self {
+        self.options.use_emoji = use_emoji;
+        self
+    }
+
+    /// Exclude empty sections from the output
+    pub fn exclude_empty(mut self, exclude_empty: bool) -> Self {
+        self.options.exclude_empty = exclude_empty;
+        self
+    }
+
+    /// Show "Used By" and "Depends On" details for each symbol
+    pub fn show_references(mut self, show_references: bool) -> Self {
+        self.options.show_references = show_references;
+        self
+    }
+
+    /// Indent structured output such as JSON
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.options.pretty = pretty;
+        self
+    }
+
+    /// Write labels and headings in a language
+    pub fn language(mut self, language: Language) -> Self {
+        self.options.language = language;
+        self
+    }
+
+    /// Add a usage section that references the template from a module source
+    pub fn module_source(mut self, module_source: ModuleSource) -> Self {
+        self.options.module_source = Some(module_source);
+        self
+    }
+
+    /// Write the default values of secure parameters instead of redacting them
+    pub fn show_secure_defaults(mut self, show_secure_defaults: bool) -> Self {
+        self.options.show_secure_defaults = show_secure_defaults;
+        self
+    }
+
+    /// Finish building the options
+    pub fn build(self) -> ExportOptions {
+        self.options
+    }
+}
+
+/// A documentation format
+pub trait Exporter: Send + Sync {
+    /// The name the format is selected by on the command line, such as `markdown`
+    fn name(&self) -> &str;
+
+    /// The extension of files written in this format, without a leading dot
+    fn extension(&self) -> &str;
+
+    /// A short description of the format for help output
+    fn description(&self) -> &str;
+
+    /// Write a Bicep document to a writer
+    ///
+    /// Output is written as it is generated, so pass a buffered writer when writing to
+    /// a file or socket.
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The BicepDocument to export
+    /// * `writer` - Where to write the exported document
+    /// * `options` - The options to export with
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure of the export operation
+    fn export_to_writer(
+        &self,
+        document: &BicepDocument,
+        writer: &mut dyn Write,
+        options: &ExportOptions,
+    ) -> Result<(), BicepError>;
+
+    /// Export a Bicep document to a string
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The BicepDocument to export
+    /// * `options` - The options to export with
+    ///
+    /// # Returns
+    ///
+    /// Result containing the exported document
+    fn export_to_string(
+        &self,
+        document: &BicepDocument,
+        options: &ExportOptions,
+    ) -> Result<String, BicepError> {
+        let mut buffer = Vec::new();
+        self.export_to_writer(document, &mut buffer, options)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+
+    /// Export a Bicep document to a file
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The BicepDocument to export
+    /// * `file_path` - Path where the file should be written
+    /// * `options` - The options to export with
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure of the export operation
+    fn export_to_file(
+        &self,
+        document: &BicepDocument,
+        file_path: &Path,
+        options: &ExportOptions,
+    ) -> Result<(), BicepError> {
+        let mut writer =
+            BufWriter::new(File::create(file_path).map_err(|e| BicepError::io(file_path, e))?);
+        self.export_to_writer(document, &mut writer, options)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_options_builder() {
+        assert_eq!(ExportOptions::builder().build(), ExportOptions::default());
+
+        let options = ExportOptions::builder()
+            .use_emoji(true)
+            .show_references(true)
+            .pretty(true)
+            .language(Language::French)
+            .build();
+        assert!(options.use_emoji);
+        assert!(!options.exclude_empty);
+        assert!(options.show_references);
+        assert!(options.pretty);
+        assert_eq!(options.language, Language::French);
+        assert_eq!(options.module_source, None);
+
+        let source = ModuleSource::parse("br/public:avm/res/storage:0.9.0").unwrap();
+        let options = ExportOptions::builder()
+            .module_source(source.clone())
+            .build();
+        assert_eq!(options.module_source, Some(source));
+    }
+
+    #[test]
+    fn test_redact_secure_defaults() {
+        let mut document = BicepDocument::default();
+        for (name, is_secure) in [("adminPassword", true), ("location", false)] {
+            document.parameters.insert(
+                name.to_string(),
+                crate::parsing::BicepParameter {
+                    is_secure,
+                    default_value: Some(BicepValue::String("value".to_string())),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let redacted = ExportOptions::default().redact(&document);
+        assert_eq!(
+            redacted.parameters["adminPassword"].default_value,
+            Some(BicepValue::String(REDACTED.to_string()))
+        );
+        assert_eq!(
+            redacted.parameters["location"].default_value,
+            Some(BicepValue::String("value".to_string()))
+        );
+
+        let options = ExportOptions::builder().show_secure_defaults(true).build();
+        assert!(matches!(options.redact(&document), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,322 @@
+/// The versioned document format of the JSON and YAML exports
+///
+/// The JSON and YAML exports write the document model with a `schemaVersion` field,
+/// and read back into an identical [`BicepDocument`], so that they can be passed
+/// between tools. The format is described by the JSON Schema in [`DOCUMENT_SCHEMA`].
+///
+/// The minor version changes when fields are added, and the major version when the
+/// meaning of existing fields changes. Documents are read if their major version is
+/// the one this release writes.
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BicepError, Span};
+use crate::parsing::BicepDocument;
+
+/// The version of the document format written by this release
+pub const SCHEMA_VERSION: &str = "2.0";
+
+/// The JSON Schema of the document format
+pub const DOCUMENT_SCHEMA: &str = include_str!("../../schemas/bicep-docs.schema.json");
+
+/// A document as it is written, with the format version first
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VersionedDocument<'a> {
+    schema_version: &'static str,
+    #[serde(flatten)]
+    document: &'a BicepDocument,
+}
+
+impl<'a> VersionedDocument<'a> {
+    /// Wrap a document to be written with the current format version
+    pub(crate) fn new(document: &'a BicepDocument) -> Self {
+        VersionedDocument {
+            schema_version: SCHEMA_VERSION,
+            document,
+        }
+    }
+}
+
+/// A document as it is read
+///
+/// Documents written before the format was versioned have no `schemaVersion`, and
+/// are read as the current version.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReadDocument {
+    schema_version: Option<String>,
+    #[serde(flatten)]
+    document: BicepDocument,
+}
+
+impl ReadDocument {
+    /// Check the format version and unwrap the document
+    fn into_document(self) -> Result<BicepDocument, BicepError> {
+        if let Some(version) = &self.schema_version {
+            let major = |version: &str| version.split('.').next().map(str::to_string);
+            if major(version) != major(SCHEMA_VERSION) {
+                return Err(BicepError::InvalidValue {
+                    kind: "schema version".to_string(),
+                    reason: format!(
+                        "Documents with schema version {version} cannot be read; this release reads version {SCHEMA_VERSION}"
+                    ),
+                    span: None,
+                });
+            }
+        }
+        Ok(self.document)
+    }
+}
+
+/// Read a document from the output of the JSON export
+///
+/// # Arguments
+///
+/// * `source` - The JSON written by the JSON export
+///
+/// # Returns
+///
+/// A Result containing the document, or an error if the JSON is not a document of a
+/// supported schema version
+pub fn from_json(source: &str) -> Result<BicepDocument, BicepError> {
+    serde_json::from_str::<ReadDocument>(source)?.into_document()
+}
+
+/// Read a document from the output of the YAML export
+///
+/// # Arguments
+///
+/// * `source` - The YAML written by the YAML export
+///
+/// # Returns
+///
+/// A Result containing the document, or an error if the YAML is not a document of a
+/// supported schema version
+pub fn from_yaml(source: &str) -> Result<BicepDocument, BicepError> {
+    let document = serde_yaml::from_str::<ReadDocument>(source).map_err(|e| {
+        let span = e
+            .location()
+            .map(|location| Span::at(location.line(), location.column()));
+        BicepError::Syntax {
+            message: e.to_string(),
+            span,
+        }
+    })?;
+    document.into_document()
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+    use crate::exports::{json, yaml, ExportOptions};
+    use crate::parsing::{
+        BicepCustomType, BicepImport, BicepImportSymbol, BicepModule, BicepOutput, BicepParameter,
+        BicepType, BicepValue, BicepVariable, ModuleSource,
+    };
+
+    /// A document with the values that do not survive a naive round trip
+    fn tricky_document() -> BicepDocument {
+        let mut document = BicepDocument {
+            name: Some("Tricky".to_string()),
+            description: Some("Line one\nLine two\n".to_string()),
+            descriptions: IndexMap::from([("fr".to_string(), "Ligne un\n".to_string())]),
+            ..Default::default()
+        };
+
+        let mut properties = IndexMap::new();
+        properties.insert(
+            "name".to_string(),
+            BicepParameter {
+                description: Some("  indented\nsecond line".to_string()),
+                ..Default::default()
+            },
+        );
+        document.types.insert(
+            "rules".to_string(),
+            BicepCustomType {
+                description: None,
+                descriptions: IndexMap::new(),
+                definition: BicepType::Array(Box::new(BicepType::Object(Some(properties)))),
+                discriminator: None,
+                is_exported: true,
+                is_sealed: false,
+                is_secure: false,
+            },
+        );
+        document.types.insert(
+            "single".to_string(),
+            BicepCustomType {
+                description: None,
+                descriptions: IndexMap::new(),
+                definition: BicepType::Union(vec!["'only'".to_string()]),
+                discriminator: None,
+                is_exported: false,
+                is_sealed: false,
+                is_secure: false,
+            },
+        );
+        document.types.insert(
+            "named".to_string(),
+            BicepCustomType {
+                description: None,
+                descriptions: IndexMap::new(),
+                definition: BicepType::CustomType("object".to_string()),
+                discriminator: Some("kind".to_string()),
+                is_exported: false,
+                is_sealed: true,
+                is_secure: false,
+            },
+        );
+
+        document.parameters.insert(
+            "tier".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Array(Box::new(BicepType::Union(vec![
+                    "'a'".to_string(),
+                    "'b'".to_string(),
+                ]))),
+                default_value: Some(BicepValue::Array(vec![BicepValue::String("a".to_string())])),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "location".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::Identifier(
+                    "resourceGroup().location".to_string(),
+                )),
+                descriptions: IndexMap::from([(
+                    "pt-BR".to_string(),
+                    "Região dos recursos".to_string(),
+                )]),
+                ..Default::default()
+            },
+        );
+
+        document.variables.insert(
+            "settings".to_string(),
+            BicepVariable {
+                description: None,
+                value: BicepValue::Object(IndexMap::from([
+                    // Strings that look like the expressions of earlier versions
+                    (
+                        "literal".to_string(),
+                        BicepValue::String("{reference:x}".to_string()),
+                    ),
+                    (
+                        "reserved".to_string(),
+                        BicepValue::Object(IndexMap::from([(
+                            "$reference".to_string(),
+                            BicepValue::Identifier("x".to_string()),
+                        )])),
+                    ),
+                    ("count".to_string(), BicepValue::Int(-3)),
+                    ("enabled".to_string(), BicepValue::Bool(true)),
+                    (
+                        "region".to_string(),
+                        BicepValue::Identifier("location".to_string()),
+                    ),
+                ])),
+                is_exported: false,
+            },
+        );
+
+        document.imports.push(BicepImport::Module {
+            source: ModuleSource::Registry {
+                alias: None,
+                registry_fqdn: Some("contoso.azurecr.io".to_string()),
+                path: "/bicep/types".to_string(),
+                version: "v1".to_string(),
+            },
+            symbols: Some(vec![BicepImportSymbol {
+                name: "ruleType".to_string(),
+                alias: Some("rule".to_string()),
+            }]),
+            wildcard_alias: None,
+        });
+        document.modules.insert(
+            "spec".to_string(),
+            BicepModule {
+                description: None,
+                name: "spec".to_string(),
+                deployment_name: Some("${toLower('spec')}".to_string()),
+                source: ModuleSource::TypeSpec {
+                    alias: None,
+                    subscription_id: Some("00000000".to_string()),
+                    resource_group_name: Some("specs".to_string()),
+                    template_spec_name: "storage".to_string(),
+                    version: "2.0".to_string(),
+                },
+                depends_on: None,
+                condition: None,
+                loop_statement: None,
+                batch_size: None,
+                params: IndexMap::new(),
+            },
+        );
+
+        document.outputs.insert(
+            "names".to_string(),
+            BicepOutput {
+                description: None,
+                descriptions: IndexMap::new(),
+                output_type: BicepType::Array(Box::new(BicepType::String)),
+                value: BicepValue::Identifier("names".to_string()),
+                discriminator: None,
+                min_length: None,
+                max_length: None,
+                min_value: None,
+                max_value: None,
+                metadata: None,
+                sealed: false,
+                secure: false,
+            },
+        );
+        document
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let document = tricky_document();
+        let json =
+            json::export_to_string(&document, &ExportOptions::builder().pretty(true).build())
+                .unwrap();
+        assert!(json.starts_with("{\n  \"schemaVersion\": \"2.0\""));
+        assert!(json.contains("\"$union\": ["));
+        assert!(json.contains("\"$reference\": \"resourceGroup().location\""));
+        assert_eq!(from_json(&json).unwrap(), document);
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let document = tricky_document();
+        let yaml = yaml::export_to_string(&document, &ExportOptions::default()).unwrap();
+        assert!(yaml.starts_with("schemaVersion: '2.0'"));
+        assert_eq!(from_yaml(&yaml).unwrap(), document, "yaml:\n{yaml}");
+    }
+
+    #[test]
+    fn test_unversioned_and_unsupported_documents() {
+        let document = from_json(r#"{ "parameters": { "name": { "type": "string[]", "optional": false, "sealed": false, "secure": false } } }"#).unwrap();
+        assert_eq!(
+            document.parameters["name"].parameter_type,
+            BicepType::Array(Box::new(BicepType::String))
+        );
+
+        // Version 1 held expressions as strings of the form {reference:<expression>}
+        let error = from_json(r#"{ "schemaVersion": "1.2" }"#).unwrap_err();
+        assert_eq!(error.code(), "BD1003");
+    }
+
+    #[test]
+    fn test_document_schema() {
+        let schema: serde_json::Value = serde_json::from_str(DOCUMENT_SCHEMA).unwrap();
+        let pattern = schema["properties"]["schemaVersion"]["pattern"]
+            .as_str()
+            .unwrap();
+        let major = SCHEMA_VERSION.split('.').next().unwrap();
+        assert_eq!(pattern, format!("^{major}\\.[0-9]+$"));
+    }
+}
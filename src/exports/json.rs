@@ -2,9 +2,17 @@
 ///
 /// This module provides functions to export parsed Bicep documents
 /// to JSON format with support for both compact and pretty-printed output.
-use std::error::Error;
-use std::{fs::File, io::Write, path::Path};
-
+/// The output is the versioned document format described in
+/// [`interchange`](super::interchange), and can be read back with
+/// [`from_json`](super::interchange::from_json).
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use crate::error::BicepError;
+use crate::exports::{interchange::VersionedDocument, ExportOptions, Exporter};
 use crate::parsing::BicepDocument;
 
 /// Export a parsed Bicep document as JSON to a file
@@ -13,8 +21,7 @@ use crate::parsing::BicepDocument;
 ///
 /// * `document` - The BicepDocument to export
 /// * `output_path` - The path where the JSON file should be written
-/// * `pretty` - Whether to format the JSON with indentation for readability
-/// * `exclude_empty` - Whether to exclude empty sections from the output
+/// * `options` - How to write the document, such as its language and layout
 ///
 /// # Returns
 ///
@@ -22,12 +29,12 @@ use crate::parsing::BicepDocument;
 pub fn export_to_file<P: AsRef<Path>>(
     document: &BicepDocument,
     output_path: P,
-    pretty: bool,
-    exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
-    let json = export_to_string(document, pretty, exclude_empty)?;
-    let mut file = File::create(output_path)?;
-    file.write_all(json.as_bytes())?;
+    options: &ExportOptions,
+) -> Result<(), BicepError> {
+    let mut writer =
+        BufWriter::new(File::create(&output_path).map_err(|e| BicepError::io(&output_path, e))?);
+    export_to_writer(document, &mut writer, options)?;
+    writer.flush()?;
     Ok(())
 }
 
@@ -36,30 +43,74 @@ pub fn export_to_file<P: AsRef<Path>>(
 /// # Arguments
 ///
 /// * `document` - The BicepDocument to export
-/// * `pretty` - Whether to format the JSON with indentation for readability
-/// * `exclude_empty` - Whether to exclude empty sections from the output
+/// * `options` - How to write the document, such as its language and layout
 ///
 /// # Returns
 ///
 /// A Result containing the JSON string or an error
 pub fn export_to_string(
     document: &BicepDocument,
-    pretty: bool,
-    _exclude_empty: bool,
-) -> Result<String, Box<dyn Error>> {
-    // Note: exclude_empty parameter is kept for API consistency with other exporters
-    // The BicepDocument already has serde attributes that handle skipping empty collections
-    let json = if pretty {
-        serde_json::to_string_pretty(document)?
+    options: &ExportOptions,
+) -> Result<String, BicepError> {
+    let mut buffer = Vec::new();
+    export_to_writer(document, &mut buffer, options)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// Write a parsed Bicep document as JSON
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to export
+/// * `writer` - Where to write the JSON
+/// * `options` - How to write the document, such as its language and layout
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn export_to_writer(
+    document: &BicepDocument,
+    writer: &mut dyn Write,
+    options: &ExportOptions,
+) -> Result<(), BicepError> {
+    // Note: exclude_empty is not needed, as the BicepDocument already has serde
+    // attributes that handle skipping empty collections. Secure defaults are not
+    // redacted, so that the document reads back as it was.
+    let document = VersionedDocument::new(document);
+    if options.pretty {
+        serde_json::to_writer_pretty(&mut *writer, &document)?;
     } else {
-        serde_json::to_string(document)?
-    };
+        serde_json::to_writer(&mut *writer, &document)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
 
-    // The #[serde(skip_serializing_if = "...")] attributes on the BicepDocument struct
-    // handle skipping empty collections during serialization, so we don't need
-    // to do any additional filtering
+/// The json format for the exporter registry
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonExporter;
 
-    Ok(json)
+impl Exporter for JsonExporter {
+    fn name(&self) -> &str {
+        "json"
+    }
+
+    fn extension(&self) -> &str {
+        "json"
+    }
+
+    fn description(&self) -> &str {
+        "Document Bicep file in JSON format"
+    }
+
+    fn export_to_writer(
+        &self,
+        document: &BicepDocument,
+        writer: &mut dyn Write,
+        options: &ExportOptions,
+    ) -> Result<(), BicepError> {
+        export_to_writer(document, writer, options)
+    }
 }
 
 // We use the #[serde(skip_serializing_if = "...")] attributes on the BicepDocument struct
@@ -83,9 +134,13 @@ pub fn parse_and_export<P: AsRef<Path>>(
     output_path: P,
     pretty: bool,
     exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), BicepError> {
     let document = crate::parse_bicep_document(source_code)?;
-    export_to_file(&document, output_path, pretty, exclude_empty)?;
+    let options = ExportOptions::builder()
+        .pretty(pretty)
+        .exclude_empty(exclude_empty)
+        .build();
+    export_to_file(&document, output_path, &options)?;
     Ok(())
 }
 
@@ -101,6 +156,7 @@ mod tests {
         let mut document = BicepDocument {
             name: None,
             description: None,
+            descriptions: IndexMap::new(),
             metadata: IndexMap::new(),
             target_scope: None,
             imports: Vec::new(),
@@ -117,7 +173,7 @@ mod tests {
             .metadata
             .insert("test".to_string(), BicepValue::String("value".to_string()));
 
-        let result = export_to_string(&document, true, false);
+        let result = export_to_string(&document, &ExportOptions::builder().pretty(true).build());
         assert!(result.is_ok());
 
         let json = result.unwrap();
@@ -130,6 +186,7 @@ mod tests {
         let document = BicepDocument {
             name: None,
             description: None,
+            descriptions: IndexMap::new(),
             metadata: IndexMap::new(),
             target_scope: None,
             imports: Vec::new(),
@@ -142,7 +199,7 @@ mod tests {
             outputs: IndexMap::new(),
         };
 
-        let result = export_to_string(&document, false, false);
+        let result = export_to_string(&document, &ExportOptions::default());
         assert!(result.is_ok());
 
         let json = result.unwrap();
@@ -169,10 +226,18 @@ mod tests {
             .insert("testParam".to_string(), parameter);
 
         // Test with exclude_empty = false (default behavior)
-        let result_with_all = export_to_string(&document, true, false).unwrap();
+        let result_with_all =
+            export_to_string(&document, &ExportOptions::builder().pretty(true).build()).unwrap();
 
         // Test with exclude_empty = true
-        let result_without_empty = export_to_string(&document, true, true).unwrap();
+        let result_without_empty = export_to_string(
+            &document,
+            &ExportOptions::builder()
+                .pretty(true)
+                .exclude_empty(true)
+                .build(),
+        )
+        .unwrap();
 
         // Both should contain the document name and the parameter
         assert!(result_with_all.contains("\"name\": \"Test Template\""));
@@ -0,0 +1,732 @@
+/// JSON Schema export functionality for Bicep documents
+///
+/// This module generates a JSON Schema (draft 2020-12) describing the parameters of
+/// a parsed Bicep document, so parameter values kept in JSON or YAML can be validated
+/// before deployment. The schema describes an object with one property per parameter,
+/// and the document's custom types are included under `$defs`.
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use serde_json::{json, Map, Value};
+
+use crate::error::BicepError;
+use crate::{
+    analysis::Evaluator,
+    exports::utils::samples::is_required,
+    exports::{ExportOptions, Exporter, REDACTED},
+    parsing::{BicepDocument, BicepParameter, BicepType, BicepValue},
+};
+
+/// The JSON Schema dialect of generated schemas
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Export the parameters schema of a Bicep document to a file
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate the schema for
+/// * `output_path` - The path where the schema should be written
+/// * `options` - How to write the document, such as its language and layout
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn export_to_file<P: AsRef<Path>>(
+    document: &BicepDocument,
+    output_path: P,
+    options: &ExportOptions,
+) -> Result<(), BicepError> {
+    let mut writer =
+        BufWriter::new(File::create(&output_path).map_err(|e| BicepError::io(&output_path, e))?);
+    export_to_writer(document, &mut writer, options)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Export the parameters schema of a Bicep document as a string
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate the schema for
+/// * `options` - How to write the document, such as its language and layout
+///
+/// # Returns
+///
+/// A Result containing the JSON Schema or an error
+pub fn export_to_string(
+    document: &BicepDocument,
+    options: &ExportOptions,
+) -> Result<String, BicepError> {
+    let mut buffer = Vec::new();
+    export_to_writer(document, &mut buffer, options)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// Write the JSON Schema of a Bicep document
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate the JSON Schema for
+/// * `writer` - Where to write the JSON Schema
+/// * `options` - How to write the document, such as its language and layout
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn export_to_writer(
+    document: &BicepDocument,
+    writer: &mut dyn Write,
+    options: &ExportOptions,
+) -> Result<(), BicepError> {
+    let schema = generate_schema(&options.redact(document));
+    if options.pretty {
+        serde_json::to_writer_pretty(&mut *writer, &schema)?;
+    } else {
+        serde_json::to_writer(&mut *writer, &schema)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// The json-schema format for the exporter registry
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonSchemaExporter;
+
+impl Exporter for JsonSchemaExporter {
+    fn name(&self) -> &str {
+        "json-schema"
+    }
+
+    fn extension(&self) -> &str {
+        "schema.json"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a JSON Schema for the parameters and exported types"
+    }
+
+    fn export_to_writer(
+        &self,
+        document: &BicepDocument,
+        writer: &mut dyn Write,
+        options: &ExportOptions,
+    ) -> Result<(), BicepError> {
+        export_to_writer(document, writer, options)
+    }
+}
+
+/// Generate the parameters schema of a Bicep document
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate the schema for
+///
+/// # Returns
+///
+/// The JSON Schema as a JSON value
+pub fn generate_schema(document: &BicepDocument) -> Value {
+    let generator = SchemaGenerator {
+        document,
+        evaluator: Evaluator::new(document),
+    };
+
+    let mut schema = Map::new();
+    schema.insert("$schema".to_string(), json!(SCHEMA_DIALECT));
+    schema.insert(
+        "title".to_string(),
+        json!(document.name.as_deref().unwrap_or("Bicep Template")),
+    );
+    if let Some(description) = &document.description {
+        schema.insert("description".to_string(), json!(description));
+    }
+
+    let object = generator.object_schema(&document.parameters, true);
+    if let Value::Object(object) = object {
+        schema.extend(object);
+    }
+
+    if !document.types.is_empty() {
+        let definitions = document
+            .types
+            .iter()
+            .map(|(name, custom_type)| {
+                let mut definition = generator.declaration_schema(
+                    &custom_type.definition,
+                    custom_type.is_sealed,
+                    custom_type.discriminator.as_deref(),
+                );
+                if let Some(description) = &custom_type.description {
+                    definition.insert("description".to_string(), json!(description));
+                }
+                if custom_type.is_secure {
+                    definition.insert("writeOnly".to_string(), json!(true));
+                }
+                (name.clone(), Value::Object(definition))
+            })
+            .collect::<Map<_, _>>();
+        schema.insert("$defs".to_string(), Value::Object(definitions));
+    }
+
+    Value::Object(schema)
+}
+
+/// Builds schemas for the types and parameters of a document
+struct SchemaGenerator<'a> {
+    document: &'a BicepDocument,
+    evaluator: Evaluator<'a>,
+}
+
+impl SchemaGenerator<'_> {
+    /// Build the schema of an object from its properties
+    fn object_schema(
+        &self,
+        properties: &indexmap::IndexMap<String, BicepParameter>,
+        sealed: bool,
+    ) -> Value {
+        let required = properties
+            .iter()
+            .filter(|(_, property)| is_required(property))
+            .map(|(name, _)| json!(name))
+            .collect::<Vec<_>>();
+
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), json!("object"));
+        schema.insert(
+            "properties".to_string(),
+            Value::Object(
+                properties
+                    .iter()
+                    .map(|(name, property)| (name.clone(), self.parameter_schema(property)))
+                    .collect(),
+            ),
+        );
+        if !required.is_empty() {
+            schema.insert("required".to_string(), Value::Array(required));
+        }
+        if sealed {
+            schema.insert("additionalProperties".to_string(), json!(false));
+        }
+        Value::Object(schema)
+    }
+
+    /// Build the schema of a parameter or object property, including its constraints
+    fn parameter_schema(&self, parameter: &BicepParameter) -> Value {
+        let mut schema = self.declaration_schema(
+            &parameter.parameter_type,
+            parameter.is_sealed,
+            parameter.discriminator.as_deref(),
+        );
+
+        if let Some(description) = &parameter.description {
+            schema.insert("description".to_string(), json!(description));
+        }
+
+        let is_array = matches!(parameter.parameter_type, BicepType::Array(_));
+        let (min_length, max_length) = if is_array {
+            ("minItems", "maxItems")
+        } else {
+            ("minLength", "maxLength")
+        };
+        if let Some(min) = parameter.min_length {
+            schema.insert(min_length.to_string(), json!(min));
+        }
+        if let Some(max) = parameter.max_length {
+            schema.insert(max_length.to_string(), json!(max));
+        }
+        if let Some(min) = parameter.min_value {
+            schema.insert("minimum".to_string(), json!(min));
+        }
+        if let Some(max) = parameter.max_value {
+            schema.insert("maximum".to_string(), json!(max));
+        }
+
+        if let Some(allowed_values) = &parameter.allowed_values {
+            let allowed = serde_json::to_value(allowed_values).unwrap_or_default();
+            // Allowed values of an array parameter constrain its items
+            match schema.get_mut("items") {
+                Some(Value::Object(items)) if is_array => {
+                    items.insert("enum".to_string(), allowed);
+                },
+                _ => {
+                    schema.insert("enum".to_string(), allowed);
+                },
+            }
+        }
+
+        // A redacted default is not a value the parameter can take
+        if let Some(default_value) = parameter
+            .default_value
+            .as_ref()
+            .filter(|value| !(parameter.is_secure && is_redacted(value)))
+            .and_then(|value| self.evaluator.evaluate_value(value))
+        {
+            schema.insert(
+                "default".to_string(),
+                serde_json::to_value(default_value).unwrap_or_default(),
+            );
+        }
+
+        if parameter.is_secure {
+            schema.insert("writeOnly".to_string(), json!(true));
+        }
+
+        if parameter.is_nullable {
+            allow_null(&mut schema);
+        }
+
+        Value::Object(schema)
+    }
+
+    /// Build the schema of a declared type with its `@sealed()` and `@discriminator()` decorators
+    fn declaration_schema(
+        &self,
+        bicep_type: &BicepType,
+        sealed: bool,
+        discriminator: Option<&str>,
+    ) -> Map<String, Value> {
+        let schema = match (bicep_type, discriminator) {
+            (BicepType::Object(Some(properties)), _) => self.object_schema(properties, sealed),
+            (BicepType::Union(members), Some(tag)) => self.tagged_union_schema(members, tag),
+            (other, _) => return self.type_schema(other),
+        };
+        match schema {
+            Value::Object(schema) => schema,
+            _ => Map::new(),
+        }
+    }
+
+    /// Build the schema of a type without constraints
+    fn type_schema(&self, bicep_type: &BicepType) -> Map<String, Value> {
+        let schema = match bicep_type {
+            BicepType::String => json!({ "type": "string" }),
+            BicepType::Int => json!({ "type": "integer" }),
+            BicepType::Bool => json!({ "type": "boolean" }),
+            BicepType::Array(item_type) => json!({
+                "type": "array",
+                "items": Value::Object(self.type_schema(item_type)),
+            }),
+            BicepType::Object(None) => json!({ "type": "object" }),
+            BicepType::Object(Some(properties)) => self.object_schema(properties, false),
+            BicepType::CustomType(name) => match name.as_str() {
+                "string" => json!({ "type": "string" }),
+                "int" => json!({ "type": "integer" }),
+                "bool" => json!({ "type": "boolean" }),
+                "object" => json!({ "type": "object" }),
+                "array" => json!({ "type": "array" }),
+                _ if self.document.types.contains_key(name) => {
+                    json!({ "$ref": format!("#/$defs/{name}") })
+                },
+                // Imported and built-in types accept any value
+                _ => json!({}),
+            },
+            BicepType::Union(members) => self.union_schema(members),
+        };
+
+        match schema {
+            Value::Object(schema) => schema,
+            _ => Map::new(),
+        }
+    }
+
+    /// Build the schema of a union type
+    ///
+    /// Unions of declared object types (tagged unions with a discriminator) become
+    /// `oneOf` the member types; unions of literals become an `enum`.
+    fn union_schema(&self, members: &[String]) -> Value {
+        if !members.is_empty() && members.iter().all(|m| self.document.types.contains_key(m)) {
+            let variants = members
+                .iter()
+                .map(|member| json!({ "$ref": format!("#/$defs/{member}") }))
+                .collect::<Vec<_>>();
+            return json!({ "oneOf": variants });
+        }
+
+        let values = members
+            .iter()
+            .map(|member| literal_value(member))
+            .collect::<Vec<_>>();
+        json!({ "enum": values })
+    }
+
+    /// Build the schema of a tagged union, whose members are told apart by a property
+    ///
+    /// Each member of the `oneOf` requires the tag to have the value its type
+    /// declares, so one member matches a value, and `discriminator` names the tag
+    /// for tools that choose the member by it, as OpenAPI does. Unions whose members
+    /// are not all declared types are built as other unions.
+    fn tagged_union_schema(&self, members: &[String], tag: &str) -> Value {
+        if members.is_empty() || !members.iter().all(|m| self.document.types.contains_key(m)) {
+            return self.union_schema(members);
+        }
+
+        let variants = members
+            .iter()
+            .map(|member| {
+                let mut variant = Map::new();
+                variant.insert("$ref".to_string(), json!(format!("#/$defs/{member}")));
+                if let Some(value) = self.tag_value(member, tag) {
+                    let mut properties = Map::new();
+                    properties.insert(tag.to_string(), json!({ "const": value }));
+                    variant.insert("properties".to_string(), Value::Object(properties));
+                }
+                Value::Object(variant)
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "type": "object",
+            "discriminator": { "propertyName": tag },
+            "required": [tag],
+            "oneOf": variants,
+        })
+    }
+
+    /// Get the value of the tag property that a member of a tagged union declares
+    fn tag_value(&self, member: &str, tag: &str) -> Option<Value> {
+        let BicepType::Object(Some(properties)) = &self.document.types.get(member)?.definition
+        else {
+            return None;
+        };
+        match &properties.get(tag)?.parameter_type {
+            BicepType::Union(values) if values.len() == 1 => Some(literal_value(&values[0])),
+            _ => None,
+        }
+    }
+}
+
+/// Convert a member of a union of literals to its JSON value
+fn literal_value(member: &str) -> Value {
+    if let Ok(number) = member.parse::<i64>() {
+        json!(number)
+    } else {
+        match member {
+            "true" => json!(true),
+            "false" => json!(false),
+            _ => json!(member),
+        }
+    }
+}
+
+/// Parse a Bicep file and export its parameters schema in one step
+///
+/// # Arguments
+///
+/// * `source_code` - The source code of the Bicep file
+/// * `output_path` - The path where the schema should be written
+/// * `pretty` - Whether to format the JSON with indentation for readability
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn parse_and_export<P: AsRef<Path>>(
+    source_code: &str,
+    output_path: P,
+    pretty: bool,
+) -> Result<(), BicepError> {
+    let document = crate::parse_bicep_document(source_code)?;
+    export_to_file(
+        &document,
+        output_path,
+        &ExportOptions::builder().pretty(pretty).build(),
+    )?;
+    Ok(())
+}
+
+/// Let a schema accept `null`, for nullable parameters and properties
+///
+/// A `type` gains `"null"` and an `enum` gains `null`; a reference or `oneOf` is
+/// wrapped in an `anyOf` with the null type.
+fn allow_null(schema: &mut Map<String, Value>) {
+    if let Some(Value::String(schema_type)) = schema.get("type") {
+        let types = json!([schema_type, "null"]);
+        schema.insert("type".to_string(), types);
+    }
+    if let Some(Value::Array(values)) = schema.get_mut("enum") {
+        values.push(Value::Null);
+    }
+    if schema.contains_key("type") || schema.contains_key("enum") {
+        return;
+    }
+
+    let variant = ["$ref", "oneOf"]
+        .into_iter()
+        .filter_map(|key| schema.remove_entry(key))
+        .collect::<Map<_, _>>();
+    if !variant.is_empty() {
+        schema.insert(
+            "anyOf".to_string(),
+            json!([Value::Object(variant), { "type": "null" }]),
+        );
+    }
+}
+
+/// Whether a value is the placeholder for a redacted secure default
+fn is_redacted(value: &BicepValue) -> bool {
+    matches!(value, BicepValue::String(text) if text == REDACTED)
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+    use crate::parsing::{BicepCustomType, BicepValue};
+
+    fn custom_type(definition: BicepType) -> BicepCustomType {
+        BicepCustomType {
+            description: None,
+            descriptions: IndexMap::new(),
+            definition,
+            discriminator: None,
+            is_exported: true,
+            is_sealed: false,
+            is_secure: false,
+        }
+    }
+
+    #[test]
+    fn test_generate_schema_parameters() {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "name".to_string(),
+            BicepParameter {
+                description: Some("Name of the vault".to_string()),
+                min_length: Some(3),
+                max_length: Some(24),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "sku".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::String("standard".to_string())),
+                allowed_values: Some(vec![
+                    BicepValue::String("standard".to_string()),
+                    BicepValue::String("premium".to_string()),
+                ]),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "zones".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Array(Box::new(BicepType::Int)),
+                allowed_values: Some(vec![BicepValue::Int(1), BicepValue::Int(2)]),
+                max_length: Some(2),
+                is_nullable: true,
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "password".to_string(),
+            BicepParameter {
+                is_secure: true,
+                ..Default::default()
+            },
+        );
+
+        let schema = generate_schema(&document);
+
+        assert_eq!(schema["$schema"], SCHEMA_DIALECT);
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(schema["required"], json!(["name", "password"]));
+        assert_eq!(
+            schema["properties"]["name"],
+            json!({
+                "type": "string",
+                "description": "Name of the vault",
+                "minLength": 3,
+                "maxLength": 24,
+            })
+        );
+        assert_eq!(
+            schema["properties"]["sku"],
+            json!({
+                "type": "string",
+                "enum": ["standard", "premium"],
+                "default": "standard",
+            })
+        );
+        assert_eq!(
+            schema["properties"]["zones"],
+            json!({
+                "type": ["array", "null"],
+                "items": { "type": "integer", "enum": [1, 2] },
+                "maxItems": 2,
+            })
+        );
+        assert_eq!(schema["properties"]["password"]["writeOnly"], true);
+    }
+
+    #[test]
+    fn test_generate_schema_nullable() {
+        let mut document = BicepDocument::default();
+        document
+            .types
+            .insert("network".to_string(), custom_type(BicepType::Object(None)));
+        let properties = IndexMap::from([(
+            "tier".to_string(),
+            BicepParameter {
+                allowed_values: Some(vec![BicepValue::String("basic".to_string())]),
+                is_nullable: true,
+                ..Default::default()
+            },
+        )]);
+        document.parameters.insert(
+            "settings".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Object(Some(properties)),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "network".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::CustomType("network".to_string()),
+                description: Some("The network".to_string()),
+                is_nullable: true,
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "size".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Union(vec!["1".to_string(), "2".to_string()]),
+                is_nullable: true,
+                ..Default::default()
+            },
+        );
+
+        let schema = generate_schema(&document);
+
+        assert_eq!(schema["required"], json!(["settings"]));
+        assert_eq!(
+            schema["properties"]["settings"]["properties"]["tier"],
+            json!({
+                "type": ["string", "null"],
+                "enum": ["basic", null],
+            })
+        );
+        assert_eq!(
+            schema["properties"]["network"],
+            json!({
+                "anyOf": [{ "$ref": "#/$defs/network" }, { "type": "null" }],
+                "description": "The network",
+            })
+        );
+        assert_eq!(
+            schema["properties"]["size"],
+            json!({ "enum": [1, 2, null] })
+        );
+    }
+
+    #[test]
+    fn test_generate_schema_custom_types() {
+        let mut cat = IndexMap::new();
+        cat.insert(
+            "kind".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Union(vec!["cat".to_string()]),
+                ..Default::default()
+            },
+        );
+        cat.insert(
+            "lives".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Int,
+                max_value: Some(9),
+                ..Default::default()
+            },
+        );
+        let mut dog = IndexMap::new();
+        dog.insert(
+            "kind".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Union(vec!["dog".to_string()]),
+                ..Default::default()
+            },
+        );
+
+        let mut document = BicepDocument::default();
+        document.types.insert(
+            "catType".to_string(),
+            custom_type(BicepType::Object(Some(cat))),
+        );
+        document.types.insert(
+            "dogType".to_string(),
+            custom_type(BicepType::Object(Some(dog))),
+        );
+        document.types.insert(
+            "pet".to_string(),
+            custom_type(BicepType::Union(vec![
+                "catType".to_string(),
+                "dogType".to_string(),
+            ])),
+        );
+        document.types.insert(
+            "taggedPet".to_string(),
+            BicepCustomType {
+                discriminator: Some("kind".to_string()),
+                ..custom_type(BicepType::Union(vec![
+                    "catType".to_string(),
+                    "dogType".to_string(),
+                ]))
+            },
+        );
+        document.types["dogType"].is_sealed = true;
+        document.parameters.insert(
+            "pet".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::CustomType("pet".to_string()),
+                discriminator: Some("kind".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let schema = generate_schema(&document);
+
+        assert_eq!(
+            schema["properties"]["pet"],
+            json!({ "$ref": "#/$defs/pet" })
+        );
+        assert_eq!(
+            schema["$defs"]["pet"],
+            json!({ "oneOf": [{ "$ref": "#/$defs/catType" }, { "$ref": "#/$defs/dogType" }] })
+        );
+        assert_eq!(
+            schema["$defs"]["catType"]["properties"]["kind"],
+            json!({ "enum": ["cat"] })
+        );
+        assert_eq!(
+            schema["$defs"]["catType"]["properties"]["lives"]["maximum"],
+            9
+        );
+        assert!(schema["$defs"]["catType"]
+            .get("additionalProperties")
+            .is_none());
+        assert_eq!(schema["$defs"]["dogType"]["additionalProperties"], false);
+
+        // A discriminator keys each member on the value of its tag
+        assert_eq!(
+            schema["$defs"]["taggedPet"],
+            json!({
+                "type": "object",
+                "discriminator": { "propertyName": "kind" },
+                "required": ["kind"],
+                "oneOf": [
+                    { "$ref": "#/$defs/catType", "properties": { "kind": { "const": "cat" } } },
+                    { "$ref": "#/$defs/dogType", "properties": { "kind": { "const": "dog" } } }
+                ]
+            })
+        );
+        // Without one, a union of declared types is only their oneOf
+        document.parameters["pet"].parameter_type =
+            BicepType::Union(vec!["catType".to_string(), "dogType".to_string()]);
+        assert_eq!(
+            generate_schema(&document)["properties"]["pet"]["discriminator"],
+            json!({ "propertyName": "kind" })
+        );
+    }
+}
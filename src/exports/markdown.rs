@@ -2,17 +2,36 @@
 ///
 /// This module provides functions to export parsed Bicep documents
 /// to Markdown format with structured documentation layout.
-use std::error::Error as StdError;
-use std::{fs, path::Path};
+use crate::error::BicepError;
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use indexmap::IndexMap;
 
 use crate::{
+    analysis::{analyze_references, Evaluator, ReferenceIndex, SymbolId, SymbolKind},
     exports::utils::{
-        common::{format_yes_no, generate_metadata_display_markdown},
+        common::{format_yes_no, generate_metadata_display_markdown, localized_description},
+        conventions::{
+            example_source, is_hidden, parameter_sections, without_conventions, DocConventions,
+        },
         formatting::{
             escape_markdown, format_bicep_array_as_list, format_bicep_type_with_backticks,
         },
     },
-    parsing::{BicepDocument, BicepFunctionArgument, BicepImport, BicepType},
+    exports::{
+        usage::{write_module_snippet, write_parameters_snippet},
+        ExportOptions, Exporter,
+    },
+    localization::Language,
+    parsing::{
+        BicepDocument, BicepFunctionArgument, BicepImport, BicepParameter, BicepType, BicepValue,
+        ModuleSource,
+    },
     t,
 };
 
@@ -22,8 +41,7 @@ use crate::{
 ///
 /// * `document` - The BicepDocument to export
 /// * `file_path` - Path where the Markdown file should be written
-/// * `use_emoji` - Whether to use emoji symbols (✅/❌) for Yes/No values
-/// * `exclude_empty` - Whether to exclude empty sections from the output
+/// * `options` - How to write the document, such as its language and layout
 ///
 /// # Returns
 ///
@@ -35,21 +53,21 @@ use crate::{
 pub fn export_to_file<P: AsRef<Path>>(
     document: &BicepDocument,
     file_path: P,
-    use_emoji: bool,
-    exclude_empty: bool,
-) -> Result<(), Box<dyn StdError>> {
-    let markdown_content = export_to_string(document, use_emoji, exclude_empty)?;
-    fs::write(file_path, markdown_content)?;
+    options: &ExportOptions,
+) -> Result<(), BicepError> {
+    let mut writer =
+        BufWriter::new(File::create(&file_path).map_err(|e| BicepError::io(&file_path, e))?);
+    export_to_writer(document, &mut writer, options)?;
+    writer.flush()?;
     Ok(())
 }
 
-/// Export a Bicep document to a Markdown string with localization support
+/// Export a Bicep document to a Markdown string
 ///
 /// # Arguments
 ///
 /// * `document` - The BicepDocument to export
-/// * `use_emoji` - Whether to use emoji symbols (✅/❌) for Yes/No values
-/// * `exclude_empty` - Whether to exclude empty sections from the output
+/// * `options` - How to write the document, such as its language and layout
 ///
 /// # Returns
 ///
@@ -60,47 +78,316 @@ pub fn export_to_file<P: AsRef<Path>>(
 /// Returns an error if serialization fails
 pub fn export_to_string(
     document: &BicepDocument,
-    use_emoji: bool,
-    exclude_empty: bool,
-) -> Result<String, Box<dyn StdError>> {
-    let mut markdown = String::new();
+    options: &ExportOptions,
+) -> Result<String, BicepError> {
+    let mut buffer = Vec::new();
+    export_to_writer(document, &mut buffer, options)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// Write a Bicep document as Markdown
+///
+/// The document is written section by section as it is generated, so the writer
+/// should be buffered when it is a file or socket.
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to export
+/// * `writer` - Where to write the Markdown
+/// * `options` - How to write the document, such as its language and layout
+///
+/// # Returns
+///
+/// Result indicating success or failure of the export operation
+///
+/// # Errors
+///
+/// Returns an error if writing fails
+pub fn export_to_writer(
+    document: &BicepDocument,
+    writer: &mut dyn Write,
+    options: &ExportOptions,
+) -> Result<(), BicepError> {
+    let document = &*options.redact(document);
+    let use_emoji = options.use_emoji;
+    let exclude_empty = options.exclude_empty;
+    let language = options.language;
+    let references = options
+        .show_references
+        .then(|| analyze_references(document));
+    let references = references.as_ref();
+    let evaluator = Evaluator::new(document);
 
     // Title and overview section
     if let Some(name) = &document.name {
-        markdown.push_str(&format!("# {name}\n\n"));
+        write!(writer, "# {name}\n\n")?;
     } else {
-        markdown.push_str(&format!("# {}\n\n", t!("export.bicep_template")));
+        write!(
+            writer,
+            "# {}\n\n",
+            t!("export.bicep_template", locale = language.code())
+        )?;
     }
 
     // Description
-    if let Some(description) = &document.description {
-        markdown.push_str(&format!("{}\n\n", escape_markdown(description)));
+    if let Some(description) = localized_description(
+        document.description.as_deref(),
+        &document.descriptions,
+        language,
+    ) {
+        write!(writer, "{}\n\n", escape_markdown(description))?;
     }
 
     if let Some(target_scope) = &document.target_scope {
-        markdown.push_str(&format!(
+        write!(
+            writer,
             "**{}:** `{target_scope}`\n\n",
-            t!("export.target_scope")
-        ));
+            t!("export.target_scope", locale = language.code())
+        )?;
     }
 
     // Additional metadata
     if !document.metadata.is_empty() {
-        markdown.push_str(&format!("## {}\n\n", t!("export.additional_metadata")));
-        generate_metadata_display_markdown(&mut markdown, &document.metadata);
+        write!(
+            writer,
+            "## {}\n\n",
+            t!("export.additional_metadata", locale = language.code())
+        )?;
+        generate_metadata_display_markdown(writer, &document.metadata)?;
+    }
+
+    if let Some(source) = &options.module_source {
+        generate_usage_section(writer, document, source, language)?;
     }
 
     // Generate sections using existing helper functions
-    generate_imports_section(&mut markdown, document, exclude_empty);
-    generate_types_section(&mut markdown, document, use_emoji, exclude_empty);
-    generate_parameters_section(&mut markdown, document, use_emoji, exclude_empty);
-    generate_variables_section(&mut markdown, document, use_emoji, exclude_empty);
-    generate_functions_section(&mut markdown, document, use_emoji, exclude_empty);
-    generate_resources_section(&mut markdown, document, use_emoji, exclude_empty);
-    generate_modules_section(&mut markdown, document, use_emoji, exclude_empty);
-    generate_outputs_section(&mut markdown, document, use_emoji, exclude_empty);
-
-    Ok(markdown)
+    generate_imports_section(writer, document, exclude_empty, language)?;
+    generate_types_section(
+        writer,
+        document,
+        references,
+        use_emoji,
+        exclude_empty,
+        language,
+    )?;
+    generate_parameters_section(
+        writer,
+        document,
+        references,
+        &evaluator,
+        use_emoji,
+        exclude_empty,
+        language,
+    )?;
+    generate_variables_section(
+        writer,
+        document,
+        references,
+        &evaluator,
+        use_emoji,
+        exclude_empty,
+        language,
+    )?;
+    generate_functions_section(
+        writer,
+        document,
+        references,
+        use_emoji,
+        exclude_empty,
+        language,
+    )?;
+    generate_resources_section(
+        writer,
+        document,
+        references,
+        &evaluator,
+        use_emoji,
+        exclude_empty,
+        language,
+    )?;
+    generate_modules_section(
+        writer,
+        document,
+        references,
+        use_emoji,
+        exclude_empty,
+        language,
+    )?;
+    generate_outputs_section(
+        writer,
+        document,
+        references,
+        &evaluator,
+        use_emoji,
+        exclude_empty,
+        language,
+    )?;
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// The markdown format for the exporter registry
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MarkdownExporter;
+
+impl Exporter for MarkdownExporter {
+    fn name(&self) -> &str {
+        "markdown"
+    }
+
+    fn extension(&self) -> &str {
+        "md"
+    }
+
+    fn description(&self) -> &str {
+        "Document Bicep file in Markdown format"
+    }
+
+    fn export_to_writer(
+        &self,
+        document: &BicepDocument,
+        writer: &mut dyn Write,
+        options: &ExportOptions,
+    ) -> Result<(), BicepError> {
+        export_to_writer(document, writer, options)
+    }
+}
+
+/// Export the documentation of a single declaration to Markdown
+///
+/// The declaration is rendered as it appears in the full document, starting at its
+/// `###` heading, so editors can show it as a hover card. The default value of a
+/// secure parameter is redacted.
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument containing the declaration
+/// * `symbol` - The declaration to document
+/// * `use_emoji` - Whether to use emoji symbols (✅/❌) for Yes/No values
+/// * `show_references` - Whether to show "Used By" and "Depends On" details
+/// * `language` - The language to write labels in
+///
+/// # Returns
+///
+/// The Markdown for the declaration, or None if the document does not declare it
+pub fn export_symbol_to_string(
+    document: &BicepDocument,
+    symbol: &SymbolId,
+    use_emoji: bool,
+    show_references: bool,
+    language: Language,
+) -> Option<String> {
+    let document = &*ExportOptions::default().redact(document);
+    let references = show_references.then(|| analyze_references(document));
+    let references = references.as_ref();
+    let evaluator = Evaluator::new(document);
+
+    // Render the section of a copy of the document that only declares the symbol
+    let mut single = BicepDocument::default();
+    let mut markdown = Vec::new();
+    match symbol.kind {
+        SymbolKind::Type => {
+            let (name, custom_type) = document.types.get_key_value(&symbol.name)?;
+            single.types.insert(name.clone(), custom_type.clone());
+            generate_types_section(
+                &mut markdown,
+                &single,
+                references,
+                use_emoji,
+                true,
+                language,
+            )
+            .ok()?;
+        },
+        SymbolKind::Function => {
+            let (name, function) = document.functions.get_key_value(&symbol.name)?;
+            single.functions.insert(name.clone(), function.clone());
+            generate_functions_section(
+                &mut markdown,
+                &single,
+                references,
+                use_emoji,
+                true,
+                language,
+            )
+            .ok()?;
+        },
+        SymbolKind::Parameter => {
+            let (name, parameter) = document.parameters.get_key_value(&symbol.name)?;
+            generate_parameter(
+                &mut markdown,
+                name,
+                parameter,
+                3,
+                references,
+                &evaluator,
+                use_emoji,
+                language,
+            )
+            .ok()?;
+        },
+        SymbolKind::Variable => {
+            let (name, variable) = document.variables.get_key_value(&symbol.name)?;
+            single.variables.insert(name.clone(), variable.clone());
+            generate_variables_section(
+                &mut markdown,
+                &single,
+                references,
+                &evaluator,
+                use_emoji,
+                true,
+                language,
+            )
+            .ok()?;
+        },
+        SymbolKind::Resource => {
+            let (name, resource) = document.resources.get_key_value(&symbol.name)?;
+            single.resources.insert(name.clone(), resource.clone());
+            generate_resources_section(
+                &mut markdown,
+                &single,
+                references,
+                &evaluator,
+                use_emoji,
+                true,
+                language,
+            )
+            .ok()?;
+        },
+        SymbolKind::Module => {
+            let (name, module) = document.modules.get_key_value(&symbol.name)?;
+            single.modules.insert(name.clone(), module.clone());
+            generate_modules_section(
+                &mut markdown,
+                &single,
+                references,
+                use_emoji,
+                true,
+                language,
+            )
+            .ok()?;
+        },
+        SymbolKind::Output => {
+            let (name, output) = document.outputs.get_key_value(&symbol.name)?;
+            single.outputs.insert(name.clone(), output.clone());
+            generate_outputs_section(
+                &mut markdown,
+                &single,
+                references,
+                &evaluator,
+                use_emoji,
+                true,
+                language,
+            )
+            .ok()?;
+        },
+    }
+
+    // Drop the section heading
+    let markdown = String::from_utf8(markdown).ok()?;
+    let start = markdown.find("### ").unwrap_or(0);
+    Some(markdown[start..].trim_end().to_string())
 }
 
 /// Parse a Bicep file and export it to Markdown
@@ -121,10 +408,14 @@ pub fn parse_and_export<P: AsRef<Path>, Q: AsRef<Path>>(
     file_path: P,
     output_path: Q,
     exclude_empty: bool,
-) -> Result<(), Box<dyn StdError>> {
-    let content = std::fs::read_to_string(file_path)?;
+) -> Result<(), BicepError> {
+    let content = std::fs::read_to_string(&file_path).map_err(|e| BicepError::io(&file_path, e))?;
     let document = crate::parse_bicep_document(&content)?;
-    export_to_file(&document, output_path, true, exclude_empty)?;
+    let options = ExportOptions::builder()
+        .use_emoji(true)
+        .exclude_empty(exclude_empty)
+        .build();
+    export_to_file(&document, output_path, &options)?;
     Ok(())
 }
 
@@ -133,17 +424,30 @@ pub fn test_parse_and_export<P: AsRef<Path>, Q: AsRef<Path>>(
     file_path: P,
     output_path: Q,
     exclude_empty: bool,
-) -> Result<(), Box<dyn StdError>> {
+) -> Result<(), BicepError> {
     parse_and_export(file_path, output_path, exclude_empty)
 }
 
 /// Generate the imports section of the Markdown
-fn generate_imports_section(markdown: &mut String, document: &BicepDocument, exclude_empty: bool) {
+fn generate_imports_section(
+    markdown: &mut dyn Write,
+    document: &BicepDocument,
+    exclude_empty: bool,
+    language: Language,
+) -> io::Result<()> {
     if !document.imports.is_empty() || !exclude_empty {
-        markdown.push_str(&format!("## {}\n\n", t!("export.imports")));
+        write!(
+            markdown,
+            "## {}\n\n",
+            t!("export.imports", locale = language.code())
+        )?;
 
         if document.imports.is_empty() {
-            markdown.push_str(&format!("*{}*\n\n", t!("export.no_imports_defined")));
+            write!(
+                markdown,
+                "*{}*\n\n",
+                t!("export.no_imports_defined", locale = language.code())
+            )?;
         } else {
             // Separate namespace and module imports
             let namespace_imports: Vec<_> = document
@@ -158,127 +462,235 @@ fn generate_imports_section(markdown: &mut String, document: &BicepDocument, exc
                 .collect();
 
             if !namespace_imports.is_empty() {
-                markdown.push_str(&format!("### {}\n\n", t!("export.namespace_imports")));
-                markdown.push_str(&format!(
-                    "| {} | {} |\n",
-                    t!("export.namespace_header"),
-                    t!("export.version_header")
-                ));
-                markdown.push_str("|-----------|----------|\n");
+                write!(
+                    markdown,
+                    "### {}\n\n",
+                    t!("export.namespace_imports", locale = language.code())
+                )?;
+                writeln!(
+                    markdown,
+                    "| {} | {} |",
+                    t!("export.namespace_header", locale = language.code()),
+                    t!("export.version_header", locale = language.code())
+                )?;
+                markdown.write_all(b"|-----------|----------|\n")?;
 
                 for import in namespace_imports {
                     if let BicepImport::Namespace { namespace, version } = import {
-                        let version_str = version.as_deref().unwrap_or("Latest");
-                        markdown.push_str(&format!(
-                            "| {} | {} |\n",
+                        let version_str = version
+                            .as_deref()
+                            .map(Cow::from)
+                            .unwrap_or_else(|| t!("export.latest", locale = language.code()));
+                        writeln!(
+                            markdown,
+                            "| {} | {} |",
                             escape_markdown(namespace),
-                            escape_markdown(version_str)
-                        ));
+                            escape_markdown(&version_str)
+                        )?;
                     }
                 }
-                markdown.push('\n');
+                markdown.write_all(b"\n")?;
             }
 
             if !module_imports.is_empty() {
-                markdown.push_str(&format!("### {}\n\n", t!("export.module_imports")));
-                markdown.push_str(&format!("| Import | {} |\n", t!("export.source_header")));
-                markdown.push_str("|--------|--------|\n");
+                write!(
+                    markdown,
+                    "### {}\n\n",
+                    t!("export.module_imports", locale = language.code())
+                )?;
+                writeln!(
+                    markdown,
+                    "| {} | {} |",
+                    t!("export.import_header", locale = language.code()),
+                    t!("export.source_header", locale = language.code())
+                )?;
+                markdown.write_all(b"|--------|--------|\n")?;
 
                 for import in module_imports {
                     if let BicepImport::Module { source, .. } = import {
-                        markdown.push_str(&format!(
-                            "| {} | {} |\n",
+                        writeln!(
+                            markdown,
+                            "| {} | {} |",
                             escape_markdown(&format!("{:?}", import)), // This will show the import details
                             escape_markdown(&source.to_string())
-                        ));
+                        )?;
                     }
                 }
-                markdown.push('\n');
+                markdown.write_all(b"\n")?;
             }
         }
     }
+    Ok(())
+}
+
+/// Generate the Usage section of the markdown
+///
+/// # Arguments
+///
+/// * `markdown` - The string buffer to append Markdown content to
+/// * `document` - The template being documented
+/// * `source` - Where consumers reference the template from
+/// * `language` - The language to write labels in
+fn generate_usage_section(
+    markdown: &mut dyn Write,
+    document: &BicepDocument,
+    source: &ModuleSource,
+    language: Language,
+) -> Result<(), BicepError> {
+    write!(
+        markdown,
+        "## {}\n\n",
+        t!("export.usage", locale = language.code())
+    )?;
+    write!(
+        markdown,
+        "**{}**\n\n",
+        t!("export.module", locale = language.code())
+    )?;
+    markdown.write_all(b"```bicep\n")?;
+    write_module_snippet(markdown, document, source)?;
+    markdown.write_all(b"```\n")?;
+    write!(
+        markdown,
+        "\n**{}**\n\n",
+        t!("export.parameters_file", locale = language.code())
+    )?;
+    markdown.write_all(b"```bicep\n")?;
+    write_parameters_snippet(markdown, document, source)?;
+    markdown.write_all(b"```\n")?;
+    markdown.write_all(b"\n")?;
+    Ok(())
 }
 
 /// Generate the Types section of the markdown
 fn generate_types_section(
-    markdown: &mut String,
+    markdown: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
     use_emoji: bool,
     exclude_empty: bool,
-) {
+    language: Language,
+) -> io::Result<()> {
     if document.types.is_empty() {
         if !exclude_empty {
-            markdown.push_str(&format!("## {}\n\n", t!("export.types")));
-            markdown.push_str(&format!("*{}*\n\n", t!("export.no_types_defined")));
+            write!(
+                markdown,
+                "## {}\n\n",
+                t!("export.types", locale = language.code())
+            )?;
+            write!(
+                markdown,
+                "*{}*\n\n",
+                t!("export.no_types_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
-    markdown.push_str(&format!("## {}\n\n", t!("export.types")));
+    write!(
+        markdown,
+        "## {}\n\n",
+        t!("export.types", locale = language.code())
+    )?;
 
     for (name, custom_type) in &document.types {
-        markdown.push_str(&format!("### `{name}`\n\n").to_string());
-
-        if let Some(description) = &custom_type.description {
-            markdown.push_str(&format!("{}\n\n", escape_markdown(description)));
+        write!(markdown, "### `{name}`\n\n")?;
+
+        if let Some(description) = localized_description(
+            custom_type.description.as_deref(),
+            &custom_type.descriptions,
+            language,
+        ) {
+            write!(markdown, "{}\n\n", escape_markdown(description))?;
         }
 
         // Basic information table
-        let items = vec![
+        let mut items = vec![
+            (
+                t!("export.exported", locale = language.code()),
+                format_yes_no(custom_type.is_exported, use_emoji, language),
+            ),
             (
-                "Exported",
-                format_yes_no(custom_type.is_exported, use_emoji),
+                t!("export.secure", locale = language.code()),
+                format_yes_no(custom_type.is_secure, use_emoji, language),
             ),
-            ("Secure", format_yes_no(custom_type.is_secure, use_emoji)),
         ];
-        generate_key_value_display(markdown, &items);
+        items.extend(reference_items(
+            references,
+            SymbolKind::Type,
+            name,
+            language,
+        ));
+        generate_key_value_display(markdown, &items)?;
 
         // Check if this is an object type with properties and add object properties section
         if let BicepType::Object(Some(properties)) = &custom_type.definition {
             if !properties.is_empty() {
-                markdown.push_str("\n**Object Definition**\n\n");
+                write!(
+                    markdown,
+                    "\n**{}**\n\n",
+                    t!("export.object_definition", locale = language.code())
+                )?;
 
                 for (prop_name, prop_param) in properties {
-                    markdown.push_str(&format!("#### `{prop_name}`\n\n").to_string());
-
-                    if let Some(description) = &prop_param.description {
-                        markdown.push_str(&format!("{}\n\n", escape_markdown(description)));
+                    let conventions = DocConventions::from_metadata(&prop_param.metadata);
+                    if conventions.hidden {
+                        continue;
+                    }
+                    write!(markdown, "#### `{prop_name}`\n\n")?;
+                    generate_deprecation_notice(markdown, &conventions, language)?;
+
+                    if let Some(description) = localized_description(
+                        prop_param.description.as_deref(),
+                        &prop_param.descriptions,
+                        language,
+                    ) {
+                        write!(markdown, "{}\n\n", escape_markdown(description))?;
                     }
 
-                    let mut prop_items = vec![("Type", format!("`{}`", prop_param.parameter_type))];
+                    let mut prop_items = vec![(
+                        t!("export.type_header", locale = language.code()),
+                        format!("`{}`", prop_param.parameter_type),
+                    )];
 
-                    prop_items.push(("Nullable", format_yes_no(prop_param.is_nullable, use_emoji)));
+                    prop_items.push((
+                        t!("export.nullable", locale = language.code()),
+                        format_yes_no(prop_param.is_nullable, use_emoji, language),
+                    ));
 
-                    prop_items.push(("Secure", format_yes_no(prop_param.is_secure, use_emoji)));
+                    prop_items.push((
+                        t!("export.secure", locale = language.code()),
+                        format_yes_no(prop_param.is_secure, use_emoji, language),
+                    ));
 
-                    generate_key_value_display(markdown, &prop_items);
+                    generate_key_value_display(markdown, &prop_items)?;
 
                     // Handle constraints separately
                     let mut constraints = Vec::new();
                     if let Some(min_value) = prop_param.min_value {
                         constraints.push((
-                            "Minimum Value",
+                            t!("export.minimum_value", locale = language.code()),
                             format_constraint_value(&min_value.to_string()),
                         ));
                     }
 
                     if let Some(max_value) = prop_param.max_value {
                         constraints.push((
-                            "Maximum Value",
+                            t!("export.maximum_value", locale = language.code()),
                             format_constraint_value(&max_value.to_string()),
                         ));
                     }
 
                     if let Some(min_length) = prop_param.min_length {
                         constraints.push((
-                            "Minimum Length",
+                            t!("export.minimum_length", locale = language.code()),
                             format_constraint_value(&min_length.to_string()),
                         ));
                     }
 
                     if let Some(max_length) = prop_param.max_length {
                         constraints.push((
-                            "Maximum Length",
+                            t!("export.maximum_length", locale = language.code()),
                             format_constraint_value(&max_length.to_string()),
                         ));
                     }
@@ -286,197 +698,390 @@ fn generate_types_section(
                     if let Some(allowed_values) = &prop_param.allowed_values {
                         if !allowed_values.is_empty() {
                             constraints.push((
-                                "Allowed Values",
+                                t!("export.allowed_values", locale = language.code()),
                                 format_bicep_array_as_list(allowed_values),
                             ));
                         }
                     }
 
                     if !constraints.is_empty() {
-                        markdown.push_str("\n**Constraints**\n\n");
-                        generate_key_value_display(markdown, &constraints);
+                        write!(
+                            markdown,
+                            "\n**{}**\n\n",
+                            t!("export.constraints", locale = language.code())
+                        )?;
+                        generate_key_value_display(markdown, &constraints)?;
                     }
 
                     if let Some(default_value) = &prop_param.default_value {
-                        markdown.push_str("\n**Default Value**\n\n");
-                        markdown.push_str(&format_code_block(&default_value.pretty_format()));
+                        write!(
+                            markdown,
+                            "\n**{}**\n\n",
+                            t!("export.default_value", locale = language.code())
+                        )?;
+                        markdown.write_all(
+                            format_code_block(&default_value.pretty_format()).as_bytes(),
+                        )?;
                     }
 
+                    generate_example(markdown, &conventions, language)?;
+
                     // Handle nested object properties recursively
                     if let BicepType::Object(Some(nested_props)) = &prop_param.parameter_type {
                         if !nested_props.is_empty() {
-                            markdown.push_str("\n**Object Definition**\n\n");
-                            generate_nested_object_properties(markdown, nested_props, 5, use_emoji);
+                            write!(
+                                markdown,
+                                "\n**{}**\n\n",
+                                t!("export.object_definition", locale = language.code())
+                            )?;
+                            generate_nested_object_properties(
+                                markdown,
+                                nested_props,
+                                5,
+                                use_emoji,
+                                language,
+                            )?;
                         }
                     }
 
-                    if !prop_param.metadata.is_empty() {
-                        markdown.push_str("\n**Metadata**\n\n");
-                        generate_metadata_display_markdown(markdown, &prop_param.metadata);
+                    let metadata = without_conventions(&prop_param.metadata);
+                    if !metadata.is_empty() {
+                        write!(
+                            markdown,
+                            "\n**{}**\n\n",
+                            t!("export.metadata", locale = language.code())
+                        )?;
+                        generate_metadata_display_markdown(markdown, &metadata)?;
                     }
 
-                    markdown.push('\n');
+                    markdown.write_all(b"\n")?;
                 }
             }
         }
 
-        markdown.push('\n');
+        markdown.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Functions section of the markdown
 fn generate_functions_section(
-    markdown: &mut String,
+    markdown: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
     use_emoji: bool,
     exclude_empty: bool,
-) {
-    if document.functions.is_empty() {
+    language: Language,
+) -> io::Result<()> {
+    if document
+        .functions
+        .values()
+        .all(|function| is_hidden(&function.metadata))
+    {
         if !exclude_empty {
-            markdown.push_str(&format!("## {}\n\n", t!("export.functions")));
-            markdown.push_str(&format!("*{}*\n\n", t!("export.no_functions_defined")));
+            write!(
+                markdown,
+                "## {}\n\n",
+                t!("export.functions", locale = language.code())
+            )?;
+            write!(
+                markdown,
+                "*{}*\n\n",
+                t!("export.no_functions_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
-    markdown.push_str(&format!("## {}\n\n", t!("export.functions")));
+    write!(
+        markdown,
+        "## {}\n\n",
+        t!("export.functions", locale = language.code())
+    )?;
 
     for (name, function) in &document.functions {
-        markdown.push_str(&format!("### `{name}`\n\n").to_string());
+        let conventions = DocConventions::from_metadata(&function.metadata);
+        if conventions.hidden {
+            continue;
+        }
+        write!(markdown, "### `{name}`\n\n")?;
+        generate_deprecation_notice(markdown, &conventions, language)?;
 
         if let Some(description) = &function.description {
-            markdown.push_str(&format!("{}\n\n", escape_markdown(description)));
+            write!(markdown, "{}\n\n", escape_markdown(description))?;
         }
 
         // Basic information table
-        let items = vec![
-            ("Return Type", format!("`{}`", function.return_type)),
-            ("Exported", format_yes_no(function.is_exported, use_emoji)),
+        let mut items = vec![
+            (
+                t!("export.return_type", locale = language.code()),
+                format!("`{}`", function.return_type),
+            ),
+            (
+                t!("export.exported", locale = language.code()),
+                format_yes_no(function.is_exported, use_emoji, language),
+            ),
         ];
-        generate_key_value_display(markdown, &items);
+        items.extend(reference_items(
+            references,
+            SymbolKind::Function,
+            name,
+            language,
+        ));
+        generate_key_value_display(markdown, &items)?;
 
         // Parameters
         if !function.arguments.is_empty() {
-            markdown.push_str("\n**Parameters**\n\n");
-            generate_function_arguments_display(markdown, &function.arguments);
+            write!(
+                markdown,
+                "\n**{}**\n\n",
+                t!("export.parameters", locale = language.code())
+            )?;
+            generate_function_arguments_display(markdown, &function.arguments, language)?;
         }
 
         // Definition
         if !function.expression.is_empty() {
-            markdown.push_str("\n**Definition**\n\n");
-            markdown.push_str(&format_code_block(&function.expression));
+            write!(
+                markdown,
+                "\n**{}**\n\n",
+                t!("export.definition", locale = language.code())
+            )?;
+            markdown.write_all(format_code_block(&function.expression).as_bytes())?;
         }
 
-        if !function.metadata.is_empty() {
-            markdown.push_str("\n**Metadata**\n\n");
-            generate_metadata_display_markdown(markdown, &function.metadata);
+        generate_example(markdown, &conventions, language)?;
+
+        let metadata = without_conventions(&function.metadata);
+        if !metadata.is_empty() {
+            write!(
+                markdown,
+                "\n**{}**\n\n",
+                t!("export.metadata", locale = language.code())
+            )?;
+            generate_metadata_display_markdown(markdown, &metadata)?;
         }
 
-        markdown.push('\n');
+        markdown.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Parameters section of the markdown
 fn generate_parameters_section(
-    markdown: &mut String,
+    markdown: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
+    evaluator: &Evaluator,
     use_emoji: bool,
     exclude_empty: bool,
-) {
-    if document.parameters.is_empty() {
+    language: Language,
+) -> io::Result<()> {
+    let sections = parameter_sections(&document.parameters);
+    if sections.is_empty() {
         if !exclude_empty {
-            markdown.push_str(&format!("## {}\n\n", t!("export.parameters")));
-            markdown.push_str(&format!("*{}*\n\n", t!("export.no_parameters_defined")));
+            write!(
+                markdown,
+                "## {}\n\n",
+                t!("export.parameters", locale = language.code())
+            )?;
+            write!(
+                markdown,
+                "*{}*\n\n",
+                t!("export.no_parameters_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
-    markdown.push_str(&format!("## {}\n\n", t!("export.parameters")));
-
-    for (name, parameter) in &document.parameters {
-        markdown.push_str(&format!("### `{name}`\n\n").to_string());
-
-        if let Some(description) = &parameter.description {
-            markdown.push_str(&format!("{}\n\n", escape_markdown(description)));
-        }
-
-        // Metadata comes first if present
-        if !parameter.metadata.is_empty() {
-            markdown.push_str("**Metadata**\n\n");
-            generate_metadata_display_markdown(markdown, &parameter.metadata);
-            markdown.push('\n');
+    write!(
+        markdown,
+        "## {}\n\n",
+        t!("export.parameters", locale = language.code())
+    )?;
+
+    for (group, parameters) in sections {
+        let level = match group {
+            Some(group) => {
+                write!(markdown, "### {}\n\n", escape_markdown(group))?;
+                4
+            },
+            None => 3,
+        };
+        for (name, parameter) in parameters {
+            generate_parameter(
+                markdown, name, parameter, level, references, evaluator, use_emoji, language,
+            )?;
         }
+    }
+    Ok(())
+}
 
-        // Basic information table
-        let mut items = vec![(
-            "Type",
-            format_bicep_type_with_backticks(&parameter.parameter_type),
-        )];
-
-        items.push(("Nullable", format_yes_no(parameter.is_nullable, use_emoji)));
-
-        items.push(("Secure", format_yes_no(parameter.is_secure, use_emoji)));
+/// Generate the documentation of one parameter
+///
+/// # Arguments
+///
+/// * `markdown` - The string buffer to append Markdown content to
+/// * `name` - The name of the parameter
+/// * `parameter` - The parameter to document
+/// * `level` - The heading level of the parameter name (3 for ###, 4 for ####)
+/// * `references` - The reference index, or None when references are not shown
+/// * `evaluator` - The evaluator for effective default values
+/// * `use_emoji` - Whether to use emoji symbols (✅/❌) for Yes/No values
+/// * `language` - The language to write labels in
+#[allow(clippy::too_many_arguments)]
+fn generate_parameter(
+    markdown: &mut dyn Write,
+    name: &str,
+    parameter: &BicepParameter,
+    level: usize,
+    references: Option<&ReferenceIndex>,
+    evaluator: &Evaluator,
+    use_emoji: bool,
+    language: Language,
+) -> io::Result<()> {
+    let conventions = DocConventions::from_metadata(&parameter.metadata);
+    write!(markdown, "{} `{name}`\n\n", "#".repeat(level))?;
+    generate_deprecation_notice(markdown, &conventions, language)?;
+
+    if let Some(description) = localized_description(
+        parameter.description.as_deref(),
+        &parameter.descriptions,
+        language,
+    ) {
+        write!(markdown, "{}\n\n", escape_markdown(description))?;
+    }
 
-        items.push(("Sealed", format_yes_no(parameter.is_sealed, use_emoji)));
+    // Metadata comes first if present
+    let metadata = without_conventions(&parameter.metadata);
+    if !metadata.is_empty() {
+        write!(
+            markdown,
+            "**{}**\n\n",
+            t!("export.metadata", locale = language.code())
+        )?;
+        generate_metadata_display_markdown(markdown, &metadata)?;
+        markdown.write_all(b"\n")?;
+    }
 
-        generate_key_value_display(markdown, &items);
+    // Basic information table
+    let mut items = vec![(
+        t!("export.type_header", locale = language.code()),
+        format_bicep_type_with_backticks(&parameter.parameter_type),
+    )];
+
+    items.push((
+        t!("export.nullable", locale = language.code()),
+        format_yes_no(parameter.is_nullable, use_emoji, language),
+    ));
+
+    items.push((
+        t!("export.secure", locale = language.code()),
+        format_yes_no(parameter.is_secure, use_emoji, language),
+    ));
+
+    items.push((
+        t!("export.sealed", locale = language.code()),
+        format_yes_no(parameter.is_sealed, use_emoji, language),
+    ));
+
+    items.extend(reference_items(
+        references,
+        SymbolKind::Parameter,
+        name,
+        language,
+    ));
+
+    generate_key_value_display(markdown, &items)?;
+
+    // Handle constraints separately
+    let mut constraints = Vec::new();
+    if let Some(min_value) = parameter.min_value {
+        constraints.push((
+            t!("export.minimum_value", locale = language.code()),
+            format_constraint_value(&min_value.to_string()),
+        ));
+    }
 
-        // Handle constraints separately
-        let mut constraints = Vec::new();
-        if let Some(min_value) = parameter.min_value {
-            constraints.push((
-                "Minimum Value",
-                format_constraint_value(&min_value.to_string()),
-            ));
-        }
+    if let Some(max_value) = parameter.max_value {
+        constraints.push((
+            t!("export.maximum_value", locale = language.code()),
+            format_constraint_value(&max_value.to_string()),
+        ));
+    }
 
-        if let Some(max_value) = parameter.max_value {
-            constraints.push((
-                "Maximum Value",
-                format_constraint_value(&max_value.to_string()),
-            ));
-        }
+    if let Some(min_length) = parameter.min_length {
+        constraints.push((
+            t!("export.minimum_length", locale = language.code()),
+            format_constraint_value(&min_length.to_string()),
+        ));
+    }
 
-        if let Some(min_length) = parameter.min_length {
-            constraints.push((
-                "Minimum Length",
-                format_constraint_value(&min_length.to_string()),
-            ));
-        }
+    if let Some(max_length) = parameter.max_length {
+        constraints.push((
+            t!("export.maximum_length", locale = language.code()),
+            format_constraint_value(&max_length.to_string()),
+        ));
+    }
 
-        if let Some(max_length) = parameter.max_length {
+    if let Some(allowed_values) = &parameter.allowed_values {
+        if !allowed_values.is_empty() {
             constraints.push((
-                "Maximum Length",
-                format_constraint_value(&max_length.to_string()),
+                t!("export.allowed_values", locale = language.code()),
+                format_bicep_array_as_list(allowed_values),
             ));
         }
+    }
 
-        if let Some(allowed_values) = &parameter.allowed_values {
-            if !allowed_values.is_empty() {
-                constraints.push(("Allowed Values", format_bicep_array_as_list(allowed_values)));
-            }
-        }
-
-        if !constraints.is_empty() {
-            markdown.push_str("\n**Constraints**\n\n");
-            generate_key_value_display(markdown, &constraints);
-        }
+    if !constraints.is_empty() {
+        write!(
+            markdown,
+            "\n**{}**\n\n",
+            t!("export.constraints", locale = language.code())
+        )?;
+        generate_key_value_display(markdown, &constraints)?;
+    }
 
-        if let Some(default_value) = &parameter.default_value {
-            markdown.push_str("\n**Default Value**\n\n");
-            markdown.push_str(&format_code_block(&default_value.pretty_format()));
+    if let Some(default_value) = &parameter.default_value {
+        write!(
+            markdown,
+            "\n**{}**\n\n",
+            t!("export.default_value", locale = language.code())
+        )?;
+        markdown.write_all(format_code_block(&default_value.pretty_format()).as_bytes())?;
+
+        if let Some(effective) = evaluator.effective_value(default_value) {
+            write!(
+                markdown,
+                "\n**{}**\n\n",
+                t!("export.effective_default_value", locale = language.code())
+            )?;
+            markdown.write_all(format_code_block(&effective.pretty_format()).as_bytes())?;
         }
+    }
 
-        // Object properties for object types
-        if let BicepType::Object(Some(properties)) = &parameter.parameter_type {
-            if !properties.is_empty() {
-                markdown.push_str("\n**Object Definition**\n\n");
-                generate_nested_object_properties(markdown, properties, 4, use_emoji);
-            }
+    generate_example(markdown, &conventions, language)?;
+
+    // Object properties for object types
+    if let BicepType::Object(Some(properties)) = &parameter.parameter_type {
+        if !properties.is_empty() {
+            write!(
+                markdown,
+                "\n**{}**\n\n",
+                t!("export.object_definition", locale = language.code())
+            )?;
+            generate_nested_object_properties(
+                markdown,
+                properties,
+                level + 1,
+                use_emoji,
+                language,
+            )?;
         }
-
-        markdown.push('\n');
     }
+
+    markdown.write_all(b"\n")?;
+    Ok(())
 }
 
 /// Generate nested object properties recursively for Markdown
@@ -486,344 +1091,593 @@ fn generate_parameters_section(
 /// * `markdown` - The string buffer to append Markdown content to
 /// * `properties` - The object properties to document
 /// * `header_level` - The header level to use (4 for #### level, 5 for ##### level, etc.)
+/// * `language` - The language to write labels in
 fn generate_nested_object_properties(
-    markdown: &mut String,
+    markdown: &mut dyn Write,
     properties: &indexmap::IndexMap<String, crate::parsing::BicepParameter>,
     header_level: usize,
     use_emoji: bool,
-) {
+    language: Language,
+) -> io::Result<()> {
     let header_prefix = "#".repeat(header_level);
 
     for (prop_name, prop_param) in properties {
-        markdown.push_str(&format!("{header_prefix} `{prop_name}`\n\n").to_string());
-
-        if let Some(description) = &prop_param.description {
-            markdown.push_str(&format!("{}\n\n", escape_markdown(description)));
+        let conventions = DocConventions::from_metadata(&prop_param.metadata);
+        if conventions.hidden {
+            continue;
+        }
+        write!(markdown, "{header_prefix} `{prop_name}`\n\n")?;
+        generate_deprecation_notice(markdown, &conventions, language)?;
+
+        if let Some(description) = localized_description(
+            prop_param.description.as_deref(),
+            &prop_param.descriptions,
+            language,
+        ) {
+            write!(markdown, "{}\n\n", escape_markdown(description))?;
         }
 
         let mut prop_items = vec![(
-            "Type",
+            t!("export.type_header", locale = language.code()),
             format_bicep_type_with_backticks(&prop_param.parameter_type),
         )];
 
-        prop_items.push(("Nullable", format_yes_no(prop_param.is_nullable, use_emoji)));
-        prop_items.push(("Secure", format_yes_no(prop_param.is_secure, use_emoji)));
+        prop_items.push((
+            t!("export.nullable", locale = language.code()),
+            format_yes_no(prop_param.is_nullable, use_emoji, language),
+        ));
+        prop_items.push((
+            t!("export.secure", locale = language.code()),
+            format_yes_no(prop_param.is_secure, use_emoji, language),
+        ));
 
-        generate_key_value_display(markdown, &prop_items);
+        generate_key_value_display(markdown, &prop_items)?;
 
         // Handle constraints separately
         let mut constraints = Vec::new();
         if let Some(min_value) = prop_param.min_value {
             constraints.push((
-                "Minimum Value",
+                t!("export.minimum_value", locale = language.code()),
                 format_constraint_value(&min_value.to_string()),
             ));
         }
         if let Some(max_value) = prop_param.max_value {
             constraints.push((
-                "Maximum Value",
+                t!("export.maximum_value", locale = language.code()),
                 format_constraint_value(&max_value.to_string()),
             ));
         }
         if let Some(min_length) = prop_param.min_length {
             constraints.push((
-                "Minimum Length",
+                t!("export.minimum_length", locale = language.code()),
                 format_constraint_value(&min_length.to_string()),
             ));
         }
         if let Some(max_length) = prop_param.max_length {
             constraints.push((
-                "Maximum Length",
+                t!("export.maximum_length", locale = language.code()),
                 format_constraint_value(&max_length.to_string()),
             ));
         }
         if let Some(allowed_values) = &prop_param.allowed_values {
             if !allowed_values.is_empty() {
-                prop_items.push(("Allowed Values", format_bicep_array_as_list(allowed_values)));
+                prop_items.push((
+                    t!("export.allowed_values", locale = language.code()),
+                    format_bicep_array_as_list(allowed_values),
+                ));
             }
         }
 
         if !constraints.is_empty() {
-            markdown.push_str("\n**Constraints**\n\n");
-            generate_key_value_display(markdown, &constraints);
+            write!(
+                markdown,
+                "\n**{}**\n\n",
+                t!("export.constraints", locale = language.code())
+            )?;
+            generate_key_value_display(markdown, &constraints)?;
         }
 
         if let Some(default_value) = &prop_param.default_value {
-            markdown.push_str("\n**Default Value**\n\n");
-            markdown.push_str(&format_code_block(&default_value.pretty_format()));
+            write!(
+                markdown,
+                "\n**{}**\n\n",
+                t!("export.default_value", locale = language.code())
+            )?;
+            markdown.write_all(format_code_block(&default_value.pretty_format()).as_bytes())?;
         }
 
+        generate_example(markdown, &conventions, language)?;
+
         // Recursively handle nested object properties (limit depth to avoid infinite recursion)
         if header_level < 7 {
             if let BicepType::Object(Some(nested_properties)) = &prop_param.parameter_type {
                 if !nested_properties.is_empty() {
-                    markdown.push_str("\n**Object Definition**\n\n");
+                    write!(
+                        markdown,
+                        "\n**{}**\n\n",
+                        t!("export.object_definition", locale = language.code())
+                    )?;
                     generate_nested_object_properties(
                         markdown,
                         nested_properties,
                         header_level + 1,
                         use_emoji,
-                    );
+                        language,
+                    )?;
                 }
             }
         }
 
-        markdown.push('\n');
+        markdown.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Variables section of the markdown
 fn generate_variables_section(
-    markdown: &mut String,
+    markdown: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
+    evaluator: &Evaluator,
     use_emoji: bool,
     exclude_empty: bool,
-) {
+    language: Language,
+) -> io::Result<()> {
     if document.variables.is_empty() {
         if !exclude_empty {
-            markdown.push_str(&format!("## {}\n\n", t!("export.variables")));
-            markdown.push_str(&format!("*{}*\n\n", t!("export.no_variables_defined")));
+            write!(
+                markdown,
+                "## {}\n\n",
+                t!("export.variables", locale = language.code())
+            )?;
+            write!(
+                markdown,
+                "*{}*\n\n",
+                t!("export.no_variables_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
-    markdown.push_str(&format!("## {}\n\n", t!("export.variables")));
+    write!(
+        markdown,
+        "## {}\n\n",
+        t!("export.variables", locale = language.code())
+    )?;
 
     for (name, variable) in &document.variables {
-        markdown.push_str(&format!("### `{name}`\n\n").to_string());
+        write!(markdown, "### `{name}`\n\n")?;
 
         if let Some(description) = &variable.description {
-            markdown.push_str(&format!("{}\n\n", escape_markdown(description)));
+            write!(markdown, "{}\n\n", escape_markdown(description))?;
         }
 
         // Basic information table
-        let items = vec![("Exported", format_yes_no(variable.is_exported, use_emoji))];
-        generate_key_value_display(markdown, &items);
+        let mut items = vec![(
+            t!("export.exported", locale = language.code()),
+            format_yes_no(variable.is_exported, use_emoji, language),
+        )];
+        items.extend(reference_items(
+            references,
+            SymbolKind::Variable,
+            name,
+            language,
+        ));
+        generate_key_value_display(markdown, &items)?;
 
         // Value
-        markdown.push_str("\n**Value**\n\n");
-        markdown.push_str(&format_code_block(&variable.value.pretty_format()));
+        write!(
+            markdown,
+            "\n**{}**\n\n",
+            t!("export.value", locale = language.code())
+        )?;
+        markdown.write_all(format_code_block(&variable.value.pretty_format()).as_bytes())?;
+
+        if let Some(effective) = evaluator.effective_value(&variable.value) {
+            write!(
+                markdown,
+                "\n**{}**\n\n",
+                t!("export.effective_value", locale = language.code())
+            )?;
+            markdown.write_all(format_code_block(&effective.pretty_format()).as_bytes())?;
+        }
 
-        markdown.push('\n');
+        markdown.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Resources section of the markdown
 fn generate_resources_section(
-    markdown: &mut String,
+    markdown: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
+    evaluator: &Evaluator,
     use_emoji: bool,
     exclude_empty: bool,
-) {
+    language: Language,
+) -> io::Result<()> {
     if document.resources.is_empty() {
         if !exclude_empty {
-            markdown.push_str(&format!("## {}\n\n", t!("export.resources")));
-            markdown.push_str(&format!("*{}*\n\n", t!("export.no_resources_defined")));
+            write!(
+                markdown,
+                "## {}\n\n",
+                t!("export.resources", locale = language.code())
+            )?;
+            write!(
+                markdown,
+                "*{}*\n\n",
+                t!("export.no_resources_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
-    markdown.push_str(&format!("## {}\n\n", t!("export.resources")));
+    write!(
+        markdown,
+        "## {}\n\n",
+        t!("export.resources", locale = language.code())
+    )?;
 
     for (name, resource) in &document.resources {
-        markdown.push_str(&format!("### `{name}`\n\n").to_string());
-
-        if let Some(description) = &resource.description {
-            markdown.push_str(&format!("{}\n\n", escape_markdown(description)));
+        write!(markdown, "### `{name}`\n\n")?;
+
+        if let Some(description) = localized_description(
+            resource.description.as_deref(),
+            &resource.descriptions,
+            language,
+        ) {
+            write!(markdown, "{}\n\n", escape_markdown(description))?;
         }
 
         // Basic information table
         let mut items = vec![
-            ("Name", format!("`{}`", resource.name)),
-            ("Type", format!("`{}`", resource.resource_type)),
-            ("API Version", format!("`{}`", resource.api_version)),
+            (
+                t!("export.name_header", locale = language.code()),
+                format!("`{}`", resource.name),
+            ),
+            (
+                t!("export.type_header", locale = language.code()),
+                format!("`{}`", resource.resource_type),
+            ),
+            (
+                t!("export.api_version", locale = language.code()),
+                format!("`{}`", resource.api_version),
+            ),
         ];
 
+        if let Some(effective) =
+            evaluator.effective_value(&BicepValue::String(resource.name.clone()))
+        {
+            items.insert(
+                1,
+                (
+                    t!("export.effective_name", locale = language.code()),
+                    format!("`{}`", effective.pretty_format()),
+                ),
+            );
+        }
+
         if let Some(scope) = &resource.scope {
             let scope_str = scope.to_string();
-            items.push(("Scope", format!("`{scope_str}`").to_string()));
+            items.push((
+                t!("export.scope", locale = language.code()),
+                format!("`{scope_str}`").to_string(),
+            ));
         }
 
         if resource.existing {
-            items.push(("Existing", format_yes_no(true, use_emoji)));
+            items.push((
+                t!("export.existing", locale = language.code()),
+                format_yes_no(true, use_emoji, language),
+            ));
         }
 
         if let Some(parent) = &resource.parent {
-            items.push(("Parent", format!("`{}`", parent.clone())));
+            items.push((
+                t!("export.parent", locale = language.code()),
+                format!("`{}`", parent.clone()),
+            ));
         }
 
-        if let Some(depends_on) = &resource.depends_on {
+        // The reference analysis includes explicit dependencies, so it replaces them when shown
+        if references.is_some() {
+            items.extend(reference_items(
+                references,
+                SymbolKind::Resource,
+                name,
+                language,
+            ));
+        } else if let Some(depends_on) = &resource.depends_on {
             if !depends_on.is_empty() {
                 let deps = depends_on
                     .iter()
                     .map(|v| format!("`{v}`").to_string())
                     .collect::<Vec<_>>()
                     .join("  \n");
-                items.push(("Depends On", deps));
+                items.push((t!("export.depends_on", locale = language.code()), deps));
             }
         }
 
         if let Some(batch_size) = resource.batch_size {
-            items.push(("Batch Size", format!("`{batch_size}`").to_string()));
+            items.push((
+                t!("export.batch_size", locale = language.code()),
+                format!("`{batch_size}`").to_string(),
+            ));
         }
 
         if let Some(condition) = &resource.condition {
             let condition_block = format_code_block(condition);
-            items.push(("Condition", format!("  \n{condition_block}")));
+            items.push((
+                t!("export.condition", locale = language.code()),
+                format!("  \n{condition_block}"),
+            ));
         }
 
         if let Some(loop_statement) = &resource.loop_statement {
             let loop_block = format_code_block(loop_statement);
-            items.push(("Loop", format!("  \n{loop_block}")));
+            items.push((
+                t!("export.loop", locale = language.code()),
+                format!("  \n{loop_block}"),
+            ));
         }
 
-        generate_key_value_display(markdown, &items);
+        generate_key_value_display(markdown, &items)?;
 
-        markdown.push('\n');
+        markdown.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Modules section of the markdown
 fn generate_modules_section(
-    markdown: &mut String,
+    markdown: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
     _use_emoji: bool,
     exclude_empty: bool,
-) {
+    language: Language,
+) -> io::Result<()> {
     if document.modules.is_empty() {
         if !exclude_empty {
-            markdown.push_str(&format!("## {}\n\n", t!("export.modules")));
-            markdown.push_str(&format!("*{}*\n\n", t!("export.no_modules_defined")));
+            write!(
+                markdown,
+                "## {}\n\n",
+                t!("export.modules", locale = language.code())
+            )?;
+            write!(
+                markdown,
+                "*{}*\n\n",
+                t!("export.no_modules_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
-    markdown.push_str(&format!("## {}\n\n", t!("export.modules")));
+    write!(
+        markdown,
+        "## {}\n\n",
+        t!("export.modules", locale = language.code())
+    )?;
 
     for (name, module) in &document.modules {
-        markdown.push_str(&format!("### {name}\n\n").to_string());
+        write!(markdown, "### {name}\n\n")?;
 
         if let Some(description) = &module.description {
-            markdown.push_str(&format!("{}\n\n", escape_markdown(description)));
+            write!(markdown, "{}\n\n", escape_markdown(description))?;
         }
 
         // Basic information table
         let mut items = vec![
-            ("Source", format!(" `{}`", module.source)),
-            ("Name", module.name.clone()),
+            (
+                t!("export.source_header", locale = language.code()),
+                format!(" `{}`", module.source),
+            ),
+            (
+                t!("export.name_header", locale = language.code()),
+                module.name.clone(),
+            ),
         ];
 
-        if let Some(depends_on) = &module.depends_on {
+        if references.is_some() {
+            items.extend(reference_items(
+                references,
+                SymbolKind::Module,
+                name,
+                language,
+            ));
+        } else if let Some(depends_on) = &module.depends_on {
             if !depends_on.is_empty() {
                 let deps = depends_on.join(", ");
-                items.push(("Depends On", deps));
+                items.push((t!("export.depends_on", locale = language.code()), deps));
             }
         }
 
         if let Some(batch_size) = module.batch_size {
-            items.push(("Batch Size", format!("`{batch_size}`").to_string()));
+            items.push((
+                t!("export.batch_size", locale = language.code()),
+                format!("`{batch_size}`").to_string(),
+            ));
         }
 
         if let Some(condition) = &module.condition {
             let condition_block = format_code_block(condition);
-            items.push(("Condition", format!("  \n{condition_block}")));
+            items.push((
+                t!("export.condition", locale = language.code()),
+                format!("  \n{condition_block}"),
+            ));
         }
 
         if let Some(loop_statement) = &module.loop_statement {
             let loop_block = format_code_block(loop_statement);
-            items.push(("Loop", format!("  \n{loop_block}")));
+            items.push((
+                t!("export.loop", locale = language.code()),
+                format!("  \n{loop_block}"),
+            ));
         }
 
-        generate_key_value_display(markdown, &items);
+        generate_key_value_display(markdown, &items)?;
 
-        markdown.push('\n');
+        markdown.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate the Outputs section of the markdown
 fn generate_outputs_section(
-    markdown: &mut String,
+    markdown: &mut dyn Write,
     document: &BicepDocument,
+    references: Option<&ReferenceIndex>,
+    evaluator: &Evaluator,
     use_emoji: bool,
     exclude_empty: bool,
-) {
-    if document.outputs.is_empty() {
+    language: Language,
+) -> io::Result<()> {
+    if document
+        .outputs
+        .values()
+        .all(|output| output.metadata.as_ref().is_some_and(is_hidden))
+    {
         if !exclude_empty {
-            markdown.push_str(&format!("## {}\n\n", t!("export.outputs")));
-            markdown.push_str(&format!("*{}*\n\n", t!("export.no_outputs_defined")));
+            write!(
+                markdown,
+                "## {}\n\n",
+                t!("export.outputs", locale = language.code())
+            )?;
+            write!(
+                markdown,
+                "*{}*\n\n",
+                t!("export.no_outputs_defined", locale = language.code())
+            )?;
         }
-        return;
+        return Ok(());
     }
 
-    markdown.push_str(&format!("## {}\n\n", t!("export.outputs")));
+    write!(
+        markdown,
+        "## {}\n\n",
+        t!("export.outputs", locale = language.code())
+    )?;
 
+    let no_metadata = IndexMap::new();
     for (name, output) in &document.outputs {
-        markdown.push_str(&format!("### `{name}`\n\n").to_string());
-
-        if let Some(description) = &output.description {
-            markdown.push_str(&format!("{}\n\n", escape_markdown(description)));
+        let conventions =
+            DocConventions::from_metadata(output.metadata.as_ref().unwrap_or(&no_metadata));
+        if conventions.hidden {
+            continue;
+        }
+        write!(markdown, "### `{name}`\n\n")?;
+        generate_deprecation_notice(markdown, &conventions, language)?;
+
+        if let Some(description) = localized_description(
+            output.description.as_deref(),
+            &output.descriptions,
+            language,
+        ) {
+            write!(markdown, "{}\n\n", escape_markdown(description))?;
         }
 
         // Basic information table
         let mut items = vec![(
-            "Type",
+            t!("export.type_header", locale = language.code()),
             format_bicep_type_with_backticks(&output.output_type),
         )];
 
         if let Some(discriminator) = &output.discriminator {
-            items.push(("Discriminator", discriminator.clone()));
+            items.push((
+                t!("export.discriminator", locale = language.code()),
+                discriminator.clone(),
+            ));
         }
 
-        items.push(("Sealed", format_yes_no(output.sealed, use_emoji)));
-        items.push(("Secure", format_yes_no(output.secure, use_emoji)));
+        items.push((
+            t!("export.sealed", locale = language.code()),
+            format_yes_no(output.sealed, use_emoji, language),
+        ));
+        items.push((
+            t!("export.secure", locale = language.code()),
+            format_yes_no(output.secure, use_emoji, language),
+        ));
 
-        generate_key_value_display(markdown, &items);
+        items.extend(reference_items(
+            references,
+            SymbolKind::Output,
+            name,
+            language,
+        ));
+
+        generate_key_value_display(markdown, &items)?;
 
         // Handle constraints separately
         let mut constraints = Vec::new();
         if let Some(min_length) = output.min_length {
             constraints.push((
-                "Minimum Length",
+                t!("export.minimum_length", locale = language.code()),
                 format_constraint_value(&min_length.to_string()),
             ));
         }
         if let Some(max_length) = output.max_length {
             constraints.push((
-                "Maximum Length",
+                t!("export.maximum_length", locale = language.code()),
                 format_constraint_value(&max_length.to_string()),
             ));
         }
         if let Some(min_value) = output.min_value {
             constraints.push((
-                "Minimum Value",
+                t!("export.minimum_value", locale = language.code()),
                 format_constraint_value(&min_value.to_string()),
             ));
         }
         if let Some(max_value) = output.max_value {
             constraints.push((
-                "Maximum Value",
+                t!("export.maximum_value", locale = language.code()),
                 format_constraint_value(&max_value.to_string()),
             ));
         }
 
         if !constraints.is_empty() {
-            markdown.push_str("\n**Constraints**\n\n");
-            generate_key_value_display(markdown, &constraints);
+            write!(
+                markdown,
+                "\n**{}**\n\n",
+                t!("export.constraints", locale = language.code())
+            )?;
+            generate_key_value_display(markdown, &constraints)?;
         }
 
         // Value in code block
-        markdown.push_str("\n**Value**\n\n");
-        markdown.push_str(&format_code_block(&output.value.pretty_format()));
+        write!(
+            markdown,
+            "\n**{}**\n\n",
+            t!("export.value", locale = language.code())
+        )?;
+        markdown.write_all(format_code_block(&output.value.pretty_format()).as_bytes())?;
+
+        if let Some(effective) = evaluator.effective_value(&output.value) {
+            write!(
+                markdown,
+                "\n**{}**\n\n",
+                t!("export.effective_value", locale = language.code())
+            )?;
+            markdown.write_all(format_code_block(&effective.pretty_format()).as_bytes())?;
+        }
+
+        generate_example(markdown, &conventions, language)?;
 
         if let Some(metadata) = &output.metadata {
+            let metadata = without_conventions(metadata);
             if !metadata.is_empty() {
-                markdown.push_str("\n**Metadata**\n\n");
-                generate_metadata_display_markdown(markdown, metadata);
+                write!(
+                    markdown,
+                    "\n**{}**\n\n",
+                    t!("export.metadata", locale = language.code())
+                )?;
+                generate_metadata_display_markdown(markdown, &metadata)?;
             }
         }
 
-        markdown.push('\n');
+        markdown.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Format a constraint value with backticks for display in markdown
@@ -836,11 +1690,134 @@ fn format_code_block(value: &str) -> String {
     format!("```bicep\n{value}\n```\n").to_string()
 }
 
+/// Generate the deprecation banner of a declaration, if it is deprecated
+///
+/// # Arguments
+///
+/// * `markdown` - The string buffer to append Markdown content to
+/// * `conventions` - The documentation conventions of the declaration
+/// * `language` - The language to write labels in
+fn generate_deprecation_notice(
+    markdown: &mut dyn Write,
+    conventions: &DocConventions,
+    language: Language,
+) -> io::Result<()> {
+    let Some(deprecation) = conventions.deprecation else {
+        return Ok(());
+    };
+
+    let label = match deprecation.since {
+        Some(version) => t!(
+            "export.deprecated_since",
+            locale = language.code(),
+            version = version
+        ),
+        None => t!("export.deprecated", locale = language.code()),
+    };
+    match deprecation.message {
+        Some(message) => write!(markdown, "> **{label}:** {}", escape_markdown(message))?,
+        None => write!(markdown, "> **{label}**")?,
+    }
+    if let Some(replacement) = deprecation.replacement {
+        let replacement = format!("`{replacement}`");
+        write!(
+            markdown,
+            "  \n> {}",
+            t!(
+                "export.use_instead",
+                locale = language.code(),
+                replacement = replacement
+            )
+        )?;
+    }
+    markdown.write_all(b"\n\n")
+}
+
+/// Generate the usage example of a declaration, if it has one
+///
+/// # Arguments
+///
+/// * `markdown` - The string buffer to append Markdown content to
+/// * `conventions` - The documentation conventions of the declaration
+/// * `language` - The language to write labels in
+fn generate_example(
+    markdown: &mut dyn Write,
+    conventions: &DocConventions,
+    language: Language,
+) -> io::Result<()> {
+    if let Some(example) = conventions.example {
+        write!(
+            markdown,
+            "\n**{}**\n\n",
+            t!("export.example", locale = language.code())
+        )?;
+        markdown.write_all(format_code_block(&example_source(example)).as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Build the "Depends On" and "Used By" rows for a symbol
+///
+/// Parameters and variables that nothing references are marked as unused.
+///
+/// # Arguments
+///
+/// * `references` - The reference index, or None when references are not shown
+/// * `kind` - The kind of the symbol
+/// * `name` - The name of the symbol
+/// * `language` - The language to write labels in
+fn reference_items(
+    references: Option<&ReferenceIndex>,
+    kind: SymbolKind,
+    name: &str,
+    language: Language,
+) -> Vec<(Cow<'static, str>, String)> {
+    let Some(references) = references else {
+        return Vec::new();
+    };
+
+    let format_symbols = |symbols: &[SymbolId]| {
+        symbols
+            .iter()
+            .map(|symbol| format!("`{symbol}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut items = Vec::new();
+    let depends_on = references.depends_on(kind, name);
+    if !depends_on.is_empty() {
+        items.push((
+            t!("export.depends_on", locale = language.code()),
+            format_symbols(depends_on),
+        ));
+    }
+
+    let used_by = references.used_by(kind, name);
+    if !used_by.is_empty() {
+        items.push((
+            t!("export.used_by", locale = language.code()),
+            format_symbols(used_by),
+        ));
+    } else if matches!(kind, SymbolKind::Parameter | SymbolKind::Variable) {
+        items.push((
+            t!("export.used_by", locale = language.code()),
+            format!("*{}*", t!("export.unused", locale = language.code())),
+        ));
+    }
+
+    items
+}
+
 /// Generate key-value property display
-fn generate_key_value_display(markdown: &mut String, items: &[(&str, String)]) {
+fn generate_key_value_display(
+    markdown: &mut dyn Write,
+    items: &[(Cow<'static, str>, String)],
+) -> io::Result<()> {
     for (key, value) in items {
-        markdown.push_str(&format!("**{key}:** {value}  \n").to_string());
+        writeln!(markdown, "**{key}:** {value}  ")?;
     }
+    Ok(())
 }
 
 /// Generate display for function arguments
@@ -849,30 +1826,36 @@ fn generate_key_value_display(markdown: &mut String, items: &[(&str, String)]) {
 ///
 /// * `markdown` - The string buffer to append markdown content to
 /// * `arguments` - The function arguments to display
-fn generate_function_arguments_display(markdown: &mut String, arguments: &[BicepFunctionArgument]) {
+/// * `language` - The language to write labels in
+fn generate_function_arguments_display(
+    markdown: &mut dyn Write,
+    arguments: &[BicepFunctionArgument],
+    language: Language,
+) -> io::Result<()> {
     for arg in arguments {
-        let optional_text = if arg.is_nullable { " (Optional)" } else { "" };
-        markdown.push_str(&format!(
-            "**{}:** {}{}\n",
+        let optional_text = if arg.is_nullable {
+            format!(" ({})", t!("export.optional", locale = language.code()))
+        } else {
+            String::new()
+        };
+        writeln!(
+            markdown,
+            "**{}:** {}{}",
             &arg.name,
             format_bicep_type_with_backticks(&arg.argument_type),
             optional_text
-        ));
+        )?;
     }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::parsing::{BicepDocument, BicepParameter, BicepType, BicepValue};
-    use serial_test::serial;
 
     #[test]
-    #[serial]
     fn test_export_to_string_basic() {
-        // Initialize localization for testing
-        crate::localization::init_localization(crate::localization::Language::English);
-
         let document = BicepDocument {
             name: Some("Test Template".to_string()),
             description: Some("A test template for unit testing".to_string()),
@@ -880,7 +1863,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = export_to_string(&document, true, false);
+        let result = export_to_string(&document, &ExportOptions::builder().use_emoji(true).build());
         assert!(result.is_ok());
 
         let markdown = result.unwrap();
@@ -889,18 +1872,23 @@ mod tests {
         assert!(markdown.contains("resourceGroup"));
 
         // When exclude_empty is false, empty sections should be present
-        assert!(markdown.contains(&format!("## {}", crate::t!("export.parameters"))));
-        assert!(markdown.contains(&crate::t!("export.no_parameters_defined").to_string()));
-        assert!(markdown.contains(&format!("## {}", crate::t!("export.resources"))));
-        assert!(markdown.contains(&crate::t!("export.no_resources_defined").to_string()));
+        assert!(markdown.contains(&format!(
+            "## {}",
+            crate::t!("export.parameters", locale = "en")
+        )));
+        assert!(markdown
+            .contains(&crate::t!("export.no_parameters_defined", locale = "en").to_string()));
+        assert!(markdown.contains(&format!(
+            "## {}",
+            crate::t!("export.resources", locale = "en")
+        )));
+        assert!(
+            markdown.contains(&crate::t!("export.no_resources_defined", locale = "en").to_string())
+        );
     }
 
     #[test]
-    #[serial]
     fn test_export_to_string_with_parameters() {
-        // Initialize localization for testing
-        crate::localization::init_localization(crate::localization::Language::English);
-
         let parameter = BicepParameter {
             parameter_type: BicepType::String,
             description: Some("Test parameter".to_string()),
@@ -916,22 +1904,142 @@ mod tests {
             .parameters
             .insert("testParam".to_string(), parameter);
 
-        let result = export_to_string(&document, true, false);
+        let result = export_to_string(&document, &ExportOptions::builder().use_emoji(true).build());
         assert!(result.is_ok());
 
         let markdown = result.unwrap();
-        assert!(markdown.contains(&format!("## {}", crate::t!("export.parameters"))));
+        assert!(markdown.contains(&format!(
+            "## {}",
+            crate::t!("export.parameters", locale = "en")
+        )));
         assert!(markdown.contains("### `testParam`"));
         assert!(markdown.contains("Test parameter"));
         assert!(markdown.contains("default"));
     }
 
     #[test]
-    #[serial]
-    fn test_export_to_string_with_exclude_empty() {
-        // Initialize localization for testing
-        crate::localization::init_localization(crate::localization::Language::English);
+    fn test_export_to_string_in_several_languages() {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "count".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Int,
+                min_value: Some(1),
+                ..Default::default()
+            },
+        );
+
+        let english = export_to_string(&document, &ExportOptions::default()).unwrap();
+        let french = export_to_string(
+            &document,
+            &ExportOptions::builder().language(Language::French).build(),
+        )
+        .unwrap();
+        let german = export_to_string(
+            &document,
+            &ExportOptions::builder().language(Language::German).build(),
+        )
+        .unwrap();
+
+        assert!(english.contains("## Parameters"));
+        assert!(english.contains("**Secure:** No"));
+        assert!(english.contains("**Minimum Value:** `1`"));
+        assert!(french.contains("## Paramètres"));
+        assert!(french.contains("**Sécurisé:** Non"));
+        assert!(!french.contains("Constraints"));
+        assert!(german.contains("**Sicher:** Nein"));
+        assert!(german.contains("**Einschränkungen**"));
+    }
+
+    #[test]
+    fn test_export_to_string_with_localized_descriptions() {
+        let document = crate::parse_arm_document(
+            r#"{
+                "$schema": "https://schema.management.azure.com/schemas/2019-04-01/deploymentTemplate.json#",
+                "metadata": { "description": "Storage account", "description_fr": "Compte de stockage" },
+                "parameters": {
+                    "name": {
+                        "type": "string",
+                        "metadata": {
+                            "description": "Account name",
+                            "descriptions": { "fr": "Nom du compte", "de": "Kontoname" },
+                            "owner": "platform"
+                        }
+                    }
+                },
+                "resources": []
+            }"#,
+        )
+        .unwrap();
+
+        let english = export_to_string(&document, &ExportOptions::default()).unwrap();
+        let french = export_to_string(
+            &document,
+            &ExportOptions::builder().language(Language::French).build(),
+        )
+        .unwrap();
+        let japanese = export_to_string(
+            &document,
+            &ExportOptions::builder()
+                .language(Language::Japanese)
+                .build(),
+        )
+        .unwrap();
+
+        assert!(english.contains("Storage account"));
+        assert!(english.contains("Account name"));
+        assert!(french.contains("Compte de stockage"));
+        assert!(french.contains("Nom du compte"));
+        assert!(!french.contains("Account name"));
+        assert!(japanese.contains("Account name"));
+        assert!(!english.contains("descriptions"));
+        assert!(english.contains("platform"));
+    }
+
+    #[test]
+    fn test_export_to_string_with_metadata_conventions() {
+        let document = crate::parse_arm_document(
+            r#"{
+                "$schema": "https://schema.management.azure.com/schemas/2019-04-01/deploymentTemplate.json#",
+                "parameters": {
+                    "name": { "type": "string", "metadata": { "example": "'stcontoso'", "owner": "platform" } },
+                    "skuName": { "type": "string", "metadata": { "group": "Pricing" } },
+                    "location": { "type": "string", "metadata": { "order": 1 } },
+                    "legacySku": {
+                        "type": "string",
+                        "metadata": { "deprecated": "Set by policy", "deprecatedSince": "2.0", "replacement": "skuName", "group": "Pricing" }
+                    },
+                    "internalTag": { "type": "string", "metadata": { "docs": { "hidden": true } } }
+                },
+                "resources": [],
+                "outputs": {
+                    "debug": { "type": "string", "value": "x", "metadata": { "docs.hidden": true } }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let markdown = export_to_string(
+            &document,
+            &ExportOptions::builder().exclude_empty(true).build(),
+        )
+        .unwrap();
+
+        let position = |text: &str| markdown.find(text).unwrap();
+        assert!(position("### `location`") < position("### `name`"));
+        assert!(position("### `name`") < position("### Pricing"));
+        assert!(position("### Pricing") < position("#### `skuName`"));
+        assert!(markdown.contains("**Example**\n\n```bicep\n'stcontoso'\n```"));
+        assert!(markdown
+            .contains("> **Deprecated since 2.0:** Set by policy  \n> Use `skuName` instead."));
+        assert!(markdown.contains("| owner | platform |"));
+        assert!(!markdown.contains("| example |"));
+        assert!(!markdown.contains("internalTag"));
+        assert!(!markdown.contains("## Outputs"));
+    }
 
+    #[test]
+    fn test_export_to_string_with_exclude_empty() {
         // Create a document with some empty collections and one non-empty collection
         let mut document = BicepDocument {
             name: Some("Test Template".to_string()),
@@ -950,22 +2058,176 @@ mod tests {
             .insert("testParam".to_string(), parameter);
 
         // Test with exclude_empty = true
-        let result = export_to_string(&document, true, true).unwrap();
+        let result = export_to_string(
+            &document,
+            &ExportOptions::builder()
+                .use_emoji(true)
+                .exclude_empty(true)
+                .build(),
+        )
+        .unwrap();
 
         // Should contain the document name and the parameter section
         assert!(result.contains("# Test Template"));
-        assert!(result.contains(&format!("## {}", crate::t!("export.parameters"))));
+        assert!(result.contains(&format!(
+            "## {}",
+            crate::t!("export.parameters", locale = "en")
+        )));
         assert!(result.contains("### `testParam`"));
 
         // Should NOT contain empty sections
-        assert!(!result.contains(&format!("## {}", crate::t!("export.resources"))));
-        assert!(!result.contains(&crate::t!("export.no_resources_defined").to_string()));
-        assert!(!result.contains(&format!("## {}", crate::t!("export.variables"))));
-        assert!(!result.contains(&crate::t!("export.no_variables_defined").to_string()));
-        assert!(!result.contains(&format!("## {}", crate::t!("export.modules"))));
-        assert!(!result.contains(&crate::t!("export.no_modules_defined").to_string()));
-        assert!(!result.contains(&format!("## {}", crate::t!("export.outputs"))));
-        assert!(!result.contains(&crate::t!("export.no_outputs_defined").to_string()));
+        assert!(!result.contains(&format!(
+            "## {}",
+            crate::t!("export.resources", locale = "en")
+        )));
+        assert!(
+            !result.contains(&crate::t!("export.no_resources_defined", locale = "en").to_string())
+        );
+        assert!(!result.contains(&format!(
+            "## {}",
+            crate::t!("export.variables", locale = "en")
+        )));
+        assert!(
+            !result.contains(&crate::t!("export.no_variables_defined", locale = "en").to_string())
+        );
+        assert!(!result.contains(&format!(
+            "## {}",
+            crate::t!("export.modules", locale = "en")
+        )));
+        assert!(
+            !result.contains(&crate::t!("export.no_modules_defined", locale = "en").to_string())
+        );
+        assert!(!result.contains(&format!(
+            "## {}",
+            crate::t!("export.outputs", locale = "en")
+        )));
+        assert!(
+            !result.contains(&crate::t!("export.no_outputs_defined", locale = "en").to_string())
+        );
+    }
+
+    #[test]
+    fn test_export_to_string_with_references() {
+        let mut document = BicepDocument::default();
+        for name in ["prefix", "unusedParam"] {
+            document.parameters.insert(
+                name.to_string(),
+                BicepParameter {
+                    parameter_type: BicepType::String,
+                    ..Default::default()
+                },
+            );
+        }
+        document.variables.insert(
+            "vaultName".to_string(),
+            crate::parsing::BicepVariable {
+                description: None,
+                value: BicepValue::String("${prefix}-kv".to_string()),
+                is_exported: false,
+            },
+        );
+
+        let result = export_to_string(
+            &document,
+            &ExportOptions::builder()
+                .exclude_empty(true)
+                .show_references(true)
+                .build(),
+        )
+        .unwrap();
+        assert!(result.contains("**Used By:** `vaultName`"));
+        assert!(result.contains("**Depends On:** `prefix`"));
+        assert!(result.contains("**Used By:** *Unused*"));
+
+        let result = export_to_string(
+            &document,
+            &ExportOptions::builder().exclude_empty(true).build(),
+        )
+        .unwrap();
+        assert!(!result.contains("Used By"));
+    }
+
+    #[test]
+    fn test_export_symbol_to_string() {
+        let mut document = BicepDocument::default();
+        for name in ["first", "second"] {
+            document.parameters.insert(
+                name.to_string(),
+                BicepParameter {
+                    parameter_type: BicepType::Int,
+                    description: Some(format!("The {name} parameter")),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let symbol = SymbolId {
+            kind: SymbolKind::Parameter,
+            name: "second".to_string(),
+        };
+        let markdown =
+            export_symbol_to_string(&document, &symbol, false, false, Language::English).unwrap();
+        assert!(markdown.starts_with("### `second`"));
+        assert!(markdown.contains("The second parameter"));
+        assert!(!markdown.contains("first"));
+
+        let missing = SymbolId {
+            kind: SymbolKind::Output,
+            name: "second".to_string(),
+        };
+        assert_eq!(
+            export_symbol_to_string(&document, &missing, false, false, Language::English),
+            None
+        );
+
+        // Hover cards redact secure defaults like the full document
+        document.parameters.insert(
+            "apiKey".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::String("hunter2".to_string())),
+                is_secure: true,
+                ..Default::default()
+            },
+        );
+        let symbol = SymbolId {
+            kind: SymbolKind::Parameter,
+            name: "apiKey".to_string(),
+        };
+        let markdown =
+            export_symbol_to_string(&document, &symbol, false, false, Language::English).unwrap();
+        assert!(markdown.contains(crate::exports::REDACTED));
+        assert!(!markdown.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_export_to_string_with_effective_values() {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "prefix".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::String,
+                default_value: Some(BicepValue::String("contoso".to_string())),
+                ..Default::default()
+            },
+        );
+        document.resources.insert(
+            "vault".to_string(),
+            crate::parsing::BicepResource {
+                name: "${toUpper('${prefix}-kv')}".to_string(),
+                resource_type: "Microsoft.KeyVault/vaults".to_string(),
+                api_version: "2023-07-01".to_string(),
+                ..Default::default()
+            },
+        );
+
+        let result = export_to_string(
+            &document,
+            &ExportOptions::builder().exclude_empty(true).build(),
+        )
+        .unwrap();
+        assert!(result.contains("**Effective Name:** `CONTOSO-KV`"));
+        // Literal default values are not repeated
+        assert!(!result.contains("Effective Default Value"));
     }
 
     #[test]
@@ -1027,6 +2289,7 @@ mod tests {
         let param = BicepParameter {
             parameter_type: BicepType::String,
             description: None,
+            descriptions: IndexMap::new(),
             metadata: IndexMap::new(),
             default_value: None,
             discriminator: None,
@@ -4,11 +4,24 @@
 /// Each export format is implemented in its own submodule to maintain
 /// separation of concerns and make it easy to add new formats.
 pub mod asciidoc;
+pub mod bicep;
+pub mod bicepparam;
+pub mod create_ui_definition;
+pub mod exporter;
+pub mod interchange;
 pub mod json;
+pub mod json_schema;
 pub mod markdown;
+pub mod parameters_json;
+pub mod registry;
+pub mod usage;
 pub mod utils;
 pub mod yaml;
 
+pub use exporter::{ExportOptions, ExportOptionsBuilder, Exporter, REDACTED};
+pub use interchange::{from_json, from_yaml, DOCUMENT_SCHEMA, SCHEMA_VERSION};
+pub use registry::{exporter_names, find_exporter, register_exporter, ExporterRegistry};
+
 // Re-export the main export functions for convenience
 pub use asciidoc::{
     export_to_file as export_asciidoc_to_file, export_to_string as export_asciidoc_to_string,
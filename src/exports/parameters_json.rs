@@ -0,0 +1,172 @@
+/// ARM parameters file export functionality
+///
+/// This module generates `parameters.json` skeletons from the parameters of a parsed
+/// Bicep document. Required parameters come first with a placeholder or sample value.
+/// JSON has no comments, so optional parameters are included with their default
+/// values where these are statically knowable and left out otherwise. The defaults
+/// of secure parameters are always left out, so that secrets are not copied.
+use std::{fs::File, io::Write, path::Path};
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::error::BicepError;
+use crate::{
+    analysis::Evaluator,
+    exports::utils::samples::{is_required, placeholder_value, required_first, sample_value},
+    parsing::{BicepDocument, BicepValue},
+};
+
+/// Schema of ARM deployment parameters files
+const PARAMETERS_SCHEMA: &str =
+    "https://schema.management.azure.com/schemas/2019-04-01/deploymentParameters.json#";
+
+/// An ARM deployment parameters file
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ParametersFile<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    content_version: &'static str,
+    parameters: IndexMap<&'a str, ParameterValue>,
+}
+
+/// The value of a single parameter
+#[derive(Serialize)]
+struct ParameterValue {
+    value: BicepValue,
+}
+
+/// Export a `parameters.json` skeleton for a Bicep document to a file
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate parameters for
+/// * `output_path` - The path where the parameters file should be written
+/// * `sample_values` - Whether to fill in sample values that satisfy each parameter's constraints
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn export_to_file<P: AsRef<Path>>(
+    document: &BicepDocument,
+    output_path: P,
+    sample_values: bool,
+) -> Result<(), BicepError> {
+    let content = export_to_string(document, sample_values)?;
+    let mut file = File::create(&output_path).map_err(|e| BicepError::io(&output_path, e))?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Export a `parameters.json` skeleton for a Bicep document as a string
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to generate parameters for
+/// * `sample_values` - Whether to fill in sample values that satisfy each parameter's constraints
+///
+/// # Returns
+///
+/// A Result containing the pretty-printed JSON or an error
+pub fn export_to_string(
+    document: &BicepDocument,
+    sample_values: bool,
+) -> Result<String, BicepError> {
+    let evaluator = Evaluator::new(document);
+    let mut parameters = IndexMap::new();
+
+    for (name, parameter) in required_first(document) {
+        let value = if is_required(parameter) {
+            if sample_values {
+                sample_value(parameter, document)
+            } else {
+                placeholder_value(&parameter.parameter_type, document)
+            }
+        } else if parameter.is_secure {
+            continue;
+        } else {
+            match parameter
+                .default_value
+                .as_ref()
+                .and_then(|default_value| evaluator.evaluate_value(default_value))
+            {
+                Some(value) => value,
+                None => continue,
+            }
+        };
+        parameters.insert(name.as_str(), ParameterValue { value });
+    }
+
+    let file = ParametersFile {
+        schema: PARAMETERS_SCHEMA,
+        content_version: "1.0.0.0",
+        parameters,
+    };
+    Ok(serde_json::to_string_pretty(&file)?)
+}
+
+/// Parse a Bicep file and export a `parameters.json` skeleton in one step
+///
+/// # Arguments
+///
+/// * `source_code` - The source code of the Bicep file
+/// * `output_path` - The path where the parameters file should be written
+/// * `sample_values` - Whether to fill in sample values
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn parse_and_export<P: AsRef<Path>>(
+    source_code: &str,
+    output_path: P,
+    sample_values: bool,
+) -> Result<(), BicepError> {
+    let document = crate::parse_bicep_document(source_code)?;
+    export_to_file(&document, output_path, sample_values)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{BicepParameter, BicepType};
+
+    #[test]
+    fn test_export_to_string() {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "location".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::Identifier(
+                    "resourceGroup().location".to_string(),
+                )),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "tier".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::String("Standard".to_string())),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "tags".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Object(None),
+                ..Default::default()
+            },
+        );
+
+        let result = export_to_string(&document, false).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+        assert_eq!(json["$schema"], PARAMETERS_SCHEMA);
+        assert_eq!(json["parameters"]["tags"]["value"], serde_json::json!({}));
+        assert_eq!(json["parameters"]["tier"]["value"], "Standard");
+        assert!(json["parameters"].get("location").is_none());
+        // Required parameters come first
+        assert!(result.find("\"tags\"").unwrap() < result.find("\"tier\"").unwrap());
+    }
+}
@@ -0,0 +1,198 @@
+/// Lookup of exporters by format name
+///
+/// The built-in formats are always available. Other crates can add their own with
+/// [`register_exporter`], after which the format can be selected by name anywhere the
+/// command line accepts a format, such as `bicep-docs export --format <name>`.
+use std::sync::{Arc, OnceLock, RwLock};
+
+use indexmap::IndexMap;
+
+use super::exporter::Exporter;
+use super::{
+    asciidoc::AsciidocExporter, bicep::BicepExporter, create_ui_definition::UiDefinitionExporter,
+    json::JsonExporter, json_schema::JsonSchemaExporter, markdown::MarkdownExporter,
+    yaml::YamlExporter,
+};
+
+/// A set of exporters, keyed by format name in registration order
+#[derive(Clone)]
+pub struct ExporterRegistry {
+    exporters: IndexMap<String, Arc<dyn Exporter>>,
+}
+
+impl ExporterRegistry {
+    /// Create a registry without any exporters
+    pub fn empty() -> Self {
+        ExporterRegistry {
+            exporters: IndexMap::new(),
+        }
+    }
+
+    /// Create a registry with the built-in exporters
+    pub fn new() -> Self {
+        let mut registry = ExporterRegistry::empty();
+        registry.register(MarkdownExporter);
+        registry.register(AsciidocExporter);
+        registry.register(YamlExporter);
+        registry.register(JsonExporter);
+        registry.register(JsonSchemaExporter);
+        registry.register(UiDefinitionExporter);
+        registry.register(BicepExporter);
+        registry
+    }
+
+    /// Add an exporter, replacing any with the same name
+    ///
+    /// # Arguments
+    ///
+    /// * `exporter` - The exporter to add
+    ///
+    /// # Returns
+    ///
+    /// The exporter that was replaced, if any
+    pub fn register(&mut self, exporter: impl Exporter + 'static) -> Option<Arc<dyn Exporter>> {
+        self.exporters
+            .insert(exporter.name().to_string(), Arc::new(exporter))
+    }
+
+    /// Find an exporter by format name
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Exporter>> {
+        self.exporters.get(name).cloned()
+    }
+
+    /// The names of the registered formats, in registration order
+    pub fn names(&self) -> Vec<String> {
+        self.exporters.keys().cloned().collect()
+    }
+
+    /// Iterate over the registered exporters in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn Exporter>> {
+        self.exporters.values()
+    }
+}
+
+impl Default for ExporterRegistry {
+    fn default() -> Self {
+        ExporterRegistry::new()
+    }
+}
+
+/// The registry shared by the whole process
+fn global() -> &'static RwLock<ExporterRegistry> {
+    static REGISTRY: OnceLock<RwLock<ExporterRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(ExporterRegistry::new()))
+}
+
+/// Add an exporter to the process-wide registry, replacing any with the same name
+///
+/// # Arguments
+///
+/// * `exporter` - The exporter to add
+///
+/// # Returns
+///
+/// The exporter that was replaced, if any
+pub fn register_exporter(exporter: impl Exporter + 'static) -> Option<Arc<dyn Exporter>> {
+    global()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .register(exporter)
+}
+
+/// Find an exporter in the process-wide registry by format name
+pub fn find_exporter(name: &str) -> Option<Arc<dyn Exporter>> {
+    global().read().unwrap_or_else(|e| e.into_inner()).get(name)
+}
+
+/// The names of the formats in the process-wide registry
+pub fn exporter_names() -> Vec<String> {
+    global().read().unwrap_or_else(|e| e.into_inner()).names()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::error::BicepError;
+    use crate::exports::ExportOptions;
+    use crate::parsing::BicepDocument;
+
+    struct NameList;
+
+    impl Exporter for NameList {
+        fn name(&self) -> &str {
+            "names"
+        }
+
+        fn extension(&self) -> &str {
+            "txt"
+        }
+
+        fn description(&self) -> &str {
+            "List the parameter names"
+        }
+
+        fn export_to_writer(
+            &self,
+            document: &BicepDocument,
+            writer: &mut dyn Write,
+            _options: &ExportOptions,
+        ) -> Result<(), BicepError> {
+            for name in document.parameters.keys() {
+                writeln!(writer, "{name}")?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_builtin_exporters() {
+        let registry = ExporterRegistry::new();
+        assert_eq!(
+            registry.names(),
+            [
+                "markdown",
+                "asciidoc",
+                "yaml",
+                "json",
+                "json-schema",
+                "ui-definition",
+                "bicep"
+            ]
+        );
+        let extensions = registry
+            .iter()
+            .map(|exporter| exporter.extension())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            extensions,
+            ["md", "adoc", "yaml", "json", "schema.json", "json", "bicep"]
+        );
+        assert!(registry.get("html").is_none());
+    }
+
+    #[test]
+    fn test_register_exporter() {
+        let mut registry = ExporterRegistry::empty();
+        assert!(registry.register(NameList).is_none());
+        assert!(registry.register(NameList).is_some());
+        assert_eq!(registry.names(), ["names"]);
+
+        let mut document = BicepDocument::default();
+        document
+            .parameters
+            .insert("location".to_string(), Default::default());
+        let exporter = registry.get("names").unwrap();
+        assert_eq!(
+            exporter
+                .export_to_string(&document, &ExportOptions::default())
+                .unwrap(),
+            "location\n"
+        );
+
+        register_exporter(NameList);
+        assert!(find_exporter("names").is_some());
+        assert!(exporter_names().contains(&"markdown".to_string()));
+    }
+}
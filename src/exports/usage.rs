@@ -0,0 +1,242 @@
+/// Usage snippets for consumers of a template
+///
+/// This module generates the ready-to-paste examples shown in the usage section of
+/// the Markdown and AsciiDoc output: a `module` declaration that references the
+/// template, and the equivalent `.bicepparam` file. Required parameters are given
+/// placeholder values; optional parameters are commented out with their defaults.
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+use crate::error::BicepError;
+use crate::{
+    exports::{
+        bicepparam,
+        utils::{
+            formatting::{format_bicep_source, quote_bicep_string},
+            samples::{is_required, placeholder_value, required_first},
+        },
+    },
+    parsing::{BicepDocument, BicepValue, ModuleSource},
+};
+
+/// The symbol name of the module in the snippet
+const MODULE_NAME: &str = "example";
+
+/// Build the module source of a template from a source template
+///
+/// The placeholders `{file}`, `{name}` and `{version}` are replaced with the file
+/// name of the template, its file name without the extension, and the `version`
+/// in its metadata, and the result is read as a local path, `br:` registry or
+/// `ts:` template spec reference.
+///
+/// # Arguments
+///
+/// * `template` - The source template, such as `br/public:avm/res/{name}:{version}`
+/// * `template_path` - The path of the template the source refers to
+/// * `document` - The parsed template, for its version
+///
+/// # Returns
+///
+/// The module source, or an error if `{version}` is used and the template has no
+/// version, or the result is not a valid module source
+pub fn module_source(
+    template: &str,
+    template_path: &Path,
+    document: &BicepDocument,
+) -> Result<ModuleSource, BicepError> {
+    let file = template_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    let name = template_path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy();
+    let mut source = template.replace("{file}", &file).replace("{name}", &name);
+
+    if source.contains("{version}") {
+        let Some(BicepValue::String(version)) = document.metadata.get("version") else {
+            return Err(BicepError::InvalidValue {
+                kind: "module source".to_string(),
+                reason: format!(
+                    "{template} uses {{version}}, but the template has no version metadata"
+                ),
+                span: None,
+            });
+        };
+        source = source.replace("{version}", version);
+    }
+
+    ModuleSource::parse(&source)
+}
+
+/// Write a `module` declaration that deploys a template
+///
+/// # Arguments
+///
+/// * `writer` - Where to write the Bicep source of the module declaration
+/// * `document` - The template to deploy
+/// * `source` - Where the template is referenced from
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn write_module_snippet(
+    writer: &mut dyn Write,
+    document: &BicepDocument,
+    source: &ModuleSource,
+) -> io::Result<()> {
+    writeln!(
+        writer,
+        "module {MODULE_NAME} {} = {{",
+        quote_bicep_string(&source.to_string())
+    )?;
+    writeln!(writer, "  name: '{MODULE_NAME}'")?;
+
+    if !document.parameters.is_empty() {
+        writeln!(writer, "  params: {{")?;
+        for (name, parameter) in required_first(document) {
+            if is_required(parameter) {
+                let value = placeholder_value(&parameter.parameter_type, document);
+                writeln!(writer, "    {name}: {}", format_bicep_source(&value, 4))?;
+            } else {
+                let value = match &parameter.default_value {
+                    Some(default_value) => format_bicep_source(default_value, 4),
+                    None => "null".to_string(),
+                };
+                for line in format!("{name}: {value}").lines() {
+                    let line = line.strip_prefix("    ").unwrap_or(line);
+                    writeln!(writer, "    // {line}")?;
+                }
+            }
+        }
+        writeln!(writer, "  }}")?;
+    }
+
+    writeln!(writer, "}}")
+}
+
+/// Write the `.bicepparam` file equivalent to the module snippet
+///
+/// # Arguments
+///
+/// * `writer` - Where to write the content of the parameters file
+/// * `document` - The template to deploy
+/// * `source` - Where the template is referenced from
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn write_parameters_snippet(
+    writer: &mut dyn Write,
+    document: &BicepDocument,
+    source: &ModuleSource,
+) -> Result<(), BicepError> {
+    bicepparam::export_to_writer(document, writer, &source.to_string(), false)
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+    use crate::parsing::{BicepParameter, BicepType};
+
+    fn module_snippet(document: &BicepDocument, source: &ModuleSource) -> String {
+        let mut buffer = Vec::new();
+        write_module_snippet(&mut buffer, document, source).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn document() -> BicepDocument {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "location".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::Identifier(
+                    "resourceGroup().location".to_string(),
+                )),
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "name".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::String,
+                ..Default::default()
+            },
+        );
+        document.parameters.insert(
+            "tags".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Object(None),
+                default_value: Some(BicepValue::Object(IndexMap::from([(
+                    "env".to_string(),
+                    BicepValue::String("dev".to_string()),
+                )]))),
+                ..Default::default()
+            },
+        );
+        document
+    }
+
+    #[test]
+    fn test_module_snippet() {
+        let source = ModuleSource::parse("./storage.bicep").unwrap();
+        assert_eq!(
+            module_snippet(&document(), &source),
+            "\
+module example './storage.bicep' = {
+  name: 'example'
+  params: {
+    name: ''
+    // location: resourceGroup().location
+    // tags: {
+    //   env: 'dev'
+    // }
+  }
+}
+"
+        );
+        assert_eq!(
+            module_snippet(&BicepDocument::default(), &source),
+            "module example './storage.bicep' = {\n  name: 'example'\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_parameters_snippet() {
+        let source = ModuleSource::parse("br/public:avm/res/storage:1.0.0").unwrap();
+        let mut buffer = Vec::new();
+        write_parameters_snippet(&mut buffer, &document(), &source).unwrap();
+        let snippet = String::from_utf8(buffer).unwrap();
+        assert!(snippet.starts_with("using 'br/public:avm/res/storage:1.0.0'\n"));
+        assert!(snippet.contains("param name = ''\n"));
+        assert!(snippet.contains("// param location = resourceGroup().location\n"));
+    }
+
+    #[test]
+    fn test_module_source() {
+        let mut document = document();
+        let path = Path::new("modules/storage.bicep");
+
+        assert_eq!(
+            module_source("./{file}", path, &document).unwrap(),
+            ModuleSource::LocalPath("./storage.bicep".to_string())
+        );
+        assert!(module_source("br/public:avm/res/{name}:{version}", path, &document).is_err());
+
+        document.metadata.insert(
+            "version".to_string(),
+            BicepValue::String("0.9.0".to_string()),
+        );
+        assert_eq!(
+            module_source("br/public:avm/res/{name}:{version}", path, &document)
+                .unwrap()
+                .to_string(),
+            "br/public:avm/res/storage:0.9.0"
+        );
+    }
+}
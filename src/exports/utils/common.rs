@@ -2,31 +2,69 @@
 ///
 /// This module contains utility functions that are used by multiple
 /// export formats to avoid code duplication and ensure consistency.
-use crate::{parsing::BicepValue, t};
+use std::io::{self, Write};
+
+use crate::{localization::Language, parsing::BicepValue, t};
 use indexmap::IndexMap;
 
+/// Pick the description of a declaration in a language
+///
+/// A description for the exact locale is preferred, then one for a more general
+/// locale, so `pt-BR` also matches a `pt` description.
+///
+/// # Arguments
+///
+/// * `description` - The default description, from `@description`
+/// * `descriptions` - Descriptions in other languages, keyed by locale code
+/// * `language` - The language being written
+///
+/// # Returns
+///
+/// The description in the language, or the default description if there is none
+pub fn localized_description<'a>(
+    description: Option<&'a str>,
+    descriptions: &'a IndexMap<String, String>,
+    language: Language,
+) -> Option<&'a str> {
+    let mut locale = language.code();
+    loop {
+        if let Some(text) = descriptions
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(locale))
+            .map(|(_, text)| text)
+        {
+            return Some(text);
+        }
+        match locale.rsplit_once('-') {
+            Some((general, _)) => locale = general,
+            None => return description,
+        }
+    }
+}
+
 /// Helper function to format Yes/No values with or without emoji
 ///
 /// # Arguments
 ///
 /// * `value` - Boolean value to format
 /// * `use_emoji` - Whether to use emoji symbols (✅/❌) or plain text (Yes/No)
+/// * `language` - The language to write Yes/No in
 ///
 /// # Returns
 ///
 /// Formatted string with either emoji or plain text
-pub fn format_yes_no(value: bool, use_emoji: bool) -> String {
+pub fn format_yes_no(value: bool, use_emoji: bool, language: Language) -> String {
     let text = if value {
-        t!("common.yes").to_string()
+        t!("common.yes", locale = language.code())
     } else {
-        t!("common.no").to_string()
+        t!("common.no", locale = language.code())
     };
 
     if use_emoji {
         let emoji = if value { "✅" } else { "❌" };
         format!("{emoji} {text}")
     } else {
-        text
+        text.into_owned()
     }
 }
 
@@ -34,56 +72,60 @@ pub fn format_yes_no(value: bool, use_emoji: bool) -> String {
 ///
 /// # Arguments
 ///
-/// * `output` - Mutable reference to the output string
+/// * `output` - Where to write the metadata table
 /// * `metadata` - Metadata map to display
 pub fn generate_metadata_display_markdown(
-    output: &mut String,
+    output: &mut dyn Write,
     metadata: &IndexMap<String, BicepValue>,
-) {
+) -> io::Result<()> {
     use super::formatting::escape_markdown;
 
     if !metadata.is_empty() {
-        output.push_str("| Key | Value |\n");
-        output.push_str("|-----|-------|\n");
+        output.write_all(b"| Key | Value |\n")?;
+        output.write_all(b"|-----|-------|\n")?;
 
         for (key, value) in metadata {
             let value_str = value.to_string();
-            output.push_str(&format!(
-                "| {} | {} |\n",
+            writeln!(
+                output,
+                "| {} | {} |",
                 escape_markdown(key),
                 escape_markdown(&value_str)
-            ));
+            )?;
         }
-        output.push('\n');
+        output.write_all(b"\n")?;
     }
+    Ok(())
 }
 
 /// Generate metadata display for AsciiDoc format
 ///
 /// # Arguments
 ///
-/// * `output` - Mutable reference to the output string
+/// * `output` - Where to write the metadata table
 /// * `metadata` - Metadata map to display
 pub fn generate_metadata_display_asciidoc(
-    output: &mut String,
+    output: &mut dyn Write,
     metadata: &IndexMap<String, BicepValue>,
-) {
+) -> io::Result<()> {
     use super::formatting::escape_asciidoc;
 
     if !metadata.is_empty() {
-        output.push_str("[%autowidth,cols=\"h,1\",frame=none]\n");
-        output.push_str("|===\n");
+        output.write_all(b"[%autowidth,cols=\"h,1\",frame=none]\n")?;
+        output.write_all(b"|===\n")?;
 
         for (key, value) in metadata {
             let value_str = value.to_string();
-            output.push_str(&format!(
+            write!(
+                output,
                 "| {}\n| {}\n\n",
                 escape_asciidoc(key),
                 escape_asciidoc(&value_str)
-            ));
+            )?;
         }
-        output.push_str("|===\n\n");
+        output.write_all(b"|===\n\n")?;
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -91,34 +133,55 @@ mod tests {
     use super::*;
 
     #[test]
-    #[serial_test::serial]
     fn test_format_yes_no() {
-        use crate::localization::{init_localization, Language};
+        assert_eq!(format_yes_no(true, true, Language::English), "✅ Yes");
+        assert_eq!(format_yes_no(true, false, Language::English), "Yes");
+        assert_eq!(format_yes_no(false, true, Language::English), "❌ No");
+        assert_eq!(format_yes_no(false, false, Language::English), "No");
+
+        assert_eq!(format_yes_no(true, false, Language::Spanish), "Sí");
+        assert_eq!(format_yes_no(false, false, Language::Spanish), "No");
+    }
 
-        init_localization(Language::English);
-        assert_eq!(format_yes_no(true, true), "✅ Yes");
-        assert_eq!(format_yes_no(true, false), "Yes");
-        assert_eq!(format_yes_no(false, true), "❌ No");
-        assert_eq!(format_yes_no(false, false), "No");
+    #[test]
+    fn test_localized_description() {
+        let descriptions = IndexMap::from([
+            ("fr".to_string(), "Nom du compte".to_string()),
+            ("pt".to_string(), "Nome da conta".to_string()),
+        ]);
+        let description = Some("Account name");
 
-        init_localization(Language::Spanish);
-        assert_eq!(format_yes_no(true, false), "Sí");
-        assert_eq!(format_yes_no(false, false), "No");
+        assert_eq!(
+            localized_description(description, &descriptions, Language::French),
+            Some("Nom du compte")
+        );
+        assert_eq!(
+            localized_description(description, &descriptions, Language::Other("pt-BR")),
+            Some("Nome da conta")
+        );
+        assert_eq!(
+            localized_description(description, &descriptions, Language::English),
+            Some("Account name")
+        );
+        assert_eq!(
+            localized_description(None, &descriptions, Language::German),
+            None
+        );
     }
 
     #[test]
     fn test_generate_metadata_display_markdown_empty() {
-        let mut output = String::new();
+        let mut output = Vec::new();
         let metadata = IndexMap::new();
-        generate_metadata_display_markdown(&mut output, &metadata);
+        generate_metadata_display_markdown(&mut output, &metadata).unwrap();
         assert!(output.is_empty());
     }
 
     #[test]
     fn test_generate_metadata_display_asciidoc_empty() {
-        let mut output = String::new();
+        let mut output = Vec::new();
         let metadata = IndexMap::new();
-        generate_metadata_display_asciidoc(&mut output, &metadata);
+        generate_metadata_display_asciidoc(&mut output, &metadata).unwrap();
         assert!(output.is_empty());
     }
 }
@@ -0,0 +1,352 @@
+/// Documentation conventions read from `@metadata`
+///
+/// A declaration can steer how it is documented with a small set of
+/// well-known metadata keys. The keys stay in the parsed metadata, so the
+/// document round-trips unchanged, and the exporters read them from here and
+/// leave them out of the generic metadata table.
+///
+/// | Key | Meaning |
+/// |-----|---------|
+/// | `example` | A usage example, shown as a code block |
+/// | `deprecated` | `true`, or a message saying why the item is deprecated |
+/// | `deprecatedSince` | The version the item was deprecated in |
+/// | `replacement` | What to use instead of a deprecated item |
+/// | `docs.hidden` | `true` to leave the item out of the documentation |
+/// | `group` | The named section a parameter is listed under |
+/// | `order` | The position of a parameter within its section |
+use indexmap::IndexMap;
+
+use crate::parsing::{BicepParameter, BicepValue};
+
+/// Metadata key for a usage example
+pub const EXAMPLE_KEY: &str = "example";
+/// Metadata key marking an item as deprecated
+pub const DEPRECATED_KEY: &str = "deprecated";
+/// Metadata key for the version an item was deprecated in
+pub const DEPRECATED_SINCE_KEY: &str = "deprecatedSince";
+/// Metadata key for what replaces a deprecated item
+pub const REPLACEMENT_KEY: &str = "replacement";
+/// Metadata key, or `docs` object key, hiding an item from the documentation
+pub const HIDDEN_KEY: &str = "docs.hidden";
+/// Metadata key for the section a parameter is listed under
+pub const GROUP_KEY: &str = "group";
+/// Metadata key for the position of a parameter within its section
+pub const ORDER_KEY: &str = "order";
+
+/// The object key that holds documentation settings such as `hidden`
+const DOCS_KEY: &str = "docs";
+
+/// Why and since when an item is deprecated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Deprecation<'a> {
+    /// Why the item is deprecated
+    pub message: Option<&'a str>,
+    /// The version the item was deprecated in
+    pub since: Option<&'a str>,
+    /// What to use instead
+    pub replacement: Option<&'a str>,
+}
+
+/// The documentation conventions of one declaration
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DocConventions<'a> {
+    /// A usage example
+    pub example: Option<&'a BicepValue>,
+    /// The deprecation notice, when the item is deprecated
+    pub deprecation: Option<Deprecation<'a>>,
+    /// Whether the item is left out of the documentation
+    pub hidden: bool,
+    /// The section the item is listed under
+    pub group: Option<&'a str>,
+    /// The position of the item within its section
+    pub order: Option<i64>,
+}
+
+impl<'a> DocConventions<'a> {
+    /// Read the conventions from the metadata of a declaration
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - The metadata of the declaration
+    ///
+    /// # Returns
+    ///
+    /// The conventions, with unset or unrecognised values left empty
+    pub fn from_metadata(metadata: &'a IndexMap<String, BicepValue>) -> Self {
+        let text = |key: &str| match metadata.get(key) {
+            Some(BicepValue::String(value)) => Some(value.as_str()),
+            _ => None,
+        };
+
+        let since = text(DEPRECATED_SINCE_KEY);
+        let replacement = text(REPLACEMENT_KEY);
+        let deprecation = match metadata.get(DEPRECATED_KEY) {
+            Some(BicepValue::Bool(true)) => Some(None),
+            Some(BicepValue::String(message)) => Some(Some(message.as_str())),
+            Some(_) => None,
+            None => since.map(|_| None),
+        }
+        .map(|message| Deprecation {
+            message,
+            since,
+            replacement,
+        });
+
+        let hidden = match metadata.get(DOCS_KEY) {
+            Some(BicepValue::Object(docs)) => {
+                matches!(docs.get("hidden"), Some(BicepValue::Bool(true)))
+            },
+            _ => false,
+        } || matches!(metadata.get(HIDDEN_KEY), Some(BicepValue::Bool(true)));
+
+        let order = match metadata.get(ORDER_KEY) {
+            Some(BicepValue::Int(order)) => Some(*order),
+            _ => None,
+        };
+
+        Self {
+            example: metadata.get(EXAMPLE_KEY),
+            deprecation,
+            hidden,
+            group: text(GROUP_KEY),
+            order,
+        }
+    }
+}
+
+/// Whether a metadata key is one of the documentation conventions
+///
+/// # Arguments
+///
+/// * `key` - The metadata key
+pub fn is_convention_key(key: &str) -> bool {
+    matches!(
+        key,
+        EXAMPLE_KEY
+            | DEPRECATED_KEY
+            | DEPRECATED_SINCE_KEY
+            | REPLACEMENT_KEY
+            | HIDDEN_KEY
+            | DOCS_KEY
+            | GROUP_KEY
+            | ORDER_KEY
+    )
+}
+
+/// Whether the metadata of a declaration hides it from the documentation
+///
+/// # Arguments
+///
+/// * `metadata` - The metadata of the declaration
+pub fn is_hidden(metadata: &IndexMap<String, BicepValue>) -> bool {
+    DocConventions::from_metadata(metadata).hidden
+}
+
+/// The metadata to list in the metadata table, without the convention keys
+///
+/// # Arguments
+///
+/// * `metadata` - The metadata of the declaration
+pub fn without_conventions(
+    metadata: &IndexMap<String, BicepValue>,
+) -> IndexMap<String, BicepValue> {
+    metadata
+        .iter()
+        .filter(|(key, _)| !is_convention_key(key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// The source of a usage example, as shown in a code block
+///
+/// String examples are shown as written, so they can hold a snippet of Bicep,
+/// and other values are formatted as Bicep literals.
+///
+/// # Arguments
+///
+/// * `example` - The value of the `example` key
+pub fn example_source(example: &BicepValue) -> String {
+    match example {
+        BicepValue::String(source) => source.clone(),
+        value => value.pretty_format(),
+    }
+}
+
+/// A group name, or None for ungrouped parameters, and the parameters in it
+pub type ParameterSection<'a> = (Option<&'a str>, Vec<(&'a String, &'a BicepParameter)>);
+
+/// The parameters to document, in their sections and order
+///
+/// Hidden parameters are left out. Parameters without a group come first,
+/// followed by each group in the order it is first used. Within a section,
+/// parameters with an `order` come first, lowest first, followed by the
+/// rest in declaration order.
+///
+/// # Arguments
+///
+/// * `parameters` - The parameters of the document or object type
+///
+/// # Returns
+///
+/// The sections with the parameters to document in each
+pub fn parameter_sections(
+    parameters: &IndexMap<String, BicepParameter>,
+) -> Vec<ParameterSection<'_>> {
+    let mut visible: Vec<_> = parameters
+        .iter()
+        .map(|(name, parameter)| {
+            (
+                name,
+                parameter,
+                DocConventions::from_metadata(&parameter.metadata),
+            )
+        })
+        .filter(|(_, _, conventions)| !conventions.hidden)
+        .collect();
+    visible.sort_by_key(|(_, _, conventions)| (conventions.order.is_none(), conventions.order));
+
+    let mut sections: IndexMap<Option<&str>, Vec<_>> = IndexMap::new();
+    sections.insert(None, Vec::new());
+    for (name, parameter, conventions) in visible {
+        sections
+            .entry(conventions.group)
+            .or_default()
+            .push((name, parameter));
+    }
+    sections
+        .into_iter()
+        .filter(|(_, parameters)| !parameters.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(entries: &[(&str, BicepValue)]) -> IndexMap<String, BicepValue> {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect()
+    }
+
+    fn parameter(entries: &[(&str, BicepValue)]) -> BicepParameter {
+        BicepParameter {
+            metadata: metadata(entries),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_from_metadata() {
+        let metadata = metadata(&[
+            ("example", BicepValue::String("'eastus'".to_string())),
+            (
+                "deprecated",
+                BicepValue::String("Set by policy".to_string()),
+            ),
+            ("deprecatedSince", BicepValue::String("2.0".to_string())),
+            ("replacement", BicepValue::String("region".to_string())),
+            (
+                "docs",
+                BicepValue::Object(IndexMap::from([(
+                    "hidden".to_string(),
+                    BicepValue::Bool(true),
+                )])),
+            ),
+            ("group", BicepValue::String("Networking".to_string())),
+            ("order", BicepValue::Int(2)),
+            ("owner", BicepValue::String("platform".to_string())),
+        ]);
+
+        let conventions = DocConventions::from_metadata(&metadata);
+        assert_eq!(
+            conventions.example,
+            Some(&BicepValue::String("'eastus'".to_string()))
+        );
+        assert_eq!(
+            conventions.deprecation,
+            Some(Deprecation {
+                message: Some("Set by policy"),
+                since: Some("2.0"),
+                replacement: Some("region"),
+            })
+        );
+        assert!(conventions.hidden);
+        assert_eq!(conventions.group, Some("Networking"));
+        assert_eq!(conventions.order, Some(2));
+
+        assert_eq!(
+            without_conventions(&metadata).keys().collect::<Vec<_>>(),
+            vec!["owner"]
+        );
+    }
+
+    #[test]
+    fn test_deprecated_and_hidden_forms() {
+        let flag = metadata(&[
+            ("deprecated", BicepValue::Bool(true)),
+            ("docs.hidden", BicepValue::Bool(true)),
+        ]);
+        let conventions = DocConventions::from_metadata(&flag);
+        assert_eq!(conventions.deprecation, Some(Deprecation::default()));
+        assert!(conventions.hidden);
+
+        let since = metadata(&[("deprecatedSince", BicepValue::String("1.4".to_string()))]);
+        assert_eq!(
+            DocConventions::from_metadata(&since).deprecation,
+            Some(Deprecation {
+                since: Some("1.4"),
+                ..Default::default()
+            })
+        );
+
+        let not_deprecated = metadata(&[
+            ("deprecated", BicepValue::Bool(false)),
+            ("docs.hidden", BicepValue::Bool(false)),
+        ]);
+        let conventions = DocConventions::from_metadata(&not_deprecated);
+        assert_eq!(conventions.deprecation, None);
+        assert!(!conventions.hidden);
+    }
+
+    #[test]
+    fn test_parameter_sections() {
+        let group = |name: &str| ("group", BicepValue::String(name.to_string()));
+        let parameters = IndexMap::from([
+            ("subnet".to_string(), parameter(&[group("Network")])),
+            ("name".to_string(), parameter(&[])),
+            (
+                "vnet".to_string(),
+                parameter(&[group("Network"), ("order", BicepValue::Int(1))]),
+            ),
+            (
+                "secret".to_string(),
+                parameter(&[("docs.hidden", BicepValue::Bool(true))]),
+            ),
+            (
+                "location".to_string(),
+                parameter(&[("order", BicepValue::Int(1))]),
+            ),
+            ("sku".to_string(), parameter(&[group("Pricing")])),
+        ]);
+
+        let sections: Vec<(Option<&str>, Vec<&str>)> = parameter_sections(&parameters)
+            .into_iter()
+            .map(|(group, parameters)| {
+                (
+                    group,
+                    parameters.iter().map(|(name, _)| name.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                (None, vec!["location", "name"]),
+                (Some("Network"), vec!["vnet", "subnet"]),
+                (Some("Pricing"), vec!["sku"]),
+            ]
+        );
+    }
+}
@@ -3,7 +3,10 @@
 /// This module provides functions for formatting Bicep types and values
 /// consistently across different export formats, along with text escaping
 /// functions for Markdown and AsciiDoc.
-use crate::parsing::{BicepType, BicepValue};
+use crate::{
+    analysis::expressions::{tokenize_template, StringSegment},
+    parsing::{BicepType, BicepValue},
+};
 
 /// Format a Bicep type with backticks for Markdown
 ///
@@ -86,6 +89,111 @@ pub fn escape_asciidoc(text: &str) -> String {
         .replace('\n', " +\n")
 }
 
+/// Format a Bicep value as Bicep source code
+///
+/// Strings are written as quoted literals, keeping their interpolations, and
+/// expressions as they are.
+///
+/// # Arguments
+///
+/// * `value` - The BicepValue to format
+/// * `indent` - The indentation of the line the value starts on
+///
+/// # Returns
+///
+/// The value in Bicep syntax, spanning multiple lines for non-empty arrays and objects
+pub fn format_bicep_source(value: &BicepValue, indent: usize) -> String {
+    let padding = " ".repeat(indent + 2);
+
+    match value {
+        BicepValue::String(text) => match tokenize_template(text) {
+            Ok(segments)
+                if segments
+                    .iter()
+                    .any(|segment| matches!(segment, StringSegment::Interpolation(_))) =>
+            {
+                format!("'{text}'")
+            },
+            _ => quote_bicep_string(text),
+        },
+        BicepValue::Identifier(name) => name.clone(),
+        BicepValue::Int(n) => n.to_string(),
+        BicepValue::Bool(b) => b.to_string(),
+        BicepValue::Array(items) if items.is_empty() => "[]".to_string(),
+        BicepValue::Array(items) => {
+            let mut source = "[\n".to_string();
+            for item in items {
+                source.push_str(&format!(
+                    "{padding}{}\n",
+                    format_bicep_source(item, indent + 2)
+                ));
+            }
+            source.push_str(&format!("{}]", " ".repeat(indent)));
+            source
+        },
+        BicepValue::Object(props) if props.is_empty() => "{}".to_string(),
+        BicepValue::Object(props) => {
+            let mut source = "{\n".to_string();
+            for (key, prop) in props {
+                source.push_str(&format!(
+                    "{padding}{}: {}\n",
+                    format_bicep_key(key),
+                    format_bicep_source(prop, indent + 2)
+                ));
+            }
+            source.push_str(&format!("{}}}", " ".repeat(indent)));
+            source
+        },
+    }
+}
+
+/// Quote and escape text as a Bicep string literal
+///
+/// # Arguments
+///
+/// * `text` - The literal text to quote
+///
+/// # Returns
+///
+/// The text as a single-quoted Bicep string
+pub fn quote_bicep_string(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace("${", "\\${")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("'{escaped}'")
+}
+
+/// Format an object key, quoting it unless it is a valid identifier
+///
+/// # Arguments
+///
+/// * `key` - The object key to format
+///
+/// # Returns
+///
+/// The key as it should appear in Bicep source
+pub fn format_bicep_key(key: &str) -> String {
+    // Keys stored with their quotes are already valid
+    if key.len() >= 2 && key.starts_with('\'') && key.ends_with('\'') {
+        return key.to_string();
+    }
+
+    let mut chars = key.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_identifier {
+        key.to_string()
+    } else {
+        quote_bicep_string(key)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use indexmap::IndexMap;
@@ -175,4 +283,53 @@ mod tests {
             "text with \\|pipes\\|"
         );
     }
+
+    #[test]
+    fn test_format_bicep_source() {
+        let mut object = IndexMap::new();
+        object.insert(
+            "name".to_string(),
+            BicepValue::String("${prefix}-kv".to_string()),
+        );
+        object.insert(
+            "cost-center".to_string(),
+            BicepValue::String("It's 100%".to_string()),
+        );
+        object.insert(
+            "sku".to_string(),
+            BicepValue::String("Standard_LRS".to_string()),
+        );
+        object.insert(
+            "location".to_string(),
+            BicepValue::Identifier("resourceGroup().location".to_string()),
+        );
+        object.insert(
+            "zones".to_string(),
+            BicepValue::Array(vec![BicepValue::Int(1), BicepValue::Int(2)]),
+        );
+
+        let expected = "{
+  name: '${prefix}-kv'
+  'cost-center': 'It\\'s 100%'
+  sku: 'Standard_LRS'
+  location: resourceGroup().location
+  zones: [
+    1
+    2
+  ]
+}";
+        assert_eq!(
+            format_bicep_source(&BicepValue::Object(object), 0),
+            expected
+        );
+        // A literal that names a symbol is still a literal
+        assert_eq!(
+            format_bicep_source(&BicepValue::String("prefix".to_string()), 0),
+            "'prefix'"
+        );
+        assert_eq!(
+            format_bicep_source(&BicepValue::String("${prefix".to_string()), 0),
+            "'\\${prefix'"
+        );
+    }
 }
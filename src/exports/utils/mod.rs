@@ -3,7 +3,9 @@
 /// This module provides common utility functions used across
 /// different export formats to eliminate code duplication.
 pub mod common;
+pub mod conventions;
 pub mod formatting;
+pub mod samples;
 
 // Re-export commonly used functions for easy access
 pub use common::{
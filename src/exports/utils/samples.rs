@@ -0,0 +1,330 @@
+/// Placeholder and sample value generation for parameters
+///
+/// This module provides the values used to fill in generated parameter files:
+/// type-appropriate placeholders, and sample values that satisfy a parameter's
+/// allowed values, length and value constraints.
+use indexmap::IndexMap;
+
+use crate::parsing::{BicepDocument, BicepParameter, BicepType, BicepValue};
+
+/// Maximum nesting of custom type references followed when generating values
+const MAX_TYPE_DEPTH: usize = 16;
+
+/// Whether a parameter must be given a value
+///
+/// # Arguments
+///
+/// * `parameter` - The parameter to check
+///
+/// # Returns
+///
+/// True if the parameter has no default value and is not nullable
+pub fn is_required(parameter: &BicepParameter) -> bool {
+    parameter.default_value.is_none() && !parameter.is_nullable
+}
+
+/// Order a document's parameters with required parameters first
+///
+/// The declaration order is kept within the required and optional groups.
+///
+/// # Arguments
+///
+/// * `document` - The document whose parameters to order
+///
+/// # Returns
+///
+/// The parameters, required ones first
+pub fn required_first(document: &BicepDocument) -> Vec<(&String, &BicepParameter)> {
+    let (mut required, optional): (Vec<_>, Vec<_>) = document
+        .parameters
+        .iter()
+        .partition(|(_, parameter)| is_required(parameter));
+    required.extend(optional);
+    required
+}
+
+/// Get an empty placeholder value for a type
+///
+/// # Arguments
+///
+/// * `bicep_type` - The type to generate a placeholder for
+/// * `document` - The document, used to resolve custom types
+///
+/// # Returns
+///
+/// An empty string, zero, false, empty array or empty object as appropriate
+pub fn placeholder_value(bicep_type: &BicepType, document: &BicepDocument) -> BicepValue {
+    match resolve_type(bicep_type, document) {
+        BicepType::Int => BicepValue::Int(0),
+        BicepType::Bool => BicepValue::Bool(false),
+        BicepType::Array(_) => BicepValue::Array(Vec::new()),
+        BicepType::Object(_) => BicepValue::Object(IndexMap::new()),
+        BicepType::Union(values) => values
+            .first()
+            .map(|value| union_member_value(value))
+            .unwrap_or(BicepValue::String(String::new())),
+        _ => BicepValue::String(String::new()),
+    }
+}
+
+/// Generate a sample value that satisfies a parameter's constraints
+///
+/// The first allowed value is used when there are allowed values. Otherwise a value of
+/// the parameter's type is generated within its length and value bounds, including
+/// every required property of object types.
+///
+/// # Arguments
+///
+/// * `parameter` - The parameter to generate a value for
+/// * `document` - The document, used to resolve custom types
+///
+/// # Returns
+///
+/// A sample value for the parameter
+pub fn sample_value(parameter: &BicepParameter, document: &BicepDocument) -> BicepValue {
+    sample_value_at_depth(parameter, document, 0)
+}
+
+fn sample_value_at_depth(
+    parameter: &BicepParameter,
+    document: &BicepDocument,
+    depth: usize,
+) -> BicepValue {
+    if let Some(first) = parameter
+        .allowed_values
+        .as_ref()
+        .and_then(|values| values.first())
+    {
+        return first.clone();
+    }
+
+    if depth > MAX_TYPE_DEPTH {
+        return placeholder_value(&parameter.parameter_type, document);
+    }
+
+    match resolve_type(&parameter.parameter_type, document) {
+        BicepType::String | BicepType::CustomType(_) => {
+            BicepValue::String(sample_string(parameter.min_length, parameter.max_length))
+        },
+        BicepType::Int => {
+            let value = match (parameter.min_value, parameter.max_value) {
+                (Some(min), _) => min,
+                (None, Some(max)) if max < 1 => max,
+                _ => 1,
+            };
+            BicepValue::Int(value)
+        },
+        BicepType::Bool => BicepValue::Bool(false),
+        BicepType::Array(item_type) => {
+            let count = 1_i64
+                .max(parameter.min_length.unwrap_or(0))
+                .min(parameter.max_length.unwrap_or(i64::MAX));
+            let item = BicepParameter {
+                parameter_type: *item_type.clone(),
+                ..Default::default()
+            };
+            BicepValue::Array(
+                (0..count)
+                    .map(|_| sample_value_at_depth(&item, document, depth + 1))
+                    .collect(),
+            )
+        },
+        BicepType::Object(Some(properties)) => BicepValue::Object(
+            properties
+                .iter()
+                .filter(|(_, property)| is_required(property))
+                .map(|(name, property)| {
+                    (
+                        name.clone(),
+                        sample_value_at_depth(property, document, depth + 1),
+                    )
+                })
+                .collect(),
+        ),
+        BicepType::Object(None) => BicepValue::Object(IndexMap::new()),
+        BicepType::Union(values) => values
+            .first()
+            .map(|value| union_member_value(value))
+            .unwrap_or(BicepValue::String(String::new())),
+    }
+}
+
+/// Follow custom type references to the underlying type definition
+///
+/// Types that are not declared in the document (e.g. imported types) are returned as is.
+///
+/// # Arguments
+///
+/// * `bicep_type` - The type to resolve
+/// * `document` - The document declaring the custom types
+///
+/// # Returns
+///
+/// The resolved type definition
+pub fn resolve_type(bicep_type: &BicepType, document: &BicepDocument) -> BicepType {
+    let mut resolved = bicep_type;
+    for _ in 0..MAX_TYPE_DEPTH {
+        match resolved {
+            BicepType::CustomType(name) => match document.types.get(name) {
+                Some(custom_type) => resolved = &custom_type.definition,
+                None => break,
+            },
+            _ => break,
+        }
+    }
+    resolved.clone()
+}
+
+/// Convert a union member into a value
+pub(crate) fn union_member_value(member: &str) -> BicepValue {
+    if let Ok(number) = member.parse::<i64>() {
+        return BicepValue::Int(number);
+    }
+    match member {
+        "true" => BicepValue::Bool(true),
+        "false" => BicepValue::Bool(false),
+        _ => BicepValue::String(member.to_string()),
+    }
+}
+
+/// Generate a sample string within the given length bounds
+fn sample_string(min_length: Option<i64>, max_length: Option<i64>) -> String {
+    const SAMPLE: &str = "sample";
+
+    let min = usize::try_from(min_length.unwrap_or(0)).unwrap_or(0);
+    let max = max_length
+        .and_then(|max| usize::try_from(max).ok())
+        .unwrap_or(usize::MAX);
+    let length = SAMPLE.len().max(min).min(max);
+
+    SAMPLE
+        .chars()
+        .chain(std::iter::repeat('x'))
+        .take(length)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::BicepCustomType;
+
+    #[test]
+    fn test_required_first() {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "location".to_string(),
+            BicepParameter {
+                default_value: Some(BicepValue::String("westeurope".to_string())),
+                ..Default::default()
+            },
+        );
+        document
+            .parameters
+            .insert("name".to_string(), BicepParameter::default());
+
+        let names = required_first(&document)
+            .into_iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["name", "location"]);
+    }
+
+    #[test]
+    fn test_sample_value_constraints() {
+        let document = BicepDocument::default();
+
+        let short = BicepParameter {
+            max_length: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            sample_value(&short, &document),
+            BicepValue::String("sam".to_string())
+        );
+
+        let long = BicepParameter {
+            min_length: Some(8),
+            ..Default::default()
+        };
+        assert_eq!(
+            sample_value(&long, &document),
+            BicepValue::String("samplexx".to_string())
+        );
+
+        let count = BicepParameter {
+            parameter_type: BicepType::Int,
+            min_value: Some(5),
+            max_value: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(sample_value(&count, &document), BicepValue::Int(5));
+
+        let sku = BicepParameter {
+            allowed_values: Some(vec![
+                BicepValue::String("Standard".to_string()),
+                BicepValue::String("Premium".to_string()),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            sample_value(&sku, &document),
+            BicepValue::String("Standard".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sample_value_nested_object() {
+        let mut properties = IndexMap::new();
+        properties.insert(
+            "name".to_string(),
+            BicepParameter {
+                min_length: Some(1),
+                ..Default::default()
+            },
+        );
+        properties.insert(
+            "tier".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::Union(vec!["Basic".to_string(), "Pro".to_string()]),
+                ..Default::default()
+            },
+        );
+        properties.insert(
+            "notes".to_string(),
+            BicepParameter {
+                is_nullable: true,
+                ..Default::default()
+            },
+        );
+
+        let mut document = BicepDocument::default();
+        document.types.insert(
+            "settingsType".to_string(),
+            BicepCustomType {
+                description: None,
+                descriptions: IndexMap::new(),
+                definition: BicepType::Object(Some(properties)),
+                discriminator: None,
+                is_exported: false,
+                is_sealed: false,
+                is_secure: false,
+            },
+        );
+
+        let parameter = BicepParameter {
+            parameter_type: BicepType::Array(Box::new(BicepType::CustomType(
+                "settingsType".to_string(),
+            ))),
+            ..Default::default()
+        };
+
+        let mut expected = IndexMap::new();
+        expected.insert("name".to_string(), BicepValue::String("sample".to_string()));
+        expected.insert("tier".to_string(), BicepValue::String("Basic".to_string()));
+        assert_eq!(
+            sample_value(&parameter, &document),
+            BicepValue::Array(vec![BicepValue::Object(expected)])
+        );
+    }
+}
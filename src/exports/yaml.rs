@@ -2,9 +2,17 @@
 ///
 /// This module provides functions to export parsed Bicep documents
 /// to YAML format with improved multiline string representation.
-use std::error::Error;
-use std::{fs::File, io::Write, path::Path};
+/// The output is the versioned document format described in
+/// [`interchange`](super::interchange), and can be read back with
+/// [`from_yaml`](super::interchange::from_yaml).
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
 
+use crate::error::BicepError;
+use crate::exports::{interchange::VersionedDocument, ExportOptions, Exporter};
 use crate::parsing::BicepDocument;
 
 /// Export a parsed Bicep document as YAML to a file
@@ -13,7 +21,7 @@ use crate::parsing::BicepDocument;
 ///
 /// * `document` - The BicepDocument to export
 /// * `output_path` - The path where the YAML file should be written
-/// * `exclude_empty` - Whether to exclude empty sections from the output
+/// * `options` - How to write the document, such as its language and layout
 ///
 /// # Returns
 ///
@@ -21,11 +29,12 @@ use crate::parsing::BicepDocument;
 pub fn export_to_file<P: AsRef<Path>>(
     document: &BicepDocument,
     output_path: P,
-    exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
-    let yaml = export_to_string(document, exclude_empty)?;
-    let mut file = File::create(output_path)?;
-    file.write_all(yaml.as_bytes())?;
+    options: &ExportOptions,
+) -> Result<(), BicepError> {
+    let mut writer =
+        BufWriter::new(File::create(&output_path).map_err(|e| BicepError::io(&output_path, e))?);
+    export_to_writer(document, &mut writer, options)?;
+    writer.flush()?;
     Ok(())
 }
 
@@ -34,22 +43,72 @@ pub fn export_to_file<P: AsRef<Path>>(
 /// # Arguments
 ///
 /// * `document` - The BicepDocument to export
-/// * `exclude_empty` - Whether to exclude empty sections from the output
+/// * `options` - How to write the document, such as its language and layout
 ///
 /// # Returns
 ///
 /// A Result containing the YAML string or an error
 pub fn export_to_string(
     document: &BicepDocument,
-    _exclude_empty: bool,
-) -> Result<String, Box<dyn Error>> {
-    // Note: exclude_empty parameter is kept for API consistency with other exporters
-    // The BicepDocument already has serde attributes that handle skipping empty collections
-    let yaml = serde_yaml::to_string(document)?;
+    options: &ExportOptions,
+) -> Result<String, BicepError> {
+    let mut buffer = Vec::new();
+    export_to_writer(document, &mut buffer, options)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// Write a parsed Bicep document as YAML
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to export
+/// * `writer` - Where to write the YAML
+/// * `options` - How to write the document, such as its language and layout
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn export_to_writer(
+    document: &BicepDocument,
+    writer: &mut dyn Write,
+    _options: &ExportOptions,
+) -> Result<(), BicepError> {
+    // Note: exclude_empty is not needed, as the BicepDocument already has serde
+    // attributes that handle skipping empty collections. Secure defaults are not
+    // redacted, so that the document reads back as it was.
+    let yaml = serde_yaml::to_string(&VersionedDocument::new(document))?;
 
     // Post-process to improve multiline string representation
-    let improved_yaml = improve_multiline_string_representation(&yaml);
-    Ok(improved_yaml)
+    write_multiline_string_representation(&yaml, writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// The yaml format for the exporter registry
+#[derive(Clone, Copy, Debug, Default)]
+pub struct YamlExporter;
+
+impl Exporter for YamlExporter {
+    fn name(&self) -> &str {
+        "yaml"
+    }
+
+    fn extension(&self) -> &str {
+        "yaml"
+    }
+
+    fn description(&self) -> &str {
+        "Document Bicep file in YAML format"
+    }
+
+    fn export_to_writer(
+        &self,
+        document: &BicepDocument,
+        writer: &mut dyn Write,
+        options: &ExportOptions,
+    ) -> Result<(), BicepError> {
+        export_to_writer(document, writer, options)
+    }
 }
 
 // We use the #[serde(skip_serializing_if = "...")] attributes on the BicepDocument struct
@@ -71,31 +130,36 @@ pub fn parse_and_export<P: AsRef<Path>>(
     source_code: &str,
     output_path: P,
     exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), BicepError> {
     let document = crate::parse_bicep_document(source_code)?;
-    export_to_file(&document, output_path, exclude_empty)?;
+    export_to_file(
+        &document,
+        output_path,
+        &ExportOptions::builder()
+            .exclude_empty(exclude_empty)
+            .build(),
+    )?;
     Ok(())
 }
 
-/// Improve the YAML representation of multiline strings by ensuring consistency
+/// Write YAML with multiline strings in a consistent representation
 ///
 /// This function processes YAML output to convert escaped multiline strings
-/// to block scalar format for better readability.
+/// to block scalar format for better readability, writing each line as it goes.
 ///
 /// # Arguments
 ///
 /// * `yaml` - The YAML string to process
+/// * `writer` - Where to write the improved YAML
 ///
 /// # Returns
 ///
-/// An improved YAML string with better multiline string representation
-fn improve_multiline_string_representation(yaml: &str) -> String {
-    let lines: Vec<&str> = yaml.lines().collect();
-    let mut result = Vec::new();
-    let mut i = 0;
-
-    while i < lines.len() {
-        let line = lines[i];
+/// A Result indicating success or an error
+fn write_multiline_string_representation(yaml: &str, writer: &mut dyn Write) -> io::Result<()> {
+    for (index, line) in yaml.lines().enumerate() {
+        if index > 0 {
+            writer.write_all(b"\n")?;
+        }
 
         // Check if this line contains a quoted multiline string that should be block style
         if line.contains("\"") && (line.contains("\\n") || line.contains("\\t")) {
@@ -111,21 +175,18 @@ fn improve_multiline_string_representation(yaml: &str) -> String {
 
                     // If it contains newlines, convert to block scalar
                     if inner_content.contains("\\n") {
-                        // Convert to block scalar format
-                        let block_content = convert_to_block_scalar(inner_content, key_part);
-                        result.push(block_content);
-                        i += 1;
+                        writer.write_all(
+                            convert_to_block_scalar(inner_content, key_part).as_bytes(),
+                        )?;
                         continue;
                     }
                 }
             }
         }
 
-        result.push(line.to_string());
-        i += 1;
+        writer.write_all(line.as_bytes())?;
     }
-
-    result.join("\n")
+    Ok(())
 }
 
 /// Unescapes a string containing common YAML escape sequences.
@@ -156,10 +217,25 @@ fn unescape_yaml_string(s: &str) -> String {
     unescaped
 }
 
+/// Whether a string reads back unchanged from a `|-` block scalar
+///
+/// Block scalars drop a trailing line break, cannot start with indentation, and
+/// have no escape sequences, so strings with any of these stay quoted.
+fn fits_block_scalar(content: &str, unescaped: &str) -> bool {
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' && !matches!(chars.next(), Some('n' | 't' | '\\' | '"' | '\'')) {
+            return false;
+        }
+    }
+    !unescaped.ends_with('\n') && !unescaped.contains('\r') && !unescaped.starts_with([' ', '\t'])
+}
+
 /// Convert escaped string content to block scalar format
 ///
 /// This function converts escaped string content to YAML block scalar format
-/// for improved readability of multiline strings.
+/// for improved readability of multiline strings. Strings that would not read
+/// back unchanged from a block scalar are kept as quoted strings.
 ///
 /// # Arguments
 ///
@@ -173,13 +249,13 @@ fn convert_to_block_scalar(content: &str, key_part: &str) -> String {
     // Unescape the content
     let unescaped = unescape_yaml_string(content);
 
-    // Determine indentation based on key part
-    let base_indent = key_part.len() - key_part.trim_start().len();
+    // Indent the content past the key, which follows any sequence markers
+    let base_indent = key_part.len() - key_part.trim_start_matches([' ', '-']).len();
     let content_indent = " ".repeat(base_indent + 2);
 
     // Split into lines and format as block scalar
-    let lines: Vec<&str> = unescaped.lines().collect();
-    if lines.len() > 1 {
+    let lines: Vec<&str> = unescaped.split('\n').collect();
+    if lines.len() > 1 && fits_block_scalar(content, &unescaped) && !key_part.contains('"') {
         let mut result = format!("{key_part} |-");
         for line in lines {
             result.push_str(&format!("\n{content_indent}{line}"));
@@ -217,9 +293,11 @@ mod tests {
     }
 
     #[test]
-    fn test_improve_multiline_string_representation() {
+    fn test_write_multiline_string_representation() {
         let yaml = r#"field: "Line 1\nLine 2\nLine 3""#;
-        let result = improve_multiline_string_representation(yaml);
+        let mut result = Vec::new();
+        write_multiline_string_representation(yaml, &mut result).unwrap();
+        let result = String::from_utf8(result).unwrap();
 
         let expected = "field: |-\n  Line 1\n  Line 2\n  Line 3";
         assert_eq!(result, expected);
@@ -245,10 +323,14 @@ mod tests {
         );
 
         // Test with exclude_empty = false (default behavior)
-        let result_with_all = export_to_string(&document, false).unwrap();
+        let result_with_all = export_to_string(&document, &ExportOptions::default()).unwrap();
 
         // Test with exclude_empty = true
-        let result_without_empty = export_to_string(&document, true).unwrap();
+        let result_without_empty = export_to_string(
+            &document,
+            &ExportOptions::builder().exclude_empty(true).build(),
+        )
+        .unwrap();
 
         // Both should contain the document name and the parameter
         assert!(result_with_all.contains("name: Test Template"));
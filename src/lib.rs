@@ -1,21 +1,41 @@
-use std::{error::Error, path::Path};
+use std::{cell::RefCell, path::Path};
 
 use tree_sitter::{Parser, Tree};
 
 // Initialize rust-i18n
-rust_i18n::i18n!("locales");
+rust_i18n::i18n!(
+    "locales",
+    fallback = "en",
+    backend = crate::localization::RuntimeCatalogs
+);
 
+pub mod analysis;
+pub mod batch;
+pub mod error;
 pub mod exports;
 pub mod localization;
+pub mod lsp;
 pub mod parsing;
+pub mod preview;
 
-pub use parsing::{BicepDocument, BicepParserError, BicepType, BicepValue};
+pub use error::{BicepError, Span};
+pub use exports::{ExportOptions, Exporter, ExporterRegistry};
+pub use localization::Language;
+pub use parsing::{BicepDocument, BicepType, BicepValue};
 
 // Re-export the t! macro to make it available throughout the crate
 pub use rust_i18n::t;
 
+thread_local! {
+    /// The parser for this thread, created on first use and reused for every file
+    static PARSER: RefCell<Option<Parser>> = const { RefCell::new(None) };
+}
+
 /// Parse a bicep file content and return the tree-sitter Tree
 ///
+/// Each thread keeps its own parser, so parsing many files does not pay for
+/// creating a parser and loading the grammar each time.
+///
 /// # Arguments
 ///
 /// * `content` - The content of the Bicep file to parse
@@ -24,16 +44,24 @@ pub use rust_i18n::t;
 ///
 /// An Option containing the parsed Tree if successful, None otherwise
 pub fn parse_bicep_file(content: &str) -> Option<Tree> {
-    let mut parser = Parser::new();
+    PARSER.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        if cell.is_none() {
+            let mut parser = Parser::new();
+            if parser
+                .set_language(&tree_sitter_bicep::LANGUAGE.into())
+                .is_err()
+            {
+                return None;
+            }
+            *cell = Some(parser);
+        }
 
-    if parser
-        .set_language(&tree_sitter_bicep::LANGUAGE.into())
-        .is_err()
-    {
-        return None;
-    }
-
-    parser.parse(content, None)
+        let parser = cell.as_mut()?;
+        // Discard any state left by a parse that did not finish
+        parser.reset();
+        parser.parse(content, None)
+    })
 }
 
 /// Wrapper function to parse a Bicep document from source code
@@ -45,14 +73,66 @@ pub fn parse_bicep_file(content: &str) -> Option<Tree> {
 /// # Returns
 ///
 /// A Result containing the parsed BicepDocument if successful, or an error
-pub fn parse_bicep_document(source_code: &str) -> Result<parsing::BicepDocument, Box<dyn Error>> {
-    let tree = parse_bicep_file(source_code)
-        .ok_or_else(|| Box::<dyn Error>::from("Failed to parse Bicep file"))?;
+pub fn parse_bicep_document(source_code: &str) -> Result<parsing::BicepDocument, BicepError> {
+    let tree = parse_bicep_file(source_code).ok_or_else(|| BicepError::Syntax {
+        message: "Failed to parse Bicep file".to_string(),
+        span: None,
+    })?;
     parsing::parse_bicep_document(&tree, source_code)
 }
 
+/// Parse an ARM JSON template into a BicepDocument
+///
+/// # Arguments
+///
+/// * `source_code` - The JSON source of the ARM template to parse
+///
+/// # Returns
+///
+/// A Result containing the parsed BicepDocument if successful, or an error
+pub fn parse_arm_document(source_code: &str) -> Result<parsing::BicepDocument, BicepError> {
+    parsing::parse_arm_template(source_code)
+}
+
+/// Parse a template as Bicep or ARM JSON depending on its file extension
+///
+/// Files with a `.json` extension are read as ARM templates; anything else is
+/// read as Bicep.
+///
+/// # Arguments
+///
+/// * `source_code` - The source code of the template
+/// * `path` - The path of the template file, used to pick the parser
+///
+/// # Returns
+///
+/// A Result containing the parsed BicepDocument if successful, or an error
+pub fn parse_template(
+    source_code: &str,
+    path: &Path,
+) -> Result<parsing::BicepDocument, BicepError> {
+    let is_arm = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    if is_arm {
+        parse_arm_document(source_code)
+    } else {
+        parse_bicep_document(source_code)
+    }
+}
+
 // Backward compatibility functions that delegate to the new export modules
 
+/// Options for the documentation formats of the functions below
+fn document_options(use_emoji: bool, exclude_empty: bool, language: Language) -> ExportOptions {
+    ExportOptions::builder()
+        .use_emoji(use_emoji)
+        .exclude_empty(exclude_empty)
+        .language(language)
+        .build()
+}
+
 /// Export a parsed Bicep document as YAML to a file
 ///
 /// # Arguments
@@ -68,8 +148,14 @@ pub fn export_bicep_document_to_yaml<P: AsRef<Path>>(
     document: &BicepDocument,
     output_path: P,
     exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
-    exports::yaml::export_to_file(document, output_path, exclude_empty)
+) -> Result<(), BicepError> {
+    exports::yaml::export_to_file(
+        document,
+        output_path,
+        &ExportOptions::builder()
+            .exclude_empty(exclude_empty)
+            .build(),
+    )
 }
 
 /// Export a parsed Bicep document as YAML string
@@ -85,8 +171,13 @@ pub fn export_bicep_document_to_yaml<P: AsRef<Path>>(
 pub fn export_bicep_document_to_yaml_string(
     document: &BicepDocument,
     exclude_empty: bool,
-) -> Result<String, Box<dyn Error>> {
-    exports::yaml::export_to_string(document, exclude_empty)
+) -> Result<String, BicepError> {
+    exports::yaml::export_to_string(
+        document,
+        &ExportOptions::builder()
+            .exclude_empty(exclude_empty)
+            .build(),
+    )
 }
 
 /// Export a parsed Bicep document as JSON to a file
@@ -106,8 +197,12 @@ pub fn export_bicep_document_to_json<P: AsRef<Path>>(
     output_path: P,
     pretty: bool,
     exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
-    exports::json::export_to_file(document, output_path, pretty, exclude_empty)
+) -> Result<(), BicepError> {
+    let options = ExportOptions::builder()
+        .pretty(pretty)
+        .exclude_empty(exclude_empty)
+        .build();
+    exports::json::export_to_file(document, output_path, &options)
 }
 
 /// Export a parsed Bicep document as JSON string
@@ -125,8 +220,12 @@ pub fn export_bicep_document_to_json_string(
     document: &BicepDocument,
     pretty: bool,
     exclude_empty: bool,
-) -> Result<String, Box<dyn Error>> {
-    exports::json::export_to_string(document, pretty, exclude_empty)
+) -> Result<String, BicepError> {
+    let options = ExportOptions::builder()
+        .pretty(pretty)
+        .exclude_empty(exclude_empty)
+        .build();
+    exports::json::export_to_string(document, &options)
 }
 
 /// Export a parsed Bicep document as Markdown to a file
@@ -146,8 +245,12 @@ pub fn export_bicep_document_to_markdown<P: AsRef<Path>>(
     output_path: P,
     use_emoji: bool,
     exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
-    exports::markdown::export_to_file(document, output_path, use_emoji, exclude_empty)
+) -> Result<(), BicepError> {
+    exports::markdown::export_to_file(
+        document,
+        output_path,
+        &document_options(use_emoji, exclude_empty, Language::English),
+    )
 }
 
 /// Export a parsed Bicep document as Markdown string
@@ -165,17 +268,21 @@ pub fn export_bicep_document_to_markdown_string(
     document: &BicepDocument,
     use_emoji: bool,
     exclude_empty: bool,
-) -> Result<String, Box<dyn Error>> {
-    exports::markdown::export_to_string(document, use_emoji, exclude_empty)
+) -> Result<String, BicepError> {
+    exports::markdown::export_to_string(
+        document,
+        &document_options(use_emoji, exclude_empty, Language::English),
+    )
 }
 
-/// Export a parsed Bicep document as Markdown string with localization support
+/// Export a parsed Bicep document as Markdown string with labels in a language
 ///
 /// # Arguments
 ///
 /// * `document` - The BicepDocument to export
 /// * `use_emoji` - Whether to use emoji symbols (✅/❌) for Yes/No values
 /// * `exclude_empty` - Whether to exclude empty sections from the output
+/// * `language` - The language to write labels and headings in
 ///
 /// # Returns
 ///
@@ -184,8 +291,12 @@ pub fn export_bicep_document_to_markdown_string_localized(
     document: &BicepDocument,
     use_emoji: bool,
     exclude_empty: bool,
-) -> Result<String, Box<dyn Error>> {
-    exports::markdown::export_to_string(document, use_emoji, exclude_empty)
+    language: Language,
+) -> Result<String, BicepError> {
+    exports::markdown::export_to_string(
+        document,
+        &document_options(use_emoji, exclude_empty, language),
+    )
 }
 
 /// Export a parsed Bicep document as AsciiDoc to a file
@@ -205,8 +316,12 @@ pub fn export_bicep_document_to_asciidoc<P: AsRef<Path>>(
     output_path: P,
     use_emoji: bool,
     exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
-    exports::asciidoc::export_to_file(document, output_path, use_emoji, exclude_empty)
+) -> Result<(), BicepError> {
+    exports::asciidoc::export_to_file(
+        document,
+        output_path,
+        &document_options(use_emoji, exclude_empty, Language::English),
+    )
 }
 
 /// Export a parsed Bicep document as AsciiDoc string
@@ -224,8 +339,35 @@ pub fn export_bicep_document_to_asciidoc_string(
     document: &BicepDocument,
     use_emoji: bool,
     exclude_empty: bool,
-) -> Result<String, Box<dyn Error>> {
-    exports::asciidoc::export_to_string(document, use_emoji, exclude_empty)
+) -> Result<String, BicepError> {
+    exports::asciidoc::export_to_string(
+        document,
+        &document_options(use_emoji, exclude_empty, Language::English),
+    )
+}
+
+/// Export a parsed Bicep document as AsciiDoc string with labels in a language
+///
+/// # Arguments
+///
+/// * `document` - The BicepDocument to export
+/// * `use_emoji` - Whether to use emoji symbols (✅/❌) for Yes/No values
+/// * `exclude_empty` - Whether to exclude empty sections from the output
+/// * `language` - The language to write labels and headings in
+///
+/// # Returns
+///
+/// A Result containing the AsciiDoc string or an error
+pub fn export_bicep_document_to_asciidoc_string_localized(
+    document: &BicepDocument,
+    use_emoji: bool,
+    exclude_empty: bool,
+    language: Language,
+) -> Result<String, BicepError> {
+    exports::asciidoc::export_to_string(
+        document,
+        &document_options(use_emoji, exclude_empty, language),
+    )
 }
 
 /// Parse a Bicep file and export it as AsciiDoc in one step
@@ -243,7 +385,7 @@ pub fn parse_and_export_to_asciidoc<P: AsRef<Path>, Q: AsRef<Path>>(
     file_path: P,
     output_path: Q,
     exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), BicepError> {
     exports::asciidoc::parse_and_export(file_path, output_path, exclude_empty)
 }
 
@@ -262,7 +404,7 @@ pub fn parse_and_export_to_yaml<P: AsRef<Path>>(
     source_code: &str,
     output_path: P,
     exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), BicepError> {
     exports::yaml::parse_and_export(source_code, output_path, exclude_empty)
 }
 
@@ -283,7 +425,7 @@ pub fn parse_and_export_to_json<P: AsRef<Path>>(
     output_path: P,
     pretty: bool,
     exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), BicepError> {
     exports::json::parse_and_export(source_code, output_path, pretty, exclude_empty)
 }
 
@@ -302,7 +444,7 @@ pub fn parse_and_export_to_markdown<P: AsRef<Path>, Q: AsRef<Path>>(
     file_path: P,
     output_path: Q,
     exclude_empty: bool,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), BicepError> {
     exports::markdown::parse_and_export(file_path, output_path, exclude_empty)
 }
 
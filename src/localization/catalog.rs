@@ -0,0 +1,418 @@
+/// Translation catalogs loaded at runtime
+///
+/// The catalogs in `locales/` are compiled into the crate. Catalogs in the same
+/// format can also be loaded from a directory at runtime, to add a language or to
+/// reword some keys of a built-in one. A loaded catalog takes precedence over the
+/// built-in catalog for its locale, and keys it does not define are looked up in
+/// the next more general locale, e.g. `pt-BR`, then `pt`, then English.
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs,
+    path::Path,
+    sync::{PoisonError, RwLock},
+};
+
+use serde_yaml::Value;
+
+use crate::error::{BicepError, Result};
+
+/// The locale that every other catalog is checked against
+pub const REFERENCE_LOCALE: &str = "en";
+
+/// The catalogs compiled into the crate
+const BUILT_IN: [(&str, &str); 6] = [
+    ("en", include_str!("../../locales/en.yml")),
+    ("es", include_str!("../../locales/es.yml")),
+    ("fr", include_str!("../../locales/fr.yml")),
+    ("de", include_str!("../../locales/de.yml")),
+    ("ja", include_str!("../../locales/ja.yml")),
+    ("zh", include_str!("../../locales/zh.yml")),
+];
+
+/// File extensions read as catalogs
+const EXTENSIONS: [&str; 3] = ["yml", "yaml", "json"];
+
+/// Registered catalogs
+static REGISTRY: RwLock<Registry> = RwLock::new(Registry {
+    catalogs: BTreeMap::new(),
+    interned: BTreeSet::new(),
+});
+
+/// Registered catalogs by locale, and the text they share
+///
+/// The rust-i18n backend hands out `&'static str`, so locales and translations are
+/// interned: each distinct text is leaked once, however often catalogs register it.
+struct Registry {
+    catalogs: BTreeMap<&'static str, HashMap<String, &'static str>>,
+    interned: BTreeSet<&'static str>,
+}
+
+/// The translations for one locale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Catalog {
+    /// The locale code, such as `pt-BR`
+    pub locale: String,
+    /// The text of each key, keyed by its dotted path such as `export.parameters`
+    pub entries: BTreeMap<String, String>,
+}
+
+/// Keys that differ between a catalog and the reference catalog
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatalogDifferences {
+    /// Keys the reference catalog has and the catalog does not
+    pub missing: Vec<String>,
+    /// Keys the catalog has and the reference catalog does not
+    pub extra: Vec<String>,
+}
+
+impl CatalogDifferences {
+    /// Whether the catalog has exactly the keys of the reference catalog
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
+impl Catalog {
+    /// Parse a catalog from YAML or JSON
+    ///
+    /// Nested mappings are flattened into dotted keys, as in the built-in catalogs.
+    ///
+    /// # Arguments
+    ///
+    /// * `locale` - The locale the catalog translates into
+    /// * `content` - The YAML or JSON source of the catalog
+    ///
+    /// # Returns
+    ///
+    /// The catalog, or a localization error if the source is not a mapping of text
+    pub fn parse(locale: &str, content: &str) -> Result<Self> {
+        let error = |message: String| BicepError::Localization {
+            locale: locale.to_string(),
+            message,
+        };
+
+        let value: Value = serde_yaml::from_str(content).map_err(|e| error(e.to_string()))?;
+        let mut entries = BTreeMap::new();
+        match value {
+            Value::Mapping(_) => flatten(&value, "", &mut entries).map_err(error)?,
+            Value::Null => {},
+            _ => return Err(error("a catalog must be a mapping of keys".to_string())),
+        }
+
+        Ok(Catalog {
+            locale: locale.to_string(),
+            entries,
+        })
+    }
+
+    /// Read a catalog from a `.yml`, `.yaml` or `.json` file named after its locale
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The catalog file, such as `locales/pt-BR.yml`
+    ///
+    /// # Returns
+    ///
+    /// The catalog, or an error if the file cannot be read or parsed
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let locale = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| BicepError::Localization {
+                locale: path.display().to_string(),
+                message: "the file name is not a locale code".to_string(),
+            })?;
+        let content = fs::read_to_string(path).map_err(|e| BicepError::io(path, e))?;
+        Catalog::parse(locale, &content)
+    }
+
+    /// The catalog compiled into the crate for a locale
+    pub fn built_in(locale: &str) -> Option<Self> {
+        BUILT_IN
+            .iter()
+            .find(|(code, _)| *code == locale)
+            .and_then(|(code, content)| Catalog::parse(code, content).ok())
+    }
+
+    /// Every catalog compiled into the crate
+    pub fn built_ins() -> Vec<Self> {
+        BUILT_IN
+            .iter()
+            .filter_map(|(code, _)| Catalog::built_in(code))
+            .collect()
+    }
+
+    /// Compare the keys of this catalog with a reference catalog
+    pub fn compare(&self, reference: &Catalog) -> CatalogDifferences {
+        let keys: BTreeSet<&String> = self.entries.keys().collect();
+        let reference_keys: BTreeSet<&String> = reference.entries.keys().collect();
+        CatalogDifferences {
+            missing: reference_keys
+                .difference(&keys)
+                .map(|key| key.to_string())
+                .collect(),
+            extra: keys
+                .difference(&reference_keys)
+                .map(|key| key.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Add the text under a YAML value to a catalog, prefixing keys with a path
+fn flatten(
+    value: &Value,
+    prefix: &str,
+    entries: &mut BTreeMap<String, String>,
+) -> std::result::Result<(), String> {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping {
+                let key = match key {
+                    Value::String(key) => key.clone(),
+                    _ => return Err(format!("the keys under '{prefix}' must be text")),
+                };
+                let path = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(value, &path, entries)?;
+            }
+        },
+        Value::String(text) => {
+            entries.insert(prefix.to_string(), text.clone());
+        },
+        Value::Bool(_) | Value::Number(_) => {
+            let text = serde_yaml::to_string(value).map_err(|e| e.to_string())?;
+            entries.insert(prefix.to_string(), text.trim_end().to_string());
+        },
+        _ => return Err(format!("the value of '{prefix}' must be text")),
+    }
+    Ok(())
+}
+
+/// Read every catalog in a directory, ordered by locale
+///
+/// # Arguments
+///
+/// * `dir` - A directory of `.yml`, `.yaml` or `.json` files named after their locales
+///
+/// # Returns
+///
+/// The catalogs, or an error if the directory or a catalog cannot be read
+pub fn read_catalogs(dir: &Path) -> Result<Vec<Catalog>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| BicepError::io(dir, e))? {
+        let path = entry.map_err(|e| BicepError::io(dir, e))?.path();
+        let is_catalog = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| EXTENSIONS.contains(&extension));
+        if path.is_file() && is_catalog {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths.iter().map(|path| Catalog::from_file(path)).collect()
+}
+
+/// Make a catalog available to translation
+///
+/// Registering a catalog for a locale that is already registered adds to and
+/// replaces its keys.
+///
+/// # Returns
+///
+/// The locale code to translate with, as the locale of a [`Language::Other`](super::Language::Other)
+pub fn register_catalog(catalog: Catalog) -> &'static str {
+    let mut registry = REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+    let locale = match registry
+        .catalogs
+        .keys()
+        .find(|locale| locale.eq_ignore_ascii_case(&catalog.locale))
+    {
+        Some(locale) => *locale,
+        None => registry.intern(catalog.locale),
+    };
+
+    for (key, text) in catalog.entries {
+        let text = registry.intern(text);
+        registry
+            .catalogs
+            .entry(locale)
+            .or_default()
+            .insert(key, text);
+    }
+    locale
+}
+
+impl Registry {
+    /// The interned copy of a text, leaking it the first time it is seen
+    fn intern(&mut self, text: String) -> &'static str {
+        if let Some(interned) = self.interned.get(text.as_str()) {
+            return interned;
+        }
+        let interned: &'static str = Box::leak(text.into_boxed_str());
+        self.interned.insert(interned);
+        interned
+    }
+}
+
+/// Read and register every catalog in a directory
+///
+/// # Returns
+///
+/// The locales of the catalogs, or an error if a catalog cannot be read
+pub fn load_locale_dir(dir: &Path) -> Result<Vec<&'static str>> {
+    Ok(read_catalogs(dir)?
+        .into_iter()
+        .map(register_catalog)
+        .collect())
+}
+
+/// The registered locale that matches a code, ignoring case
+pub fn registered_locale(code: &str) -> Option<&'static str> {
+    let registry = REGISTRY.read().unwrap_or_else(PoisonError::into_inner);
+    registry
+        .catalogs
+        .keys()
+        .find(|locale| locale.eq_ignore_ascii_case(code))
+        .copied()
+}
+
+/// The locales of every registered catalog
+pub fn registered_locales() -> Vec<&'static str> {
+    let registry = REGISTRY.read().unwrap_or_else(PoisonError::into_inner);
+    registry.catalogs.keys().copied().collect()
+}
+
+/// The rust-i18n backend that serves registered catalogs
+///
+/// It extends the built-in catalogs, so its keys take precedence over theirs.
+pub struct RuntimeCatalogs;
+
+impl rust_i18n::Backend for RuntimeCatalogs {
+    fn available_locales(&self) -> Vec<&str> {
+        registered_locales()
+    }
+
+    fn translate(&self, locale: &str, key: &str) -> Option<&str> {
+        let registry = REGISTRY.read().unwrap_or_else(PoisonError::into_inner);
+        registry
+            .catalogs
+            .get(locale)
+            .and_then(|entries| entries.get(key))
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::Backend;
+
+    use super::*;
+    use crate::localization::{try_translate, Language};
+
+    #[test]
+    fn test_parse_flattens_keys() {
+        let catalog = Catalog::parse(
+            "pt",
+            "cli:\n  port_help: Porta de escuta\nexport:\n  parameters: Parâmetros\n",
+        )
+        .unwrap();
+
+        assert_eq!(catalog.locale, "pt");
+        assert_eq!(catalog.entries["cli.port_help"], "Porta de escuta");
+        assert_eq!(catalog.entries["export.parameters"], "Parâmetros");
+
+        let json = Catalog::parse("pt", r#"{"common": {"yes": "Sim"}}"#).unwrap();
+        assert_eq!(json.entries["common.yes"], "Sim");
+
+        let error = Catalog::parse("pt", "cli: [a, b]").unwrap_err();
+        assert_eq!(error.code(), "BD3001");
+    }
+
+    #[test]
+    fn test_built_in_catalogs_match_english() {
+        let english = Catalog::built_in(REFERENCE_LOCALE).unwrap();
+        assert!(!english.entries.is_empty());
+
+        let catalogs = Catalog::built_ins();
+        assert_eq!(catalogs.len(), Language::all().len());
+        for catalog in catalogs {
+            let differences = catalog.compare(&english);
+            assert!(
+                differences.is_empty(),
+                "{} catalog differs from English: {differences:?}",
+                catalog.locale
+            );
+        }
+    }
+
+    #[test]
+    fn test_compare_reports_missing_and_extra_keys() {
+        let english = Catalog::parse("en", "a: A\nb: B\n").unwrap();
+        let catalog = Catalog::parse("xx", "a: A\nc: C\n").unwrap();
+
+        let differences = catalog.compare(&english);
+        assert_eq!(differences.missing, vec!["b"]);
+        assert_eq!(differences.extra, vec!["c"]);
+    }
+
+    #[test]
+    fn test_registered_catalogs_fall_back_to_more_general_locales() {
+        let general = Catalog::parse("tlh", "cli:\n  port_help: general\n  help_help: help\n");
+        let regional = Catalog::parse("tlh-KX", "cli:\n  port_help: regional\n");
+        register_catalog(general.unwrap());
+        let locale = register_catalog(regional.unwrap());
+        assert_eq!(registered_locale("TLH-kx"), Some(locale));
+
+        let language = Language::from_code("tlh-KX").unwrap();
+        assert_eq!(language, Language::Other(locale));
+        assert_eq!(
+            try_translate(language, "cli.port_help").as_deref(),
+            Some("regional")
+        );
+        assert_eq!(
+            try_translate(language, "cli.help_help").as_deref(),
+            Some("help")
+        );
+        assert_eq!(
+            try_translate(language, "cli.version_help").as_deref(),
+            Some("Print version")
+        );
+
+        // Locales with no catalog of their own use the nearest one that has
+        assert_eq!(
+            Language::from_code("tlh-ZZ"),
+            Some(Language::Other(registered_locale("tlh").unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_registering_a_catalog_again_reuses_its_text() {
+        let catalog = Catalog::parse("qya", "cli:\n  port_help: Ando\n").unwrap();
+        let locale = register_catalog(catalog.clone());
+        let first = RuntimeCatalogs.translate(locale, "cli.port_help").unwrap();
+
+        assert_eq!(register_catalog(catalog), locale);
+        let second = RuntimeCatalogs.translate(locale, "cli.port_help").unwrap();
+        assert!(std::ptr::eq(first, second));
+    }
+
+    #[test]
+    fn test_registered_catalogs_override_built_in_keys() {
+        register_catalog(Catalog::parse("ja", "cli:\n  port_help: 上書き\n").unwrap());
+
+        assert_eq!(
+            try_translate(Language::Japanese, "cli.port_help").as_deref(),
+            Some("上書き")
+        );
+        assert_eq!(
+            try_translate(Language::Japanese, "cli.help_help").as_deref(),
+            Some("ヘルプを表示")
+        );
+    }
+}
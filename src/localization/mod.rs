@@ -0,0 +1,149 @@
+/// Localization module for bicep-docs using rust-i18n
+///
+/// This module provides internationalization support for the CLI and generated documentation
+/// using the rust-i18n crate.
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+pub mod catalog;
+
+pub use catalog::{load_locale_dir, Catalog, CatalogDifferences, RuntimeCatalogs};
+
+/// Supported languages in the application
+///
+/// Languages are serialized as their locale codes, such as `en`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    French,
+    German,
+    Japanese,
+    Chinese,
+    /// A language from a catalog loaded at runtime, by its registered locale code
+    Other(&'static str),
+}
+
+impl Language {
+    /// Get the language code as a string
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Japanese => "ja",
+            Language::Chinese => "zh",
+            Language::Other(code) => code,
+        }
+    }
+
+    /// Get the language name in its native form
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+            Language::French => "Français",
+            Language::German => "Deutsch",
+            Language::Japanese => "日本語",
+            Language::Chinese => "中文",
+            Language::Other(code) => code,
+        }
+    }
+
+    /// Parse a language code string to a Language enum
+    ///
+    /// A loaded catalog for the exact locale, such as `fr-CA`, is preferred over the
+    /// built-in language. A locale with neither falls back to a more general one, so
+    /// `pt-BR` is read as `pt`.
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|language| language.code().eq_ignore_ascii_case(code))
+            .or_else(|| catalog::registered_locale(code).map(Language::Other))
+            .or_else(|| match code.to_lowercase().as_str() {
+                "en-us" | "en-gb" => Some(Language::English),
+                "es-es" | "es-mx" => Some(Language::Spanish),
+                "fr-fr" | "fr-ca" => Some(Language::French),
+                "de-de" | "de-at" => Some(Language::German),
+                "ja-jp" => Some(Language::Japanese),
+                "zh-cn" | "zh-tw" => Some(Language::Chinese),
+                _ => None,
+            })
+            .or_else(|| {
+                let (general, _) = code.rsplit_once('-')?;
+                Self::from_code(general)
+            })
+    }
+
+    /// Get the locale codes of every available language, built in or loaded
+    pub fn available_codes() -> Vec<&'static str> {
+        let mut codes: Vec<&str> = Self::all().iter().map(|language| language.code()).collect();
+        for locale in catalog::registered_locales() {
+            if !codes.contains(&locale) {
+                codes.push(locale);
+            }
+        }
+        codes
+    }
+
+    /// Get all built-in languages
+    pub fn all() -> Vec<Self> {
+        vec![
+            Language::English,
+            Language::Spanish,
+            Language::French,
+            Language::German,
+            Language::Japanese,
+            Language::Chinese,
+        ]
+    }
+}
+
+impl Serialize for Language {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Language {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Language::from_code(&code)
+            .ok_or_else(|| de::Error::custom(format!("unsupported language '{code}'")))
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Initialize localization with the specified language
+pub fn init_localization(language: Language) {
+    rust_i18n::set_locale(language.code());
+}
+
+/// Detect the system locale and return the corresponding Language
+pub fn detect_system_locale() -> Language {
+    if let Some(locale) = sys_locale::get_locale() {
+        Language::from_code(&locale).unwrap_or(Language::English)
+    } else {
+        Language::English
+    }
+}
+
+/// Translate a key using rust-i18n's t! macro
+/// This is a convenience function that wraps the t! macro
+pub fn translate(key: &str) -> String {
+    crate::t!(key).to_string()
+}
+
+/// Look up a key in a language, falling back to English
+///
+/// Unlike [`translate`], this reports a key that no catalog defines as `None`
+/// instead of returning the key itself.
+pub fn try_translate(language: Language, key: &str) -> Option<String> {
+    crate::_rust_i18n_try_translate(language.code(), key).map(|text| text.into_owned())
+}
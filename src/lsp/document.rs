@@ -0,0 +1,248 @@
+/// Open text documents and their incremental parse state
+///
+/// Each open document keeps its text, the tree-sitter tree for that text and the
+/// `BicepDocument` model built from the tree. Changes are applied to the tree with
+/// tree-sitter's edit API so that re-parsing reuses the unchanged parts of the tree.
+///
+/// Positions follow the LSP default encoding: lines are zero-based and characters
+/// count UTF-16 code units.
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use tree_sitter::{InputEdit, Node, Parser, Point, Tree};
+
+use crate::parsing::{self, BicepDocument};
+
+/// A position in a text document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    /// Zero-based line number
+    pub line: u32,
+    /// Zero-based character offset in UTF-16 code units
+    pub character: u32,
+}
+
+/// A range in a text document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Range {
+    /// The start of the range
+    pub start: Position,
+    /// The end of the range, exclusive
+    pub end: Position,
+}
+
+/// An open Bicep document
+pub struct TextDocument {
+    /// The version number supplied by the client
+    pub version: i64,
+    /// The current text of the document
+    pub text: String,
+    /// The tree for the current text, if it could be parsed
+    pub tree: Option<Tree>,
+    /// The model from the last successful parse
+    pub model: Option<BicepDocument>,
+}
+
+impl TextDocument {
+    /// Open a document and parse its text
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text of the document
+    /// * `version` - The version number supplied by the client
+    /// * `parser` - The parser to use
+    pub fn open(text: String, version: i64, parser: &mut Parser) -> Self {
+        let mut document = TextDocument {
+            version,
+            text,
+            tree: None,
+            model: None,
+        };
+        document.reparse(parser);
+        document
+    }
+
+    /// Apply a change to the text of the document
+    ///
+    /// Ranged changes are also applied to the tree, so the next call to
+    /// [`TextDocument::reparse`] is incremental. A change without a range replaces
+    /// the whole text and discards the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of text that is replaced, or None for the whole text
+    /// * `new_text` - The replacement text
+    pub fn apply_change(&mut self, range: Option<Range>, new_text: &str) {
+        let Some(range) = range else {
+            self.text = new_text.to_string();
+            self.tree = None;
+            return;
+        };
+
+        let start_byte = offset_at(&self.text, range.start);
+        let old_end_byte = offset_at(&self.text, range.end).max(start_byte);
+        let start_position = point_at(&self.text, start_byte);
+        let old_end_position = point_at(&self.text, old_end_byte);
+
+        self.text.replace_range(start_byte..old_end_byte, new_text);
+
+        let new_end_byte = start_byte + new_text.len();
+        let edit = InputEdit {
+            start_byte,
+            old_end_byte,
+            new_end_byte,
+            start_position,
+            old_end_position,
+            new_end_position: point_at(&self.text, new_end_byte),
+        };
+        if let Some(tree) = &mut self.tree {
+            tree.edit(&edit);
+        }
+    }
+
+    /// Parse the current text, reusing the edited tree where possible
+    ///
+    /// The model is only replaced when the new tree can be read, so the outline
+    /// and hover information stay available while the text is being edited.
+    ///
+    /// # Arguments
+    ///
+    /// * `parser` - The parser to use
+    pub fn reparse(&mut self, parser: &mut Parser) {
+        self.tree = parser.parse(&self.text, self.tree.as_ref());
+        if let Some(tree) = &self.tree {
+            match parsing::parse_bicep_document(tree, &self.text) {
+                Ok(model) => self.model = Some(model),
+                Err(e) => debug!("Keeping previous model, parse failed: {}", e),
+            }
+        }
+    }
+
+    /// Get the LSP range of a node
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - A node of the document's tree
+    ///
+    /// # Returns
+    ///
+    /// The range of the node in UTF-16 positions
+    pub fn node_range(&self, node: &Node) -> Range {
+        Range {
+            start: position_at(&self.text, node.start_byte()),
+            end: position_at(&self.text, node.end_byte()),
+        }
+    }
+}
+
+/// Convert an LSP position to a byte offset
+///
+/// Positions past the end of a line or of the text are clamped.
+///
+/// # Arguments
+///
+/// * `text` - The document text
+/// * `position` - The position to convert
+///
+/// # Returns
+///
+/// The byte offset of the position
+pub fn offset_at(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(index) => line_start += index + 1,
+            None => return text.len(),
+        }
+    }
+
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |index| line_start + index);
+    let mut units = 0;
+    for (index, c) in text[line_start..line_end].char_indices() {
+        if units >= position.character as usize {
+            return line_start + index;
+        }
+        units += c.len_utf16();
+    }
+    line_end
+}
+
+/// Convert a byte offset to an LSP position
+///
+/// # Arguments
+///
+/// * `text` - The document text
+/// * `offset` - The byte offset to convert
+///
+/// # Returns
+///
+/// The position of the offset
+pub fn position_at(text: &str, offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: before[line_start..].encode_utf16().count() as u32,
+    }
+}
+
+/// Convert a byte offset to a tree-sitter point, whose column counts bytes
+fn point_at(text: &str, offset: usize) -> Point {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    Point {
+        row: before.matches('\n').count(),
+        column: offset - line_start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(line: u32, character: u32) -> Position {
+        Position { line, character }
+    }
+
+    #[test]
+    fn test_offset_and_position() {
+        let text = "param a string\nvar é𝄞 = 'x'\n";
+        assert_eq!(offset_at(text, position(0, 6)), 6);
+        // 'é' is one UTF-16 unit and two bytes, '𝄞' is two UTF-16 units and four bytes
+        assert_eq!(offset_at(text, position(1, 5)), 15 + 6);
+        assert_eq!(offset_at(text, position(1, 7)), 15 + 10);
+        assert_eq!(position_at(text, 15 + 10), position(1, 7));
+        // Positions past the end of a line or the text are clamped
+        assert_eq!(offset_at(text, position(0, 99)), 14);
+        assert_eq!(offset_at(text, position(9, 0)), text.len());
+    }
+
+    #[test]
+    fn test_point_at() {
+        let text = "a\nbé c";
+        assert_eq!(point_at(text, 5), Point { row: 1, column: 3 });
+    }
+
+    #[test]
+    fn test_apply_change() {
+        let mut document = TextDocument {
+            version: 1,
+            text: "param name string\nparam count int\n".to_string(),
+            tree: None,
+            model: None,
+        };
+        document.apply_change(
+            Some(Range {
+                start: position(1, 6),
+                end: position(1, 11),
+            }),
+            "total",
+        );
+        assert_eq!(document.text, "param name string\nparam total int\n");
+
+        document.apply_change(None, "param other bool\n");
+        assert_eq!(document.text, "param other bool\n");
+    }
+}
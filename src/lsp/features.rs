@@ -0,0 +1,425 @@
+/// Language features computed from an open document
+///
+/// Declarations are located in the tree-sitter tree for their ranges, and their
+/// details come from the `BicepDocument` model so that the outline, hover cards
+/// and diagnostics match the generated documentation.
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use super::document::{offset_at, position_at, Position, Range, TextDocument};
+use crate::{
+    analysis::{lint::rules::needs_description, SymbolId, SymbolKind},
+    exports::markdown::export_symbol_to_string,
+    localization::Language,
+    parsing::BicepDocument,
+    t,
+};
+
+/// LSP diagnostic severity for warnings
+const SEVERITY_WARNING: u8 = 2;
+
+/// A declaration found in the tree of a document
+struct Declaration<'tree> {
+    /// The kind of declaration
+    kind: SymbolKind,
+    /// The name of the declaration in the document model, e.g. `parent::child`
+    key: String,
+    /// The range of the declaration, including its decorators
+    range: Range,
+    /// The identifier node that names the declaration
+    name: Node<'tree>,
+    /// Resources declared inside the body of a resource
+    children: Vec<Declaration<'tree>>,
+}
+
+/// Map a tree-sitter node kind to the kind of declaration it is
+fn declaration_kind(kind: &str) -> Option<SymbolKind> {
+    match kind {
+        "type_declaration" => Some(SymbolKind::Type),
+        "user_defined_function" | "function_declaration" => Some(SymbolKind::Function),
+        "parameter_declaration" => Some(SymbolKind::Parameter),
+        "variable_declaration" => Some(SymbolKind::Variable),
+        "resource_declaration" => Some(SymbolKind::Resource),
+        "module_declaration" => Some(SymbolKind::Module),
+        "output_declaration" => Some(SymbolKind::Output),
+        _ => None,
+    }
+}
+
+/// Find the identifier that names a declaration
+fn name_node<'tree>(node: &Node<'tree>) -> Option<Node<'tree>> {
+    let mut cursor = node.walk();
+    let name = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "identifier");
+    name
+}
+
+/// Find the top-level declarations of a document
+fn declarations(document: &TextDocument) -> Vec<Declaration<'_>> {
+    let Some(tree) = &document.tree else {
+        return Vec::new();
+    };
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let mut found = Vec::new();
+    let mut decorators_start = None;
+
+    for node in root.children(&mut cursor) {
+        if node.kind() == "decorators" {
+            decorators_start.get_or_insert(node.start_byte());
+            continue;
+        }
+        let start = decorators_start.take().unwrap_or(node.start_byte());
+
+        let Some(kind) = declaration_kind(node.kind()) else {
+            continue;
+        };
+        let Some(name) = name_node(&node) else {
+            continue;
+        };
+        let key = name.utf8_text(document.text.as_bytes()).unwrap_or_default();
+        let mut range = document.node_range(&node);
+        range.start = position_at(&document.text, start);
+
+        let mut children = Vec::new();
+        if kind == SymbolKind::Resource {
+            nested_resources(document, &node, key, &mut children);
+        }
+        found.push(Declaration {
+            kind,
+            key: key.to_string(),
+            range,
+            name,
+            children,
+        });
+    }
+    found
+}
+
+/// Find the resources declared inside the body of a resource
+fn nested_resources<'tree>(
+    document: &TextDocument,
+    node: &Node<'tree>,
+    parent_key: &str,
+    found: &mut Vec<Declaration<'tree>>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.kind() != "resource_declaration" {
+            nested_resources(document, &child, parent_key, found);
+            continue;
+        }
+        let Some(name) = name_node(&child) else {
+            continue;
+        };
+        let key = format!(
+            "{parent_key}::{}",
+            name.utf8_text(document.text.as_bytes()).unwrap_or_default()
+        );
+        let mut children = Vec::new();
+        nested_resources(document, &child, &key, &mut children);
+        found.push(Declaration {
+            kind: SymbolKind::Resource,
+            key,
+            range: document.node_range(&child),
+            name,
+            children,
+        });
+    }
+}
+
+/// Map a declaration kind to an LSP symbol kind
+fn lsp_symbol_kind(kind: SymbolKind) -> u8 {
+    match kind {
+        SymbolKind::Module => 2,
+        SymbolKind::Output => 7,
+        SymbolKind::Parameter => 8,
+        SymbolKind::Function => 12,
+        SymbolKind::Variable => 13,
+        SymbolKind::Resource => 19,
+        SymbolKind::Type => 23,
+    }
+}
+
+/// Short detail shown next to a symbol in the outline
+fn symbol_detail(model: &BicepDocument, kind: SymbolKind, key: &str) -> Option<String> {
+    match kind {
+        SymbolKind::Type => model
+            .types
+            .get(key)
+            .map(|custom_type| custom_type.definition.to_string()),
+        SymbolKind::Function => model
+            .functions
+            .get(key)
+            .map(|function| function.return_type.to_string()),
+        SymbolKind::Parameter => model.parameters.get(key).map(|parameter| {
+            let nullable = if parameter.is_nullable { "?" } else { "" };
+            format!("{}{nullable}", parameter.parameter_type)
+        }),
+        SymbolKind::Variable => None,
+        SymbolKind::Resource => model
+            .resources
+            .get(key)
+            .map(|resource| format!("{}@{}", resource.resource_type, resource.api_version)),
+        SymbolKind::Module => model
+            .modules
+            .get(key)
+            .map(|module| module.source.to_string()),
+        SymbolKind::Output => model
+            .outputs
+            .get(key)
+            .map(|output| output.output_type.to_string()),
+    }
+}
+
+/// Convert a declaration to an LSP `DocumentSymbol`
+fn document_symbol(document: &TextDocument, declaration: &Declaration) -> Value {
+    let name = declaration
+        .key
+        .rsplit("::")
+        .next()
+        .unwrap_or(&declaration.key);
+    let mut symbol = json!({
+        "name": name,
+        "kind": lsp_symbol_kind(declaration.kind),
+        "range": declaration.range,
+        "selectionRange": document.node_range(&declaration.name),
+        "children": declaration
+            .children
+            .iter()
+            .map(|child| document_symbol(document, child))
+            .collect::<Vec<_>>(),
+    });
+    if let Some(detail) = document
+        .model
+        .as_ref()
+        .and_then(|model| symbol_detail(model, declaration.kind, &declaration.key))
+    {
+        symbol["detail"] = json!(detail);
+    }
+    symbol
+}
+
+/// Build the outline of a document
+///
+/// # Arguments
+///
+/// * `document` - The open document
+///
+/// # Returns
+///
+/// The declarations of the document as LSP `DocumentSymbol` values
+pub fn document_symbols(document: &TextDocument) -> Vec<Value> {
+    declarations(document)
+        .iter()
+        .map(|declaration| document_symbol(document, declaration))
+        .collect()
+}
+
+/// Find the declaration an identifier names or refers to
+fn resolve_symbol(
+    declarations: &[Declaration],
+    model: &BicepDocument,
+    identifier: &Node,
+    text: &str,
+) -> Option<SymbolId> {
+    // The identifier may be the name of a declaration, including an output
+    let mut pending = declarations.iter().collect::<Vec<_>>();
+    while let Some(declaration) = pending.pop() {
+        if declaration.name.id() == identifier.id() {
+            return Some(SymbolId {
+                kind: declaration.kind,
+                name: declaration.key.clone(),
+            });
+        }
+        pending.extend(declaration.children.iter());
+    }
+
+    // Object keys and the members of `a.b` are not references to declarations
+    if let Some(parent) = identifier.parent() {
+        let is_first_child = parent
+            .child(0)
+            .is_some_and(|first| first.id() == identifier.id());
+        match parent.kind() {
+            "object_property" if is_first_child => return None,
+            "member_expression" if !is_first_child => return None,
+            _ => {},
+        }
+    }
+
+    // Otherwise it refers to a declaration by name
+    let name = identifier.utf8_text(text.as_bytes()).ok()?;
+    let kind = if model.parameters.contains_key(name) {
+        SymbolKind::Parameter
+    } else if model.variables.contains_key(name) {
+        SymbolKind::Variable
+    } else if model.modules.contains_key(name) {
+        SymbolKind::Module
+    } else if model.types.contains_key(name) {
+        SymbolKind::Type
+    } else if model.functions.contains_key(name) {
+        SymbolKind::Function
+    } else {
+        // Nested resources are referenced as `parent::child`
+        let key = model.resources.keys().find(|key| {
+            key.as_str() == name || key.rsplit("::").next().is_some_and(|last| last == name)
+        })?;
+        return Some(SymbolId {
+            kind: SymbolKind::Resource,
+            name: key.clone(),
+        });
+    };
+    Some(SymbolId {
+        kind,
+        name: name.to_string(),
+    })
+}
+
+/// Build the hover card for a position
+///
+/// # Arguments
+///
+/// * `document` - The open document
+/// * `position` - The position of the cursor
+/// * `language` - The language to write the documentation in
+///
+/// # Returns
+///
+/// An LSP `Hover` with the Markdown documentation of the declaration under the
+/// cursor, or None if the cursor is not on a declared symbol
+pub fn hover(document: &TextDocument, position: Position, language: Language) -> Option<Value> {
+    let tree = document.tree.as_ref()?;
+    let model = document.model.as_ref()?;
+    let offset = offset_at(&document.text, position);
+
+    // Accept a cursor placed just after the identifier as well as on it
+    let identifier = [offset, offset.saturating_sub(1)]
+        .into_iter()
+        .filter_map(|at| tree.root_node().descendant_for_byte_range(at, at))
+        .find(|node| node.kind() == "identifier")?;
+
+    let symbol = resolve_symbol(&declarations(document), model, &identifier, &document.text)?;
+    let markdown = export_symbol_to_string(model, &symbol, false, true, language)?;
+    Some(json!({
+        "contents": { "kind": "markdown", "value": markdown },
+        "range": document.node_range(&identifier),
+    }))
+}
+
+/// Report declarations that are missing a description
+///
+/// # Arguments
+///
+/// * `document` - The open document
+/// * `language` - The language to write the messages in
+///
+/// # Returns
+///
+/// LSP `Diagnostic` values, one per declaration without a description
+pub fn diagnostics(document: &TextDocument, language: Language) -> Vec<Value> {
+    let Some(model) = &document.model else {
+        return Vec::new();
+    };
+
+    declarations(document)
+        .iter()
+        .filter(|declaration| needs_description(model, declaration.kind, &declaration.key))
+        .map(|declaration| {
+            let locale = language.code();
+            let kind = match declaration.kind {
+                SymbolKind::Type => t!("lsp.type", locale = locale),
+                SymbolKind::Function => t!("lsp.function", locale = locale),
+                SymbolKind::Parameter => t!("lsp.parameter", locale = locale),
+                SymbolKind::Variable => t!("lsp.variable", locale = locale),
+                SymbolKind::Resource => t!("lsp.resource", locale = locale),
+                SymbolKind::Module => t!("lsp.module", locale = locale),
+                SymbolKind::Output => t!("lsp.output", locale = locale),
+            };
+            json!({
+                "range": document.node_range(&declaration.name),
+                "severity": SEVERITY_WARNING,
+                "code": "missing-description",
+                "source": "bicep-docs",
+                "message": t!(
+                    "lsp.missing_description",
+                    locale = locale,
+                    kind = kind,
+                    name = declaration.key
+                ),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use tree_sitter::Parser;
+
+    use super::*;
+    use crate::exports::REDACTED;
+
+    #[test]
+    fn test_hover_redacts_secure_defaults() {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_bicep::LANGUAGE.into())
+            .unwrap();
+        let text = "@secure()\nparam apiKey string = 'hunter2'\n";
+        let document = TextDocument::open(text.to_string(), 1, &mut parser);
+
+        let hover = hover(
+            &document,
+            Position {
+                line: 1,
+                character: 8,
+            },
+            Language::English,
+        )
+        .unwrap();
+        let markdown = hover["contents"]["value"].as_str().unwrap();
+        assert!(markdown.contains(REDACTED));
+        assert!(!markdown.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_hover_uses_language() {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_bicep::LANGUAGE.into())
+            .unwrap();
+        let text =
+            "@description('The key')\n@metadata({ description_fr: 'La clé' })\nparam key string\n";
+        let document = TextDocument::open(text.to_string(), 1, &mut parser);
+        let position = Position {
+            line: 2,
+            character: 7,
+        };
+
+        let hover = hover(&document, position, Language::French).unwrap();
+        let markdown = hover["contents"]["value"].as_str().unwrap();
+        assert!(markdown.contains("La clé"));
+        assert!(!markdown.contains("The key"));
+    }
+    #[test]
+    fn test_diagnostics_use_language() {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_bicep::LANGUAGE.into())
+            .unwrap();
+        let text = "param vaultId string
+";
+        let document = TextDocument::open(text.to_string(), 1, &mut parser);
+
+        let english = diagnostics(&document, Language::English);
+        assert_eq!(
+            english[0]["message"],
+            "Parameter 'vaultId' has no description; add a @description decorator"
+        );
+        let french = diagnostics(&document, Language::French);
+        assert_eq!(
+            french[0]["message"],
+            "Le paramètre 'vaultId' n'a pas de description ; ajoutez un décorateur @description"
+        );
+    }
+}
@@ -0,0 +1,33 @@
+/// Language server for Bicep documentation
+///
+/// The `bicep-docs lsp` command runs a Language Server Protocol server over stdio.
+/// It provides an outline of the declarations in a Bicep file, hover cards with the
+/// documentation the Markdown exporter would generate, and warnings for declarations
+/// that have no description. Documents are re-parsed incrementally as they change.
+pub mod document;
+pub mod features;
+pub mod protocol;
+pub mod server;
+
+use std::io;
+
+pub use document::{Position, Range, TextDocument};
+pub use server::Server;
+
+use crate::{error::BicepError, localization::Language};
+
+/// Run the language server over stdin and stdout
+///
+/// # Arguments
+///
+/// * `language` - The language to write hover cards and diagnostics in
+///
+/// # Returns
+///
+/// A Result indicating a clean exit, or an error if the client did not shut the
+/// server down before exiting
+pub fn run_stdio(language: Language) -> Result<(), BicepError> {
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    Server::new(io::stdout(), language)?.run(&mut reader)
+}
@@ -0,0 +1,120 @@
+/// JSON-RPC message framing for the Language Server Protocol
+///
+/// Messages are JSON objects preceded by a `Content-Length` header and a blank line,
+/// as described in the base protocol of the LSP specification.
+use std::io::{BufRead, Write};
+
+use serde_json::{json, Value};
+
+use crate::error::BicepError;
+
+/// Error code for a request the server does not implement
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// Error code for a request with invalid parameters
+pub const INVALID_PARAMS: i64 = -32602;
+/// Error code for a request received before `initialize`
+pub const SERVER_NOT_INITIALIZED: i64 = -32002;
+/// Error code for a request received after `shutdown`
+pub const INVALID_REQUEST: i64 = -32600;
+
+/// Read the next message from a stream
+///
+/// # Arguments
+///
+/// * `reader` - The stream to read from
+///
+/// # Returns
+///
+/// The message, None at the end of the stream, or an error for a malformed message
+pub fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Value>, BicepError> {
+    let mut content_length = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = Some(value.trim().parse::<usize>()?);
+            }
+        }
+    }
+
+    let content_length = content_length.ok_or_else(|| BicepError::Syntax {
+        message: "Message has no Content-Length header".to_string(),
+        span: None,
+    })?;
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+/// Write a message to a stream
+///
+/// # Arguments
+///
+/// * `writer` - The stream to write to
+/// * `message` - The message to write
+///
+/// # Returns
+///
+/// A Result indicating success or an error
+pub fn write_message<W: Write>(writer: &mut W, message: &Value) -> Result<(), BicepError> {
+    let content = serde_json::to_string(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Build the response to a request
+pub fn response(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+/// Build an error response to a request
+pub fn error_response(id: &Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// Build a notification from the server
+pub fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_message_round_trip() {
+        let message = notification("initialized", json!({}));
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &message).unwrap();
+        write_message(&mut buffer, &response(&json!(1), Value::Null)).unwrap();
+
+        let mut reader = Cursor::new(buffer);
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message));
+        assert_eq!(
+            read_message(&mut reader).unwrap(),
+            Some(json!({ "jsonrpc": "2.0", "id": 1, "result": null }))
+        );
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_message_requires_content_length() {
+        let mut reader = Cursor::new(b"Content-Type: text/plain\r\n\r\n{}".to_vec());
+        assert!(read_message(&mut reader).is_err());
+    }
+}
@@ -0,0 +1,242 @@
+/// The language server message loop
+///
+/// The server handles the lifecycle messages, full and incremental text document
+/// synchronisation, document symbol and hover requests, and publishes diagnostics
+/// whenever a document is opened or changed.
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+use tree_sitter::Parser;
+
+use super::{
+    document::{Position, Range, TextDocument},
+    features,
+    protocol::{self, read_message, write_message},
+};
+use crate::{error::BicepError, localization::Language};
+
+/// A language server writing its messages to a stream
+pub struct Server<W: Write> {
+    writer: W,
+    parser: Parser,
+    documents: HashMap<String, TextDocument>,
+    language: Language,
+    initialized: bool,
+    shutdown: bool,
+}
+
+impl<W: Write> Server<W> {
+    /// Create a server that writes its messages to a stream
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The stream to write responses and notifications to
+    /// * `language` - The language to write hover cards and diagnostics in
+    ///
+    /// # Returns
+    ///
+    /// The server, or an error if the Bicep grammar cannot be loaded
+    pub fn new(writer: W, language: Language) -> Result<Self, BicepError> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_bicep::LANGUAGE.into())
+            .map_err(|e| BicepError::Unsupported {
+                construct: format!("Bicep grammar: {e}"),
+                span: None,
+            })?;
+        Ok(Server {
+            writer,
+            parser,
+            documents: HashMap::new(),
+            language,
+            initialized: false,
+            shutdown: false,
+        })
+    }
+
+    /// Handle messages from a stream until the client exits
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The stream to read client messages from
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating a clean exit, or an error if the stream is malformed or
+    /// the client exits without requesting a shutdown first
+    pub fn run<R: BufRead>(&mut self, reader: &mut R) -> Result<(), BicepError> {
+        while let Some(message) = read_message(reader)? {
+            if !self.handle(&message)? {
+                return if self.shutdown {
+                    Ok(())
+                } else {
+                    Err(io::Error::other("Client exited without requesting a shutdown").into())
+                };
+            }
+        }
+        debug!("Client closed the input stream");
+        Ok(())
+    }
+
+    /// Handle a single message
+    ///
+    /// # Returns
+    ///
+    /// False once the client has sent the `exit` notification
+    fn handle(&mut self, message: &Value) -> Result<bool, BicepError> {
+        let Some(method) = message["method"].as_str() else {
+            // Responses to server requests are not expected
+            return Ok(true);
+        };
+        let params = &message["params"];
+        debug!("Received {}", method);
+
+        let Some(id) = message.get("id") else {
+            return self.handle_notification(method, params);
+        };
+
+        let response = if method == "initialize" {
+            self.initialized = true;
+            protocol::response(id, capabilities())
+        } else if !self.initialized {
+            protocol::error_response(
+                id,
+                protocol::SERVER_NOT_INITIALIZED,
+                "Server is not initialized",
+            )
+        } else if self.shutdown {
+            protocol::error_response(id, protocol::INVALID_REQUEST, "Server is shutting down")
+        } else {
+            match method {
+                "shutdown" => {
+                    self.shutdown = true;
+                    protocol::response(id, Value::Null)
+                },
+                "textDocument/documentSymbol" => match self.document(params) {
+                    Some(document) => {
+                        protocol::response(id, json!(features::document_symbols(document)))
+                    },
+                    None => protocol::response(id, Value::Null),
+                },
+                "textDocument/hover" => {
+                    match serde_json::from_value::<Position>(params["position"].clone()) {
+                        Ok(position) => {
+                            let language = self.language;
+                            let hover = self
+                                .document(params)
+                                .and_then(|document| features::hover(document, position, language));
+                            protocol::response(id, hover.unwrap_or(Value::Null))
+                        },
+                        Err(e) => protocol::error_response(
+                            id,
+                            protocol::INVALID_PARAMS,
+                            &format!("Invalid position: {e}"),
+                        ),
+                    }
+                },
+                _ => protocol::error_response(
+                    id,
+                    protocol::METHOD_NOT_FOUND,
+                    &format!("Unsupported method: {method}"),
+                ),
+            }
+        };
+        write_message(&mut self.writer, &response)?;
+        Ok(true)
+    }
+
+    /// Handle a notification from the client
+    fn handle_notification(&mut self, method: &str, params: &Value) -> Result<bool, BicepError> {
+        if method == "exit" {
+            return Ok(false);
+        }
+        if !self.initialized {
+            return Ok(true);
+        }
+
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                let version = params["textDocument"]["version"].as_i64().unwrap_or(0);
+                let document = TextDocument::open(text, version, &mut self.parser);
+                self.documents.insert(uri.clone(), document);
+                self.publish_diagnostics(&uri)?;
+            },
+            "textDocument/didChange" => {
+                let Some(document) = self.documents.get_mut(&uri) else {
+                    warn!("Change received for a document that is not open: {}", uri);
+                    return Ok(true);
+                };
+                for change in params["contentChanges"].as_array().into_iter().flatten() {
+                    let range = serde_json::from_value::<Range>(change["range"].clone()).ok();
+                    document.apply_change(range, change["text"].as_str().unwrap_or_default());
+                }
+                if let Some(version) = params["textDocument"]["version"].as_i64() {
+                    document.version = version;
+                }
+                document.reparse(&mut self.parser);
+                self.publish_diagnostics(&uri)?;
+            },
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                let notification = protocol::notification(
+                    "textDocument/publishDiagnostics",
+                    json!({ "uri": uri, "diagnostics": [] }),
+                );
+                write_message(&mut self.writer, &notification)?;
+            },
+            _ => debug!("Ignoring notification {}", method),
+        }
+        Ok(true)
+    }
+
+    /// Find the open document a request refers to
+    fn document(&self, params: &Value) -> Option<&TextDocument> {
+        self.documents.get(params["textDocument"]["uri"].as_str()?)
+    }
+
+    /// Send the diagnostics of an open document to the client
+    fn publish_diagnostics(&mut self, uri: &str) -> Result<(), BicepError> {
+        let Some(document) = self.documents.get(uri) else {
+            return Ok(());
+        };
+        let notification = protocol::notification(
+            "textDocument/publishDiagnostics",
+            json!({
+                "uri": uri,
+                "version": document.version,
+                "diagnostics": features::diagnostics(document, self.language),
+            }),
+        );
+        write_message(&mut self.writer, &notification)
+    }
+}
+
+/// The result of the `initialize` request
+fn capabilities() -> Value {
+    json!({
+        "capabilities": {
+            "positionEncoding": "utf-16",
+            "textDocumentSync": {
+                "openClose": true,
+                // Incremental
+                "change": 2,
+            },
+            "documentSymbolProvider": true,
+            "hoverProvider": true,
+        },
+        "serverInfo": {
+            "name": "bicep-docs",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
@@ -0,0 +1,1059 @@
+//! ARM JSON template parsing.
+//!
+//! This module reads compiled ARM templates (such as the `main.json` emitted by
+//! `bicep build`, or the ARM JSON shipped with registry modules) into the same
+//! [`BicepDocument`] model as Bicep files, so every exporter works on them.
+//!
+//! ARM template expressions (`"[...]"`) are kept as expression text, with
+//! `parameters('x')`, `variables('x')` and `lambdaVariables('x')` references
+//! rewritten to plain symbol names so that references and static evaluation
+//! work as they do for Bicep. `copy` loops become `for` loops, with
+//! `copyIndex()` calls (including the named and offset forms) rewritten to a
+//! loop variable that does not shadow any declared symbol. Both symbolic-name (`languageVersion` 2.0) and
+//! array-style resource collections are supported, and nested deployments
+//! emitted by Bicep for modules (recognised by their `metadata._generator`)
+//! are read as modules.
+
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+use tracing::{debug, warn};
+
+use super::{
+    utils::decorators::{is_description_key, localized_descriptions},
+    BicepCustomType, BicepDocument, BicepFunction, BicepFunctionArgument, BicepImport,
+    BicepImportSymbol, BicepModule, BicepOutput, BicepParameter, BicepResource, BicepType,
+    BicepValue, BicepVariable, ModuleSource,
+};
+use crate::error::{BicepError, Span};
+
+/// Metadata key Bicep uses to mark exported types, variables and functions
+const EXPORT_METADATA_KEY: &str = "__bicep_export!";
+
+/// Metadata key Bicep uses to record where an imported symbol came from
+const IMPORTED_FROM_METADATA_KEY: &str = "__bicep_imported_from!";
+
+/// Metadata key Bicep uses to list exported variables
+const EXPORTED_VARIABLES_METADATA_KEY: &str = "__bicep_exported_variables!";
+
+/// Namespace Bicep emits user-defined functions into
+const BICEP_FUNCTION_NAMESPACE: &str = "__bicep";
+
+/// Name of the loop variable used for `copy` loops, numbered if a symbol already has it
+const COPY_INDEX_VARIABLE: &str = "i";
+
+// ---------------------------------------------------------------
+// Structs
+// ---------------------------------------------------------------
+
+/// A `copy` loop whose `copyIndex()` calls are rewritten to a Bicep loop variable.
+#[derive(Debug, Clone, Copy)]
+struct CopyLoop<'a> {
+    /// Name of the loop, matched by `copyIndex('name')`
+    name: Option<&'a str>,
+    /// Loop variable the index is read from
+    variable: &'a str,
+}
+
+impl CopyLoop<'_> {
+    /// Translates the arguments of a `copyIndex` call into a Bicep index expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `arguments` - The ARM argument expressions: an optional loop name, then an optional offset
+    ///
+    /// # Returns
+    ///
+    /// The index expression, or None if the call names a different loop
+    fn index(self, arguments: &[&str]) -> Option<String> {
+        let (name, offset) = match arguments {
+            [] => (None, None),
+            [name] if name.starts_with('\'') => (Some(*name), None),
+            [offset] => (None, Some(*offset)),
+            [name, offset] => (Some(*name), Some(*offset)),
+            _ => return None,
+        };
+
+        if let Some(name) = name {
+            let name = name.strip_prefix('\'')?.strip_suffix('\'')?;
+            if !self
+                .name
+                .is_some_and(|loop_name| loop_name.eq_ignore_ascii_case(name))
+            {
+                return None;
+            }
+        }
+
+        Some(match offset {
+            Some(offset) => {
+                let (offset, _) = arm_expression(&format!("[{offset}]"), Some(self));
+                format!("({} + {offset})", self.variable)
+            },
+            None => self.variable.to_string(),
+        })
+    }
+}
+
+// ---------------------------------------------------------------
+// Functions
+// ---------------------------------------------------------------
+
+/// Parses an ARM JSON template into a BicepDocument.
+///
+/// # Arguments
+///
+/// * `source_code` - The JSON source of the ARM template
+///
+/// # Returns
+///
+/// A Result containing the parsed BicepDocument if successful, or an error
+///
+/// # Errors
+///
+/// Returns an error if the source is not valid JSON or is not a JSON object
+pub fn parse_arm_template(source_code: &str) -> Result<BicepDocument, BicepError> {
+    let template: Value = serde_json::from_str(source_code)?;
+    let template = template.as_object().ok_or_else(|| BicepError::Syntax {
+        message: "ARM template must be a JSON object".to_string(),
+        span: Some(Span::at(1, 1)),
+    })?;
+
+    let mut document = BicepDocument {
+        target_scope: template
+            .get("$schema")
+            .and_then(Value::as_str)
+            .and_then(target_scope_from_schema),
+        ..Default::default()
+    };
+    let loop_variable = loop_variable(template);
+
+    // Template metadata, including the generator and description emitted by Bicep
+    let mut exported_variables = IndexMap::new();
+    if let Some(metadata) = template.get("metadata").and_then(Value::as_object) {
+        for (key, value) in metadata {
+            match key.as_str() {
+                "name" => document.name = value.as_str().map(str::to_string),
+                "description" => document.description = value.as_str().map(str::to_string),
+                "_generator" => {
+                    if let Some(generator) = generator_text(value) {
+                        debug!("ARM template generated by {}", generator);
+                        document
+                            .metadata
+                            .insert("generator".to_string(), BicepValue::String(generator));
+                    }
+                },
+                EXPORTED_VARIABLES_METADATA_KEY => {
+                    for variable in value.as_array().into_iter().flatten() {
+                        if let Some(name) = variable.get("name").and_then(Value::as_str) {
+                            let description = variable
+                                .get("description")
+                                .and_then(Value::as_str)
+                                .map(str::to_string);
+                            exported_variables.insert(name.to_string(), description);
+                        }
+                    }
+                },
+                _ if key.ends_with('!') => {},
+                _ if is_description_key(key) => {},
+                _ => {
+                    document
+                        .metadata
+                        .insert(key.clone(), convert_value(value, None));
+                },
+            }
+        }
+        document.descriptions = descriptions(template.get("metadata"));
+    }
+
+    if let Some(definitions) = template.get("definitions").and_then(Value::as_object) {
+        for (name, definition) in definitions {
+            if let Some(source) = imported_from(definition) {
+                add_import(&mut document.imports, source, name);
+            }
+            document
+                .types
+                .insert(name.clone(), parse_custom_type(definition));
+        }
+    }
+
+    if let Some(namespaces) = template.get("functions").and_then(Value::as_array) {
+        for namespace in namespaces {
+            let prefix = namespace
+                .get("namespace")
+                .and_then(Value::as_str)
+                .unwrap_or(BICEP_FUNCTION_NAMESPACE);
+            let Some(members) = namespace.get("members").and_then(Value::as_object) else {
+                continue;
+            };
+            for (member, function) in members {
+                let name = if prefix == BICEP_FUNCTION_NAMESPACE {
+                    member.clone()
+                } else {
+                    format!("{prefix}.{member}")
+                };
+                if let Some(source) = imported_from(function) {
+                    add_import(&mut document.imports, source, &name);
+                }
+                document.functions.insert(name, parse_function(function));
+            }
+        }
+    }
+
+    if let Some(parameters) = template.get("parameters").and_then(Value::as_object) {
+        for (name, parameter) in parameters {
+            document
+                .parameters
+                .insert(name.clone(), parse_parameter(parameter));
+        }
+    }
+
+    if let Some(variables) = template.get("variables").and_then(Value::as_object) {
+        for (name, value) in variables {
+            if name == "copy" {
+                // Variable copy loops declare arrays built from an item template
+                for copy in value.as_array().into_iter().flatten() {
+                    let Some(copy_name) = copy.get("name").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    let input = copy.get("input").unwrap_or(&Value::Null);
+                    let copy_loop = CopyLoop {
+                        name: Some(copy_name),
+                        variable: &loop_variable,
+                    };
+                    document.variables.insert(
+                        copy_name.to_string(),
+                        BicepVariable {
+                            description: exported_variables.get(copy_name).cloned().flatten(),
+                            value: convert_value(input, Some(copy_loop)),
+                            is_exported: exported_variables.contains_key(copy_name),
+                        },
+                    );
+                }
+                continue;
+            }
+
+            // Variables Bicep emits for its own use (e.g. `$fxv#0`) are not declarations
+            if name.starts_with('$') {
+                continue;
+            }
+
+            document.variables.insert(
+                name.clone(),
+                BicepVariable {
+                    description: exported_variables.get(name).cloned().flatten(),
+                    value: convert_value(value, None),
+                    is_exported: exported_variables.contains_key(name),
+                },
+            );
+        }
+    }
+
+    match template.get("resources") {
+        // languageVersion 2.0 templates key resources by their symbolic name
+        Some(Value::Object(resources)) => {
+            for (name, resource) in resources {
+                add_resource(&mut document, name.clone(), resource, None, &loop_variable);
+            }
+        },
+        Some(Value::Array(resources)) => {
+            for resource in resources {
+                let name = symbolic_name(resource, &document);
+                add_resource(&mut document, name, resource, None, &loop_variable);
+            }
+        },
+        _ => {},
+    }
+
+    if let Some(outputs) = template.get("outputs").and_then(Value::as_object) {
+        for (name, output) in outputs {
+            document
+                .outputs
+                .insert(name.clone(), parse_output(output, &loop_variable));
+        }
+    }
+
+    Ok(document)
+}
+
+/// Derives the target scope from a template's `$schema`.
+///
+/// Resource group deployments are the default scope and return None.
+fn target_scope_from_schema(schema: &str) -> Option<String> {
+    let schema = schema.to_ascii_lowercase();
+    if schema.contains("subscriptiondeploymenttemplate") {
+        Some("subscription".to_string())
+    } else if schema.contains("managementgroupdeploymenttemplate") {
+        Some("managementGroup".to_string())
+    } else if schema.contains("tenantdeploymenttemplate") {
+        Some("tenant".to_string())
+    } else {
+        None
+    }
+}
+
+/// Formats the `_generator` metadata as e.g. `bicep 0.30.23.60470`.
+fn generator_text(generator: &Value) -> Option<String> {
+    let name = generator.get("name").and_then(Value::as_str)?;
+    match generator.get("version").and_then(Value::as_str) {
+        Some(version) => Some(format!("{name} {version}")),
+        None => Some(name.to_string()),
+    }
+}
+
+/// Whether a template was generated by Bicep.
+fn is_bicep_generated(template: &Value) -> bool {
+    template
+        .pointer("/metadata/_generator/name")
+        .and_then(Value::as_str)
+        .is_some_and(|name| name.eq_ignore_ascii_case("bicep"))
+}
+
+/// Gets the source template of an imported type or function.
+fn imported_from(node: &Value) -> Option<&str> {
+    node.get("metadata")?
+        .get(IMPORTED_FROM_METADATA_KEY)?
+        .get("sourceTemplate")?
+        .as_str()
+}
+
+/// Records an imported symbol, grouping symbols by their source template.
+fn add_import(imports: &mut Vec<BicepImport>, source: &str, name: &str) {
+    let symbol = BicepImportSymbol {
+        name: name.to_string(),
+        alias: None,
+    };
+
+    for import in imports.iter_mut() {
+        if let BicepImport::Module {
+            source: ModuleSource::LocalPath(path),
+            symbols: Some(symbols),
+            ..
+        } = import
+        {
+            if path == source {
+                symbols.push(symbol);
+                return;
+            }
+        }
+    }
+
+    imports.push(BicepImport::Module {
+        source: ModuleSource::LocalPath(source.to_string()),
+        symbols: Some(vec![symbol]),
+        wildcard_alias: None,
+    });
+}
+
+/// Gets the `metadata.description` of a declaration.
+fn description(node: &Value) -> Option<String> {
+    node.get("metadata")?
+        .get("description")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Gets the descriptions in other languages from the metadata of a declaration.
+fn descriptions(metadata: Option<&Value>) -> IndexMap<String, String> {
+    match metadata.map(|metadata| convert_value(metadata, None)) {
+        Some(BicepValue::Object(metadata)) => localized_descriptions(&metadata),
+        _ => IndexMap::new(),
+    }
+}
+
+/// Gets the metadata of a declaration, except the descriptions and Bicep's own keys.
+fn metadata(node: &Value) -> IndexMap<String, BicepValue> {
+    node.get("metadata")
+        .and_then(Value::as_object)
+        .map(|metadata| {
+            metadata
+                .iter()
+                .filter(|(key, _)| !is_description_key(key) && !key.ends_with('!'))
+                .map(|(key, value)| (key.clone(), convert_value(value, None)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether a declaration is marked as exported.
+fn is_exported(node: &Value) -> bool {
+    node.get("metadata")
+        .and_then(|metadata| metadata.get(EXPORT_METADATA_KEY))
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Whether a type node uses one of the secure types.
+fn is_secure_type(node: &Value) -> bool {
+    node.get("type").and_then(Value::as_str).is_some_and(|t| {
+        t.eq_ignore_ascii_case("securestring") || t.eq_ignore_ascii_case("secureobject")
+    })
+}
+
+/// Parses a type node (a parameter, output, definition or property) into a BicepType.
+fn parse_type(node: &Value) -> BicepType {
+    if let Some(reference) = node.get("$ref").and_then(Value::as_str) {
+        return BicepType::CustomType(definition_name(reference));
+    }
+
+    // Tagged unions map each discriminator value to a variant type
+    if let Some(mapping) = node
+        .get("discriminator")
+        .and_then(|discriminator| discriminator.get("mapping"))
+        .and_then(Value::as_object)
+    {
+        let variants = mapping
+            .values()
+            .filter_map(|variant| variant.get("$ref").and_then(Value::as_str))
+            .map(definition_name)
+            .collect::<Vec<_>>();
+        if !variants.is_empty() && variants.len() == mapping.len() {
+            return BicepType::Union(variants);
+        }
+        return BicepType::Object(None);
+    }
+
+    let type_name = node
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match type_name.as_str() {
+        "string" | "securestring" => BicepType::String,
+        "int" => BicepType::Int,
+        "bool" => BicepType::Bool,
+        "array" => match node.get("items") {
+            Some(items) if items.is_object() => BicepType::Array(Box::new(parse_type(items))),
+            _ => BicepType::CustomType("array".to_string()),
+        },
+        "object" | "secureobject" => match node.get("properties").and_then(Value::as_object) {
+            Some(properties) => BicepType::Object(Some(
+                properties
+                    .iter()
+                    .map(|(name, property)| (name.clone(), parse_parameter(property)))
+                    .collect(),
+            )),
+            None => BicepType::Object(None),
+        },
+        "" => BicepType::CustomType("any".to_string()),
+        other => BicepType::CustomType(other.to_string()),
+    }
+}
+
+/// Gets the definition name from a `#/definitions/<name>` reference.
+fn definition_name(reference: &str) -> String {
+    reference
+        .rsplit('/')
+        .next()
+        .unwrap_or(reference)
+        .to_string()
+}
+
+/// Parses a parameter or object property.
+fn parse_parameter(node: &Value) -> BicepParameter {
+    BicepParameter {
+        description: description(node),
+        descriptions: descriptions(node.get("metadata")),
+        metadata: metadata(node),
+        parameter_type: parse_type(node),
+        default_value: node
+            .get("defaultValue")
+            .map(|value| convert_value(value, None)),
+        discriminator: node
+            .pointer("/discriminator/propertyName")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        allowed_values: node
+            .get("allowedValues")
+            .and_then(Value::as_array)
+            .map(|values| {
+                values
+                    .iter()
+                    .map(|value| convert_value(value, None))
+                    .collect()
+            }),
+        is_nullable: node
+            .get("nullable")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        is_sealed: node.get("additionalProperties") == Some(&Value::Bool(false)),
+        is_secure: is_secure_type(node),
+        min_length: node.get("minLength").and_then(Value::as_i64),
+        max_length: node.get("maxLength").and_then(Value::as_i64),
+        min_value: node.get("minValue").and_then(Value::as_i64),
+        max_value: node.get("maxValue").and_then(Value::as_i64),
+    }
+}
+
+/// Parses a type definition.
+fn parse_custom_type(node: &Value) -> BicepCustomType {
+    BicepCustomType {
+        description: description(node),
+        descriptions: descriptions(node.get("metadata")),
+        definition: parse_type(node),
+        discriminator: node
+            .pointer("/discriminator/propertyName")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        is_exported: is_exported(node),
+        is_sealed: node.get("additionalProperties") == Some(&Value::Bool(false)),
+        is_secure: is_secure_type(node),
+    }
+}
+
+/// Parses a user-defined function.
+fn parse_function(node: &Value) -> BicepFunction {
+    let arguments = node
+        .get("parameters")
+        .and_then(Value::as_array)
+        .map(|parameters| {
+            parameters
+                .iter()
+                .map(|parameter| BicepFunctionArgument {
+                    name: parameter
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    argument_type: parse_type(parameter),
+                    is_nullable: parameter
+                        .get("nullable")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let output = node.get("output").unwrap_or(&Value::Null);
+    let expression = match output.get("value") {
+        Some(Value::String(text)) => arm_expression(text, None).0,
+        Some(value) => serde_json::to_string(value).unwrap_or_default(),
+        None => String::new(),
+    };
+
+    BicepFunction {
+        description: description(node),
+        metadata: metadata(node),
+        arguments,
+        return_type: parse_type(output),
+        expression,
+        is_exported: is_exported(node),
+    }
+}
+
+/// Parses an output.
+fn parse_output(node: &Value, loop_variable: &str) -> BicepOutput {
+    // Output copy loops declare arrays built from an item template
+    let copy_loop = CopyLoop {
+        name: None,
+        variable: loop_variable,
+    };
+    let value = match node.get("copy").and_then(|copy| copy.get("input")) {
+        Some(input) => convert_value(input, Some(copy_loop)),
+        None => convert_value(node.get("value").unwrap_or(&Value::Null), None),
+    };
+    let metadata = metadata(node);
+
+    BicepOutput {
+        description: description(node),
+        descriptions: descriptions(node.get("metadata")),
+        output_type: parse_type(node),
+        value,
+        discriminator: node
+            .pointer("/discriminator/propertyName")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        min_length: node.get("minLength").and_then(Value::as_i64),
+        max_length: node.get("maxLength").and_then(Value::as_i64),
+        min_value: node.get("minValue").and_then(Value::as_i64),
+        max_value: node.get("maxValue").and_then(Value::as_i64),
+        metadata: (!metadata.is_empty()).then_some(metadata),
+        sealed: node.get("additionalProperties") == Some(&Value::Bool(false)),
+        secure: is_secure_type(node),
+    }
+}
+
+/// Derives a symbolic name for a resource in an array-style template.
+///
+/// Bicep symbolic names are not kept in these templates, so modules are named after
+/// their deployment name and resources after the last segment of their type.
+fn symbolic_name(resource: &Value, document: &BicepDocument) -> String {
+    let resource_type = resource.get("type").and_then(Value::as_str).unwrap_or("");
+    let deployment_name = resource
+        .get("name")
+        .and_then(Value::as_str)
+        .filter(|name| !name.starts_with('['));
+
+    let base = match deployment_name {
+        Some(name) if resource_type.eq_ignore_ascii_case("Microsoft.Resources/deployments") => {
+            identifier(name)
+        },
+        _ => identifier(resource_type.rsplit('/').next().unwrap_or("")),
+    };
+    let base = if base.is_empty() {
+        "resource".to_string()
+    } else {
+        base
+    };
+
+    let is_taken =
+        |name: &str| document.resources.contains_key(name) || document.modules.contains_key(name);
+    if !is_taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|index| format!("{base}{index}"))
+        .find(|name| !is_taken(name))
+        .unwrap_or(base)
+}
+
+/// Turns text into a camelCase identifier, e.g. `storage-deploy` into `storageDeploy`.
+fn identifier(text: &str) -> String {
+    let mut name = String::with_capacity(text.len());
+    for word in text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if name.is_empty() {
+                if first.is_ascii_digit() {
+                    name.push('_');
+                }
+                name.extend(first.to_lowercase());
+            } else {
+                name.extend(first.to_uppercase());
+            }
+            name.push_str(chars.as_str());
+        }
+    }
+    name
+}
+
+/// Adds a resource, or a module for Bicep-generated nested deployments, to the document.
+///
+/// Child resources nested in a resource's `resources` array are added with
+/// `parent::child` names.
+fn add_resource(
+    document: &mut BicepDocument,
+    name: String,
+    node: &Value,
+    parent: Option<&str>,
+    loop_variable: &str,
+) {
+    let Some(resource) = node.as_object() else {
+        warn!("Skipping resource '{}': not a JSON object", name);
+        return;
+    };
+
+    let copy = resource.get("copy");
+    let copy_loop = copy.map(|copy| CopyLoop {
+        name: copy.get("name").and_then(Value::as_str),
+        variable: loop_variable,
+    });
+    let expression = |value: &Value| convert_value(value, copy_loop);
+    let text = |value: &Value| value_text(value, copy_loop);
+
+    let condition = resource.get("condition").map(|condition| {
+        let condition = text(condition);
+        format!("({condition})")
+    });
+    let loop_statement = copy
+        .and_then(|copy| copy.get("count"))
+        .map(|count| format!("for {loop_variable} in range(0, {})", text(count)));
+    let batch_size = copy
+        .and_then(|copy| copy.get("batchSize"))
+        .and_then(Value::as_i64);
+    let depends_on = resource
+        .get("dependsOn")
+        .and_then(Value::as_array)
+        .map(|dependencies| dependencies.iter().map(text).collect::<Vec<_>>());
+    // Names that are expressions are kept as `${expression}`, as they are for Bicep
+    let resource_name = match resource.get("name").map(expression) {
+        Some(BicepValue::Identifier(source)) => format!("${{{source}}}"),
+        Some(_) => resource.get("name").map(text).unwrap_or_default(),
+        None => String::new(),
+    };
+    let resource_type = resource
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+
+    let template = resource
+        .get("properties")
+        .and_then(|properties| properties.get("template"));
+    let is_module = resource_type.eq_ignore_ascii_case("Microsoft.Resources/deployments")
+        && (template.is_some_and(is_bicep_generated)
+            || resource
+                .get("properties")
+                .and_then(|properties| properties.get("templateLink"))
+                .is_some());
+
+    if is_module && parent.is_none() {
+        let properties = resource.get("properties");
+        let source = properties
+            .and_then(|properties| properties.get("templateLink"))
+            .and_then(|link| {
+                link.get("relativePath")
+                    .or_else(|| link.get("uri"))
+                    .or_else(|| link.get("id"))
+            })
+            .map(text)
+            .or_else(|| {
+                template
+                    .and_then(|template| template.pointer("/metadata/name"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| name.clone());
+
+        let params = properties
+            .and_then(|properties| properties.get("parameters"))
+            .and_then(Value::as_object)
+            .map(|parameters| {
+                parameters
+                    .iter()
+                    .filter_map(|(key, parameter)| {
+                        // Key Vault references have no value to document
+                        parameter
+                            .get("value")
+                            .map(|value| (key.clone(), expression(value)))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        document.modules.insert(
+            name.clone(),
+            BicepModule {
+                description: description(node),
+                name,
+                deployment_name: Some(resource_name).filter(|name| !name.is_empty()),
+                source: ModuleSource::LocalPath(source),
+                depends_on,
+                condition,
+                loop_statement,
+                batch_size,
+                params,
+            },
+        );
+        return;
+    }
+
+    let properties = resource
+        .iter()
+        .filter(|(key, _)| {
+            !matches!(
+                key.as_str(),
+                "type"
+                    | "apiVersion"
+                    | "name"
+                    | "condition"
+                    | "copy"
+                    | "dependsOn"
+                    | "scope"
+                    | "existing"
+                    | "comments"
+                    | "metadata"
+                    | "resources"
+            )
+        })
+        .map(|(key, value)| (key.clone(), expression(value)))
+        .collect();
+
+    let full_name = match parent {
+        Some(parent) => format!("{parent}::{name}"),
+        None => name,
+    };
+
+    document.resources.insert(
+        full_name.clone(),
+        BicepResource {
+            description: description(node).or_else(|| {
+                resource
+                    .get("comments")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            }),
+            descriptions: descriptions(node.get("metadata")),
+            resource_type,
+            api_version: resource
+                .get("apiVersion")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            existing: resource
+                .get("existing")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            scope: resource.get("scope").map(expression),
+            name: resource_name,
+            parent: parent.map(str::to_string),
+            depends_on,
+            condition,
+            loop_statement,
+            batch_size,
+            properties,
+        },
+    );
+
+    if let Some(children) = resource.get("resources").and_then(Value::as_array) {
+        for child in children {
+            let child_name = symbolic_name(child, document);
+            add_resource(document, child_name, child, Some(&full_name), loop_variable);
+        }
+    }
+}
+
+/// Converts a JSON value into a BicepValue, translating ARM expressions.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to convert
+/// * `copy_loop` - The copy loop whose `copyIndex()` calls are rewritten to its variable
+fn convert_value(value: &Value, copy_loop: Option<CopyLoop>) -> BicepValue {
+    match value {
+        Value::Null => BicepValue::Identifier("null".to_string()),
+        Value::Bool(b) => BicepValue::Bool(*b),
+        Value::Number(number) => match number.as_i64() {
+            Some(n) => BicepValue::Int(n),
+            None => BicepValue::String(number.to_string()),
+        },
+        Value::String(text) => {
+            let (text, is_expression) = arm_expression(text, copy_loop);
+            // Expressions are marked apart from string literals, as they are for Bicep
+            if is_expression {
+                BicepValue::Identifier(text)
+            } else {
+                BicepValue::String(text)
+            }
+        },
+        Value::Array(items) => BicepValue::Array(
+            items
+                .iter()
+                .map(|item| convert_value(item, copy_loop))
+                .collect(),
+        ),
+        Value::Object(map) => BicepValue::Object(convert_object(map, copy_loop)),
+    }
+}
+
+/// Converts the entries of a JSON object.
+fn convert_object(
+    map: &Map<String, Value>,
+    copy_loop: Option<CopyLoop>,
+) -> IndexMap<String, BicepValue> {
+    map.iter()
+        .map(|(key, value)| (key.clone(), convert_value(value, copy_loop)))
+        .collect()
+}
+
+/// Gets the text of a scalar value, translating ARM expressions.
+fn value_text(value: &Value, copy_loop: Option<CopyLoop>) -> String {
+    match value {
+        Value::String(text) => arm_expression(text, copy_loop).0,
+        other => other.to_string(),
+    }
+}
+
+/// Whether text is a plain identifier.
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Translates an ARM string into Bicep value text.
+///
+/// Strings wrapped in brackets are expressions: the brackets are removed, string
+/// literals are re-escaped for Bicep, and symbol references are rewritten to plain
+/// names. Strings starting with `[[` are literals with an escaped bracket.
+///
+/// # Returns
+///
+/// The text, and whether it is an expression
+fn arm_expression(text: &str, copy_loop: Option<CopyLoop>) -> (String, bool) {
+    if let Some(literal) = text.strip_prefix("[[") {
+        return (format!("[{literal}"), false);
+    }
+    let Some(inner) = text
+        .strip_prefix('[')
+        .and_then(|text| text.strip_suffix(']'))
+    else {
+        return (text.to_string(), false);
+    };
+
+    const REFERENCE_FUNCTIONS: [&str; 3] = ["parameters", "variables", "lambdaVariables"];
+
+    let inner = inner.trim();
+    let mut result = String::with_capacity(inner.len());
+    let mut rest = inner;
+    while let Some(c) = rest.chars().next() {
+        // String literals: ARM escapes quotes by doubling them
+        if c == '\'' {
+            let mut end = 1;
+            let bytes = rest.as_bytes();
+            let mut literal = String::new();
+            while end < bytes.len() {
+                if bytes[end] == b'\'' {
+                    if bytes.get(end + 1) == Some(&b'\'') {
+                        literal.push_str("\\'");
+                        end += 2;
+                        continue;
+                    }
+                    break;
+                }
+                let ch = rest[end..].chars().next().unwrap_or_default();
+                if ch == '\\' {
+                    literal.push_str("\\\\");
+                } else if rest[end..].starts_with("${") {
+                    literal.push('\\');
+                    literal.push(ch);
+                } else {
+                    literal.push(ch);
+                }
+                end += ch.len_utf8();
+            }
+            result.push('\'');
+            result.push_str(&literal);
+            result.push('\'');
+            rest = &rest[(end + 1).min(rest.len())..];
+            continue;
+        }
+
+        if c.is_ascii_alphabetic() {
+            let length = rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..length];
+            let after = &rest[length..];
+
+            if REFERENCE_FUNCTIONS
+                .iter()
+                .any(|function| function.eq_ignore_ascii_case(word))
+            {
+                if let Some((symbol, remainder)) = after
+                    .strip_prefix("('")
+                    .and_then(|after| after.split_once("')"))
+                {
+                    if is_identifier(symbol) {
+                        result.push_str(symbol);
+                        rest = remainder;
+                        continue;
+                    }
+                }
+            }
+
+            if let Some(copy_loop) = copy_loop {
+                if word.eq_ignore_ascii_case("copyIndex") {
+                    if let Some((index, remainder)) =
+                        call_arguments(after).and_then(|(arguments, remainder)| {
+                            Some((copy_loop.index(&arguments)?, remainder))
+                        })
+                    {
+                        result.push_str(&index);
+                        rest = remainder;
+                        continue;
+                    }
+                }
+            }
+
+            result.push_str(word);
+            rest = after;
+            continue;
+        }
+
+        result.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    (result, true)
+}
+
+/// Splits the arguments of a function call from the text following the function name.
+///
+/// # Returns
+///
+/// The trimmed top-level argument expressions and the text after the closing parenthesis,
+/// or None if the text does not start with a complete argument list
+fn call_arguments(text: &str) -> Option<(Vec<&str>, &str)> {
+    let inner = text.strip_prefix('(')?;
+    let mut arguments = Vec::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut start = 0;
+    for (index, c) in inner.char_indices() {
+        match c {
+            // Doubled quotes in ARM literals toggle twice, leaving the state unchanged
+            '\'' => in_string = !in_string,
+            _ if in_string => {},
+            '(' | '[' => depth += 1,
+            ')' if depth == 0 => {
+                let last = inner[start..index].trim();
+                if !last.is_empty() || !arguments.is_empty() {
+                    arguments.push(last);
+                }
+                return Some((arguments, &inner[index + 1..]));
+            },
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                arguments.push(inner[start..index].trim());
+                start = index + 1;
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Chooses the loop variable for `copy` loops, so it does not shadow a declared symbol.
+///
+/// # Returns
+///
+/// `i`, or the first of `i1`, `i2`, ... that no parameter, variable, type, function,
+/// resource or output is named
+fn loop_variable(template: &Map<String, Value>) -> String {
+    let section = |name: &str| {
+        template
+            .get(name)
+            .and_then(Value::as_object)
+            .into_iter()
+            .flat_map(|section| section.keys())
+    };
+    let copy_variables = template
+        .get("variables")
+        .and_then(|variables| variables.get("copy"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|copy| copy.get("name").and_then(Value::as_str));
+    let functions = template
+        .get("functions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|namespace| namespace.get("members").and_then(Value::as_object))
+        .flat_map(|members| members.keys());
+    let symbols: HashSet<&str> = [
+        "parameters",
+        "variables",
+        "definitions",
+        "resources",
+        "outputs",
+    ]
+    .into_iter()
+    .flat_map(section)
+    .chain(functions)
+    .map(String::as_str)
+    .chain(copy_variables)
+    .collect();
+
+    std::iter::once(COPY_INDEX_VARIABLE.to_string())
+        .chain((1..).map(|n| format!("{COPY_INDEX_VARIABLE}{n}")))
+        .find(|name| !symbols.contains(name.as_str()))
+        .unwrap_or_default()
+}
@@ -4,8 +4,6 @@
 //! Functions allow developers to create reusable logic that can be called throughout
 //! the template, improving code organization and reducing duplication.
 
-use std::error::Error;
-
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -18,8 +16,9 @@ use super::{
         get_node_text,
         types::{parse_property_type, parse_type_node},
     },
-    BicepDecorator, BicepParserError, BicepType, BicepValue,
+    BicepDecorator, BicepType, BicepValue,
 };
+use crate::error::BicepError;
 
 // ---------------------------------------------------------------
 // Structs, Enums & Types
@@ -39,7 +38,7 @@ pub struct BicepFunction {
     pub description: Option<String>,
 
     /// Metadata associated with the function
-    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub metadata: IndexMap<String, BicepValue>,
 
     /// List of function parameters/arguments
@@ -115,7 +114,7 @@ pub(crate) fn parse_function_declaration(
     node: Node,
     source_code: &str,
     decorators: Vec<BicepDecorator>,
-) -> Result<(String, BicepFunction), Box<dyn Error>> {
+) -> Result<(String, BicepFunction), BicepError> {
     let mut metadata: IndexMap<String, BicepValue> = IndexMap::new();
     let mut is_exported = false;
 
@@ -124,16 +123,18 @@ pub(crate) fn parse_function_declaration(
 
     // Validate we have the expected number of children
     if children.len() < 6 {
-        return Err(Box::new(BicepParserError::ParseError(
-            "Function declaration missing required elements".to_string(),
-        )));
+        return Err(BicepError::syntax(
+            "Function declaration missing required elements",
+            &node,
+        ));
     }
 
     let name = get_node_text(&children[1], source_code)?;
     if name.is_empty() {
-        return Err(Box::new(BicepParserError::ParseError(
-            "Function declaration missing name".to_string(),
-        )));
+        return Err(BicepError::syntax(
+            "Function declaration missing name",
+            &node,
+        ));
     }
 
     debug!("Parsing function declaration: {}", name);
@@ -212,7 +213,7 @@ fn process_function_decorators(
 fn parse_function_parameters(
     node: Node,
     source_code: &str,
-) -> Result<Vec<BicepFunctionArgument>, Box<dyn Error>> {
+) -> Result<Vec<BicepFunctionArgument>, BicepError> {
     let mut arguments = Vec::new();
     let mut cursor = node.walk();
 
@@ -243,7 +244,7 @@ fn parse_function_parameters(
 fn parse_function_argument(
     node: Node,
     source_code: &str,
-) -> Result<BicepFunctionArgument, Box<dyn Error>> {
+) -> Result<BicepFunctionArgument, BicepError> {
     let mut name = String::new();
     let mut argument_type = BicepType::String;
     let mut is_nullable = false;
@@ -262,9 +263,7 @@ fn parse_function_argument(
     }
 
     if name.is_empty() {
-        return Err(Box::new(BicepParserError::ParseError(
-            "Function parameter missing name".to_string(),
-        )));
+        return Err(BicepError::syntax("Function parameter missing name", &node));
     }
 
     debug!(
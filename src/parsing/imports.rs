@@ -4,15 +4,14 @@
 //! Imports allow templates to reference external modules, namespaces, and symbols,
 //! enabling code reuse and modular template design.
 
-use std::error::Error;
-
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use tracing::debug;
 use tree_sitter::Node;
 
 use super::utils::get_node_text;
-use super::{BicepParserError, ModuleSource};
+use super::ModuleSource;
+use crate::error::{BicepError, Span};
 
 // ---------------------------------------------------------------
 // Structs, Enums & Types
@@ -103,10 +102,7 @@ pub struct BicepImportSymbol {
 /// ```
 ///
 /// Note: This example is marked as ignored in doctests because it requires a valid tree-sitter node.
-pub fn parse_namespace_import(
-    node: Node,
-    source_code: &str,
-) -> Result<BicepImport, Box<dyn Error>> {
+pub fn parse_namespace_import(node: Node, source_code: &str) -> Result<BicepImport, BicepError> {
     let mut namespace = String::new();
     let mut version: Option<String> = None;
 
@@ -118,9 +114,10 @@ pub fn parse_namespace_import(
             let clean_text = import_text.trim_matches('\'').trim_matches('"');
 
             if clean_text.is_empty() {
-                return Err(Box::new(BicepParserError::ParseError(
-                    "Namespace import has empty string".to_string(),
-                )));
+                return Err(BicepError::syntax(
+                    "Namespace import has empty string",
+                    &node,
+                ));
             }
 
             // Parse namespace and version (separated by @)
@@ -129,9 +126,10 @@ pub fn parse_namespace_import(
                 version = Some(clean_text[at_index + 1..].to_string());
 
                 if namespace.is_empty() {
-                    return Err(Box::new(BicepParserError::ParseError(
-                        "Namespace import has empty namespace".to_string(),
-                    )));
+                    return Err(BicepError::syntax(
+                        "Namespace import has empty namespace",
+                        &node,
+                    ));
                 }
 
                 debug!(
@@ -149,9 +147,10 @@ pub fn parse_namespace_import(
     }
 
     if namespace.is_empty() {
-        return Err(Box::new(BicepParserError::ParseError(
-            "Could not extract namespace from import statement".to_string(),
-        )));
+        return Err(BicepError::syntax(
+            "Could not extract namespace from import statement",
+            &node,
+        ));
     }
 
     Ok(BicepImport::Namespace { namespace, version })
@@ -186,7 +185,7 @@ pub fn parse_namespace_import(
 /// ```
 ///
 /// Note: This example is marked as ignored in doctests because it requires a valid tree-sitter node.
-pub fn parse_module_import(node: Node, source_code: &str) -> Result<BicepImport, Box<dyn Error>> {
+pub fn parse_module_import(node: Node, source_code: &str) -> Result<BicepImport, BicepError> {
     let mut symbols: Vec<BicepImportSymbol> = Vec::new();
     let mut wildcard_alias: Option<String> = None;
 
@@ -220,28 +219,27 @@ pub fn parse_module_import(node: Node, source_code: &str) -> Result<BicepImport,
 }
 
 /// Extracts the source path from module import children nodes.
-fn extract_import_source_path(
-    children: &[Node],
-    source_code: &str,
-) -> Result<String, Box<dyn Error>> {
+fn extract_import_source_path(children: &[Node], source_code: &str) -> Result<String, BicepError> {
     for (i, child) in children.iter().enumerate() {
         if child.kind() == "from" && i + 1 < children.len() && children[i + 1].kind() == "string" {
             let path = get_node_text(&children[i + 1], source_code)?;
             let clean_path = path.trim_matches('\'').trim_matches('"');
 
             if clean_path.is_empty() {
-                return Err(Box::new(BicepParserError::ParseError(
-                    "Module import has empty source path".to_string(),
-                )));
+                return Err(BicepError::syntax(
+                    "Module import has empty source path",
+                    &children[i + 1],
+                ));
             }
 
             return Ok(clean_path.to_string());
         }
     }
 
-    Err(Box::new(BicepParserError::ParseError(
-        "Could not find source path in module import".to_string(),
-    )))
+    Err(BicepError::Syntax {
+        message: "Could not find source path in module import".to_string(),
+        span: children.last().map(Span::from_node),
+    })
 }
 
 /// Parses import specifiers (symbols or wildcard).
@@ -250,7 +248,7 @@ fn parse_import_specifiers(
     source_code: &str,
     symbols: &mut Vec<BicepImportSymbol>,
     wildcard_alias: &mut Option<String>,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), BicepError> {
     for (i, child) in children.iter().enumerate() {
         match child.kind() {
             "*" => {
@@ -295,7 +293,7 @@ fn parse_symbol_list(
     brace_index: usize,
     source_code: &str,
     symbols: &mut Vec<BicepImportSymbol>,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), BicepError> {
     let mut j = brace_index + 1;
 
     while j < children.len() && children[j].kind() != "}" {
@@ -316,7 +314,7 @@ fn parse_import_symbol(
     start_index: usize,
     source_code: &str,
     current_index: &mut usize,
-) -> Result<BicepImportSymbol, Box<dyn Error>> {
+) -> Result<BicepImportSymbol, BicepError> {
     let symbol_name = get_node_text(&children[start_index], source_code)?;
 
     // Check for alias: symbol as alias
@@ -15,14 +15,15 @@
 //! - `outputs.rs` - Output declaration parsing
 //! - `imports.rs` - Import statement parsing
 
-use std::{error::Error, fmt};
-
 use indexmap::IndexMap;
 use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
 use serde_with::skip_serializing_none;
 use tracing::warn;
 use tree_sitter::{Node, Tree};
 
+use crate::error::BicepError;
+
+mod arm;
 mod functions;
 mod imports;
 mod modules;
@@ -33,6 +34,7 @@ mod types;
 pub mod utils;
 mod variables;
 
+pub use arm::parse_arm_template;
 pub use functions::{BicepFunction, BicepFunctionArgument};
 pub use imports::{parse_module_import, parse_namespace_import, BicepImport, BicepImportSymbol};
 pub use modules::{parse_module_declaration, BicepModule, ModuleSource};
@@ -49,31 +51,6 @@ pub use variables::BicepVariable;
 // Structs, Enums & Types
 // ---------------------------------------------------------------
 
-/// Custom error types for Bicep parsing operations
-#[derive(Debug, Clone, PartialEq)]
-pub enum BicepParserError {
-    /// Unknown node kind encountered during parsing
-    UnknownKind(String),
-    /// Invalid value found for a specific type
-    InvalidValue { kind: String, reason: String },
-    /// General parsing error
-    ParseError(String),
-}
-
-impl fmt::Display for BicepParserError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            BicepParserError::UnknownKind(kind) => write!(f, "Unknown kind: {kind}"),
-            BicepParserError::InvalidValue { kind, reason } => {
-                write!(f, "Invalid {kind} value: {reason}")
-            },
-            BicepParserError::ParseError(msg) => write!(f, "Parse error: {msg}"),
-        }
-    }
-}
-
-impl Error for BicepParserError {}
-
 /// A complete Bicep document containing all parsed components
 ///
 /// This structure represents the complete contents of a Bicep file after parsing,
@@ -90,8 +67,8 @@ impl Error for BicepParserError {}
 /// - Resource declarations
 /// - Module declarations
 /// - Output declarations
-#[derive(Debug, Serialize, Deserialize, Default, PartialEq)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
 #[skip_serializing_none]
 pub struct BicepDocument {
     /// Optional name of the document/template
@@ -100,6 +77,9 @@ pub struct BicepDocument {
     /// Optional description of the template's purpose
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Descriptions of the template in other languages, keyed by locale code
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub descriptions: IndexMap<String, String>,
     /// Template metadata
     #[serde(skip_serializing_if = "IndexMap::is_empty")]
     pub metadata: IndexMap<String, BicepValue>,
@@ -131,8 +111,13 @@ pub struct BicepDocument {
 /// - Complex types (arrays, objects)
 /// - Custom type references
 /// - Union types for multiple allowed values
-#[derive(Debug, Deserialize, Clone, PartialEq)]
-#[serde(rename_all = "camelCase")]
+///
+/// Types serialize as their Bicep text, such as `string[]` or `'a' | 'b'`, and inline
+/// objects as a map of their properties. Types whose text would read back as a
+/// different type, such as an array of inline objects, serialize as a map with a
+/// single `$array`, `$union` or `$customType` key instead, so that every type
+/// deserializes to the value it was serialized from.
+#[derive(Debug, Clone, PartialEq)]
 pub enum BicepType {
     /// Array type with element type specification
     Array(Box<BicepType>),
@@ -169,32 +154,124 @@ impl std::fmt::Display for BicepType {
     }
 }
 
-// Custom serialize implementation for BicepType that will allow us
-// to handle special cases like Union types correctly
+impl BicepType {
+    /// Read a type from its Bicep text, as written by `Display`
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text of the type, such as `string[]` or `'a' | 'b'`
+    ///
+    /// # Returns
+    ///
+    /// The type the text describes; names that are not built-in types are custom types
+    pub fn from_type_text(text: &str) -> BicepType {
+        match text {
+            "string" => BicepType::String,
+            "int" => BicepType::Int,
+            "bool" => BicepType::Bool,
+            "object" => BicepType::Object(None),
+            _ => {
+                if let Some(element) = text.strip_suffix("[]") {
+                    BicepType::Array(Box::new(BicepType::from_type_text(element)))
+                } else if text.contains(" | ") {
+                    BicepType::Union(text.split(" | ").map(str::to_string).collect())
+                } else {
+                    BicepType::CustomType(text.to_string())
+                }
+            },
+        }
+    }
+}
+
+// Custom serialize implementation for BicepType that writes types as their Bicep text
+// where that text reads back as the same type
 impl Serialize for BicepType {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         match self {
-            // Case 1: Generic object type with no properties serializes as "object"
-            BicepType::Object(None) => "object".serialize(serializer),
-            // Case 3: Inline specific object type with properties serializes as its structure
+            // Inline object types serialize as their properties
             BicepType::Object(Some(properties)) => {
-                // For inline object definitions, serialize the structure
                 let mut map = serializer.serialize_map(Some(properties.len()))?;
                 for (key, param) in properties {
                     map.serialize_entry(key, &param)?;
                 }
                 map.end()
             },
-            // Case 2: Custom type references serialize as their name string
-            BicepType::CustomType(name) => name.clone().serialize(serializer),
-            // Handle union types specially - just output the joined string without "type:" prefix
-            BicepType::Union(values) => values.join(" | ").serialize(serializer),
-            // All other types serialize as strings
-            _ => self.to_string().serialize(serializer),
+            _ => {
+                let text = self.to_string();
+                if BicepType::from_type_text(&text) == *self {
+                    return text.serialize(serializer);
+                }
+
+                // The text is ambiguous, so name the kind of type explicitly
+                let mut map = serializer.serialize_map(Some(1))?;
+                match self {
+                    BicepType::Array(element) => map.serialize_entry("$array", element)?,
+                    BicepType::Union(values) => map.serialize_entry("$union", values)?,
+                    _ => map.serialize_entry("$customType", &text)?,
+                }
+                map.end()
+            },
+        }
+    }
+}
+
+// Custom deserialize implementation for BicepType that reads the forms written by
+// its serializer
+impl<'de> Deserialize<'de> for BicepType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use std::fmt;
+
+        use serde::de::{self, MapAccess, Visitor};
+
+        struct BicepTypeVisitor;
+
+        impl<'de> Visitor<'de> for BicepTypeVisitor {
+            type Value = BicepType;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a type name or a map of object properties")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(BicepType::from_type_text(value))
+            }
+
+            fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+            where
+                M: MapAccess<'de>,
+            {
+                let mut properties = IndexMap::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    let explicit = match key.as_str() {
+                        "$array" => BicepType::Array(Box::new(map.next_value()?)),
+                        "$union" => BicepType::Union(map.next_value()?),
+                        "$customType" => BicepType::CustomType(map.next_value()?),
+                        _ => {
+                            properties.insert(key, map.next_value()?);
+                            continue;
+                        },
+                    };
+                    if !properties.is_empty() || map.next_key::<String>()?.is_some() {
+                        return Err(de::Error::custom(format!(
+                            "{key} cannot be combined with other keys"
+                        )));
+                    }
+                    return Ok(explicit);
+                }
+                Ok(BicepType::Object(Some(properties)))
+            }
         }
+
+        deserializer.deserialize_any(BicepTypeVisitor)
     }
 }
 
@@ -204,6 +281,11 @@ impl Serialize for BicepType {
 /// - Primitive values (strings, numbers, booleans)
 /// - Complex values (arrays, objects)
 /// - Identifier references to other template elements
+///
+/// Expressions serialize as a map with a single `$reference` key, so that they can be
+/// told apart from string literals. Objects with a `$reference` or `$object` key of
+/// their own serialize as a map with a single `$object` key holding them, so that
+/// every value deserializes to the value it was serialized from.
 #[derive(Debug, Clone, PartialEq)]
 pub enum BicepValue {
     /// Array of values
@@ -216,7 +298,7 @@ pub enum BicepValue {
     Bool(bool),
     /// Object with key-value pairs
     Object(IndexMap<String, BicepValue>),
-    /// Reference to another identifier in the template
+    /// Expression, such as a reference to another identifier in the template
     Identifier(String),
 }
 
@@ -296,6 +378,9 @@ impl BicepValue {
     }
 }
 
+/// Keys of the maps that values other than plain objects serialize as
+const RESERVED_VALUE_KEYS: [&str; 2] = ["$reference", "$object"];
+
 // Implement a custom serializer for BicepValue to avoid YAML tags
 impl Serialize for BicepValue {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -307,10 +392,20 @@ impl Serialize for BicepValue {
             BicepValue::String(s) => s.serialize(serializer),
             BicepValue::Int(n) => n.serialize(serializer),
             BicepValue::Bool(b) => b.serialize(serializer),
+            BicepValue::Object(map)
+                if map
+                    .keys()
+                    .any(|key| RESERVED_VALUE_KEYS.contains(&key.as_str())) =>
+            {
+                let mut wrapper = serializer.serialize_map(Some(1))?;
+                wrapper.serialize_entry("$object", map)?;
+                wrapper.end()
+            },
             BicepValue::Object(map) => map.serialize(serializer),
             BicepValue::Identifier(id) => {
-                // Corrected: use 'id' instead of 'reference'
-                id.serialize(serializer)
+                let mut reference = serializer.serialize_map(Some(1))?;
+                reference.serialize_entry("$reference", id)?;
+                reference.end()
             },
         }
     }
@@ -332,8 +427,7 @@ impl<'de> Deserialize<'de> for BicepValue {
             type Value = BicepValue;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter
-                    .write_str("a string, number, boolean, sequence, map or identifier reference")
+                formatter.write_str("a string, number, boolean, sequence or map")
             }
 
             fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E>
@@ -361,11 +455,6 @@ impl<'de> Deserialize<'de> for BicepValue {
             where
                 E: de::Error,
             {
-                // Check if this is an identifier reference in our special format
-                if value.starts_with("{reference:") && value.ends_with("}") {
-                    let id = value[11..value.len() - 1].to_string();
-                    return Ok(BicepValue::Identifier(id));
-                }
                 Ok(BicepValue::String(value.to_string()))
             }
 
@@ -373,11 +462,6 @@ impl<'de> Deserialize<'de> for BicepValue {
             where
                 E: de::Error,
             {
-                // Check if this is an identifier reference in our special format
-                if value.starts_with("{reference:") && value.ends_with("}") {
-                    let id = value[11..value.len() - 1].to_string();
-                    return Ok(BicepValue::Identifier(id));
-                }
                 Ok(BicepValue::String(value))
             }
 
@@ -397,8 +481,21 @@ impl<'de> Deserialize<'de> for BicepValue {
                 M: MapAccess<'de>,
             {
                 let mut values = IndexMap::new();
-                while let Some((key, value)) = map.next_entry()? {
-                    values.insert(key, value);
+                while let Some(key) = map.next_key::<String>()? {
+                    let explicit = match key.as_str() {
+                        "$reference" => BicepValue::Identifier(map.next_value()?),
+                        "$object" => BicepValue::Object(map.next_value()?),
+                        _ => {
+                            values.insert(key, map.next_value()?);
+                            continue;
+                        },
+                    };
+                    if !values.is_empty() || map.next_key::<String>()?.is_some() {
+                        return Err(de::Error::custom(format!(
+                            "{key} cannot be combined with other keys"
+                        )));
+                    }
+                    return Ok(explicit);
                 }
                 Ok(BicepValue::Object(values))
             }
@@ -499,10 +596,7 @@ fn parse_decorators_from_node_list(
 
 // parse_parameter_declaration function is now defined in parameters.rs
 
-pub fn parse_bicep_document(
-    tree: &Tree,
-    source_code: &str,
-) -> Result<BicepDocument, Box<dyn Error>> {
+pub fn parse_bicep_document(tree: &Tree, source_code: &str) -> Result<BicepDocument, BicepError> {
     let mut document = BicepDocument::default();
     let root_node = tree.root_node();
     // Pre-allocate collections with estimated capacities for better performance
@@ -574,6 +668,8 @@ pub fn parse_bicep_document(
                             custom_type.description =
                                 extract_description_from_decorators(&all_decorators);
                         }
+                        custom_type.descriptions =
+                            utils::extract_localized_descriptions(&all_decorators);
 
                         // Check for secure decorator
                         custom_type.is_secure = all_decorators.iter().any(|d| d.name == "secure");
@@ -581,6 +677,17 @@ pub fn parse_bicep_document(
                         // Check for export decorator
                         custom_type.is_exported = all_decorators.iter().any(|d| d.name == "export");
 
+                        custom_type.is_sealed = all_decorators.iter().any(|d| d.name == "sealed");
+                        custom_type.discriminator =
+                            all_decorators
+                                .iter()
+                                .find_map(|d| match (&*d.name, &d.argument) {
+                                    ("discriminator", BicepValue::String(property)) => {
+                                        Some(property.clone())
+                                    },
+                                    _ => None,
+                                });
+
                         // Add all decorators to the custom type if it has a field for them
                         // Assuming BicepCustomType might have a field like `decorators: Vec<BicepDecorator>`
                         // If not, this part can be adjusted or removed.
@@ -738,10 +845,11 @@ pub fn parse_bicep_document(
     if let Some(BicepValue::String(desc)) = metadata.get("description") {
         document.description = Some(desc.to_string());
     }
+    document.descriptions = utils::decorators::localized_descriptions(&metadata);
 
-    // Remove name and description from metadata to avoid duplication
+    // Remove name and descriptions from metadata to avoid duplication
     metadata.shift_remove("name");
-    metadata.shift_remove("description");
+    metadata.retain(|key, _| !utils::decorators::is_description_key(key));
 
     // Set document metadata and types
     document.metadata = metadata;
@@ -813,7 +921,7 @@ fn parse_metadata(node: Node, source_code: &str) -> (String, Option<BicepValue>)
 }
 
 /// Extract a primitive Bicep value from a node
-fn get_primitive_value(node: Node, source_code: &str) -> Result<BicepValue, Box<dyn Error>> {
+fn get_primitive_value(node: Node, source_code: &str) -> Result<BicepValue, BicepError> {
     match node.kind() {
         "string" => {
             // For string nodes, look for string_content child nodes instead of using the entire text
@@ -847,26 +955,28 @@ fn get_primitive_value(node: Node, source_code: &str) -> Result<BicepValue, Box<
             let node_text = utils::get_node_text(&node, source_code)?;
             match node_text.parse::<i64>() {
                 Ok(n) => Ok(BicepValue::Int(n)),
-                Err(_) => Err(Box::new(BicepParserError::InvalidValue {
-                    kind: "number".to_string(),
-                    reason: format!("Could not parse '{node_text}' as integer"),
-                })),
+                Err(_) => Err(BicepError::invalid_value(
+                    "number",
+                    format!("Could not parse '{node_text}' as integer"),
+                    &node,
+                )),
             }
         },
         "boolean" => {
             let node_text = utils::get_node_text(&node, source_code)?;
             match node_text.parse::<bool>() {
                 Ok(b) => Ok(BicepValue::Bool(b)),
-                Err(_) => Err(Box::new(BicepParserError::InvalidValue {
-                    kind: "boolean".to_string(),
-                    reason: format!("Could not parse '{node_text}' as boolean"),
-                })),
+                Err(_) => Err(BicepError::invalid_value(
+                    "boolean",
+                    format!("Could not parse '{node_text}' as boolean"),
+                    &node,
+                )),
             }
         },
-        _ => Err(Box::new(BicepParserError::UnknownKind(format!(
-            "Unknown primitive value type: {}",
-            node.kind()
-        )))),
+        _ => Err(BicepError::unsupported(
+            format!("primitive value type {}", node.kind()),
+            &node,
+        )),
     }
 }
 
@@ -12,8 +12,7 @@
 //! - **Registry**: Azure Container Registry or other OCI registries with aliases or FQDNs
 //! - **TypeSpec**: Template specifications with subscription and resource group references
 
-use std::error::Error;
-
+use indexmap::IndexMap;
 use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
 use serde_with::skip_serializing_none;
 use tracing::debug;
@@ -23,17 +22,17 @@ use super::{
     utils::{
         decorators::extract_description_from_decorators, get_node_text, values::parse_value_node,
     },
-    BicepDecorator, BicepParserError, BicepValue,
+    BicepDecorator, BicepValue,
 };
+use crate::error::{BicepError, Span};
 
 // ---------------------------------------------------------------
 // Structs, Enums & Types
 // ---------------------------------------------------------------
 
 /// Represents the source of a Bicep module
-#[derive(Debug, Deserialize, Clone, PartialEq)]
-#[serde(rename_all = "camelCase")]
-#[skip_serializing_none]
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(from = "SerializedModuleSource")]
 pub enum ModuleSource {
     /// Local file path module source
     LocalPath(String),
@@ -43,7 +42,6 @@ pub enum ModuleSource {
         /// Optional registry alias (for br/<alias>:<path>:<version>)
         alias: Option<String>,
         /// Optional registry FQDN (for br:<registry fqdn>/<path>:<version>)
-        #[serde(rename = "registry")]
         registry_fqdn: Option<String>,
         /// Required path to the module
         path: String,
@@ -66,6 +64,66 @@ pub enum ModuleSource {
     },
 }
 
+/// The forms a ModuleSource serializes to: a local path, or a map tagged by `type`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SerializedModuleSource {
+    LocalPath(String),
+    Remote(RemoteModuleSource),
+}
+
+/// The map form of a registry or TypeSpec module source
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum RemoteModuleSource {
+    Registry {
+        alias: Option<String>,
+        registry: Option<String>,
+        path: String,
+        version: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    TypeSpec {
+        alias: Option<String>,
+        subscription: Option<String>,
+        resource_group: Option<String>,
+        name: String,
+        version: String,
+    },
+}
+
+impl From<SerializedModuleSource> for ModuleSource {
+    fn from(source: SerializedModuleSource) -> Self {
+        match source {
+            SerializedModuleSource::LocalPath(path) => ModuleSource::LocalPath(path),
+            SerializedModuleSource::Remote(RemoteModuleSource::Registry {
+                alias,
+                registry,
+                path,
+                version,
+            }) => ModuleSource::Registry {
+                alias,
+                registry_fqdn: registry,
+                path,
+                version,
+            },
+            SerializedModuleSource::Remote(RemoteModuleSource::TypeSpec {
+                alias,
+                subscription,
+                resource_group,
+                name,
+                version,
+            }) => ModuleSource::TypeSpec {
+                alias,
+                subscription_id: subscription,
+                resource_group_name: resource_group,
+                template_spec_name: name,
+                version,
+            },
+        }
+    }
+}
+
 // Custom Display implementation for ModuleSource for better debug output
 impl std::fmt::Display for ModuleSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -186,7 +244,7 @@ impl ModuleSource {
     /// let local_path = ModuleSource::parse("./modules/storage.bicep").unwrap();
     /// let registry = ModuleSource::parse("br:mcr.microsoft.com/bicep/storage:v1.0").unwrap();
     /// ```
-    pub fn parse(source: &str) -> Result<Self, Box<dyn Error>> {
+    pub fn parse(source: &str) -> Result<Self, BicepError> {
         // Check if it's a local file path (doesn't contain :)
         if !source.contains(":") {
             return Ok(ModuleSource::LocalPath(source.to_string()));
@@ -215,9 +273,11 @@ impl ModuleSource {
             _ => {},
         }
 
-        Err(Box::new(BicepParserError::ParseError(format!(
-            "Unknown module source format: {source}"
-        ))))
+        Err(BicepError::InvalidValue {
+            kind: "module source".to_string(),
+            reason: format!("Unknown module source format: {source}"),
+            span: None,
+        })
     }
 
     /// Parse br/<alias>:<path>:<version> format module source
@@ -235,7 +295,7 @@ impl ModuleSource {
     fn parse_br_alias_format(
         source_without_prefix: &str,
         full_source: &str,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, BicepError> {
         if let Some(colon_idx) = source_without_prefix.find(':') {
             let alias = source_without_prefix[0..colon_idx].to_string();
             let remaining = &source_without_prefix[colon_idx + 1..];
@@ -253,9 +313,11 @@ impl ModuleSource {
             }
         }
 
-        Err(Box::new(BicepParserError::ParseError(format!(
-            "Invalid registry module format with alias: {full_source}"
-        ))))
+        Err(BicepError::InvalidValue {
+            kind: "module source".to_string(),
+            reason: format!("Invalid registry module format with alias: {full_source}"),
+            span: None,
+        })
     }
 
     /// Parse br:<registry fqdn>/<path>:<version> format module source
@@ -273,7 +335,7 @@ impl ModuleSource {
     fn parse_br_fqdn_format(
         source_without_prefix: &str,
         full_source: &str,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, BicepError> {
         if let Some(slash_idx) = source_without_prefix.find('/') {
             let fqdn = source_without_prefix[0..slash_idx].to_string();
             let remaining = &source_without_prefix[slash_idx + 1..];
@@ -291,9 +353,11 @@ impl ModuleSource {
             }
         }
 
-        Err(Box::new(BicepParserError::ParseError(format!(
-            "Invalid registry module format with FQDN: {full_source}"
-        ))))
+        Err(BicepError::InvalidValue {
+            kind: "module source".to_string(),
+            reason: format!("Invalid registry module format with FQDN: {full_source}"),
+            span: None,
+        })
     }
 
     /// Parse ts/<alias>:<template-spec-name>:<version> format module source
@@ -311,7 +375,7 @@ impl ModuleSource {
     fn parse_ts_alias_format(
         source_without_prefix: &str,
         full_source: &str,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, BicepError> {
         if let Some(colon_idx) = source_without_prefix.find(':') {
             let alias = source_without_prefix[0..colon_idx].to_string();
             let remaining = &source_without_prefix[colon_idx + 1..];
@@ -330,9 +394,11 @@ impl ModuleSource {
             }
         }
 
-        Err(Box::new(BicepParserError::ParseError(format!(
-            "Invalid TypeSpec module format with alias: {full_source}"
-        ))))
+        Err(BicepError::InvalidValue {
+            kind: "module source".to_string(),
+            reason: format!("Invalid TypeSpec module format with alias: {full_source}"),
+            span: None,
+        })
     }
 
     /// Parse ts:<subscription-id>/<resource-group-name>/<template-spec-name>:<version> format
@@ -351,7 +417,7 @@ impl ModuleSource {
     fn parse_ts_subscription_format(
         source_without_prefix: &str,
         full_source: &str,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, BicepError> {
         let parts: Vec<&str> = source_without_prefix.split('/').collect();
         if parts.len() >= 3 {
             let subscription_id = parts[0].to_string();
@@ -372,9 +438,11 @@ impl ModuleSource {
             }
         }
 
-        Err(Box::new(BicepParserError::ParseError(format!(
-            "Invalid TypeSpec module format with subscription: {full_source}"
-        ))))
+        Err(BicepError::InvalidValue {
+            kind: "module source".to_string(),
+            reason: format!("Invalid TypeSpec module format with subscription: {full_source}"),
+            span: None,
+        })
     }
 }
 
@@ -391,6 +459,10 @@ pub struct BicepModule {
     pub description: Option<String>,
     /// Name of the module
     pub name: String,
+    /// Name of the deployment, from the `name` property, as Bicep string text with an
+    /// expression written as `${expression}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployment_name: Option<String>,
     /// Source of the module (local path, registry, or TypeSpec)
     pub source: ModuleSource,
     /// List of resources this module depends on
@@ -405,12 +477,71 @@ pub struct BicepModule {
     /// Batch size for deployment (from @batchSize decorator)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub batch_size: Option<i64>,
+    /// Parameters passed to the module
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub params: IndexMap<String, BicepValue>,
 }
 
 // ---------------------------------------------------------------
 // Functions
 // ---------------------------------------------------------------
 
+/// Find the first object node within a tree node
+///
+/// Conditional and loop modules nest their body object inside the `if` or `for`
+/// statement, so the body has to be searched for rather than read from a direct child.
+///
+/// # Arguments
+///
+/// * `node` - The tree-sitter Node to search
+///
+/// # Returns
+///
+/// The first object node found in a depth-first search, if any
+fn find_first_object_node(node: Node) -> Option<Node> {
+    if node.kind() == "object" {
+        return Some(node);
+    }
+
+    let mut cursor = node.walk();
+    let children = node.children(&mut cursor).collect::<Vec<_>>();
+    children.into_iter().find_map(find_first_object_node)
+}
+
+/// Extract the `params` object from a module body
+///
+/// # Arguments
+///
+/// * `props` - The parsed properties of the module body
+///
+/// # Returns
+///
+/// The module parameters, or an empty map if the body has no `params` object
+fn extract_module_params(props: &IndexMap<String, BicepValue>) -> IndexMap<String, BicepValue> {
+    match props.get("params") {
+        Some(BicepValue::Object(params)) => params.clone(),
+        _ => IndexMap::new(),
+    }
+}
+
+/// Extract the deployment name from a module body
+///
+/// # Arguments
+///
+/// * `props` - The parsed properties of the module body
+///
+/// # Returns
+///
+/// The `name` property as Bicep string text, or None if the body has no name
+fn extract_deployment_name(props: &IndexMap<String, BicepValue>) -> Option<String> {
+    props.get("name").map(|name| match name {
+        BicepValue::String(text) => text.clone(),
+        // Keep expressions as `${expression}`, as for resource names
+        BicepValue::Identifier(expression) => format!("${{{expression}}}"),
+        value => format!("{value}"),
+    })
+}
+
 /// Extract description from decorators
 ///
 /// This function searches through a list of decorators for description metadata
@@ -459,7 +590,7 @@ pub fn parse_module_declaration(
     node: Node,
     source_code: &str,
     decorators: Vec<BicepDecorator>,
-) -> Result<BicepModule, Box<dyn Error>> {
+) -> Result<BicepModule, BicepError> {
     debug!(
         "Parsing module declaration with {} decorators",
         decorators.len()
@@ -472,6 +603,8 @@ pub fn parse_module_declaration(
     let mut loop_iterator: Option<String> = None;
     let mut loop_array: Option<String> = None;
     let mut batch_size: Option<i64> = None;
+    let mut params: IndexMap<String, BicepValue> = IndexMap::new();
+    let mut deployment_name: Option<String> = None;
 
     // Extract description from decorators
     let description = extract_description_from_decorators(&decorators);
@@ -512,19 +645,20 @@ pub fn parse_module_declaration(
                                 source = parsed_source;
                             },
                             Err(e) => {
-                                return Err(Box::new(BicepParserError::ParseError(format!(
-                                    "Failed to parse module source: {e}"
-                                ))));
+                                return Err(e.with_span(Span::from_node(&children[i + 2])));
                             },
                         }
                     }
                 }
             },
             "object" => {
-                // This is the module properties object - only extract dependsOn
+                // This is the module properties object - extract params and dependsOn
                 if let Ok(Some(BicepValue::Object(props))) =
                     parse_value_node(children[i], source_code)
                 {
+                    params = extract_module_params(&props);
+                    deployment_name = extract_deployment_name(&props);
+
                     // Look for dependsOn property
                     if let Some(depends_value) = props.get("dependsOn") {
                         match depends_value {
@@ -574,6 +708,15 @@ pub fn parse_module_declaration(
                         }
                     }
                 }
+
+                if let Some(object_node) = find_first_object_node(children[i]) {
+                    if let Ok(Some(BicepValue::Object(props))) =
+                        parse_value_node(object_node, source_code)
+                    {
+                        params = extract_module_params(&props);
+                        deployment_name = extract_deployment_name(&props);
+                    }
+                }
             },
             "for_statement" => {
                 // Loop module - extract the loop details and nested object
@@ -599,6 +742,15 @@ pub fn parse_module_declaration(
                         }
                     }
                 }
+
+                if let Some(object_node) = find_first_object_node(children[i]) {
+                    if let Ok(Some(BicepValue::Object(props))) =
+                        parse_value_node(object_node, source_code)
+                    {
+                        params = extract_module_params(&props);
+                        deployment_name = extract_deployment_name(&props);
+                    }
+                }
             },
             "array" => {
                 // This might be a module loop with array literal
@@ -700,12 +852,14 @@ pub fn parse_module_declaration(
     // Create the module
     let module = BicepModule {
         name: name.clone(),
+        deployment_name,
         source,
         description,
         depends_on,
         condition,
         loop_statement,
         batch_size,
+        params,
     };
 
     debug!("Successfully parsed module: {}", name);
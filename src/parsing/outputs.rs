@@ -10,8 +10,6 @@
 //! - Length and value constraints
 //! - Complex object and array outputs
 
-use std::error::Error;
-
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -20,12 +18,16 @@ use tree_sitter::Node;
 
 use super::{
     utils::{
-        decorators::extract_description_from_decorators,
+        decorators::{
+            extract_description_from_decorators, extract_localized_descriptions,
+            extract_metadata_without_description,
+        },
         types::parse_array_type,
         values::{parse_array_items, parse_value_node},
     },
     BicepDecorator, BicepParameter, BicepType, BicepValue,
 };
+use crate::error::BicepError;
 
 // ---------------------------------------------------------------
 // Structs, Enums & Types
@@ -52,6 +54,10 @@ pub struct BicepOutput {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// Descriptions in other languages, keyed by locale code
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub descriptions: IndexMap<String, String>,
+
     /// Type of the output (string, int, etc.)
     #[serde(rename = "type")]
     pub output_type: BicepType,
@@ -144,9 +150,7 @@ fn extract_decorator_properties(decorators: &[BicepDecorator]) -> DecoratorPrope
                 }
             },
             "metadata" => {
-                if let BicepValue::Object(map) = &decorator.argument {
-                    metadata = Some(map.clone());
-                }
+                metadata = extract_metadata_without_description(decorator);
             },
             "sealed" => {
                 sealed = true;
@@ -207,7 +211,7 @@ pub fn parse_output_declaration(
     node: Node,
     source_code: &str,
     decorators: Vec<BicepDecorator>,
-) -> Result<(String, BicepOutput), Box<dyn Error>> {
+) -> Result<(String, BicepOutput), BicepError> {
     debug!(
         "Parsing output declaration with {} decorators",
         decorators.len()
@@ -219,6 +223,7 @@ pub fn parse_output_declaration(
 
     // Extract description from decorators
     let description = extract_description_from_decorators(&decorators);
+    let descriptions = extract_localized_descriptions(&decorators);
 
     // Extract constraint and property values from decorators
     let (discriminator, max_length, min_length, max_value, min_value, metadata, sealed, secure) =
@@ -376,7 +381,7 @@ pub fn parse_output_declaration(
 
                     // The full expression text becomes the value
                     let expr_text = crate::parsing::utils::get_node_text(&value_node, source_code)?;
-                    value = BicepValue::String(expr_text);
+                    value = BicepValue::Identifier(expr_text);
                 }
             }
         }
@@ -387,6 +392,7 @@ pub fn parse_output_declaration(
         output_type,
         value,
         description,
+        descriptions,
         discriminator,
         max_length,
         min_length,
@@ -1,5 +1,3 @@
-use std::error::Error;
-
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -8,11 +6,15 @@ use tree_sitter::Node;
 
 use super::{
     utils::{
-        decorators::extract_description_from_decorators, types::parse_type_node,
+        decorators::{
+            extract_description_from_decorators, is_description_key, localized_descriptions,
+        },
+        types::parse_type_node,
         values::parse_value_node,
     },
     BicepDecorator, BicepType, BicepValue,
 };
+use crate::error::BicepError;
 
 // ---------------------------------------------------------------
 // Structs, Enums & Types
@@ -29,13 +31,17 @@ use super::{
 /// - Parameter with default: `param location string = 'eastus'`
 /// - Parameter with constraints: `@minLength(3) param name string`
 #[derive(Debug, Deserialize, Clone, PartialEq)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 #[skip_serializing_none]
 pub struct BicepParameter {
     /// Optional description of the parameter's purpose
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// Descriptions in other languages, keyed by locale code
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub descriptions: IndexMap<String, String>,
+
     /// Additional metadata associated with the parameter
     #[serde(skip_serializing_if = "IndexMap::is_empty")]
     pub metadata: IndexMap<String, BicepValue>,
@@ -89,6 +95,7 @@ impl Default for BicepParameter {
     fn default() -> BicepParameter {
         BicepParameter {
             description: None,
+            descriptions: IndexMap::new(),
             metadata: IndexMap::new(),
             parameter_type: BicepType::String,
             default_value: None,
@@ -135,7 +142,7 @@ pub(crate) fn parse_parameter_declaration(
     node: Node,
     source_code: &str,
     decorators: Vec<BicepDecorator>,
-) -> Result<(String, BicepParameter), Box<dyn Error>> {
+) -> Result<(String, BicepParameter), BicepError> {
     let mut parameter = BicepParameter::default();
 
     let mut cursor = node.walk();
@@ -318,7 +325,7 @@ fn process_parameter_decorators(
     parameter: &mut BicepParameter,
     decorators: &[BicepDecorator],
     name: &str,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), BicepError> {
     for decorator in decorators {
         match decorator.name.as_str() {
             "description" | "sys.description" => {
@@ -335,10 +342,11 @@ fn process_parameter_decorators(
             },
             "metadata" | "sys.metadata" => {
                 if let BicepValue::Object(map) = &decorator.argument {
-                    // Exclude 'description' field as it's handled separately
+                    // Exclude the description fields as they're handled separately
+                    parameter.descriptions = localized_descriptions(map);
                     parameter.metadata = map
                         .iter()
-                        .filter(|(k, _)| *k != "description")
+                        .filter(|(k, _)| !is_description_key(k))
                         .map(|(k, v)| (k.to_string(), v.clone()))
                         .collect();
                 }
@@ -385,7 +393,7 @@ fn process_parameter_decorators(
 /// # Errors
 ///
 /// Returns an error if string-based array parsing fails
-fn parse_allowed_values(argument: &BicepValue) -> Result<Option<Vec<BicepValue>>, Box<dyn Error>> {
+fn parse_allowed_values(argument: &BicepValue) -> Result<Option<Vec<BicepValue>>, BicepError> {
     match argument {
         BicepValue::Array(array) => Ok(Some(array.to_vec())),
         BicepValue::String(str_val) if str_val.starts_with('[') && str_val.ends_with(']') => {
@@ -446,7 +454,7 @@ fn parse_allowed_values(argument: &BicepValue) -> Result<Option<Vec<BicepValue>>
 /// # Errors
 ///
 /// Returns an error if string-to-number parsing fails
-fn parse_numeric_constraint(argument: &BicepValue) -> Result<Option<i64>, Box<dyn Error>> {
+fn parse_numeric_constraint(argument: &BicepValue) -> Result<Option<i64>, BicepError> {
     match argument {
         BicepValue::String(value) => Ok(Some(value.parse::<i64>()?)),
         BicepValue::Int(num) => Ok(Some(*num)),
@@ -508,6 +516,9 @@ impl Serialize for BicepParameter {
         if self.description.is_some() {
             size += 1;
         }
+        if !self.descriptions.is_empty() {
+            size += 1;
+        }
         if !self.metadata.is_empty() {
             size += 1;
         }
@@ -540,19 +551,15 @@ impl Serialize for BicepParameter {
             map.serialize_entry("description", desc)?;
         }
 
+        if !self.descriptions.is_empty() {
+            map.serialize_entry("descriptions", &self.descriptions)?;
+        }
+
         if !self.metadata.is_empty() {
             map.serialize_entry("metadata", &self.metadata)?;
         }
 
-        // Handle type serialization with special case for union types
-        match &self.parameter_type {
-            BicepType::Union(values) => {
-                map.serialize_entry("type", &values.join(" | "))?;
-            },
-            _ => {
-                map.serialize_entry("type", &self.parameter_type)?;
-            },
-        }
+        map.serialize_entry("type", &self.parameter_type)?;
 
         if let Some(default_value) = &self.default_value {
             map.serialize_entry("defaultValue", default_value)?;
@@ -4,14 +4,16 @@
 //! including resource properties, dependencies, conditions, loops, and scope configuration.
 //! Resources represent Azure services and their configuration in Infrastructure as Code.
 
-use std::error::Error;
-
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use tree_sitter::Node;
 
-use super::{utils::values::parse_value_node, BicepDecorator, BicepValue};
+use super::{
+    utils::{decorators::localized_descriptions, values::parse_value_node},
+    BicepDecorator, BicepValue,
+};
+use crate::error::BicepError;
 
 // ---------------------------------------------------------------
 // Structs, Enums & Types
@@ -22,7 +24,7 @@ use super::{utils::values::parse_value_node, BicepDecorator, BicepValue};
 /// Resources define Azure services and their configuration, including properties,
 /// dependencies, conditions, and deployment scope. They are the core building blocks
 /// of Infrastructure as Code in Bicep.
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 #[skip_serializing_none]
 pub struct BicepResource {
@@ -30,6 +32,10 @@ pub struct BicepResource {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// Descriptions in other languages, keyed by locale code
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub descriptions: IndexMap<String, String>,
+
     /// The Azure resource type (e.g., "Microsoft.Storage/storageAccounts")
     #[serde(rename = "type")]
     pub resource_type: String,
@@ -66,6 +72,13 @@ pub struct BicepResource {
     /// Batch size for parallel deployment in loops
     #[serde(skip_serializing_if = "Option::is_none")]
     pub batch_size: Option<i64>,
+
+    /// Remaining properties declared in the resource body
+    ///
+    /// Properties that are already modelled by dedicated fields (`name`, `parent`,
+    /// `scope` and `dependsOn`) are not repeated here.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub properties: IndexMap<String, BicepValue>,
 }
 
 // ---------------------------------------------------------------
@@ -143,7 +156,7 @@ pub fn parse_resource_declaration(
     node: Node,
     source_code: &str,
     decorators: Vec<BicepDecorator>,
-) -> Result<Vec<(String, BicepResource)>, Box<dyn Error>> {
+) -> Result<Vec<(String, BicepResource)>, BicepError> {
     let mut identifier = String::new();
     let mut resource_type = String::new();
     let mut api_version: Option<String> = None;
@@ -157,9 +170,11 @@ pub fn parse_resource_declaration(
     let mut loop_array: Option<String> = None;
     let mut batch_size: Option<i64> = None;
     let mut properties: IndexMap<String, BicepValue> = IndexMap::new();
+    let mut body: IndexMap<String, BicepValue> = IndexMap::new();
 
     // Extract description from decorators
     let mut description = None;
+    let mut descriptions = IndexMap::new();
 
     // Process decorators to extract description and batch size
     for decorator in &decorators {
@@ -170,12 +185,13 @@ pub fn parse_resource_declaration(
                     description = Some(desc_text.clone());
                 }
             },
-            // Handle metadata decorators with description
+            // Handle metadata decorators with descriptions
             "metadata" | "sys.metadata" => {
                 if let BicepValue::Object(map) = &decorator.argument {
                     if let Some(BicepValue::String(desc_text)) = map.get("description") {
                         description = Some(desc_text.clone());
                     }
+                    descriptions.extend(localized_descriptions(map));
                 }
             },
             // Handle batchSize decorator
@@ -237,6 +253,7 @@ pub fn parse_resource_declaration(
                     for (key, value) in props.iter() {
                         properties.insert(key.clone(), value.clone());
                     }
+                    body = props.clone();
 
                     // Extract the name property from the object
                     if let Some(name_value) = props.get("name") {
@@ -661,6 +678,7 @@ pub fn parse_resource_declaration(
             }
             // Break after first object to avoid overwriting
             if !name.is_empty() {
+                body = props;
                 break;
             }
         }
@@ -685,12 +703,17 @@ pub fn parse_resource_declaration(
         api_version: api_version.unwrap_or_default(),
         existing,
         description,
+        descriptions,
         scope,
         parent,
         depends_on,
         condition: conditions,
         loop_statement,
         batch_size,
+        properties: body
+            .into_iter()
+            .filter(|(key, _)| !matches!(key.as_str(), "name" | "parent" | "scope" | "dependsOn"))
+            .collect(),
     };
 
     // Collect child resources from the node
@@ -4,8 +4,6 @@
 //! including object types, union types, and array types with their decorators
 //! and validation constraints.
 
-use std::error::Error;
-
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -17,8 +15,9 @@ use super::{
         decorators::{extract_description_from_decorators, parse_decorator, parse_decorators},
         types::{parse_array_type, parse_property_type, parse_union_type},
     },
-    BicepParameter, BicepParserError, BicepType, BicepValue,
+    BicepParameter, BicepType, BicepValue,
 };
+use crate::error::BicepError;
 
 // ---------------------------------------------------------------
 // Structs, Enums & Types
@@ -31,9 +30,17 @@ use super::{
 pub struct BicepCustomType {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub descriptions: IndexMap<String, String>,
     pub definition: BicepType,
+    /// Property that tells the members of a union type apart, from `@discriminator()`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<String>,
     #[serde(rename = "exported")]
     pub is_exported: bool,
+    /// Whether an object type allows no properties beyond its own, from `@sealed()`
+    #[serde(default, rename = "sealed")]
+    pub is_sealed: bool,
     #[serde(rename = "secure")]
     pub is_secure: bool,
 }
@@ -66,7 +73,7 @@ pub struct BicepCustomType {
 pub fn parse_type_declaration(
     node: Node,
     source_code: &str,
-) -> Result<(String, BicepCustomType), Box<dyn Error>> {
+) -> Result<(String, BicepCustomType), BicepError> {
     let description: Option<String> = None;
     let mut name = String::new();
     let mut definition = BicepType::Object(None); // Empty object type
@@ -86,9 +93,10 @@ pub fn parse_type_declaration(
 
     // Return early if we couldn't find a name
     if name.is_empty() {
-        return Err(Box::new(BicepParserError::ParseError(
-            "Type declaration missing identifier".to_string(),
-        )));
+        return Err(BicepError::syntax(
+            "Type declaration missing identifier",
+            &node,
+        ));
     }
 
     // Find the type definition
@@ -191,7 +199,10 @@ pub fn parse_type_declaration(
         BicepCustomType {
             definition,
             description,
+            descriptions: IndexMap::new(),
+            discriminator: None,
             is_secure,
+            is_sealed: false,
             is_exported,
         },
     ))
@@ -201,7 +212,7 @@ pub fn parse_type_declaration(
 pub fn parse_object_properties(
     node: Node,
     source_code: &str,
-) -> Result<IndexMap<String, BicepParameter>, Box<dyn Error>> {
+) -> Result<IndexMap<String, BicepParameter>, BicepError> {
     let mut properties = IndexMap::new();
     let mut property_decorators = IndexMap::new();
     let mut cursor = node.walk();
@@ -289,7 +300,7 @@ pub fn parse_object_properties(
 pub fn parse_object_property(
     node: Node,
     source_code: &str,
-) -> Result<(String, BicepParameter), Box<dyn Error>> {
+) -> Result<(String, BicepParameter), BicepError> {
     let mut name = String::new();
     let mut property_type = BicepType::String; // Default type
     let mut description: Option<String> = None;
@@ -440,6 +451,7 @@ pub fn parse_object_property(
         name.clone(),
         BicepParameter {
             description,
+            descriptions: IndexMap::new(),
             metadata: IndexMap::new(),
             parameter_type: property_type,
             default_value: None,
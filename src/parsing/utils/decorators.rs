@@ -4,8 +4,6 @@
 //! across all Bicep declaration types. It handles common decorator patterns like
 //! description extraction, metadata processing, and constraint validation.
 
-use std::error::Error;
-
 use indexmap::IndexMap;
 use tracing::{debug, warn};
 use tree_sitter::Node;
@@ -15,6 +13,7 @@ use super::{
     get_node_text,
     values::parse_value_node,
 };
+use crate::error::BicepError;
 
 /// Type alias for the return type of `process_common_decorators` function.
 ///
@@ -81,6 +80,75 @@ pub fn extract_description_from_decorators(decorators: &[BicepDecorator]) -> Opt
     None
 }
 
+/// The metadata key holding descriptions in other languages, keyed by locale code
+pub const LOCALIZED_DESCRIPTIONS_KEY: &str = "descriptions";
+
+/// The prefix of metadata keys holding a description in another language, such as `description_fr`
+pub const LOCALIZED_DESCRIPTION_PREFIX: &str = "description_";
+
+/// Whether a metadata key holds a description, in the default or another language
+pub fn is_description_key(key: &str) -> bool {
+    key == "description"
+        || key == LOCALIZED_DESCRIPTIONS_KEY
+        || key.starts_with(LOCALIZED_DESCRIPTION_PREFIX)
+}
+
+/// Extract descriptions in other languages from a metadata object.
+///
+/// Descriptions are read from a `descriptions` object keyed by locale code, such as
+/// `{ descriptions: { fr: '...', 'pt-BR': '...' } }`, and from keys such as
+/// `description_fr` or `description_pt_BR`. The object takes precedence when both
+/// give a locale.
+///
+/// # Arguments
+///
+/// * `metadata` - The metadata object of a declaration
+///
+/// # Returns
+///
+/// The descriptions keyed by locale code, in the order they appear
+pub fn localized_descriptions(metadata: &IndexMap<String, BicepValue>) -> IndexMap<String, String> {
+    let mut descriptions = IndexMap::new();
+    for (key, value) in metadata {
+        match (key.strip_prefix(LOCALIZED_DESCRIPTION_PREFIX), value) {
+            (Some(locale), BicepValue::String(text)) if !locale.is_empty() => {
+                descriptions.insert(locale.replace('_', "-"), text.clone());
+            },
+            _ => {},
+        }
+    }
+    if let Some(BicepValue::Object(map)) = metadata.get(LOCALIZED_DESCRIPTIONS_KEY) {
+        for (locale, value) in map {
+            if let BicepValue::String(text) = value {
+                descriptions.insert(locale.clone(), text.clone());
+            }
+        }
+    }
+    descriptions.retain(|_, text| !text.is_empty());
+    descriptions
+}
+
+/// Extract descriptions in other languages from @metadata decorators.
+///
+/// # Arguments
+///
+/// * `decorators` - Slice of decorators to search
+///
+/// # Returns
+///
+/// The descriptions keyed by locale code, empty if there are none
+pub fn extract_localized_descriptions(decorators: &[BicepDecorator]) -> IndexMap<String, String> {
+    let mut descriptions = IndexMap::new();
+    for decorator in decorators {
+        if let ("metadata" | "sys.metadata", BicepValue::Object(map)) =
+            (decorator.name.as_str(), &decorator.argument)
+        {
+            descriptions.extend(localized_descriptions(map));
+        }
+    }
+    descriptions
+}
+
 // ---------------------------------------------------------------
 // Decorator Parsing
 // ---------------------------------------------------------------
@@ -98,10 +166,7 @@ pub fn extract_description_from_decorators(decorators: &[BicepDecorator]) -> Opt
 /// # Returns
 ///
 /// Result containing vector of parsed decorators or an error
-pub fn parse_decorators(
-    node: Node,
-    source_code: &str,
-) -> Result<Vec<BicepDecorator>, Box<dyn Error>> {
+pub fn parse_decorators(node: Node, source_code: &str) -> Result<Vec<BicepDecorator>, BicepError> {
     let mut decorators = Vec::new();
     let mut cursor = node.walk();
 
@@ -137,7 +202,7 @@ pub fn parse_decorators(
 /// # Returns
 ///
 /// Result containing the parsed BicepDecorator or an error
-pub fn parse_decorator(node: Node, source_code: &str) -> Result<BicepDecorator, Box<dyn Error>> {
+pub fn parse_decorator(node: Node, source_code: &str) -> Result<BicepDecorator, BicepError> {
     let mut name = String::new();
     let mut argument = BicepValue::String(String::new());
 
@@ -162,7 +227,11 @@ pub fn parse_decorator(node: Node, source_code: &str) -> Result<BicepDecorator,
                             if let Ok(Some(value)) = parse_value_node(grandchild, source_code) {
                                 argument = value;
                             } else {
-                                return Err(format!("Invalid decorator argument for {name}").into());
+                                return Err(BicepError::invalid_value(
+                                    "decorator argument",
+                                    format!("Invalid decorator argument for {name}"),
+                                    &grandchild,
+                                ));
                             }
                         },
                     }
@@ -214,7 +283,7 @@ pub fn is_boolean_flag_decorator(decorator_name: &str) -> bool {
     )
 }
 
-/// Extract metadata object from decorator, excluding description field.
+/// Extract metadata object from decorator, excluding description fields.
 ///
 /// # Arguments
 ///
@@ -222,13 +291,13 @@ pub fn is_boolean_flag_decorator(decorator_name: &str) -> bool {
 ///
 /// # Returns
 ///
-/// Optional metadata map without the description field
+/// Optional metadata map without the description fields, in any language
 pub fn extract_metadata_without_description(
     decorator: &BicepDecorator,
 ) -> Option<IndexMap<String, BicepValue>> {
     if let BicepValue::Object(map) = &decorator.argument {
         let mut metadata = map.clone();
-        metadata.shift_remove("description");
+        metadata.retain(|key, _| !is_description_key(key));
         if !metadata.is_empty() {
             Some(metadata)
         } else {
@@ -5,16 +5,20 @@
 //! - types: Type parsing utilities for various Bicep type expressions
 //! - values: Value parsing utilities for literals and expressions
 
-use std::error::Error;
 use std::str;
 use tree_sitter::Node;
 
+use crate::error::BicepError;
+
 pub mod decorators;
 pub mod types;
 pub mod values;
 
 // Re-export commonly used utilities
-pub use decorators::{extract_description_from_decorators, parse_decorator, parse_decorators};
+pub use decorators::{
+    extract_description_from_decorators, extract_localized_descriptions, parse_decorator,
+    parse_decorators,
+};
 
 pub use types::{parse_array_type, parse_property_type, parse_type_node, parse_union_type};
 pub use values::{parse_array_items, parse_value_node};
@@ -32,7 +36,7 @@ pub use values::{parse_array_items, parse_value_node};
 ///
 /// # Returns
 ///
-/// Returns a `Result<String, Box<dyn Error>>` containing the trimmed node text on success,
+/// Returns a `Result<String, BicepError>` containing the trimmed node text on success,
 /// or an error if the node's text cannot be extracted (e.g., invalid UTF-8).
 ///
 /// # Errors
@@ -46,7 +50,7 @@ pub use values::{parse_array_items, parse_value_node};
 /// let text = get_node_text(&node, source_code)?;
 /// ```
 ///
-pub fn get_node_text(node: &Node, source_code: &str) -> Result<String, Box<dyn Error>> {
+pub fn get_node_text(node: &Node, source_code: &str) -> Result<String, BicepError> {
     let mut text = node.utf8_text(source_code.as_bytes())?;
     text = text.strip_prefix('\n').unwrap_or(text);
 
@@ -3,8 +3,6 @@
 //! This module contains utilities for parsing various type expressions in Bicep,
 //! including union types, array types, and property types.
 
-use std::error::Error;
-
 use indexmap::IndexMap;
 use tree_sitter::Node;
 
@@ -12,6 +10,7 @@ use super::super::BicepParameter;
 use super::decorators::{
     extract_description_from_decorators, parse_decorators, process_common_decorators,
 };
+use crate::error::BicepError;
 use crate::BicepType;
 
 /// Parse a property type from a type node
@@ -27,7 +26,7 @@ use crate::BicepType;
 /// # Returns
 ///
 /// A Result containing the parsed BicepType or an error
-pub fn parse_property_type(node: Node, source_code: &str) -> Result<BicepType, Box<dyn Error>> {
+pub fn parse_property_type(node: Node, source_code: &str) -> Result<BicepType, BicepError> {
     let mut type_value: Option<BicepType> = None;
     let mut cursor = node.walk();
     let children = node.children(&mut cursor).collect::<Vec<_>>();
@@ -104,7 +103,7 @@ pub fn parse_property_type(node: Node, source_code: &str) -> Result<BicepType, B
 /// # Returns
 ///
 /// A Result containing a BicepType::Union with the parsed options
-pub fn parse_union_type(node: Node, source_code: &str) -> Result<BicepType, Box<dyn Error>> {
+pub fn parse_union_type(node: Node, source_code: &str) -> Result<BicepType, BicepError> {
     let mut values = Vec::new();
     let mut cursor = node.walk();
     let children = node.children(&mut cursor).collect::<Vec<_>>();
@@ -178,7 +177,7 @@ pub fn parse_union_type(node: Node, source_code: &str) -> Result<BicepType, Box<
 /// # Returns
 ///
 /// A Result containing a BicepType::Array with the parsed element type
-pub fn parse_array_type(node: Node, source_code: &str) -> Result<BicepType, Box<dyn Error>> {
+pub fn parse_array_type(node: Node, source_code: &str) -> Result<BicepType, BicepError> {
     let mut inner_type = BicepType::String; // Default
     let mut cursor = node.walk();
     let children = node.children(&mut cursor).collect::<Vec<_>>();
@@ -256,7 +255,7 @@ pub fn parse_array_type(node: Node, source_code: &str) -> Result<BicepType, Box<
 pub fn parse_inline_object_type(
     object_node: Node,
     source_code: &str,
-) -> Result<IndexMap<String, BicepParameter>, Box<dyn Error>> {
+) -> Result<IndexMap<String, BicepParameter>, BicepError> {
     let mut properties = IndexMap::new();
     let mut cursor = object_node.walk();
     let all_children = object_node.children(&mut cursor).collect::<Vec<_>>();
@@ -351,12 +350,15 @@ pub fn parse_inline_object_type(
 fn parse_object_property(
     property_node: Node,
     source_code: &str,
-) -> Result<(String, BicepParameter), Box<dyn Error>> {
+) -> Result<(String, BicepParameter), BicepError> {
     let mut cursor = property_node.walk();
     let children = property_node.children(&mut cursor).collect::<Vec<_>>();
 
     if children.len() < 3 {
-        return Err("Invalid object property structure".into());
+        return Err(BicepError::syntax(
+            "Invalid object property structure",
+            &property_node,
+        ));
     }
 
     // First child should be the property name (identifier)
@@ -398,7 +400,7 @@ fn parse_object_property(
 /// # Returns
 ///
 /// A Result containing a tuple of (BicepType, is_nullable)
-pub fn parse_type_node(node: Node, source_code: &str) -> Result<(BicepType, bool), Box<dyn Error>> {
+pub fn parse_type_node(node: Node, source_code: &str) -> Result<(BicepType, bool), BicepError> {
     let mut nullable = false;
     let mut bicep_type = BicepType::String; // Default
 
@@ -3,11 +3,10 @@
 //! This module contains utilities for parsing various value expressions in Bicep,
 //! including arrays, objects, and literal values.
 
-use std::error::Error;
-
 use indexmap::IndexMap;
 use tree_sitter::Node;
 
+use crate::error::BicepError;
 use crate::BicepValue;
 
 use super::get_node_text;
@@ -28,7 +27,7 @@ use super::get_node_text;
 pub fn parse_array_items(
     array_item_node: Node,
     source_code: &str,
-) -> Result<Vec<BicepValue>, Box<dyn Error>> {
+) -> Result<Vec<BicepValue>, BicepError> {
     let mut items = Vec::new();
     let mut cursor = array_item_node.walk();
     let children = array_item_node.children(&mut cursor).collect::<Vec<_>>();
@@ -58,33 +57,54 @@ pub fn parse_array_items(
 /// # Returns
 ///
 /// A Result containing the parsed BicepValue wrapped in Option, or None if parsing fails
-pub fn parse_value_node(
-    node: Node,
-    source_code: &str,
-) -> Result<Option<BicepValue>, Box<dyn Error>> {
+pub fn parse_value_node(node: Node, source_code: &str) -> Result<Option<BicepValue>, BicepError> {
     match node.kind() {
         "string" => {
-            // For string nodes, look for string_content child nodes instead of using the entire text
+            // Interpolated strings keep their template text (without the surrounding quotes)
+            // so that expressions such as '${prefix}-kv' are not reduced to their literal parts
             let mut cursor = node.walk();
+            if node
+                .children(&mut cursor)
+                .any(|child| child.kind() == "interpolation")
+            {
+                let text = node.utf8_text(source_code.as_bytes())?;
+                let content = text
+                    .strip_prefix('\'')
+                    .and_then(|t| t.strip_suffix('\''))
+                    .unwrap_or(text);
+                return Ok(Some(BicepValue::String(content.to_string())));
+            }
+
+            // For string nodes, look for string_content child nodes instead of using the entire text
             for child in node.children(&mut cursor) {
                 if child.kind() == "string_content" {
                     let content = get_node_text(&child, source_code)?;
                     return Ok(Some(BicepValue::String(content)));
                 }
             }
-            Err("No string_content child found".into())
+            Err(BicepError::syntax("No string_content child found", &node))
+        },
+        "integer" | "number" => {
+            let text = node.utf8_text(source_code.as_bytes())?;
+            match text.parse::<i64>() {
+                Ok(n) => Ok(Some(BicepValue::Int(n))),
+                Err(_) => Err(BicepError::invalid_value(
+                    "number",
+                    format!("Could not parse '{text}' as integer"),
+                    &node,
+                )),
+            }
         },
-        "integer" | "number" => Ok(Some(BicepValue::Int(
-            node.utf8_text(source_code.as_bytes())?
-                .to_string()
-                .parse::<i64>()?,
-        ))),
         "boolean" => {
             let text = node.utf8_text(source_code.as_bytes())?.to_string();
             match text.as_str() {
                 "true" => Ok(Some(BicepValue::Bool(true))),
                 "false" => Ok(Some(BicepValue::Bool(false))),
-                _ => Err("Invalid boolean value".into()),
+                _ => Err(BicepError::invalid_value(
+                    "boolean",
+                    format!("Could not parse '{text}' as boolean"),
+                    &node,
+                )),
             }
         },
         "array" => {
@@ -104,25 +124,16 @@ pub fn parse_value_node(
             let properties = parse_object_properties_for_value(node, source_code)?;
             Ok(Some(BicepValue::Object(properties)))
         },
-        "identifier" => {
-            let name = node.utf8_text(source_code.as_bytes())?.to_string();
-            Ok(Some(BicepValue::String(name)))
-        },
-        "member_expression" => {
-            let text = node.utf8_text(source_code.as_bytes())?.to_string();
-            Ok(Some(BicepValue::String(text)))
-        },
-        "call_expression" => {
-            let text = node.utf8_text(source_code.as_bytes())?.to_string();
-            Ok(Some(BicepValue::String(text)))
-        },
-        "binary_expression" => {
+        // Expressions are kept as their source text, marked apart from string literals
+        "identifier"
+        | "member_expression"
+        | "call_expression"
+        | "binary_expression"
+        | "unary_expression"
+        | "subscript_expression"
+        | "conditional_expression" => {
             let text = node.utf8_text(source_code.as_bytes())?.to_string();
-            Ok(Some(BicepValue::String(text)))
-        },
-        "unary_expression" => {
-            let text = node.utf8_text(source_code.as_bytes())?.to_string();
-            Ok(Some(BicepValue::String(text)))
+            Ok(Some(BicepValue::Identifier(text)))
         },
         "parenthesized_expression" => {
             let mut cursor = node.walk();
@@ -133,15 +144,11 @@ pub fn parse_value_node(
                     return parse_value_node(child, source_code);
                 }
             }
-            Ok(Some(BicepValue::String(
+            Ok(Some(BicepValue::Identifier(
                 node.utf8_text(source_code.as_bytes())?.to_string(),
             )))
         },
-        "subscript_expression" => {
-            let text = node.utf8_text(source_code.as_bytes())?.to_string();
-            Ok(Some(BicepValue::String(text)))
-        },
-        "null" => Ok(Some(BicepValue::String("null".to_string()))),
+        "null" => Ok(Some(BicepValue::Identifier("null".to_string()))),
         _ => {
             // For unknown node types, just get the text
             let text = node.utf8_text(source_code.as_bytes())?.to_string();
@@ -166,7 +173,7 @@ pub fn parse_value_node(
 pub fn parse_object_properties_for_value(
     object_node: Node,
     source_code: &str,
-) -> Result<IndexMap<String, BicepValue>, Box<dyn Error>> {
+) -> Result<IndexMap<String, BicepValue>, BicepError> {
     let mut properties = IndexMap::new();
     let mut cursor = object_node.walk();
     let children = object_node.children(&mut cursor).collect::<Vec<_>>();
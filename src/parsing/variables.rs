@@ -4,8 +4,6 @@
 //! Variables are used to store computed values and constants for reuse throughout
 //! the template, improving maintainability and reducing duplication.
 
-use std::error::Error;
-
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use tracing::{debug, warn};
@@ -13,8 +11,9 @@ use tree_sitter::Node;
 
 use super::{
     utils::{decorators::extract_description_from_decorators, values::parse_value_node},
-    BicepDecorator, BicepParserError, BicepValue,
+    BicepDecorator, BicepValue,
 };
+use crate::error::BicepError;
 
 // ---------------------------------------------------------------
 // Structs, Enums & Types
@@ -79,7 +78,7 @@ pub fn parse_variable_declaration(
     node: Node,
     source_code: &str,
     decorators: Vec<BicepDecorator>,
-) -> Result<(String, BicepVariable), Box<dyn Error>> {
+) -> Result<(String, BicepVariable), BicepError> {
     let mut is_exported = false;
 
     // Extract description from centralized function
@@ -94,17 +93,19 @@ pub fn parse_variable_declaration(
 
     // Validate we have the expected number of children for a variable declaration
     if children.len() < 4 {
-        return Err(Box::new(BicepParserError::ParseError(
-            "Variable declaration missing required elements".to_string(),
-        )));
+        return Err(BicepError::syntax(
+            "Variable declaration missing required elements",
+            &node,
+        ));
     }
 
     // Extract variable name (should be at index 1: var NAME = value)
     let name = children[1].utf8_text(source_code.as_bytes())?.to_string();
     if name.is_empty() {
-        return Err(Box::new(BicepParserError::ParseError(
-            "Variable declaration missing name".to_string(),
-        )));
+        return Err(BicepError::syntax(
+            "Variable declaration missing name",
+            &node,
+        ));
     }
 
     debug!("Parsing variable declaration: {}", name);
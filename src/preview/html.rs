@@ -0,0 +1,268 @@
+//! HTML rendering of the Markdown documentation for the preview server
+//!
+//! This covers the Markdown the Markdown exporter generates: ATX headings,
+//! paragraphs with hard line breaks, pipe tables, fenced code blocks, bullet lists,
+//! and bold, italic and code spans. It is not a general Markdown renderer.
+
+/// Escape text for use in HTML
+///
+/// # Arguments
+///
+/// * `text` - The text to escape
+///
+/// # Returns
+///
+/// The text with HTML special characters replaced by entities
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Render Markdown generated by the Markdown exporter as HTML
+///
+/// # Arguments
+///
+/// * `markdown` - The Markdown to render
+///
+/// # Returns
+///
+/// The HTML body content
+pub fn markdown_to_html(markdown: &str) -> String {
+    let lines = markdown.lines().collect::<Vec<_>>();
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        let starts_block = trimmed.is_empty()
+            || trimmed.starts_with('#')
+            || trimmed.starts_with("```")
+            || trimmed.starts_with('|')
+            || trimmed.starts_with("- ");
+        if starts_block {
+            flush_paragraph(&mut html, &mut paragraph);
+        }
+
+        if trimmed.is_empty() {
+            i += 1;
+        } else if let Some(fence) = trimmed.strip_prefix("```") {
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with("```") {
+                code.push(lines[i]);
+                i += 1;
+            }
+            i += 1;
+            let class = if fence.is_empty() {
+                String::new()
+            } else {
+                format!(" class=\"language-{}\"", escape_html(fence))
+            };
+            html.push_str(&format!(
+                "<pre><code{class}>{}</code></pre>\n",
+                escape_html(&code.join("\n"))
+            ));
+        } else if trimmed.starts_with('#') {
+            let level = trimmed.chars().take_while(|c| *c == '#').count().min(6);
+            let text = trimmed[level..].trim();
+            html.push_str(&format!("<h{level}>{}</h{level}>\n", inline(text)));
+            i += 1;
+        } else if trimmed.starts_with('|') {
+            let mut rows = Vec::new();
+            while i < lines.len() && lines[i].trim().starts_with('|') {
+                rows.push(lines[i].trim());
+                i += 1;
+            }
+            push_table(&mut html, &rows);
+        } else if trimmed.starts_with("- ") {
+            html.push_str("<ul>\n");
+            while i < lines.len() {
+                let Some(item) = lines[i].trim().strip_prefix("- ") else {
+                    break;
+                };
+                html.push_str(&format!("<li>{}</li>\n", inline(item)));
+                i += 1;
+            }
+            html.push_str("</ul>\n");
+        } else {
+            paragraph.push(line);
+            i += 1;
+        }
+    }
+    flush_paragraph(&mut html, &mut paragraph);
+    html
+}
+
+/// Write the pending paragraph lines, keeping hard line breaks
+fn flush_paragraph(html: &mut String, paragraph: &mut Vec<&str>) {
+    if paragraph.is_empty() {
+        return;
+    }
+    let mut content = String::new();
+    for (index, line) in paragraph.iter().enumerate() {
+        if index > 0 {
+            content.push('\n');
+        }
+        content.push_str(&inline(line.trim_end()));
+        if line.ends_with("  ") && index + 1 < paragraph.len() {
+            content.push_str("<br>");
+        }
+    }
+    html.push_str(&format!("<p>{content}</p>\n"));
+    paragraph.clear();
+}
+
+/// Write a pipe table whose second row is the header separator
+fn push_table(html: &mut String, rows: &[&str]) {
+    let cells = |row: &str| -> Vec<String> {
+        let row = row.trim_start_matches('|');
+        let row = row.strip_suffix('|').unwrap_or(row);
+        // Split on pipes that are not escaped
+        let mut cells = Vec::new();
+        let mut cell = String::new();
+        let mut chars = row.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    cell.push(c);
+                    if let Some(next) = chars.next() {
+                        cell.push(next);
+                    }
+                },
+                '|' => cells.push(std::mem::take(&mut cell)),
+                _ => cell.push(c),
+            }
+        }
+        cells.push(cell);
+        cells.iter().map(|cell| inline(cell.trim())).collect()
+    };
+
+    let has_header = rows
+        .get(1)
+        .is_some_and(|row| row.chars().all(|c| matches!(c, '|' | '-' | ':' | ' ')));
+    html.push_str("<table>\n");
+    let mut body = rows;
+    if has_header {
+        html.push_str("<thead><tr>");
+        for cell in cells(rows[0]) {
+            html.push_str(&format!("<th>{cell}</th>"));
+        }
+        html.push_str("</tr></thead>\n");
+        body = &rows[2..];
+    }
+    html.push_str("<tbody>\n");
+    for row in body {
+        html.push_str("<tr>");
+        for cell in cells(row) {
+            html.push_str(&format!("<td>{cell}</td>"));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n</table>\n");
+}
+
+/// Render code spans, bold, italic and backslash escapes within a line
+fn inline(text: &str) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut html = String::new();
+    let mut bold = false;
+    let mut italic = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() && chars[i + 1].is_ascii_punctuation() => {
+                html.push_str(&escape_html(&chars[i + 1].to_string()));
+                i += 2;
+            },
+            '`' => {
+                let close = chars[i + 1..].iter().position(|c| *c == '`');
+                match close {
+                    Some(length) => {
+                        let code = chars[i + 1..i + 1 + length].iter().collect::<String>();
+                        html.push_str(&format!("<code>{}</code>", escape_html(&code)));
+                        i += length + 2;
+                    },
+                    None => {
+                        html.push('`');
+                        i += 1;
+                    },
+                }
+            },
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                html.push_str(if bold { "</strong>" } else { "<strong>" });
+                bold = !bold;
+                i += 2;
+            },
+            '*' => {
+                html.push_str(if italic { "</em>" } else { "<em>" });
+                italic = !italic;
+                i += 1;
+            },
+            c => {
+                html.push_str(&escape_html(&c.to_string()));
+                i += 1;
+            },
+        }
+    }
+
+    // Close emphasis left open by unbalanced markers
+    if italic {
+        html.push_str("</em>");
+    }
+    if bold {
+        html.push_str("</strong>");
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_html() {
+        let markdown = "\
+# Storage <Account>
+
+Deploys *one* account  \n\
+with a **secure** key.
+
+| Key | Value |
+|-----|-------|
+| owner | a \\| b |
+
+**Allowed Values:**  \n\
+- `Standard_LRS`
+- `Premium_LRS`
+
+```bicep
+param name string = 'a<b'
+```
+";
+        let html = markdown_to_html(markdown);
+        let expected = "\
+<h1>Storage &lt;Account&gt;</h1>
+<p>Deploys <em>one</em> account<br>
+with a <strong>secure</strong> key.</p>
+<table>
+<thead><tr><th>Key</th><th>Value</th></tr></thead>
+<tbody>
+<tr><td>owner</td><td>a | b</td></tr>
+</tbody>
+</table>
+<p><strong>Allowed Values:</strong></p>
+<ul>
+<li><code>Standard_LRS</code></li>
+<li><code>Premium_LRS</code></li>
+</ul>
+<pre><code class=\"language-bicep\">param name string = 'a&lt;b'</code></pre>
+";
+        assert_eq!(html, expected);
+    }
+}
@@ -0,0 +1,16 @@
+/// Watch mode and local preview of generated documentation
+///
+/// The `watch` command regenerates documentation whenever a Bicep file or the Bicep
+/// configuration changes, and the `serve` command renders the documentation of a
+/// directory of Bicep files on a local web server that reloads pages as files change.
+pub mod html;
+pub mod serve;
+pub mod watch;
+
+use std::time::Duration;
+
+pub use serve::{PreviewServer, ServeOptions};
+pub use watch::{is_watched, watched_files, Watcher, BICEP_CONFIG};
+
+/// How often watched files are checked for changes
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
@@ -0,0 +1,469 @@
+/// Local preview server with live reload
+///
+/// The server renders each Bicep file with the Markdown exporter on request, so
+/// pages always reflect the files on disk. A background thread polls the files for
+/// changes, and each page polls the server so that it reloads when they change.
+///
+/// The server only listens on the loopback interface and only serves `.bicep` files
+/// from within the served directory.
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use tracing::{debug, error, info, warn};
+
+use super::{
+    html::{escape_html, markdown_to_html},
+    watch::{watched_files, Watcher},
+    POLL_INTERVAL,
+};
+use crate::error::BicepError;
+use crate::exports::{markdown, usage, ExportOptions};
+use crate::localization::Language;
+
+/// How long to wait for a client to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Options for rendering the documentation pages
+#[derive(Debug, Clone, Default)]
+pub struct ServeOptions {
+    /// Whether to use emoji symbols (✅/❌) for Yes/No values
+    pub use_emoji: bool,
+    /// Whether to exclude empty sections
+    pub exclude_empty: bool,
+    /// Whether to show "Used By" and "Depends On" details
+    pub show_references: bool,
+    /// The language to write labels and headings in
+    pub language: Language,
+    /// The source template of the usage section, as taken by
+    /// [`usage::module_source`], or None to leave the section out
+    pub usage: Option<String>,
+}
+
+/// State shared by the request handlers
+struct State {
+    root: PathBuf,
+    options: ServeOptions,
+    /// Incremented whenever a watched file changes
+    generation: AtomicU64,
+}
+
+/// A preview server bound to a local port
+pub struct PreviewServer {
+    listener: TcpListener,
+    state: Arc<State>,
+}
+
+impl PreviewServer {
+    /// Bind the server to a port on the loopback interface
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - A Bicep file or a directory of Bicep files to serve
+    /// * `port` - The port to listen on, or 0 for any free port
+    /// * `options` - Options for rendering the documentation pages
+    ///
+    /// # Returns
+    ///
+    /// The bound server, or an error if the path or port cannot be used
+    pub fn bind(root: &Path, port: u16, options: ServeOptions) -> Result<Self, BicepError> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        Ok(PreviewServer {
+            listener,
+            state: Arc::new(State {
+                root: fs::canonicalize(root).map_err(|e| BicepError::io(root, e))?,
+                options,
+                generation: AtomicU64::new(0),
+            }),
+        })
+    }
+
+    /// Get the address the server is listening on
+    pub fn local_addr(&self) -> Result<SocketAddr, BicepError> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Watch the files and serve requests until the process is stopped
+    ///
+    /// # Returns
+    ///
+    /// An error if the files cannot be watched
+    pub fn run(self) -> Result<(), BicepError> {
+        let mut watcher = Watcher::new(&self.state.root)?;
+        let state = Arc::clone(&self.state);
+        thread::spawn(move || loop {
+            thread::sleep(POLL_INTERVAL);
+            match watcher.changes() {
+                Ok(changed) if !changed.is_empty() => {
+                    for path in &changed {
+                        info!("Changed: {}", path.display());
+                    }
+                    state.generation.fetch_add(1, Ordering::SeqCst);
+                },
+                Ok(_) => {},
+                Err(e) => warn!("Unable to check for changes: {}", e),
+            }
+        });
+
+        for stream in self.listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let state = Arc::clone(&self.state);
+                    thread::spawn(move || {
+                        if let Err(e) = handle_connection(stream, &state) {
+                            debug!("Connection failed: {}", e);
+                        }
+                    });
+                },
+                Err(e) => warn!("Unable to accept connection: {}", e),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read a request and write the response
+fn handle_connection(mut stream: TcpStream, state: &State) -> Result<(), BicepError> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip the headers
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or("/"));
+    debug!("{} {}", method, target);
+    let path = target.split('?').next().unwrap_or("/");
+
+    let (status, content_type, body) = if method != "GET" {
+        (
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method not allowed".to_string(),
+        )
+    } else if path == "/__version" {
+        let generation = state.generation.load(Ordering::SeqCst);
+        ("200 OK", "text/plain", generation.to_string())
+    } else if path == "/" && state.root.is_file() {
+        render_page(state, &state.root)
+    } else if path == "/" {
+        render_index(state)
+    } else {
+        match path
+            .strip_prefix("/docs/")
+            .and_then(|relative| resolve(&state.root, &percent_decode(relative)))
+        {
+            Some(file) => render_page(state, &file),
+            None => ("404 Not Found", "text/plain", "Not found".to_string()),
+        }
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}; charset=utf-8\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Resolve a requested path to a Bicep file within the served directory
+///
+/// Paths that leave the directory, or that are not `.bicep` files, are rejected.
+fn resolve(root: &Path, relative: &str) -> Option<PathBuf> {
+    let relative = Path::new(relative);
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    let file = fs::canonicalize(root.join(relative)).ok()?;
+    let is_bicep = file
+        .extension()
+        .is_some_and(|extension| extension == "bicep");
+    (file.starts_with(root) && is_bicep && file.is_file()).then_some(file)
+}
+
+/// Decode `%XX` escapes in a URL path
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            },
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            },
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Encode a relative path for use in a URL
+fn percent_encode(path: &str) -> String {
+    path.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (byte as char).to_string()
+            },
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Get the path of a file relative to the served directory, with `/` separators
+fn relative_path(root: &Path, file: &Path) -> String {
+    file.strip_prefix(root)
+        .unwrap_or(file)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Render the list of Bicep files in the served directory
+fn render_index(state: &State) -> (&'static str, &'static str, String) {
+    let files = match watched_files(&state.root) {
+        Ok(files) => files,
+        Err(e) => {
+            error!("Unable to list files: {}", e);
+            return (
+                "500 Internal Server Error",
+                "text/plain",
+                format!("Unable to list files: {e}"),
+            );
+        },
+    };
+
+    let mut content = format!(
+        "<h1>{}</h1>\n<ul>\n",
+        escape_html(&state.root.display().to_string())
+    );
+    for file in files.iter().filter(|file| {
+        file.extension()
+            .is_some_and(|extension| extension == "bicep")
+    }) {
+        let relative = relative_path(&state.root, file);
+        content.push_str(&format!(
+            "<li><a href=\"/docs/{}\">{}</a></li>\n",
+            percent_encode(&relative),
+            escape_html(&relative)
+        ));
+    }
+    content.push_str("</ul>\n");
+    ("200 OK", "text/html", page(state, "bicep-docs", &content))
+}
+
+/// Render the documentation of a Bicep file
+fn render_page(state: &State, file: &Path) -> (&'static str, &'static str, String) {
+    let title = relative_path(&state.root, file);
+    let title = if title.is_empty() {
+        file.display().to_string()
+    } else {
+        title
+    };
+    let rendered = fs::read_to_string(file)
+        .map_err(|e| BicepError::io(file, e))
+        .and_then(|source| crate::parse_template(&source, file))
+        .and_then(|document| {
+            let mut options = ExportOptions::builder()
+                .use_emoji(state.options.use_emoji)
+                .exclude_empty(state.options.exclude_empty)
+                .show_references(state.options.show_references)
+                .language(state.options.language);
+            if let Some(template) = &state.options.usage {
+                options = options.module_source(usage::module_source(template, file, &document)?);
+            }
+            markdown::export_to_string(&document, &options.build())
+        });
+
+    match rendered {
+        Ok(markdown) => (
+            "200 OK",
+            "text/html",
+            page(state, &title, &markdown_to_html(&markdown)),
+        ),
+        Err(e) => {
+            warn!("Unable to render {}: {}", file.display(), e);
+            let content = format!(
+                "<h1>{}</h1>\n<p>Unable to render this file: {}</p>\n",
+                escape_html(&title),
+                escape_html(&e.to_string())
+            );
+            (
+                "500 Internal Server Error",
+                "text/html",
+                page(state, &title, &content),
+            )
+        },
+    }
+}
+
+/// Wrap content in an HTML page that reloads when the files change
+fn page(state: &State, title: &str, content: &str) -> String {
+    let generation = state.generation.load(Ordering::SeqCst);
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: system-ui, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; line-height: 1.5; }}
+code {{ background: #f3f3f3; padding: 0 0.2rem; border-radius: 3px; }}
+pre {{ background: #f3f3f3; padding: 0.75rem; overflow-x: auto; }}
+pre code {{ padding: 0; }}
+table {{ border-collapse: collapse; }}
+th, td {{ border: 1px solid #ccc; padding: 0.25rem 0.5rem; text-align: left; }}
+</style>
+</head>
+<body>
+{content}<script>
+setInterval(async () => {{
+  try {{
+    const response = await fetch('/__version', {{ cache: 'no-store' }});
+    if ((await response.text()) !== '{generation}') location.reload();
+  }} catch (e) {{}}
+}}, 1000);
+</script>
+</body>
+</html>
+"#,
+        title = escape_html(title),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    fn get(address: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_serve_index_and_version() {
+        let root = std::env::temp_dir().join(format!("bicep-docs-serve-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("my modules")).unwrap();
+        fs::write(root.join("my modules").join("storage.bicep"), "").unwrap();
+        fs::write(root.join("secret.txt"), "").unwrap();
+
+        let server = PreviewServer::bind(&root, 0, ServeOptions::default()).unwrap();
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || {
+            let _ = server.run();
+        });
+
+        let index = get(address, "/");
+        assert!(index.starts_with("HTTP/1.1 200 OK"));
+        assert!(index.contains("<a href=\"/docs/my%20modules/storage.bicep\">"));
+
+        assert!(get(address, "/__version").ends_with("\r\n\r\n0"));
+        assert!(get(address, "/docs/secret.txt").starts_with("HTTP/1.1 404"));
+        assert!(get(address, "/docs/../secret.txt").starts_with("HTTP/1.1 404"));
+        assert!(get(address, "/docs/%2E%2E/secret.txt").starts_with("HTTP/1.1 404"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_render_page_redacts_secure_defaults() {
+        let root = std::env::temp_dir().join(format!("bicep-docs-render-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let file = root.join("template.json");
+        fs::write(
+            &file,
+            r#"{
+  "$schema": "https://schema.management.azure.com/schemas/2019-04-01/deploymentTemplate.json#",
+  "contentVersion": "1.0.0.0",
+  "parameters": {
+    "apiKey": { "type": "securestring", "defaultValue": "hunter2" }
+  },
+  "resources": []
+}"#,
+        )
+        .unwrap();
+
+        let state = State {
+            root: root.clone(),
+            options: ServeOptions::default(),
+            generation: AtomicU64::new(0),
+        };
+        let (status, _, page) = render_page(&state, &file);
+        assert_eq!(status, "200 OK");
+        assert!(page.contains("REDACTED"));
+        assert!(!page.contains("hunter2"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_render_page_usage() {
+        let root = std::env::temp_dir().join(format!("bicep-docs-usage-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let file = root.join("storage.json");
+        fs::write(
+            &file,
+            r#"{
+  "$schema": "https://schema.management.azure.com/schemas/2019-04-01/deploymentTemplate.json#",
+  "contentVersion": "1.0.0.0",
+  "parameters": {
+    "name": { "type": "string" }
+  },
+  "resources": []
+}"#,
+        )
+        .unwrap();
+
+        let mut state = State {
+            root: root.clone(),
+            options: ServeOptions::default(),
+            generation: AtomicU64::new(0),
+        };
+        let (_, _, page) = render_page(&state, &file);
+        assert!(!page.contains("<h2>Usage</h2>"));
+
+        state.options.usage = Some("./modules/{file}".to_string());
+        let (status, _, page) = render_page(&state, &file);
+        assert_eq!(status, "200 OK");
+        assert!(page.contains("<h2>Usage</h2>"));
+        assert!(page.contains("module example './modules/storage.json' = {"));
+        assert!(page.contains("using './modules/storage.json'"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("my%20modules/a.bicep"), "my modules/a.bicep");
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+}
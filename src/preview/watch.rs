@@ -0,0 +1,192 @@
+/// Change detection for Bicep files and their configuration
+///
+/// Files are polled for their modification times rather than watched with
+/// operating system notifications, which keeps the watcher dependency free and
+/// behaves the same on every platform and on network drives.
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use tracing::debug;
+
+use crate::error::BicepError;
+
+/// The Bicep configuration file, which affects how modules resolve
+pub const BICEP_CONFIG: &str = "bicepconfig.json";
+
+/// Whether a path is a file the watcher should track
+///
+/// # Arguments
+///
+/// * `path` - The path to check
+///
+/// # Returns
+///
+/// True for `.bicep` files and `bicepconfig.json`
+pub fn is_watched(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == BICEP_CONFIG)
+        || path
+            .extension()
+            .is_some_and(|extension| extension == "bicep")
+}
+
+/// Find the files to watch under a path
+///
+/// Directories are searched recursively, skipping hidden directories such as `.git`.
+///
+/// # Arguments
+///
+/// * `root` - A Bicep file or a directory
+///
+/// # Returns
+///
+/// The watched files, sorted by path
+pub fn watched_files(root: &Path) -> Result<Vec<PathBuf>, BicepError> {
+    let mut files = Vec::new();
+    if root.is_file() {
+        files.push(root.to_path_buf());
+        // The configuration applies to templates in its directory and below
+        if let Some(config) = root.parent().map(|parent| parent.join(BICEP_CONFIG)) {
+            if config.is_file() {
+                files.push(config);
+            }
+        }
+        return Ok(files);
+    }
+
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(directory) = pending.pop() {
+        let entries = fs::read_dir(&directory).map_err(|e| BicepError::io(&directory, e))?;
+        for entry in entries {
+            let path = entry.map_err(|e| BicepError::io(&directory, e))?.path();
+            let hidden = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with('.'));
+            if path.is_dir() {
+                if !hidden {
+                    pending.push(path);
+                }
+            } else if is_watched(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Tracks the modification times of the watched files under a path
+pub struct Watcher {
+    root: PathBuf,
+    snapshot: HashMap<PathBuf, Option<SystemTime>>,
+}
+
+impl Watcher {
+    /// Create a watcher and record the current state of the files
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - A Bicep file or a directory
+    ///
+    /// # Returns
+    ///
+    /// The watcher, or an error if the path cannot be read
+    pub fn new(root: &Path) -> Result<Self, BicepError> {
+        let mut watcher = Watcher {
+            root: root.to_path_buf(),
+            snapshot: HashMap::new(),
+        };
+        watcher.snapshot = watcher.scan()?;
+        Ok(watcher)
+    }
+
+    /// Get the files currently being watched
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.snapshot.keys().cloned().collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    /// Compare the files with the last check
+    ///
+    /// # Returns
+    ///
+    /// The files that were added, modified or removed since the last check, sorted by path
+    pub fn changes(&mut self) -> Result<Vec<PathBuf>, BicepError> {
+        let current = self.scan()?;
+        let mut changed = current
+            .iter()
+            .filter(|(path, modified)| self.snapshot.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .chain(
+                self.snapshot
+                    .keys()
+                    .filter(|path| !current.contains_key(*path))
+                    .cloned(),
+            )
+            .collect::<Vec<_>>();
+        changed.sort();
+        if !changed.is_empty() {
+            debug!("Detected changes in {} file(s)", changed.len());
+        }
+        self.snapshot = current;
+        Ok(changed)
+    }
+
+    fn scan(&self) -> Result<HashMap<PathBuf, Option<SystemTime>>, BicepError> {
+        Ok(watched_files(&self.root)?
+            .into_iter()
+            .map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (path, modified)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_watcher_changes() {
+        let root = std::env::temp_dir().join(format!("bicep-docs-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("modules")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("main.bicep"), "param a string\n").unwrap();
+        fs::write(root.join("main.md"), "# main\n").unwrap();
+        fs::write(root.join(".git").join("hidden.bicep"), "").unwrap();
+
+        let mut watcher = Watcher::new(&root).unwrap();
+        assert_eq!(watcher.files(), vec![root.join("main.bicep")]);
+        assert!(watcher.changes().unwrap().is_empty());
+
+        // Modification times may be coarse, so make sure the next write is newer
+        std::thread::sleep(Duration::from_millis(1100));
+        fs::write(root.join("main.bicep"), "param b string\n").unwrap();
+        fs::write(root.join("modules").join("storage.bicep"), "").unwrap();
+        fs::write(root.join(BICEP_CONFIG), "{}").unwrap();
+        fs::write(root.join("main.md"), "# changed\n").unwrap();
+        assert_eq!(
+            watcher.changes().unwrap(),
+            vec![
+                root.join("bicepconfig.json"),
+                root.join("main.bicep"),
+                root.join("modules").join("storage.bicep"),
+            ]
+        );
+
+        fs::remove_file(root.join("modules").join("storage.bicep")).unwrap();
+        assert_eq!(
+            watcher.changes().unwrap(),
+            vec![root.join("modules").join("storage.bicep")]
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,152 @@
+// Test suite for the analyses of parsed Bicep files
+//
+// This test suite runs the reference index, evaluator, expansion, linter, security
+// review and AVM checks over templates read by the parser, rather than over
+// documents built by hand, so that they see values as the parser stores them.
+use std::{fs, path::Path};
+
+use bicep_docs::{
+    analysis::{
+        analyze_references, expand::DeploymentStatus, expand_document, security, Evaluator, Linter,
+        RuleSet, SymbolId, SymbolKind,
+    },
+    parse_bicep_document, BicepDocument, BicepValue, Language,
+};
+use indexmap::IndexMap;
+
+#[cfg(test)]
+mod analysis {
+    use super::*;
+
+    // Helper function to read and parse a bicep file
+    //
+    // Returns the source along with the document, for the analyses that place their
+    // diagnostics in the source.
+    fn parse_test_bicep_file(filename: &str) -> (String, BicepDocument) {
+        let test_path = Path::new("tests").join("parsing").join(filename);
+        let content = fs::read_to_string(test_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {}", filename));
+        let document = parse_bicep_document(&content)
+            .unwrap_or_else(|_| panic!("Failed to parse bicep file: {}", filename));
+        (content, document)
+    }
+
+    fn symbol(kind: SymbolKind, name: &str) -> SymbolId {
+        SymbolId::new(kind, name)
+    }
+
+    #[test]
+    fn references() {
+        // String literals that equal symbol names are not references
+        let (_, document) = parse_test_bicep_file("analysis.bicep");
+        let index = analyze_references(&document);
+
+        assert_eq!(
+            index.used_by(SymbolKind::Parameter, "prefix"),
+            &[symbol(SymbolKind::Variable, "vaultName")]
+        );
+        assert_eq!(
+            index.used_by(SymbolKind::Variable, "vaultName"),
+            &[symbol(SymbolKind::Resource, "vault")]
+        );
+        assert!(index.depends_on(SymbolKind::Variable, "label").is_empty());
+        assert_eq!(
+            index.depends_on(SymbolKind::Resource, "storage"),
+            &[symbol(SymbolKind::Parameter, "location")]
+        );
+        assert_eq!(index.unused_parameters(), vec!["adminPassword", "apiKey"]);
+    }
+
+    #[test]
+    fn evaluator() {
+        let (_, document) = parse_test_bicep_file("analysis.bicep");
+        let evaluator = Evaluator::new(&document);
+
+        assert_eq!(
+            evaluator.symbol_value("vaultName"),
+            Some(&BicepValue::String("contoso-prod-kv".to_string()))
+        );
+        assert_eq!(evaluator.symbol_value("location"), None);
+        // A literal that names a parameter is not read as a reference to it
+        assert_eq!(
+            evaluator.symbol_value("label"),
+            Some(&BicepValue::String("environment".to_string()))
+        );
+    }
+
+    #[test]
+    fn expand() {
+        let (_, document) = parse_test_bicep_file("analysis.bicep");
+        let parameters = IndexMap::from([(
+            "environment".to_string(),
+            BicepValue::String("test".to_string()),
+        )]);
+        let expansion = expand_document(&document, parameters);
+
+        assert_eq!(expansion.missing_parameters, vec!["adminPassword"]);
+        let vault = &expansion.deployments[0];
+        assert_eq!(vault.symbol, "vault");
+        assert_eq!(vault.status, DeploymentStatus::Deployed);
+        assert_eq!(vault.instances[0].name, "contoso-test-kv");
+        assert!(vault.instances[0].resolved);
+        let storage = &expansion.deployments[1];
+        assert_eq!(storage.symbol, "storage");
+        assert_eq!(storage.instances[0].name, "prefix");
+    }
+
+    #[test]
+    fn lint() {
+        let (source, document) = parse_test_bicep_file("analysis.bicep");
+        let diagnostics = Linter::new().lint(&document, Some(&source));
+        let found: Vec<_> = diagnostics
+            .iter()
+            .filter_map(|diagnostic| {
+                let symbol = diagnostic.symbol.as_ref()?;
+                Some((diagnostic.rule.as_str(), symbol.name.as_str()))
+            })
+            .collect();
+
+        assert!(found.contains(&("secure-parameter-default", "apiKey")));
+        assert!(found.contains(&("no-secret-outputs", "storageKey")));
+        // A literal that names a secure parameter does not expose it
+        assert!(!found.contains(&("no-secret-outputs", "passwordLabel")));
+    }
+
+    #[test]
+    fn security_review() {
+        let (source, document) = parse_test_bicep_file("analysis.bicep");
+        let report = security::review(
+            &document,
+            Some(&source),
+            &IndexMap::new(),
+            Language::English,
+        );
+        let found: Vec<_> = report
+            .diagnostics
+            .iter()
+            .filter_map(|diagnostic| {
+                let symbol = diagnostic.symbol.as_ref()?;
+                Some((diagnostic.rule.as_str(), symbol.name.as_str()))
+            })
+            .collect();
+
+        assert!(found.contains(&("secure-parameter-default", "apiKey")));
+        assert!(found.contains(&("no-secret-outputs", "storageKey")));
+        assert!(!found.iter().any(|(_, name)| *name == "passwordLabel"));
+
+        let password = &report.secure_parameters[0];
+        assert_eq!(password.name, "adminPassword");
+        assert!(password.flows_to.is_empty());
+    }
+
+    #[test]
+    fn avm() {
+        let (_, document) = parse_test_bicep_file("analysis.bicep");
+        let violations = RuleSet::latest().check(&document);
+        let rules: Vec<_> = violations.iter().map(|violation| violation.rule).collect();
+
+        // `location` defaults to an expression, which is a default value all the same
+        assert!(!rules.contains(&"AVM1001"));
+        assert!(rules.contains(&"AVM1006"));
+    }
+}
@@ -0,0 +1,73 @@
+use std::fs;
+
+use bicep_docs::{
+    batch::{self, BatchOptions, Cache, FileStatus},
+    exports::ExportOptions,
+};
+
+#[cfg(test)]
+mod batch_runs {
+    use super::*;
+
+    #[test]
+    fn unchanged_templates_are_skipped() {
+        let root =
+            std::env::temp_dir().join(format!("bicep-docs-batch-run-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("modules")).unwrap();
+        fs::copy(
+            "./examples/single-file/example.bicep",
+            root.join("main.bicep"),
+        )
+        .unwrap();
+        fs::write(
+            root.join("modules").join("storage.bicep"),
+            "@description('Storage account name')\nparam name string\n",
+        )
+        .unwrap();
+        fs::write(root.join("modules").join("broken.bicep"), [0xff, 0xfe]).unwrap();
+
+        let templates = batch::find_templates(std::slice::from_ref(&root)).unwrap();
+        assert_eq!(templates.len(), 3);
+        let options = BatchOptions {
+            formats: vec!["markdown".to_string(), "json".to_string()],
+            jobs: 2,
+            ..Default::default()
+        };
+        let mut cache = Cache::default();
+
+        let first = batch::run(&templates, &options, Some(&mut cache)).unwrap();
+        assert_eq!(first.generated(), 2);
+        assert_eq!(first.failed(), 1);
+        assert!(root.join("main.md").is_file());
+        assert!(root.join("modules").join("storage.json").is_file());
+
+        let second = batch::run(&templates, &options, Some(&mut cache)).unwrap();
+        assert_eq!(second.generated(), 0);
+        assert_eq!(second.unchanged(), 2);
+        assert_eq!(second.failed(), 1);
+
+        // Editing a template, deleting an output or changing the options regenerates
+        fs::write(
+            root.join("modules").join("storage.bicep"),
+            "@description('Storage account name')\nparam accountName string\n",
+        )
+        .unwrap();
+        fs::remove_file(root.join("main.json")).unwrap();
+        let third = batch::run(&templates, &options, Some(&mut cache)).unwrap();
+        assert_eq!(third.generated(), 2);
+        assert!(fs::read_to_string(root.join("modules").join("storage.md"))
+            .unwrap()
+            .contains("accountName"));
+
+        let emoji = BatchOptions {
+            export: ExportOptions::builder().use_emoji(true).build(),
+            ..options.clone()
+        };
+        let fourth = batch::run(&templates, &emoji, Some(&mut cache)).unwrap();
+        assert_eq!(fourth.generated(), 2);
+        assert_eq!(fourth.files[0].status, FileStatus::Generated);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
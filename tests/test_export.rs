@@ -1,8 +1,17 @@
 use std::fs;
 
 use bicep_docs::{
-    export_bicep_document_to_json_string, export_bicep_document_to_yaml_string,
-    parse_bicep_document,
+    export_bicep_document_to_asciidoc, export_bicep_document_to_asciidoc_string,
+    export_bicep_document_to_asciidoc_string_localized, export_bicep_document_to_json_string,
+    export_bicep_document_to_markdown, export_bicep_document_to_markdown_string,
+    export_bicep_document_to_markdown_string_localized, export_bicep_document_to_yaml_string,
+    exports::{
+        asciidoc, bicep, bicep::export_to_string as export_bicep_to_string, create_ui_definition,
+        find_exporter, from_json, from_yaml, json_schema, markdown, ExportOptions, REDACTED,
+    },
+    localization::Language,
+    parse_arm_document, parse_bicep_document,
+    parsing::{BicepDocument, BicepParameter, BicepType, BicepValue},
 };
 
 #[cfg(test)]
@@ -62,4 +71,149 @@ mod exports {
         // Verify that compact JSON is smaller than pretty JSON
         assert!(json_compact.len() < json_pretty.len());
     }
+
+    #[test]
+    fn bicep_round_trip() {
+        // Read the canonically formatted Bicep file
+        let test_file = "./tests/parsing/roundtrip.bicep";
+        let source_code = fs::read_to_string(test_file).unwrap();
+
+        // Parse, emit as Bicep and parse the emitted source again
+        let document = parse_bicep_document(&source_code).unwrap();
+        let emitted = export_bicep_to_string(&document).unwrap();
+        let reparsed = parse_bicep_document(&emitted).unwrap();
+
+        // The emitted source must describe the same template
+        assert_eq!(document, reparsed, "emitted source:\n{emitted}");
+        assert_eq!(document.parameters.len(), 8);
+        // Secure defaults are part of the template, not redacted
+        assert!(emitted.contains("param adminUsername string = 'azureuser'"));
+        assert!(emitted.contains("resource vault 'Microsoft.KeyVault/vaults@2023-07-01' = {"));
+    }
+
+    #[test]
+    fn interchange_round_trip() {
+        // Read an ARM template, which covers types, functions, loops and modules
+        let test_file = "./tests/parsing/arm.json";
+        let source_code = fs::read_to_string(test_file).unwrap();
+        let document = parse_arm_document(&source_code).unwrap();
+
+        // The JSON and YAML exports must read back into the same document
+        let json = export_bicep_document_to_json_string(&document, true, false).unwrap();
+        assert_eq!(from_json(&json).unwrap(), document, "json:\n{json}");
+        let yaml = export_bicep_document_to_yaml_string(&document, false).unwrap();
+        assert_eq!(from_yaml(&yaml).unwrap(), document, "yaml:\n{yaml}");
+    }
+
+    /// A document with a secret written into the default of a secure parameter
+    fn document_with_secure_default() -> BicepDocument {
+        let mut document = BicepDocument::default();
+        document.parameters.insert(
+            "apiKey".to_string(),
+            BicepParameter {
+                parameter_type: BicepType::String,
+                default_value: Some(BicepValue::String("hunter2".to_string())),
+                is_secure: true,
+                ..Default::default()
+            },
+        );
+        document
+    }
+
+    #[test]
+    fn secure_defaults_round_trip() {
+        let document = document_with_secure_default();
+
+        // The template formats keep secure defaults, so they read back unchanged
+        let emitted = bicep::export_to_string(&document).unwrap();
+        assert!(
+            emitted.contains("param apiKey string = 'hunter2'"),
+            "{emitted}"
+        );
+        let json = export_bicep_document_to_json_string(&document, true, false).unwrap();
+        assert_eq!(from_json(&json).unwrap(), document, "json:\n{json}");
+        let yaml = export_bicep_document_to_yaml_string(&document, false).unwrap();
+        assert_eq!(from_yaml(&yaml).unwrap(), document, "yaml:\n{yaml}");
+
+        let options = ExportOptions::default();
+        for format in ["bicep", "json", "yaml"] {
+            let exporter = find_exporter(format).unwrap();
+            let output = exporter.export_to_string(&document, &options).unwrap();
+            assert!(output.contains("hunter2"), "{format}:\n{output}");
+            assert!(!output.contains(REDACTED), "{format}:\n{output}");
+        }
+    }
+
+    #[test]
+    fn secure_defaults_are_redacted() {
+        let document = document_with_secure_default();
+
+        // Every free export function of a documentation format redacts, as the
+        // exporters do by default
+        let outputs = [
+            export_bicep_document_to_markdown_string(&document, false, false).unwrap(),
+            export_bicep_document_to_markdown_string_localized(
+                &document,
+                false,
+                false,
+                Language::French,
+            )
+            .unwrap(),
+            export_bicep_document_to_asciidoc_string(&document, false, false).unwrap(),
+            export_bicep_document_to_asciidoc_string_localized(
+                &document,
+                false,
+                false,
+                Language::French,
+            )
+            .unwrap(),
+            markdown::export_to_string(&document, &ExportOptions::default()).unwrap(),
+            asciidoc::export_to_string(&document, &ExportOptions::default()).unwrap(),
+            json_schema::export_to_string(
+                &document,
+                &ExportOptions::builder().pretty(true).build(),
+            )
+            .unwrap(),
+            create_ui_definition::export_to_string(
+                &document,
+                &ExportOptions::builder().pretty(true).build(),
+            )
+            .unwrap(),
+        ];
+        for output in outputs {
+            assert!(!output.contains("hunter2"), "output:\n{output}");
+        }
+
+        // Writers and files too
+        let mut writer = Vec::new();
+        markdown::export_to_writer(&document, &mut writer, &ExportOptions::default()).unwrap();
+        asciidoc::export_to_writer(&document, &mut writer, &ExportOptions::default()).unwrap();
+        let written = String::from_utf8(writer).unwrap();
+        assert!(written.contains(REDACTED));
+        assert!(!written.contains("hunter2"));
+
+        let directory =
+            std::env::temp_dir().join(format!("bicep-docs-redact-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        export_bicep_document_to_markdown(&document, directory.join("doc.md"), false, false)
+            .unwrap();
+        export_bicep_document_to_asciidoc(&document, directory.join("doc.adoc"), false, false)
+            .unwrap();
+        for file in ["doc.md", "doc.adoc"] {
+            let content = fs::read_to_string(directory.join(file)).unwrap();
+            assert!(!content.contains("hunter2"), "{file}:\n{content}");
+        }
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn secure_defaults_opt_out() {
+        let document = document_with_secure_default();
+        let options = ExportOptions::builder().show_secure_defaults(true).build();
+        for format in ["markdown", "asciidoc", "json-schema"] {
+            let exporter = find_exporter(format).unwrap();
+            let output = exporter.export_to_string(&document, &options).unwrap();
+            assert!(output.contains("hunter2"), "{format}:\n{output}");
+        }
+    }
 }
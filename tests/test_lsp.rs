@@ -0,0 +1,182 @@
+use std::{
+    io::{BufReader, Write},
+    process::{Command, Stdio},
+};
+
+use bicep_docs::lsp::protocol::{read_message, write_message};
+use serde_json::{json, Value};
+
+#[cfg(test)]
+mod lsp {
+    use super::*;
+
+    const URI: &str = "file:///workspace/main.bicep";
+
+    const SOURCE: &str = "\
+@description('Prefix for resource names')
+param prefix string
+
+param location string = resourceGroup().location
+
+resource vault 'Microsoft.KeyVault/vaults@2023-07-01' = {
+  name: '${prefix}-kv'
+  location: location
+}
+
+output vaultId string = vault.id
+";
+
+    /// Run a language server session and collect everything the server sends
+    fn run_session(messages: &[Value]) -> Vec<Value> {
+        let mut server = Command::new(env!("CARGO_BIN_EXE_bicep-docs"))
+            .args(["--quiet", "lsp", "--stdio"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("Failed to start the language server");
+
+        let mut stdin = server.stdin.take().unwrap();
+        for message in messages {
+            write_message(&mut stdin, message).unwrap();
+        }
+        stdin.flush().unwrap();
+        drop(stdin);
+
+        let mut reader = BufReader::new(server.stdout.take().unwrap());
+        let mut received = Vec::new();
+        while let Some(message) = read_message(&mut reader).unwrap() {
+            received.push(message);
+        }
+        assert!(server.wait().unwrap().success());
+        received
+    }
+
+    fn request(id: i64, method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+    }
+
+    fn notification(method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "method": method, "params": params })
+    }
+
+    fn response(received: &[Value], id: i64) -> &Value {
+        received
+            .iter()
+            .find(|message| message["id"] == id)
+            .unwrap_or_else(|| panic!("No response to request {id}"))
+    }
+
+    fn diagnostics(received: &[Value]) -> Vec<&Value> {
+        received
+            .iter()
+            .filter(|message| message["method"] == "textDocument/publishDiagnostics")
+            .collect()
+    }
+
+    #[test]
+    fn session() {
+        let received = run_session(&[
+            request(1, "initialize", json!({ "capabilities": {} })),
+            notification("initialized", json!({})),
+            notification(
+                "textDocument/didOpen",
+                json!({
+                    "textDocument": {
+                        "uri": URI,
+                        "languageId": "bicep",
+                        "version": 1,
+                        "text": SOURCE,
+                    }
+                }),
+            ),
+            request(
+                2,
+                "textDocument/documentSymbol",
+                json!({ "textDocument": { "uri": URI } }),
+            ),
+            // `location` in `location: location` on line 7
+            request(
+                3,
+                "textDocument/hover",
+                json!({
+                    "textDocument": { "uri": URI },
+                    "position": { "line": 7, "character": 13 },
+                }),
+            ),
+            // Describe `location`, which fixes one of the two warnings
+            notification(
+                "textDocument/didChange",
+                json!({
+                    "textDocument": { "uri": URI, "version": 2 },
+                    "contentChanges": [{
+                        "range": {
+                            "start": { "line": 3, "character": 0 },
+                            "end": { "line": 3, "character": 0 },
+                        },
+                        "text": "@description('Deployment region')\n",
+                    }],
+                }),
+            ),
+            request(4, "shutdown", Value::Null),
+            notification("exit", Value::Null),
+        ]);
+
+        let capabilities = &response(&received, 1)["result"]["capabilities"];
+        assert_eq!(capabilities["hoverProvider"], true);
+        assert_eq!(capabilities["textDocumentSync"]["change"], 2);
+
+        let symbols = response(&received, 2)["result"].as_array().unwrap();
+        let names = symbols
+            .iter()
+            .map(|symbol| symbol["name"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["prefix", "location", "vault", "vaultId"]);
+        assert_eq!(
+            symbols[0]["range"]["start"],
+            json!({ "line": 0, "character": 0 })
+        );
+        assert_eq!(symbols[2]["detail"], "Microsoft.KeyVault/vaults@2023-07-01");
+
+        let hover = &response(&received, 3)["result"];
+        assert_eq!(hover["contents"]["kind"], "markdown");
+        assert!(hover["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .starts_with("### `location`"));
+
+        // Missing descriptions are reported when the document opens and after each change
+        let published = diagnostics(&received);
+        assert_eq!(published.len(), 2);
+        let opened = published[0]["params"]["diagnostics"].as_array().unwrap();
+        assert_eq!(opened.len(), 2);
+        assert_eq!(opened[0]["code"], "missing-description");
+        assert_eq!(
+            opened[0]["range"]["start"],
+            json!({ "line": 3, "character": 6 })
+        );
+        let changed = published[1]["params"]["diagnostics"].as_array().unwrap();
+        assert_eq!(published[1]["params"]["version"], 2);
+        assert_eq!(changed.len(), 1);
+        assert!(changed[0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("'vaultId'"));
+
+        assert_eq!(response(&received, 4)["result"], Value::Null);
+    }
+
+    #[test]
+    fn requests_before_initialize_are_rejected() {
+        let received = run_session(&[
+            request(1, "textDocument/documentSymbol", json!({})),
+            request(2, "initialize", json!({ "capabilities": {} })),
+            request(3, "workspace/symbol", json!({ "query": "" })),
+            request(4, "shutdown", Value::Null),
+            notification("exit", Value::Null),
+        ]);
+
+        assert_eq!(response(&received, 1)["error"]["code"], -32002);
+        assert!(response(&received, 2)["result"]["capabilities"].is_object());
+        assert_eq!(response(&received, 3)["error"]["code"], -32601);
+    }
+}
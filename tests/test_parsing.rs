@@ -8,7 +8,8 @@
 use std::{fs, path::Path};
 
 use bicep_docs::{
-    parse_bicep_document, parsing::ModuleSource, BicepDocument, BicepType, BicepValue,
+    parse_arm_document, parse_bicep_document, parsing::ModuleSource, BicepDocument, BicepType,
+    BicepValue,
 };
 
 #[cfg(test)]
@@ -651,6 +652,11 @@ mod parsing {
             doc.modules.contains_key("localModule"),
             "Missing localModule"
         );
+        // The deployment name comes from the body, not the symbolic name
+        assert_eq!(
+            doc.modules["localModule"].deployment_name.as_deref(),
+            Some("localModuleDeployment")
+        );
 
         // Check for conditional module if it exists
         let has_conditional_module = doc.modules.values().any(|m| m.condition.is_some());
@@ -793,4 +799,202 @@ mod parsing {
             assert!(f.is_exported, "sayHello should be exported");
         }
     }
+
+    #[test]
+    fn arm_template() {
+        // Tests reading a compiled ARM JSON template into the same document model:
+        // - Template metadata and the Bicep generator
+        // - Definitions, user-defined functions, parameters and variables
+        // - Symbolic-name resources, copy loops and conditions
+        // - Nested deployments emitted by Bicep read as modules
+        // - ARM expressions rewritten to Bicep expressions, kept apart from string literals
+        let test_path = Path::new("tests").join("parsing").join("arm.json");
+        let content = fs::read_to_string(test_path).expect("Failed to read test file: arm.json");
+        let doc = parse_arm_document(&content).expect("Failed to parse ARM template");
+
+        assert_eq!(doc.name.as_deref(), Some("Key Vault"));
+        assert_eq!(
+            doc.description.as_deref(),
+            Some("Deploys a Key Vault with diagnostics")
+        );
+        assert_eq!(doc.target_scope, None);
+        assert_eq!(
+            doc.metadata.get("generator"),
+            Some(&BicepValue::String("bicep 0.30.23.60470".to_string()))
+        );
+        assert_eq!(
+            doc.metadata.get("owner"),
+            Some(&BicepValue::String("platform-team".to_string()))
+        );
+
+        // Types
+        let sku_type = &doc.types["skuType"];
+        assert!(sku_type.is_exported);
+        assert_eq!(sku_type.description.as_deref(), Some("The vault SKU"));
+        assert!(!sku_type.is_sealed);
+        assert!(doc.types["networkAclsType"].is_sealed);
+        match &doc.types["networkAclsType"].definition {
+            BicepType::Object(Some(properties)) => {
+                assert!(properties["ipRules"].is_nullable);
+                assert_eq!(
+                    properties["ipRules"].parameter_type,
+                    BicepType::Array(Box::new(BicepType::String))
+                );
+            },
+            other => panic!("Expected an object type, got {other:?}"),
+        }
+
+        // Functions
+        let function = &doc.functions["vaultName"];
+        assert_eq!(function.expression, "format('{0}-kv', prefix)");
+        assert_eq!(function.arguments[0].name, "prefix");
+        assert_eq!(function.return_type, BicepType::String);
+
+        // Parameters
+        let prefix = &doc.parameters["prefix"];
+        assert_eq!(prefix.min_length, Some(3));
+        assert_eq!(prefix.max_length, Some(11));
+        assert_eq!(
+            prefix.description.as_deref(),
+            Some("Prefix for resource names")
+        );
+        assert_eq!(
+            doc.parameters["location"].default_value,
+            Some(BicepValue::Identifier(
+                "resourceGroup().location".to_string()
+            ))
+        );
+        assert_eq!(
+            doc.parameters["sku"].parameter_type,
+            BicepType::CustomType("skuType".to_string())
+        );
+        assert!(doc.parameters["networkAcls"].is_nullable);
+        assert!(doc.parameters["adminPassword"].is_secure);
+        assert_eq!(doc.parameters["replicaCount"].max_value, Some(5));
+
+        // Variables
+        assert_eq!(
+            doc.variables["vaultName"].value,
+            BicepValue::Identifier("format('{0}-kv', prefix)".to_string())
+        );
+        match &doc.variables["tags"].value {
+            BicepValue::Object(tags) => assert_eq!(
+                tags["note"],
+                BicepValue::String("[not an expression]".to_string())
+            ),
+            other => panic!("Expected an object value, got {other:?}"),
+        }
+
+        // Resources
+        let vault = &doc.resources["vault"];
+        assert_eq!(vault.resource_type, "Microsoft.KeyVault/vaults");
+        assert_eq!(vault.api_version, "2023-07-01");
+        assert_eq!(vault.name, "${vaultName}");
+        assert_eq!(
+            vault.properties["location"],
+            BicepValue::Identifier("location".to_string())
+        );
+
+        let replicas = &doc.resources["replicas"];
+        assert_eq!(replicas.condition.as_deref(), Some("(deployDiagnostics)"));
+        assert_eq!(
+            replicas.loop_statement.as_deref(),
+            Some("for i in range(0, length(range(0, replicaCount)))")
+        );
+        assert_eq!(replicas.name, "${format('{0}st{1}', prefix, i)}");
+        assert_eq!(replicas.depends_on, Some(vec!["vault".to_string()]));
+        assert!(!doc.resources.contains_key("diagnostics"));
+
+        // Modules
+        let diagnostics = &doc.modules["diagnostics"];
+        assert_eq!(diagnostics.name, "diagnostics");
+        assert_eq!(diagnostics.deployment_name.as_deref(), Some("diagnostics"));
+        assert_eq!(
+            diagnostics.params["vaultName"],
+            BicepValue::Identifier("vaultName".to_string())
+        );
+
+        // Outputs
+        let output = &doc.outputs["vaultUri"];
+        assert_eq!(output.description.as_deref(), Some("The URI of the vault"));
+        assert_eq!(
+            output.value,
+            BicepValue::Identifier("reference('vault').vaultUri".to_string())
+        );
+    }
+
+    #[test]
+    fn arm_copy_index() {
+        // Tests the translation of copy loops:
+        // - copyIndex() with an offset, a loop name, or both
+        // - copyIndex('other') for a loop other than the enclosing one is kept as is
+        // - The loop variable is renamed when a declared symbol already has its name
+        let content = r#"{
+            "$schema": "https://schema.management.azure.com/schemas/2019-04-01/deploymentTemplate.json#",
+            "contentVersion": "1.0.0.0",
+            "parameters": {
+                "i": { "type": "int", "defaultValue": 1 }
+            },
+            "variables": {
+                "i1": "taken",
+                "copy": [
+                    { "name": "ports", "count": 3, "input": "[add(8080, copyIndex('ports'))]" }
+                ]
+            },
+            "resources": [
+                {
+                    "type": "Microsoft.Storage/storageAccounts",
+                    "apiVersion": "2023-01-01",
+                    "name": "[format('st{0}', copyIndex(parameters('i')))]",
+                    "copy": { "name": "accounts", "count": 2 },
+                    "properties": {
+                        "first": "[copyIndex('accounts')]",
+                        "second": "[copyIndex('accounts', 1)]",
+                        "other": "[copyIndex('disks')]"
+                    }
+                }
+            ],
+            "outputs": {
+                "names": {
+                    "type": "array",
+                    "copy": { "count": 2, "input": "[copyIndex()]" }
+                }
+            }
+        }"#;
+        let doc = parse_arm_document(content).expect("Failed to parse ARM template");
+
+        assert_eq!(
+            doc.variables["ports"].value,
+            BicepValue::Identifier("add(8080, i2)".to_string())
+        );
+
+        let accounts = &doc.resources["storageAccounts"];
+        assert_eq!(
+            accounts.loop_statement.as_deref(),
+            Some("for i2 in range(0, 2)")
+        );
+        assert_eq!(accounts.name, "${format('st{0}', (i2 + i))}");
+        match &accounts.properties["properties"] {
+            BicepValue::Object(properties) => {
+                assert_eq!(
+                    properties["first"],
+                    BicepValue::Identifier("i2".to_string())
+                );
+                assert_eq!(
+                    properties["second"],
+                    BicepValue::Identifier("(i2 + 1)".to_string())
+                );
+                assert_eq!(
+                    properties["other"],
+                    BicepValue::Identifier("copyIndex('disks')".to_string())
+                );
+            },
+            other => panic!("Expected an object value, got {other:?}"),
+        }
+
+        assert_eq!(
+            doc.outputs["names"].value,
+            BicepValue::Identifier("i2".to_string())
+        );
+    }
 }